@@ -0,0 +1,124 @@
+//!
+//! A module for describing widgets to assistive technologies such as screen readers.
+//!
+//! Each widget describes itself via `Widget::accessibility` whenever its state changes. The
+//! descriptions of all widgets, along with their positions, may be retrieved as a list of `Node`s
+//! via `Ui::accessibility_nodes` so that they may be forwarded to the platform's accessibility
+//! API.
+//!
+
+use position::{Dimensions, Point};
+use ui::UiId;
+
+
+/// The kind of control that a widget presents to the user.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// A widget whose role is not known.
+    Unknown,
+    /// A push button.
+    Button,
+    /// A button that is either on or off.
+    Toggle,
+    /// A control for selecting a value within a range.
+    Slider,
+    /// A group of options of which only one may be selected.
+    RadioGroup,
+    /// A control for selecting a value within a range by dragging the digits of the value.
+    NumberDialer,
+    /// A value flanked by buttons that step it down and up.
+    NumberStepper,
+    /// A control for selecting a pair of values within a 2D area.
+    XYPad,
+    /// Editable text.
+    TextBox,
+    /// Static text.
+    Text,
+    /// A button that opens a list of options, from which one may be chosen.
+    DropDownList,
+    /// A list of commands.
+    Menu,
+    /// A floating panel hosting other widgets.
+    Popover,
+    /// An area within which arbitrary content is drawn.
+    Graphic,
+    /// An editor for a series of points.
+    EnvelopeEditor,
+    /// A pannable and zoomable area hosting other widgets.
+    EditorCanvas,
+    /// A scale along the edge of an EditorCanvas, along with its guide lines.
+    Ruler,
+    /// An area within which widgets are selected by dragging a rectangle over them.
+    Marquee,
+    /// A rectangle that may be dragged around, i.e. the title bar of a panel.
+    Draggable,
+    /// A list of messages that is continually appended to.
+    Log,
+    /// A display of the application's current status, i.e. performance measurements.
+    Status,
+    /// A divider that may be dragged to resize the panes on either side of it.
+    Splitter,
+    /// A header that may be clicked to show or hide the content beneath it.
+    Disclosure,
+    /// A strip of menus, each of which may be opened to reveal a list of commands.
+    MenuBar,
+    /// A window of controls with which a choice is made, i.e. choosing a file.
+    Dialog,
+    /// A grid of the days of a month from which a date may be chosen.
+    Calendar,
+    /// A chart plotting one or more series of data.
+    Chart,
+    /// A window with a title bar that floats above the other widgets and may be moved.
+    Window,
+}
+
+/// A description of a widget for assistive technologies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accessibility {
+    /// The kind of control that the widget presents.
+    pub role: Role,
+    /// The text that names the widget (i.e. a button's label).
+    pub maybe_label: Option<String>,
+    /// The widget's current value as text (i.e. a slider's value).
+    pub maybe_value: Option<String>,
+}
+
+/// A widget's description along with its identifier and position, as returned by
+/// `Ui::accessibility_nodes`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    /// The widget being described.
+    pub ui_id: UiId,
+    /// The kind of control that the widget presents.
+    pub role: Role,
+    /// The text that names the widget.
+    pub maybe_label: Option<String>,
+    /// The widget's current value as text.
+    pub maybe_value: Option<String>,
+    /// The centre of the widget.
+    pub xy: Point,
+    /// The dimensions of the widget.
+    pub dim: Dimensions,
+}
+
+
+impl Accessibility {
+
+    /// Construct a description of a widget with the given role and no label or value.
+    pub fn new(role: Role) -> Accessibility {
+        Accessibility { role: role, maybe_label: None, maybe_value: None }
+    }
+
+    /// Set the text that names the widget.
+    pub fn label(mut self, label: Option<&str>) -> Accessibility {
+        self.maybe_label = label.map(|label| label.to_string());
+        self
+    }
+
+    /// Set the widget's current value as text.
+    pub fn value<T: ToString>(mut self, value: T) -> Accessibility {
+        self.maybe_value = Some(value.to_string());
+        self
+    }
+
+}
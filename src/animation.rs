@@ -0,0 +1,259 @@
+
+use color::{rgba, Color};
+use std::any::Any;
+
+
+/// The curve along which an animated value travels from its start to its target.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum Ease {
+    /// Travel at a constant rate.
+    Linear,
+    /// Start slowly and accelerate.
+    QuadIn,
+    /// Start quickly and decelerate.
+    QuadOut,
+    /// Accelerate until halfway, then decelerate.
+    QuadInOut,
+    /// Like `QuadIn` but with a sharper curve.
+    CubicIn,
+    /// Like `QuadOut` but with a sharper curve.
+    CubicOut,
+    /// Like `QuadInOut` but with a sharper curve.
+    CubicInOut,
+}
+
+/// Types whose values may be smoothly interpolated between by an `Animation`.
+pub trait Tween: Copy + PartialEq {
+    /// Interpolate between `self` and `target` where a `t` of 0.0 is `self` and 1.0 is `target`.
+    fn tween(self, target: Self, t: f64) -> Self;
+}
+
+/// A value travelling from `start` to `target` over some duration.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Animation<T> {
+    start: T,
+    target: T,
+    start_ns: u64,
+    duration_ns: u64,
+    ease: Ease,
+}
+
+/// A single animated property along with the time at which its animation will complete.
+struct Entry {
+    end_ns: u64,
+    animation: Box<Any>,
+}
+
+/// Stores the animated properties of a single widget, keyed by the name of the property.
+pub struct Animations {
+    entries: Vec<(&'static str, Entry)>,
+}
+
+
+impl Ease {
+
+    /// Map the linear progress `t` (between 0.0 and 1.0) onto the easing curve.
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = if t < 0.0 { 0.0 } else if t > 1.0 { 1.0 } else { t };
+        match *self {
+            Ease::Linear => t,
+            Ease::QuadIn => t * t,
+            Ease::QuadOut => t * (2.0 - t),
+            Ease::QuadInOut => if t < 0.5 { 2.0 * t * t }
+                               else { -1.0 + (4.0 - 2.0 * t) * t },
+            Ease::CubicIn => t * t * t,
+            Ease::CubicOut => { let t = t - 1.0; t * t * t + 1.0 },
+            Ease::CubicInOut => if t < 0.5 { 4.0 * t * t * t }
+                                else { let t = 2.0 * t - 2.0; 0.5 * t * t * t + 1.0 },
+        }
+    }
+
+}
+
+
+impl Tween for f64 {
+    fn tween(self, target: f64, t: f64) -> f64 {
+        self + (target - self) * t
+    }
+}
+
+impl Tween for f32 {
+    fn tween(self, target: f32, t: f64) -> f32 {
+        self + (target - self) * t as f32
+    }
+}
+
+impl Tween for [f64; 2] {
+    fn tween(self, target: [f64; 2], t: f64) -> [f64; 2] {
+        [self[0].tween(target[0], t), self[1].tween(target[1], t)]
+    }
+}
+
+impl Tween for Color {
+    fn tween(self, target: Color, t: f64) -> Color {
+        let a = self.to_rgb();
+        let b = target.to_rgb();
+        rgba(a.0.tween(b.0, t), a.1.tween(b.1, t), a.2.tween(b.2, t), a.3.tween(b.3, t))
+    }
+}
+
+
+impl<T: Tween> Animation<T> {
+
+    /// An Animation that has already arrived at the given value.
+    pub fn at_rest(value: T) -> Animation<T> {
+        Animation { start: value, target: value, start_ns: 0, duration_ns: 0, ease: Ease::Linear }
+    }
+
+    /// Begin a new Animation at `start_ns` from `start` towards `target`, lasting `duration`
+    /// seconds.
+    pub fn new(start: T, target: T, start_ns: u64, duration: f64, ease: Ease) -> Animation<T> {
+        Animation {
+            start: start,
+            target: target,
+            start_ns: start_ns,
+            duration_ns: (duration.max(0.0) * 1_000_000_000.0) as u64,
+            ease: ease,
+        }
+    }
+
+    /// The value toward which the Animation is travelling.
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    /// The value of the Animation at the given time in nanoseconds.
+    pub fn value_at(&self, now_ns: u64) -> T {
+        if self.is_complete_at(now_ns) {
+            return self.target;
+        }
+        if now_ns <= self.start_ns {
+            return self.start;
+        }
+        let t = (now_ns - self.start_ns) as f64 / self.duration_ns as f64;
+        self.start.tween(self.target, self.ease.apply(t))
+    }
+
+    /// Whether or not the Animation has reached its target by the given time in nanoseconds.
+    pub fn is_complete_at(&self, now_ns: u64) -> bool {
+        now_ns >= self.start_ns + self.duration_ns
+    }
+
+}
+
+
+impl Animations {
+
+    /// Construct an empty store.
+    pub fn new() -> Animations {
+        Animations { entries: Vec::new() }
+    }
+
+    /// Drive the given property toward `target` and return its value at `now_ns`.
+    ///
+    /// The first time a property is animated it starts at rest at its target. Each time the
+    /// target changes, a new Animation begins from the property's current value.
+    pub fn animate<T>(&mut self,
+                      property: &'static str,
+                      target: T,
+                      duration: f64,
+                      ease: Ease,
+                      now_ns: u64) -> T
+        where
+            T: Tween + Any,
+    {
+        let maybe_index = self.entries.iter().position(|&(name, _)| name == property);
+        let maybe_current = maybe_index
+            .and_then(|i| self.entries[i].1.animation.downcast_ref::<Animation<T>>())
+            .map(|animation| *animation);
+        let animation = match maybe_current {
+            Some(animation) if animation.target() == target => return animation.value_at(now_ns),
+            Some(animation) =>
+                Animation::new(animation.value_at(now_ns), target, now_ns, duration, ease),
+            None => Animation::at_rest(target),
+        };
+        let entry = Entry {
+            end_ns: animation.start_ns + animation.duration_ns,
+            animation: Box::new(animation),
+        };
+        match maybe_index {
+            Some(i) => self.entries[i].1 = entry,
+            None => self.entries.push((property, entry)),
+        }
+        animation.value_at(now_ns)
+    }
+
+    /// Whether or not any of the properties are still travelling toward their targets at
+    /// `now_ns`.
+    pub fn is_animating(&self, now_ns: u64) -> bool {
+        self.entries.iter().any(|&(_, ref entry)| entry.end_ns > now_ns)
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, Animations, Ease};
+
+    const SEC: u64 = 1_000_000_000;
+
+    const EASES: [Ease; 7] = [Ease::Linear, Ease::QuadIn, Ease::QuadOut, Ease::QuadInOut,
+                              Ease::CubicIn, Ease::CubicOut, Ease::CubicInOut];
+
+    #[test]
+    fn every_ease_begins_and_ends_with_the_progress() {
+        for ease in EASES.iter() {
+            assert_eq!(ease.apply(0.0), 0.0);
+            assert_eq!(ease.apply(1.0), 1.0);
+            // Progress outside of the animation is clamped.
+            assert_eq!(ease.apply(-1.0), 0.0);
+            assert_eq!(ease.apply(2.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn eases_lead_or_lag_behind_linear_progress() {
+        assert_eq!(Ease::QuadIn.apply(0.5), 0.25);
+        assert_eq!(Ease::QuadOut.apply(0.5), 0.75);
+        assert_eq!(Ease::CubicIn.apply(0.5), 0.125);
+        assert_eq!(Ease::CubicOut.apply(0.5), 0.875);
+        // The in-out eases pass through the middle at the halfway point.
+        assert_eq!(Ease::QuadInOut.apply(0.5), 0.5);
+        assert_eq!(Ease::CubicInOut.apply(0.5), 0.5);
+        assert!(Ease::QuadInOut.apply(0.25) < 0.25);
+        assert!(Ease::QuadInOut.apply(0.75) > 0.75);
+    }
+
+    #[test]
+    fn animation_travels_from_start_to_target() {
+        let animation = Animation::new(0.0, 10.0, SEC, 1.0, Ease::Linear);
+        assert_eq!(animation.value_at(0), 0.0);
+        assert_eq!(animation.value_at(SEC + SEC / 2), 5.0);
+        assert!(!animation.is_complete_at(SEC + SEC / 2));
+        assert_eq!(animation.value_at(2 * SEC), 10.0);
+        assert!(animation.is_complete_at(2 * SEC));
+    }
+
+    #[test]
+    fn animation_at_rest_is_already_complete() {
+        let animation = Animation::at_rest(3.0);
+        assert_eq!(animation.value_at(0), 3.0);
+        assert!(animation.is_complete_at(0));
+    }
+
+    #[test]
+    fn retargeting_continues_from_the_current_value() {
+        let mut animations = Animations::new();
+        // The first target is arrived at immediately.
+        assert_eq!(animations.animate("x", 0.0, 1.0, Ease::Linear, 0), 0.0);
+        assert!(!animations.is_animating(0));
+        assert_eq!(animations.animate("x", 10.0, 1.0, Ease::Linear, 0), 0.0);
+        assert_eq!(animations.animate("x", 10.0, 1.0, Ease::Linear, SEC / 2), 5.0);
+        assert!(animations.is_animating(SEC / 2));
+        assert_eq!(animations.animate("x", 0.0, 1.0, Ease::Linear, SEC / 2), 5.0);
+        assert_eq!(animations.animate("x", 0.0, 1.0, Ease::Linear, 3 * SEC / 2), 0.0);
+        assert!(!animations.is_animating(3 * SEC / 2));
+    }
+
+}
@@ -0,0 +1,379 @@
+//!
+//! A `render::Backend` that draws the `Ui` using glium.
+//!
+//! Rather than drawing each `Form` individually, the `Renderer` collects every primitive produced
+//! by the `Ui` into a single vertex buffer and draws the whole `Ui` in one draw call. All glyphs
+//! are rasterised by the `GlyphCache` into a single texture atlas so that text and shapes may be
+//! drawn together. Glyphs are rasterised at the `Ui`'s scale factor (see `Ui::set_scale_factor`)
+//! so that text remains crisp on high-DPI displays.
+//!
+//! ```ignore
+//! let glyph_cache = GlyphCache::new(&font_path).unwrap();
+//! let mut ui = Ui::new(glyph_cache, Theme::default());
+//! let mut renderer = Renderer::new(&display);
+//! ...
+//! let mut target = display.draw();
+//! renderer.draw(&display, &mut target, &mut ui);
+//! target.finish();
+//! ```
+//!
+
+use ::glium::{self, Display, Program, Surface, VertexBuffer};
+use ::glium::texture::Texture2d;
+use freetype;
+use graphics::ImageSize;
+use graphics::character::{Character, CharacterCache};
+use label::FontSize;
+use render::{Backend, Primitive, Rgba};
+use std::collections::HashMap;
+use std::path::Path;
+use ui::Ui;
+
+
+/// The width of the glyph atlas in pixels. The atlas grows in height as glyphs are added.
+const ATLAS_WIDTH: u32 = 1024;
+
+/// The vertex shader, transforming window coordinates to normalised device coordinates.
+const VERTEX_SHADER: &'static str = "
+    #version 140
+
+    uniform vec2 win_dim;
+    uniform vec2 atlas_dim;
+
+    in vec2 position;
+    in vec2 tex_coords;
+    in vec4 color;
+    in float mode;
+
+    out vec2 v_tex_coords;
+    out vec4 v_color;
+    out float v_mode;
+
+    void main() {
+        gl_Position = vec4(position * 2.0 / win_dim, 0.0, 1.0);
+        v_tex_coords = tex_coords / atlas_dim;
+        v_color = color;
+        v_mode = mode;
+    }
+";
+
+/// The fragment shader, sampling the glyph atlas' coverage for textured vertices.
+const FRAGMENT_SHADER: &'static str = "
+    #version 140
+
+    uniform sampler2D atlas;
+
+    in vec2 v_tex_coords;
+    in vec4 v_color;
+    in float v_mode;
+
+    out vec4 f_color;
+
+    void main() {
+        if (v_mode == 0.0) {
+            f_color = v_color;
+        } else {
+            f_color = v_color * vec4(1.0, 1.0, 1.0, texture(atlas, v_tex_coords).a);
+        }
+    }
+";
+
+/// Vertices with this mode are filled with their color.
+const MODE_COLOR: f32 = 0.0;
+/// Vertices with this mode sample the coverage of a glyph from the atlas.
+const MODE_GLYPH: f32 = 1.0;
+
+
+/// A single vertex uploaded to the GPU.
+#[derive(Copy, Clone, Debug)]
+pub struct Vertex {
+    /// The position in window coordinates.
+    pub position: [f32; 2],
+    /// The position within the glyph atlas in pixels.
+    pub tex_coords: [f32; 2],
+    /// The color of the vertex.
+    pub color: [f32; 4],
+    /// Whether the vertex is filled with its color or samples a glyph.
+    pub mode: f32,
+}
+
+implement_vertex!(Vertex, position, tex_coords, color, mode);
+
+
+/// The region of the glyph atlas occupied by a single rasterised glyph. This is the texture type
+/// produced by the `GlyphCache`.
+#[derive(Copy, Clone, Debug)]
+pub struct Glyph {
+    /// The position of the glyph's top left corner within the atlas in pixels.
+    pub xy: [u32; 2],
+    /// The width and height of the glyph in pixels.
+    pub dim: [u32; 2],
+    /// The width and height at which the glyph is drawn in the `Ui`'s units, which is smaller
+    /// than `dim` when rasterised for a high-DPI display.
+    pub size: [u32; 2],
+}
+
+/// A `CharacterCache` that rasterises glyphs via freetype into a single texture atlas.
+pub struct GlyphCache {
+    face: freetype::Face,
+    characters: HashMap<(FontSize, char), Character<Glyph>>,
+    atlas: Atlas,
+    /// The number of pixels rasterised per unit of font size.
+    scale: f64,
+}
+
+/// The CPU side of the glyph atlas, packed in rows ("shelves") from the top left.
+struct Atlas {
+    /// The coverage of each pixel, where the first row is the top of the atlas.
+    rows: Vec<Vec<u8>>,
+    /// The position at which the next glyph will be placed.
+    cursor: [u32; 2],
+    /// The height of the tallest glyph within the current shelf.
+    shelf_h: u32,
+    /// Whether or not the atlas has changed since it was last uploaded.
+    is_dirty: bool,
+}
+
+/// Draws a `Ui` whose `CharacterCache` is a `GlyphCache`.
+pub struct Renderer {
+    program: Program,
+    atlas_texture: Texture2d,
+    atlas_dim: [u32; 2],
+    batch: Batch,
+}
+
+/// Collects the primitives produced by the `Ui` into a list of vertices.
+struct Batch {
+    vertices: Vec<Vertex>,
+    maybe_clear: Option<Rgba>,
+}
+
+
+impl ImageSize for Glyph {
+    fn get_size(&self) -> (u32, u32) {
+        (self.size[0], self.size[1])
+    }
+}
+
+
+impl GlyphCache {
+
+    /// Construct a GlyphCache for the font at the given path.
+    pub fn new(font: &Path) -> Result<GlyphCache, freetype::error::Error> {
+        let library = try!(freetype::Library::init());
+        let face = try!(library.new_face(font, 0));
+        Ok(GlyphCache {
+            face: face,
+            characters: HashMap::new(),
+            atlas: Atlas::new(),
+            scale: 1.0,
+        })
+    }
+
+    /// Set the number of pixels rasterised per unit of font size, discarding all glyphs
+    /// rasterised at the previous scale. This is called with the `Ui`'s scale factor by
+    /// `Renderer::draw`.
+    ///
+    /// Returns whether or not the scale changed, in which case the advance widths of the glyphs
+    /// may have changed too (as they are rounded to whole pixels at the new scale).
+    pub fn set_scale(&mut self, scale: f64) -> bool {
+        if scale == self.scale {
+            return false;
+        }
+        self.scale = scale;
+        self.characters.clear();
+        self.atlas = Atlas::new();
+        true
+    }
+
+    /// Rasterise the given character and add it to the atlas.
+    fn rasterise(&mut self, font_size: FontSize, ch: char) -> Character<Glyph> {
+        let scale = self.scale;
+        let pixel_size = (font_size as f64 * scale).round() as u32;
+        self.face.set_pixel_sizes(0, pixel_size).unwrap();
+        self.face.load_char(ch as usize, freetype::face::DEFAULT).unwrap();
+        let glyph = self.face.glyph().get_glyph().unwrap();
+        let bitmap_glyph = glyph.to_bitmap(freetype::render_mode::RenderMode::Normal, None)
+            .unwrap();
+        let bitmap = bitmap_glyph.bitmap();
+        let offset = [bitmap_glyph.left() as f64 / scale, bitmap_glyph.top() as f64 / scale];
+        let size = [(glyph.advance_x() >> 16) as f64 / scale,
+                    (glyph.advance_y() >> 16) as f64 / scale];
+        let dim = [bitmap.width() as u32, bitmap.rows() as u32];
+        let drawn_size = [(dim[0] as f64 / scale).round() as u32,
+                          (dim[1] as f64 / scale).round() as u32];
+        let xy = self.atlas.insert(dim, bitmap.buffer());
+        let texture = Glyph { xy: xy, dim: dim, size: drawn_size };
+        Character { offset: offset, size: size, texture: texture }
+    }
+
+}
+
+impl CharacterCache for GlyphCache {
+    type Texture = Glyph;
+
+    fn character(&mut self, font_size: FontSize, ch: char) -> &Character<Glyph> {
+        if !self.characters.contains_key(&(font_size, ch)) {
+            let character = self.rasterise(font_size, ch);
+            self.characters.insert((font_size, ch), character);
+        }
+        &self.characters[&(font_size, ch)]
+    }
+}
+
+
+impl Atlas {
+
+    /// Construct an empty atlas.
+    fn new() -> Atlas {
+        Atlas { rows: Vec::new(), cursor: [0, 0], shelf_h: 0, is_dirty: true }
+    }
+
+    /// The dimensions of the atlas in pixels.
+    fn dim(&self) -> [u32; 2] {
+        [ATLAS_WIDTH, ::std::cmp::max(self.rows.len() as u32, 1)]
+    }
+
+    /// Copy the given coverage into the atlas, returning the top left corner of its region.
+    fn insert(&mut self, dim: [u32; 2], coverage: &[u8]) -> [u32; 2] {
+        // Begin a new shelf if the glyph doesn't fit on the current one.
+        if self.cursor[0] + dim[0] > ATLAS_WIDTH {
+            self.cursor = [0, self.cursor[1] + self.shelf_h];
+            self.shelf_h = 0;
+        }
+        let xy = self.cursor;
+        let bottom = (xy[1] + dim[1]) as usize;
+        while self.rows.len() < bottom {
+            self.rows.push(vec![0; ATLAS_WIDTH as usize]);
+        }
+        for y in 0..dim[1] as usize {
+            let src = &coverage[y * dim[0] as usize..(y + 1) * dim[0] as usize];
+            let row = &mut self.rows[xy[1] as usize + y];
+            for (x, &alpha) in src.iter().enumerate() {
+                row[xy[0] as usize + x] = alpha;
+            }
+        }
+        self.cursor[0] += dim[0];
+        self.shelf_h = ::std::cmp::max(self.shelf_h, dim[1]);
+        self.is_dirty = true;
+        xy
+    }
+
+    /// The atlas as white pixels with the coverage of each glyph as their alpha.
+    fn pixels(&self) -> Vec<Vec<(u8, u8, u8, u8)>> {
+        if self.rows.is_empty() {
+            return vec![vec![(255, 255, 255, 0); ATLAS_WIDTH as usize]];
+        }
+        self.rows.iter()
+            .map(|row| row.iter().map(|&alpha| (255, 255, 255, alpha)).collect())
+            .collect()
+    }
+
+}
+
+
+impl Renderer {
+
+    /// Construct a Renderer for the given display.
+    pub fn new(display: &Display) -> Renderer {
+        let program = Program::from_source(display, VERTEX_SHADER, FRAGMENT_SHADER, None)
+            .unwrap();
+        let atlas = Atlas::new();
+        Renderer {
+            program: program,
+            atlas_texture: Texture2d::new(display, atlas.pixels()),
+            atlas_dim: atlas.dim(),
+            batch: Batch { vertices: Vec::new(), maybe_clear: None },
+        }
+    }
+
+    /// Draw the `Ui` in its current state to the given surface.
+    pub fn draw<S>(&mut self, display: &Display, surface: &mut S, ui: &mut Ui<GlyphCache>)
+        where
+            S: Surface,
+    {
+        use ::glium::index::{NoIndices, PrimitiveType};
+        use ::glium::uniforms::MagnifySamplerFilter;
+
+        // Rasterise glyphs at the physical size of the display, measuring them again if the scale
+        // has changed.
+        let scale_factor = ui.scale_factor();
+        if ui.character_cache.set_scale(scale_factor) {
+            ui.clear_glyph_widths();
+        }
+
+        // Collect the primitives of the whole Ui into a single batch of vertices.
+        self.batch.vertices.clear();
+        self.batch.maybe_clear = None;
+        ui.draw_primitives(&mut self.batch);
+
+        // Upload the atlas again if any glyphs were rasterised.
+        if ui.character_cache.atlas.is_dirty {
+            self.atlas_texture = Texture2d::new(display, ui.character_cache.atlas.pixels());
+            self.atlas_dim = ui.character_cache.atlas.dim();
+            ui.character_cache.atlas.is_dirty = false;
+        }
+
+        if let Some(color) = self.batch.maybe_clear {
+            surface.clear_color(color[0], color[1], color[2], color[3]);
+        }
+        if self.batch.vertices.is_empty() {
+            return;
+        }
+
+        let vertex_buffer = VertexBuffer::new(display, self.batch.vertices.clone());
+        let uniforms = uniform! {
+            win_dim: [ui.win_w as f32, ui.win_h as f32],
+            atlas_dim: [self.atlas_dim[0] as f32, self.atlas_dim[1] as f32],
+            atlas: self.atlas_texture.sampled().magnify_filter(MagnifySamplerFilter::Nearest),
+        };
+        let params = glium::DrawParameters {
+            blending_function: Some(glium::BlendingFunction::Addition {
+                source: glium::LinearBlendingFactor::SourceAlpha,
+                destination: glium::LinearBlendingFactor::OneMinusSourceAlpha,
+            }),
+            .. Default::default()
+        };
+        surface.draw(&vertex_buffer,
+                     &NoIndices(PrimitiveType::TrianglesList),
+                     &self.program,
+                     &uniforms,
+                     &params).unwrap();
+    }
+
+}
+
+
+impl Backend for Batch {
+    type Texture = Glyph;
+
+    fn clear(&mut self, color: Rgba) {
+        // Anything drawn before the clear would be hidden by it.
+        self.vertices.clear();
+        self.maybe_clear = Some(color);
+    }
+
+    fn primitive(&mut self, primitive: Primitive<Glyph>) {
+        match primitive {
+            Primitive::Triangles { color, vertices } => {
+                self.vertices.extend(vertices.iter().map(|xy| Vertex {
+                    position: [xy[0] as f32, xy[1] as f32],
+                    tex_coords: [0.0, 0.0],
+                    color: color,
+                    mode: MODE_COLOR,
+                }));
+            },
+            Primitive::TexturedTriangles { color, texture, vertices, uvs } => {
+                let (x, y) = (texture.xy[0] as f64, texture.xy[1] as f64);
+                let (w, h) = (texture.dim[0] as f64, texture.dim[1] as f64);
+                self.vertices.extend(vertices.iter().zip(uvs.iter()).map(|(xy, uv)| Vertex {
+                    position: [xy[0] as f32, xy[1] as f32],
+                    tex_coords: [(x + uv[0] * w) as f32, (y + uv[1] * h) as f32],
+                    color: color,
+                    mode: MODE_GLYPH,
+                }));
+            },
+        }
+    }
+
+}
@@ -0,0 +1,11 @@
+//!
+//! Reference integrations of the `Ui` with specific graphics and windowing APIs.
+//!
+//! Each backend is optional and is enabled via the cargo feature of the same name (i.e. the
+//! `glium` backend requires the `backend-glium` feature).
+//!
+
+#[cfg(feature = "backend-glium")]
+pub mod glium;
+#[cfg(feature = "backend-sdl2")]
+pub mod sdl2;
@@ -0,0 +1,161 @@
+//!
+//! Glue between the `Ui` and SDL2, for applications that manage their own SDL window rather than
+//! using piston's window backends.
+//!
+//! `convert_event` translates the SDL events that the `Ui` cares about (mouse, keyboard, text
+//! input, window focus and resizing) into piston events which are given to `Ui::handle_event`.
+//! Mouse coordinates and window dimensions are converted from SDL's window points to pixels so
+//! that they agree with the `Ui`'s scale factor on high-DPI displays, which should be set to
+//! the window's `pixels_per_point` (see `Ui::set_scale_factor`). Note that SDL only delivers
+//! text input between calls to `TextInputUtil::start` and `TextInputUtil::stop`.
+//!
+//! The `Renderer` draws the `Ui` via an SDL renderer by rasterising it in software (see
+//! `Ui::draw_to_image`) into a streaming texture which is blended over whatever has already been
+//! drawn. SDL has no means of drawing the `Ui`'s triangles directly, so applications drawing with
+//! OpenGL may prefer the glium backend.
+//!
+//! ```ignore
+//! let mut renderer = Renderer::new();
+//! 'main: loop {
+//!     for event in event_pump.poll_iter() {
+//!         handle_event(&mut ui, &event, &window);
+//!     }
+//!     ui.update(dt);
+//!     set_widgets(&mut ui);
+//!     sdl_renderer.clear();
+//!     renderer.draw(&mut sdl_renderer, &mut ui).unwrap();
+//!     sdl_renderer.present();
+//! }
+//! ```
+//!
+
+use ::sdl2::event::{Event as SdlEvent, WindowEvent};
+use ::sdl2::keyboard::Keycode;
+use ::sdl2::mouse::MouseButton as SdlMouseButton;
+use ::sdl2::pixels::PixelFormatEnum;
+use ::sdl2::render::{BlendMode, Renderer as SdlRenderer, Texture};
+use ::sdl2::video::Window;
+use graphics::character::CharacterCache;
+use piston::event::{Event, RenderArgs};
+use piston::input::{Button, Input, Motion, MouseButton};
+use piston::input::keyboard::Key;
+use render::Sample;
+use ui::Ui;
+
+
+/// The SDL pixel format whose bytes are laid out in the RGBA order produced by
+/// `Ui::draw_to_image`.
+#[cfg(target_endian = "little")]
+const PIXEL_FORMAT: PixelFormatEnum = PixelFormatEnum::ABGR8888;
+#[cfg(target_endian = "big")]
+const PIXEL_FORMAT: PixelFormatEnum = PixelFormatEnum::RGBA8888;
+
+
+/// Draws a `Ui` via an SDL renderer, reusing its texture between frames.
+pub struct Renderer {
+    maybe_texture: Option<(Texture, [u32; 2])>,
+}
+
+
+/// The number of pixels spanned by each of the given window's points, i.e. `2.0` for a high-DPI
+/// window on most displays.
+pub fn pixels_per_point(window: &Window) -> f64 {
+    let (w, _) = window.size();
+    let (draw_w, _) = window.drawable_size();
+    if w > 0 { draw_w as f64 / w as f64 } else { 1.0 }
+}
+
+/// Convert the given SDL event into the piston event understood by `Ui::handle_event`, if the
+/// `Ui` has any use for it. The window is that within which the event occurred.
+pub fn convert_event(event: &SdlEvent, window: &Window) -> Option<Event> {
+    let scale = pixels_per_point(window);
+    let input = match *event {
+        SdlEvent::MouseMotion { x, y, .. } =>
+            Input::Move(Motion::MouseCursor(x as f64 * scale, y as f64 * scale)),
+        SdlEvent::MouseWheel { x, y, .. } =>
+            Input::Move(Motion::MouseScroll(x as f64, y as f64)),
+        SdlEvent::MouseButtonDown { mouse_btn, .. } =>
+            Input::Press(Button::Mouse(mouse_button(mouse_btn))),
+        SdlEvent::MouseButtonUp { mouse_btn, .. } =>
+            Input::Release(Button::Mouse(mouse_button(mouse_btn))),
+        SdlEvent::KeyDown { keycode: Some(keycode), .. } =>
+            Input::Press(Button::Keyboard(key(keycode))),
+        SdlEvent::KeyUp { keycode: Some(keycode), .. } =>
+            Input::Release(Button::Keyboard(key(keycode))),
+        SdlEvent::TextInput { ref text, .. } => Input::Text(text.clone()),
+        SdlEvent::Window { win_event, .. } => match win_event {
+            WindowEvent::Resized(w, h) | WindowEvent::SizeChanged(w, h) =>
+                Input::Resize((w as f64 * scale) as u32, (h as f64 * scale) as u32),
+            WindowEvent::FocusGained => Input::Focus(true),
+            WindowEvent::FocusLost => Input::Focus(false),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Event::Input(input))
+}
+
+/// Give the given SDL event to the `Ui`, if the `Ui` has any use for it.
+pub fn handle_event<C>(ui: &mut Ui<C>, event: &SdlEvent, window: &Window) {
+    if let Some(event) = convert_event(event, window) {
+        ui.handle_event(&event);
+    }
+}
+
+/// The piston mouse button equivalent to the given SDL mouse button.
+fn mouse_button(button: SdlMouseButton) -> MouseButton {
+    match button {
+        SdlMouseButton::Left => MouseButton::Left,
+        SdlMouseButton::Middle => MouseButton::Middle,
+        SdlMouseButton::Right => MouseButton::Right,
+        SdlMouseButton::X1 => MouseButton::X1,
+        SdlMouseButton::X2 => MouseButton::X2,
+        _ => MouseButton::Unknown,
+    }
+}
+
+/// The piston key equivalent to the given SDL keycode. Piston's keys share SDL's key codes.
+fn key(keycode: Keycode) -> Key {
+    Key::from(keycode as u32)
+}
+
+
+impl Renderer {
+
+    /// Construct a Renderer. Its texture is created upon the first call to `draw`.
+    pub fn new() -> Renderer {
+        Renderer { maybe_texture: None }
+    }
+
+    /// End the `Ui`'s current input cycle and draw it in its current state over the whole output
+    /// of the given SDL renderer.
+    pub fn draw<C>(&mut self, renderer: &mut SdlRenderer, ui: &mut Ui<C>) -> Result<(), String>
+        where
+            C: CharacterCache,
+            C::Texture: Sample,
+    {
+        let (w, h) = try!(renderer.output_size().map_err(|e| e.to_string()));
+        if w == 0 || h == 0 {
+            return Ok(());
+        }
+
+        // Let the `Ui` know that the frame has been rendered, as a piston window would.
+        let args = RenderArgs { ext_dt: 0.0, width: w, height: h, draw_width: w, draw_height: h };
+        ui.handle_event(&Event::Render(args));
+
+        // Recreate the texture whenever the output is resized.
+        let is_stale = self.maybe_texture.as_ref().map_or(true, |&(_, dim)| dim != [w, h]);
+        if is_stale {
+            let mut texture = try!(renderer.create_texture_streaming(PIXEL_FORMAT, (w, h))
+                .map_err(|e| e.to_string()));
+            texture.set_blend_mode(BlendMode::Blend);
+            self.maybe_texture = Some((texture, [w, h]));
+        }
+
+        let pixels = ui.draw_to_image(w, h);
+        let texture = &mut self.maybe_texture.as_mut().unwrap().0;
+        try!(texture.update(None, &pixels, w as usize * 4).map_err(|e| e.to_string()));
+        renderer.copy(texture, None, None)
+    }
+
+}
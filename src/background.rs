@@ -2,12 +2,14 @@
 use color::{Color, Colorable};
 use graphics::{self, Graphics};
 use graphics::character::CharacterCache;
+use palette::{ColorRole, Paletted};
 use ui::Ui;
 
 /// A type for drawing a colored window background.
 #[derive(Copy, Clone)]
 pub struct Background {
     maybe_color: Option<Color>,
+    maybe_color_role: Option<ColorRole>,
 }
 
 impl Background {
@@ -16,6 +18,7 @@ impl Background {
     pub fn new() -> Background {
         Background {
             maybe_color: None,
+            maybe_color_role: None,
         }
     }
 
@@ -25,7 +28,9 @@ impl Background {
             B: Graphics<Texture = <C as CharacterCache>::Texture>,
             C: CharacterCache
     {
-        let color = self.maybe_color.unwrap_or(ui.theme.background_color);
+        let color = self.maybe_color
+            .or(self.maybe_color_role.map(|role| ui.theme.palette[role]))
+            .unwrap_or(ui.theme.background_color);
         graphics::clear(color.to_fsa(), graphics);
     }
 
@@ -38,3 +43,9 @@ impl Colorable for Background {
     }
 }
 
+impl Paletted for Background {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.maybe_color_role = Some(role);
+        self
+    }
+}
@@ -0,0 +1,74 @@
+//!
+//! External control bindings, i.e. for mapping the knobs and faders of a hardware controller onto
+//! value widgets.
+//!
+//! While learning, the next value widget with which the user interacts is reported to the
+//! application, which may then bind it to whichever control it likes. Thereafter the application
+//! pushes the control's values into the widget via `Ui::push_control_value`, and the widget
+//! adopts them (and reacts with them) at its next update.
+//!
+
+use std::collections::HashMap;
+use ui::UiId;
+
+
+/// Stores the learning state along with the control values awaiting each widget.
+pub struct Bindings {
+    /// The function to be called with the next widget interacted with while learning.
+    maybe_on_learn: Option<Box<FnMut(UiId)>>,
+    /// The most recent control value pushed to each widget since its last update.
+    values: HashMap<UiId, f64>,
+}
+
+
+impl Bindings {
+
+    /// Construct an empty store that is not learning.
+    pub fn new() -> Bindings {
+        Bindings { maybe_on_learn: None, values: HashMap::new() }
+    }
+
+    /// Begin learning, replacing any function given previously.
+    pub fn learn(&mut self, on_learn: Box<FnMut(UiId)>) {
+        self.maybe_on_learn = Some(on_learn);
+    }
+
+    /// Stop learning without reporting a widget.
+    pub fn cancel_learn(&mut self) {
+        self.maybe_on_learn = None;
+    }
+
+    /// Whether or not the next interacted widget will be reported.
+    pub fn is_learning(&self) -> bool {
+        self.maybe_on_learn.is_some()
+    }
+
+    /// Report the widget if learning, in which case learning ends. Returns whether or not the
+    /// widget was reported.
+    pub fn interacted(&mut self, ui_id: UiId) -> bool {
+        match self.maybe_on_learn.take() {
+            Some(mut on_learn) => {
+                on_learn(ui_id);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Store the value for the widget to adopt at its next update, replacing any value that it
+    /// has not yet adopted.
+    pub fn push(&mut self, ui_id: UiId, value: f64) {
+        self.values.insert(ui_id, value);
+    }
+
+    /// Take the value awaiting the widget, if there is one.
+    pub fn take(&mut self, ui_id: UiId) -> Option<f64> {
+        self.values.remove(&ui_id)
+    }
+
+    /// Discard the value awaiting the widget, i.e. once the widget itself has been removed.
+    pub fn remove(&mut self, ui_id: UiId) {
+        self.values.remove(&ui_id);
+    }
+
+}
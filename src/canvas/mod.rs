@@ -1,6 +1,8 @@
 
+use color::Color;
 use elmesque::Element;
-use position::{Padding, Point};
+use elmesque::form::Form;
+use position::{Dimensions, Padding, Point};
 
 pub mod split;
 
@@ -20,6 +22,9 @@ pub struct Canvas {
     pub padding: Padding,
     /// Has the Canvas been set since the last time the Ui was drawn?
     pub has_updated: bool,
+    /// The time (in nanoseconds) of the most recent pointer activity over the Canvas if it
+    /// auto-hides, along with its current opacity.
+    pub maybe_activity: Option<(u64, f32)>,
 }
 
 impl Canvas {
@@ -32,6 +37,7 @@ impl Canvas {
             padding: Padding::none(),
             kind: Kind::NoCanvas,
             has_updated: false,
+            maybe_activity: None,
         }
     }
 
@@ -58,3 +64,73 @@ impl Kind {
         }
     }
 }
+
+/// A background drawn over a Canvas' color within its frame.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum Pattern {
+    /// The image at the given path, stretched to fill the Canvas.
+    Image(String),
+    /// The image at the given path, repeated at its natural size to fill the Canvas.
+    TiledImage(String),
+    /// Alternating squares of the given size, filled with the Canvas' color and the given color.
+    /// Commonly used to indicate transparency.
+    Checkerboard(f64, Color),
+    /// Lines of the given color, spaced the given distance apart. Commonly used within editors.
+    Grid(f64, Color),
+}
+
+impl Pattern {
+
+    /// Construct the Forms for the pattern filling the given Dimensions, centred upon the origin.
+    pub fn forms(&self, dim: Dimensions) -> Vec<Form> {
+        use elmesque::element::{image, tiled_image};
+        use elmesque::form::{line, rect, solid, to_form};
+        use std::path::PathBuf;
+        let (left, bottom) = (-dim[0] / 2.0, -dim[1] / 2.0);
+        let (right, top) = (dim[0] / 2.0, dim[1] / 2.0);
+        match *self {
+            Pattern::Image(ref path) => {
+                let element = image(dim[0] as i32, dim[1] as i32, PathBuf::from(path));
+                vec![to_form(element)]
+            },
+            Pattern::TiledImage(ref path) => {
+                let element = tiled_image(dim[0] as i32, dim[1] as i32, PathBuf::from(path));
+                vec![to_form(element)]
+            },
+            Pattern::Checkerboard(size, color) => {
+                // Only every other square is drawn, as the rest show the Canvas' color.
+                let mut forms = Vec::new();
+                if size <= 0.0 { return forms }
+                let cols = (dim[0] / size).ceil() as usize;
+                let rows = (dim[1] / size).ceil() as usize;
+                for row in 0..rows {
+                    for col in 0..cols {
+                        if (row + col) % 2 == 1 { continue }
+                        let x = left + col as f64 * size;
+                        let y = top - row as f64 * size;
+                        let w = size.min(right - x);
+                        let h = size.min(y - bottom);
+                        forms.push(rect(w, h).filled(color).shift(x + w / 2.0, y - h / 2.0));
+                    }
+                }
+                forms
+            },
+            Pattern::Grid(spacing, color) => {
+                let mut forms = Vec::new();
+                if spacing <= 0.0 { return forms }
+                let mut x = left + spacing;
+                while x < right {
+                    forms.push(line(solid(color), x, bottom, x, top));
+                    x += spacing;
+                }
+                let mut y = top - spacing;
+                while y > bottom {
+                    forms.push(line(solid(color), left, y, right, y));
+                    y -= spacing;
+                }
+                forms
+            },
+        }
+    }
+
+}
@@ -1,11 +1,15 @@
 
-use color::Color;
+use color::{rgba, Color};
+use corner::{CornerRadii, Roundable};
+use frame::{self, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use gradient::{self, Gradient, Gradientable};
 use graphics::math::Scalar;
+use palette::ColorRole;
 use position::{self, Dimensions, Direction, Point};
 use theme::Theme;
 use ui::Ui;
 
-use super::{CanvasId, Kind};
+use super::{CanvasId, Kind, Pattern};
 
 /// The length of a Split.
 pub type Length = Scalar;
@@ -19,6 +23,7 @@ pub struct Split<'a> {
     id: CanvasId,
     maybe_splits: Option<(Direction, &'a [Split<'a>])>,
     maybe_length: Option<f64>,
+    maybe_auto_hide: Option<f64>,
     style: Style,
     //maybe_adjustable: Option<Bounds>,
 }
@@ -29,7 +34,14 @@ pub struct Split<'a> {
 pub struct Style {
     maybe_frame: Option<f64>,
     maybe_frame_color: Option<Color>,
+    maybe_frame_sides: Option<FrameSides>,
+    maybe_frame_widths: Option<FrameWidths>,
+    maybe_frame_style: Option<FrameStyle>,
+    maybe_corner_radii: Option<CornerRadii>,
+    maybe_gradient: Option<Gradient>,
     maybe_color: Option<Color>,
+    maybe_color_role: Option<ColorRole>,
+    maybe_pattern: Option<Pattern>,
     padding: Padding,
     margin: Margin,
 }
@@ -63,9 +75,18 @@ impl Style {
 
     /// Get the color for the Split's Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_canvas_split.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.background_color)
-        })).unwrap_or(theme.background_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_canvas_split.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.background_color)
+            })).unwrap_or(theme.background_color)
+    }
+
+    /// Get the Pattern drawn over the color of the Split's Element, if there is one.
+    pub fn pattern(&self, theme: &Theme) -> Option<Pattern> {
+        self.maybe_pattern.clone().or(theme.maybe_canvas_split.as_ref().and_then(|style| {
+            style.maybe_pattern.clone()
+        }))
     }
 
     /// Get the frame for an Element.
@@ -82,6 +103,41 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_canvas_split.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_canvas_split.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_canvas_split.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of the Split's Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_canvas_split.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
+    /// Get the Gradient with which the Split's Element is filled, if there is one.
+    pub fn gradient(&self, theme: &Theme) -> Option<Gradient> {
+        self.maybe_gradient
+            .or(theme.maybe_canvas_split.as_ref().and_then(|style| style.maybe_gradient))
+            .or(theme.maybe_gradient)
+    }
+
     /// Get the Padding for the Canvas Split.
     pub fn padding(&self, theme: &Theme) -> position::Padding {
         position::Padding {
@@ -129,6 +185,7 @@ impl<'a> Split<'a> {
             id: id,
             maybe_splits: None,
             maybe_length: None,
+            maybe_auto_hide: None,
             //maybe_adjustable: None,
             style: Style::new(),
         }
@@ -166,6 +223,12 @@ impl<'a> Split<'a> {
         self.flow(Direction::Left, splits)
     }
 
+    /// Set a Pattern (i.e. an image, checkerboard or grid) to be drawn over the Split's color.
+    pub fn pattern(mut self, pattern: Pattern) -> Split<'a> {
+        self.style.maybe_pattern = Some(pattern);
+        self
+    }
+
     /// Set the padding from the left edge.
     pub fn pad_left(mut self, pad: Scalar) -> Split<'a> {
         self.style.padding.maybe_left = Some(pad);
@@ -224,6 +287,15 @@ impl<'a> Split<'a> {
         self.margin_left(pad).margin_right(pad).margin_top(pad).margin_bottom(pad)
     }
 
+    /// Fade the Split out once the given number of seconds pass without pointer activity over it,
+    /// i.e. for the toolbars of a media player. It reappears as soon as the pointer moves over it
+    /// again (see `Ui::auto_hide_canvas`). The widgets upon it may be faded along with it via
+    /// `Ui::canvas_opacity`.
+    pub fn auto_hide(mut self, seconds: f64) -> Split<'a> {
+        self.maybe_auto_hide = Some(seconds);
+        self
+    }
+
     /// Store the Canvas and it's children within the `Ui`. Each Canvas can be accessed via it's
     /// unique identifier `CanvasId`.
     pub fn set<C>(self, ui: &mut Ui<C>) {
@@ -233,19 +305,36 @@ impl<'a> Split<'a> {
 
     /// Construct a Canvas from a Split.
     fn into_ui<C>(&self, dim: Dimensions, xy: Point, ui: &mut Ui<C>) {
-        use elmesque::form::{rect, collage};
+        use elmesque::form::collage;
         use vecmath::{vec2_add, vec2_sub, vec2_scale};
-        let Split { id, ref maybe_splits, ref style, .. } = *self;
+        let Split { id, ref maybe_splits, maybe_auto_hide, ref style, .. } = *self;
 
-        let color = style.color(&ui.theme);
-        let frame_color = style.frame_color(&ui.theme);
         let frame = style.frame(&ui.theme);
         let pad = style.padding(&ui.theme);
         let mgn = style.margin(&ui.theme);
 
         let mgn_offset = [(mgn.left - mgn.right), (mgn.bottom - mgn.top)];
         let dim = vec2_sub(dim, [mgn.left + mgn.right, mgn.top + mgn.bottom]);
-        let frame_dim = vec2_sub(dim, [frame * 2.0; 2]);
+
+        // Fade the colors along with the Split if it auto-hides.
+        let opacity = match maybe_auto_hide {
+            Some(secs) => ui.auto_hide_canvas(id, vec2_add(xy, mgn_offset), dim, secs),
+            None => 1.0,
+        };
+        let color = fade(style.color(&ui.theme), opacity);
+        let maybe_pattern = style.pattern(&ui.theme).and_then(|pattern| match pattern {
+            Pattern::Checkerboard(size, color) =>
+                Some(Pattern::Checkerboard(size, fade(color, opacity))),
+            Pattern::Grid(spacing, color) => Some(Pattern::Grid(spacing, fade(color, opacity))),
+            // Images cannot be faded, so they are hidden along with the Split.
+            image => if opacity > 0.0 { Some(image) } else { None },
+        });
+        let frame_color = fade(style.frame_color(&ui.theme), opacity);
+        let maybe_gradient = style.gradient(&ui.theme).map(|gradient| match gradient {
+            Gradient::To(direction, end) => Gradient::To(direction, fade(end, opacity)),
+            shade => shade,
+        });
+        let (frame_offset, frame_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
         let pad_offset = [(pad.bottom - pad.top), (pad.left - pad.right)];
         let pad_dim = vec2_sub(frame_dim, [pad.left + pad.right, pad.top + pad.bottom]);
 
@@ -255,7 +344,7 @@ impl<'a> Split<'a> {
         if let Some((direction, splits)) = *maybe_splits {
             use position::{align_top_of, align_bottom_of, align_left_of, align_right_of};
             // Offset xy so that it is in the center of the padded area.
-            let xy = vec2_add(xy, pad_offset);
+            let xy = vec2_add(vec2_add(xy, frame_offset), pad_offset);
             let num = splits.len() as f64;
             let split_dim = match splits.len() {
                 0 => [0.0, 0.0],
@@ -284,10 +373,18 @@ impl<'a> Split<'a> {
             }
         }
 
-        let frame_form = rect(dim[0], dim[1]).filled(frame_color);
-        let inner_form = rect(frame_dim[0], frame_dim[1]).filled(color);
+        let radii = style.corner_radii(&ui.theme);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let inner_form = gradient::filled_rect(frame_dim, radii.inset(frame), color,
+                                               maybe_gradient);
+        let pattern_forms = maybe_pattern.map(|pattern| pattern.forms(frame_dim))
+            .unwrap_or_else(Vec::new);
+        let inner_forms = Some(inner_form).into_iter()
+            .chain(pattern_forms.into_iter())
+            .map(|form| form.shift(frame_offset[0], frame_offset[1]));
         let form_chain = Some(frame_form).into_iter()
-            .chain(Some(inner_form).into_iter())
+            .chain(inner_forms)
             .map(|form| form.shift(xy[0], xy[1]));
 
         let element = collage(frame_dim[0] as i32, frame_dim[1] as i32, form_chain.collect());
@@ -298,13 +395,27 @@ impl<'a> Split<'a> {
 }
 
 
+/// The given color with its alpha scaled by the given opacity.
+fn fade(color: Color, opacity: f32) -> Color {
+    let c = color.to_rgb();
+    rgba(c.0, c.1, c.2, c.3 * opacity)
+}
+
+
 impl Style {
     /// Construct a default Style.
     pub fn new() -> Style {
         Style {
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
+            maybe_gradient: None,
             maybe_color: None,
+            maybe_color_role: None,
+            maybe_pattern: None,
             padding: Padding::new(),
             margin: Margin::new(),
         }
@@ -343,6 +454,13 @@ impl<'a> ::color::Colorable for Split<'a> {
     }
 }
 
+impl<'a> ::palette::Paletted for Split<'a> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
 impl<'a> ::frame::Frameable for Split<'a> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -352,5 +470,30 @@ impl<'a> ::frame::Frameable for Split<'a> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
 }
 
+impl<'a> Roundable for Split<'a> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
+}
+
+impl<'a> Gradientable for Split<'a> {
+    fn gradient(mut self, gradient: Gradient) -> Self {
+        self.style.maybe_gradient = Some(gradient);
+        self
+    }
+}
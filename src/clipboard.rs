@@ -0,0 +1,35 @@
+
+/// A place to which text may be copied and from which it may be pasted.
+///
+/// The `Ui` uses a `MemoryClipboard` by default, which only shares text between the widgets of
+/// a single `Ui`. Implement this trait for a type that talks to your platform's clipboard and pass
+/// it to `Ui::set_clipboard` to share text with other applications.
+pub trait Clipboard {
+    /// Return the text currently stored within the clipboard, if there is any.
+    fn get(&mut self) -> Option<String>;
+    /// Replace the contents of the clipboard with the given text.
+    fn set(&mut self, text: String);
+}
+
+/// A Clipboard that simply stores its text in memory.
+#[derive(Clone, Debug)]
+pub struct MemoryClipboard {
+    maybe_text: Option<String>,
+}
+
+
+impl MemoryClipboard {
+    /// Construct an empty MemoryClipboard.
+    pub fn new() -> MemoryClipboard {
+        MemoryClipboard { maybe_text: None }
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get(&mut self) -> Option<String> {
+        self.maybe_text.clone()
+    }
+    fn set(&mut self, text: String) {
+        self.maybe_text = Some(text);
+    }
+}
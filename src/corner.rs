@@ -0,0 +1,183 @@
+//!
+//! Rounding of the corners of the rectangles drawn by widgets, each corner with its own radius and
+//! either a circular or superellipse ("squircle") profile.
+//!
+
+use elmesque::form::{polygon, rect, Shape};
+use position::Dimensions;
+use std::f64::consts::PI;
+
+
+/// The profile of each rounded corner of a rectangle.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum CornerProfile {
+    /// Each corner is a quarter circle.
+    Circular,
+    /// Each corner is a quarter of a superellipse with the given exponent. `4.0` gives the
+    /// familiar "squircle", while greater exponents give squarer corners. An exponent of `2.0` is
+    /// the same as `Circular`.
+    Superellipse(f64),
+}
+
+/// The radius of each corner of a rectangle, along with the profile of the rounded corners.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct CornerRadii {
+    pub top_left: f64,
+    pub top_right: f64,
+    pub bottom_left: f64,
+    pub bottom_right: f64,
+    pub profile: CornerProfile,
+}
+
+/// Widgets whose corners may be rounded.
+pub trait Roundable: Sized {
+
+    /// Set the radius of each of the widget's corners, along with their profile.
+    fn corner_radii(self, radii: CornerRadii) -> Self;
+
+    /// Round each of the widget's corners with the given radius.
+    fn corner_radius(self, radius: f64) -> Self {
+        self.corner_radii(CornerRadii::all(radius))
+    }
+
+}
+
+
+impl CornerProfile {
+
+    /// The exponent of the superellipse describing the profile.
+    fn exponent(&self) -> f64 {
+        match *self {
+            CornerProfile::Circular => 2.0,
+            CornerProfile::Superellipse(exponent) => exponent.max(::std::f64::EPSILON),
+        }
+    }
+
+}
+
+
+impl CornerRadii {
+
+    /// Square corners.
+    pub fn none() -> CornerRadii {
+        CornerRadii::all(0.0)
+    }
+
+    /// Round all corners with the given radius.
+    pub fn all(radius: f64) -> CornerRadii {
+        CornerRadii {
+            top_left: radius,
+            top_right: radius,
+            bottom_left: radius,
+            bottom_right: radius,
+            profile: CornerProfile::Circular,
+        }
+    }
+
+    /// Round only the top corners with the given radius, i.e. for a tab.
+    pub fn top(radius: f64) -> CornerRadii {
+        CornerRadii { bottom_left: 0.0, bottom_right: 0.0, ..CornerRadii::all(radius) }
+    }
+
+    /// Round only the bottom corners with the given radius.
+    pub fn bottom(radius: f64) -> CornerRadii {
+        CornerRadii { top_left: 0.0, top_right: 0.0, ..CornerRadii::all(radius) }
+    }
+
+    /// Round only the left corners with the given radius.
+    pub fn left(radius: f64) -> CornerRadii {
+        CornerRadii { top_right: 0.0, bottom_right: 0.0, ..CornerRadii::all(radius) }
+    }
+
+    /// Round only the right corners with the given radius.
+    pub fn right(radius: f64) -> CornerRadii {
+        CornerRadii { top_left: 0.0, bottom_left: 0.0, ..CornerRadii::all(radius) }
+    }
+
+    /// Use the given profile for the rounded corners.
+    pub fn profile(self, profile: CornerProfile) -> CornerRadii {
+        CornerRadii { profile: profile, ..self }
+    }
+
+    /// Use the "squircle" profile (a superellipse with an exponent of `4.0`) for the rounded
+    /// corners.
+    pub fn squircle(self) -> CornerRadii {
+        self.profile(CornerProfile::Superellipse(4.0))
+    }
+
+    /// Whether or not all corners are square.
+    pub fn is_square(&self) -> bool {
+        self.top_left <= 0.0 && self.top_right <= 0.0
+            && self.bottom_left <= 0.0 && self.bottom_right <= 0.0
+    }
+
+    /// The radii of the corners of a rectangle inset by the given width, i.e. the area within a
+    /// frame, so that it follows the curve of the outer corners.
+    pub fn inset(&self, width: f64) -> CornerRadii {
+        let inset = |radius: f64| (radius - width).max(0.0);
+        CornerRadii {
+            top_left: inset(self.top_left),
+            top_right: inset(self.top_right),
+            bottom_left: inset(self.bottom_left),
+            bottom_right: inset(self.bottom_right),
+            profile: self.profile,
+        }
+    }
+
+}
+
+impl From<f64> for CornerRadii {
+    fn from(radius: f64) -> CornerRadii {
+        CornerRadii::all(radius)
+    }
+}
+
+
+/// A rectangle with the given dimensions and corners, centred upon the origin.
+///
+/// Each rounded corner is tessellated into a number of segments depending upon its radius. Radii
+/// are limited to half of the rectangle's shortest side. Where all corners are square, this is
+/// the same as `elmesque::form::rect`.
+pub fn rounded_rect(dim: Dimensions, radii: CornerRadii) -> Shape {
+    if radii.is_square() {
+        return rect(dim[0], dim[1]);
+    }
+    polygon(rounded_rect_points(dim, radii))
+}
+
+/// The outline of a rectangle with the given dimensions and corners, centred upon the origin,
+/// anticlockwise from its top right corner. The outline is always convex.
+pub fn rounded_rect_points(dim: Dimensions, radii: CornerRadii) -> Vec<(f64, f64)> {
+    let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+    let max_radius = half_w.min(half_h).max(0.0);
+    let power = 2.0 / radii.profile.exponent();
+
+    // Each corner's radius, the signs of its position and the angle at which its arc begins,
+    // anticlockwise from the top right.
+    let corners = [
+        (radii.top_right, 1.0, 1.0, 0.0),
+        (radii.top_left, -1.0, 1.0, PI / 2.0),
+        (radii.bottom_left, -1.0, -1.0, PI),
+        (radii.bottom_right, 1.0, -1.0, PI * 1.5),
+    ];
+
+    let mut points = Vec::new();
+    for &(radius, sign_x, sign_y, start) in corners.iter() {
+        let radius = radius.max(0.0).min(max_radius);
+        let centre_x = sign_x * (half_w - radius);
+        let centre_y = sign_y * (half_h - radius);
+        if radius == 0.0 {
+            points.push((centre_x, centre_y));
+            continue;
+        }
+        let segments = (radius / 2.0).ceil().max(2.0).min(16.0) as usize;
+        for i in 0..segments + 1 {
+            let angle = start + (i as f64 / segments as f64) * PI / 2.0;
+            let (cos, sin) = (angle.cos(), angle.sin());
+            let x = cos.signum() * cos.abs().powf(power);
+            let y = sin.signum() * sin.abs().powf(power);
+            points.push((centre_x + radius * x, centre_y + radius * y));
+        }
+    }
+    points
+}
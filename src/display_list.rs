@@ -0,0 +1,127 @@
+//!
+//! The list of Forms drawn by the `Ui` each frame.
+//!
+//! Rather than rendering a collage for every widget, the `Ui` appends the Forms of every widget
+//! updated since the last frame to a single display list ordered from back to front, layer by
+//! layer, with the widgets capturing the keyboard and mouse drawn above all others within their
+//! layer. Consecutive widgets drawn with the same font and clipping rectangle are grouped into a
+//! batch whose Forms are drawn as a single collage by a single renderer.
+//!
+//! The display list is retained by the `Ui` so that its sorting buffer is reused between frames.
+//!
+
+use elmesque::form::Form;
+use font::FontId;
+use position::{Depth, Dimensions, Layer, Point};
+use std::cmp::Ordering;
+use ui::UiId;
+use widget;
+
+
+/// A run of consecutive Forms within the display list that share a font and clipping rectangle.
+#[derive(Copy, Clone, Debug)]
+pub struct Batch {
+    /// The font with which the Forms are drawn.
+    pub font: FontId,
+    /// The rectangle to which the Forms are cropped, if any.
+    pub maybe_clip: Option<(Point, Dimensions)>,
+    /// The index of the batch's first Form within the display list.
+    start: usize,
+}
+
+/// A widget's position within the drawing order.
+#[derive(Copy, Clone, Debug)]
+struct Entry {
+    layer: Layer,
+    /// Captured widgets are drawn above all others within their layer.
+    capture: u8,
+    depth: Depth,
+    set_order: u64,
+    ui_id: UiId,
+}
+
+/// The Forms to be drawn this frame, ordered from back to front and grouped into batches.
+#[derive(Debug)]
+pub struct DisplayList {
+    entries: Vec<Entry>,
+    forms: Vec<Form>,
+    batches: Vec<Batch>,
+}
+
+
+impl DisplayList {
+
+    /// Construct an empty display list.
+    pub fn new() -> DisplayList {
+        DisplayList {
+            entries: Vec::new(),
+            forms: Vec::new(),
+            batches: Vec::new(),
+        }
+    }
+
+    /// Rebuild the list from every widget within the cache that has been updated since the last
+    /// frame. Within each layer, the widgets capturing the keyboard and then the mouse are drawn
+    /// last.
+    pub fn build(&mut self,
+                 widget_cache: &[widget::Cached],
+                 maybe_keyboard: Option<UiId>,
+                 maybe_mouse: Option<UiId>) {
+        let DisplayList { ref mut entries, ref mut forms, ref mut batches } = *self;
+        entries.clear();
+        forms.clear();
+        batches.clear();
+
+        entries.extend(widget_cache.iter().enumerate()
+            .filter(|&(_, widget)| widget.has_updated)
+            .map(|(ui_id, widget)| {
+                let capture = if Some(ui_id) == maybe_mouse { 2 }
+                              else if Some(ui_id) == maybe_keyboard { 1 }
+                              else { 0 };
+                Entry {
+                    layer: widget.layer,
+                    capture: capture,
+                    depth: widget.depth,
+                    set_order: widget.set_order,
+                    ui_id: ui_id,
+                }
+            }));
+
+        // Lower layers first, then captured widgets last within each layer, then greater depths
+        // first (as greater depths are further back), then in the order in which they were set.
+        entries.sort_by(|a, b| match (a.layer, a.capture).cmp(&(b.layer, b.capture)) {
+            Ordering::Equal => match b.depth.partial_cmp(&a.depth) {
+                Some(Ordering::Equal) | None => a.set_order.cmp(&b.set_order),
+                Some(ordering) => ordering,
+            },
+            ordering => ordering,
+        });
+
+        for entry in entries.iter() {
+            let widget = &widget_cache[entry.ui_id];
+            if widget.forms.is_empty() {
+                continue;
+            }
+            let start = forms.len();
+            forms.extend(widget.forms.iter().cloned());
+            if let Some(last) = batches.last() {
+                if last.font == widget.font && last.maybe_clip == widget.maybe_clip {
+                    continue;
+                }
+            }
+            batches.push(Batch { font: widget.font, maybe_clip: widget.maybe_clip, start: start });
+        }
+    }
+
+    /// Take the Forms of each batch, from back to front, leaving the list empty until it is next
+    /// built.
+    pub fn take_batches(&mut self) -> Vec<(Batch, Vec<Form>)> {
+        let mut forms = ::std::mem::replace(&mut self.forms, Vec::new());
+        let mut batches: Vec<_> = self.batches.drain(..).rev()
+            .map(|batch| (batch, forms.split_off(batch.start)))
+            .collect();
+        batches.reverse();
+        batches
+    }
+
+}
@@ -0,0 +1,90 @@
+//!
+//! Enabling and disabling of widgets, along with the treatment with which disabled widgets are
+//! drawn so that they are distinguishable from those that accept user input.
+//!
+//! Value widgets may also be made read-only, i.e. for dashboards displaying values that are
+//! controlled elsewhere. Unlike disabled widgets, read-only widgets are not "temporarily
+//! unavailable" and so are drawn at full strength, only without hover or click feedback.
+//!
+
+use color::{rgba, Color};
+use elmesque::form::Form;
+use position::Dimensions;
+use theme::Theme;
+
+
+/// Widgets that may be disabled, in which case they ignore user input and are drawn with the
+/// theme's `disabled` treatment.
+pub trait Enableable: Sized {
+
+    /// If true, will allow user inputs.  If false, will disallow user inputs.
+    fn enabled(self, flag: bool) -> Self;
+
+    /// Disallow user inputs.
+    fn disabled(self) -> Self {
+        self.enabled(false)
+    }
+
+}
+
+/// Widgets that may display a value controlled elsewhere, in which case they ignore user input
+/// and are drawn without hover or click feedback (and with a lock icon if the theme's
+/// `read_only_lock` is set).
+pub trait ReadOnly: Sized {
+
+    /// If true, the value is displayed but may not be changed by the user.
+    fn read_only(self, flag: bool) -> Self;
+
+}
+
+/// The treatment with which the colors of disabled widgets are drawn.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Disabled {
+    /// The saturation of the colors, where `0.0` is entirely grey and `1.0` is unchanged.
+    pub saturation: f32,
+    /// How far the colors are faded towards the theme's background color, where `0.0` is
+    /// unchanged and `1.0` is the background color itself.
+    pub fade: f32,
+}
+
+
+/// The given color as drawn by a widget that is either enabled or not, i.e. greyed and faded
+/// towards the background via the theme's `disabled` treatment if the widget is disabled.
+pub fn color(color: Color, enabled: bool, theme: &Theme) -> Color {
+    if enabled {
+        return color;
+    }
+    let c = color.to_rgb();
+    let bg = theme.background_color.to_rgb();
+    let Disabled { saturation, fade } = theme.disabled;
+    let grey = c.0 * 0.3 + c.1 * 0.59 + c.2 * 0.11;
+    let treat = |channel: f32, bg: f32| {
+        let desaturated = grey + (channel - grey) * saturation;
+        desaturated + (bg - desaturated) * fade
+    };
+    rgba(treat(c.0, bg.0), treat(c.1, bg.1), treat(c.2, bg.2), c.3)
+}
+
+/// The Forms of a small lock icon in the top right corner within the frame of a widget with the
+/// given dimensions (centred upon the origin), or none if the widget is not read-only or the
+/// theme's `read_only_lock` is unset.
+pub fn lock_forms(read_only: bool, dim: Dimensions, frame: f64, color: Color, theme: &Theme)
+    -> Vec<Form>
+{
+    use elmesque::form::{line, rect, solid};
+    const SIZE: f64 = 8.0;
+    if !read_only || !theme.read_only_lock {
+        return Vec::new();
+    }
+    let (x, y) = (dim[0] / 2.0 - frame - SIZE, dim[1] / 2.0 - frame - SIZE);
+    let body_h = SIZE * 0.6;
+    let body_top = y - SIZE / 2.0 + body_h;
+    let (shackle_l, shackle_r, shackle_top) = (x - SIZE / 4.0, x + SIZE / 4.0, y + SIZE / 2.0);
+    let shackle_style = solid(color).width(1.5);
+    vec![
+        rect(SIZE, body_h).filled(color).shift(x, body_top - body_h / 2.0),
+        line(shackle_style.clone(), shackle_l, body_top, shackle_l, shackle_top),
+        line(shackle_style.clone(), shackle_r, body_top, shackle_r, shackle_top),
+        line(shackle_style, shackle_l, shackle_top, shackle_r, shackle_top),
+    ]
+}
@@ -0,0 +1,154 @@
+//!
+//! A module for logging the input received by a `Ui` along with the interactions that followed
+//! (which widget captured the mouse or keyboard, where focus moved, which transients were
+//! dismissed, etc), in order to diagnose why some interaction didn't behave as expected.
+//!
+//! Logging is started via `Ui::enable_event_log` (or simply by setting an `EventMonitor` widget,
+//! which displays the log) and the most recent entries are available via `Ui::event_log`.
+//!
+
+use record::Input;
+use std::collections::VecDeque;
+use std::fmt;
+use ui::UiId;
+
+
+/// The number of entries kept by a log enabled by an `EventMonitor`.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// Something that happened within the `Ui`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// Some input was received via `Ui::handle_event`. Consecutive mouse movements are merged
+    /// into a single entry.
+    Input(Input),
+    /// The left mouse button was pressed over the given widgets (as of their last update).
+    PressedOver(Vec<UiId>),
+    /// The widget captured the mouse.
+    MouseCaptured(UiId),
+    /// The widget released the mouse.
+    MouseUncaptured(UiId),
+    /// The widget tried to capture the mouse while it was already captured by another widget.
+    MouseCaptureRefused(UiId),
+    /// The widget captured the keyboard.
+    KeyboardCaptured(UiId),
+    /// The widget released the keyboard.
+    KeyboardUncaptured(UiId),
+    /// The widget tried to capture the keyboard while it was already captured by another widget.
+    KeyboardCaptureRefused(UiId),
+    /// The widget was given keyboard focus, or focus was lost.
+    Focused(Option<UiId>),
+    /// The transient widget (i.e. a menu) was dismissed.
+    Dismissed(UiId),
+    /// The widget began dragging a payload.
+    DragStarted(UiId),
+    /// A payload being dragged was dropped over a target.
+    Dropped,
+    /// The widget was reported while learning an external control binding.
+    BindingLearned(UiId),
+}
+
+/// An `Event` along with when it happened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// The frame number of the `Ui` at which the event happened.
+    pub frame: u64,
+    /// The time in nanoseconds at which the event happened.
+    pub time_ns: u64,
+    /// The event itself.
+    pub event: Event,
+}
+
+/// The most recent events within a `Ui`, oldest first.
+#[derive(Clone, Debug)]
+pub struct EventLog {
+    capacity: usize,
+    entries: VecDeque<Entry>,
+}
+
+
+impl EventLog {
+
+    /// Construct an empty log keeping at most `capacity` entries.
+    pub fn new(capacity: usize) -> EventLog {
+        EventLog { capacity: capacity, entries: VecDeque::with_capacity(capacity) }
+    }
+
+    /// Add an entry to the log, discarding the oldest entry if the log is full.
+    pub fn push(&mut self, entry: Entry) {
+        if let Event::Input(Input::Move(_)) = entry.event {
+            let last_was_move = match self.entries.back() {
+                Some(&Entry { event: Event::Input(Input::Move(_)), .. }) => true,
+                _ => false,
+            };
+            if last_was_move {
+                self.entries.pop_back();
+            }
+        }
+        if self.capacity == 0 { return }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// The entries within the log, oldest first.
+    pub fn entries(&self) -> ::std::collections::vec_deque::Iter<Entry> {
+        self.entries.iter()
+    }
+
+    /// The maximum number of entries kept by the log.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of entries within the log.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Remove all entries from the log.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+}
+
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::Input(Input::Move(xy)) => write!(f, "Mouse moved to {:.0}, {:.0}", xy[0], xy[1]),
+            Event::Input(Input::Scroll(xy)) => write!(f, "Scrolled {}, {}", xy[0], xy[1]),
+            Event::Input(Input::Press(button)) => write!(f, "Pressed {:?}", button),
+            Event::Input(Input::Release(button)) => write!(f, "Released {:?}", button),
+            Event::Input(Input::Text(ref text)) => write!(f, "Entered text {:?}", text),
+            Event::Input(Input::Focus(true)) => write!(f, "Window gained focus"),
+            Event::Input(Input::Focus(false)) => write!(f, "Window lost focus"),
+            Event::PressedOver(ref ui_ids) if ui_ids.is_empty() =>
+                write!(f, "Pressed over no widget"),
+            Event::PressedOver(ref ui_ids) => write!(f, "Pressed over widgets {:?}", ui_ids),
+            Event::MouseCaptured(ui_id) => write!(f, "Widget {} captured the mouse", ui_id),
+            Event::MouseUncaptured(ui_id) => write!(f, "Widget {} released the mouse", ui_id),
+            Event::MouseCaptureRefused(ui_id) =>
+                write!(f, "Widget {} could not capture the mouse", ui_id),
+            Event::KeyboardCaptured(ui_id) => write!(f, "Widget {} captured the keyboard", ui_id),
+            Event::KeyboardUncaptured(ui_id) =>
+                write!(f, "Widget {} released the keyboard", ui_id),
+            Event::KeyboardCaptureRefused(ui_id) =>
+                write!(f, "Widget {} could not capture the keyboard", ui_id),
+            Event::Focused(Some(ui_id)) => write!(f, "Widget {} gained focus", ui_id),
+            Event::Focused(None) => write!(f, "Focus lost"),
+            Event::Dismissed(ui_id) => write!(f, "Widget {} was dismissed", ui_id),
+            Event::DragStarted(ui_id) => write!(f, "Widget {} started a drag", ui_id),
+            Event::Dropped => write!(f, "Payload dropped"),
+            Event::BindingLearned(ui_id) => write!(f, "Widget {} was bound to a control", ui_id),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.frame, self.event)
+    }
+}
@@ -0,0 +1,214 @@
+//!
+//! The registry of fonts available to the `Ui` along with the fallback chains used to find glyphs
+//! that are missing from a font.
+//!
+//! The `CharacterCache` given to `Ui::new` is the default font, identified by `DEFAULT_FONT`.
+//! Further fonts may be added via `Ui::add_font`, and a font may be chosen for any widget that
+//! displays text via `Fontable::font` (or for all widgets via `Theme::font`).
+//!
+//! A widget's text is measured and drawn using the widget's font. Whenever the font has no glyph
+//! for some character, the fonts within its fallback chain (see `Ui::set_font_fallbacks`) are
+//! tried in order, and the first that has the glyph is used.
+//!
+
+use graphics::character::{Character, CharacterCache};
+use label::FontSize;
+use std::collections::HashMap;
+
+
+/// Uniquely identifies a font that has been added to the `Ui`.
+pub type FontId = usize;
+
+/// The font given to `Ui::new`.
+pub const DEFAULT_FONT: FontId = 0;
+
+/// The fonts added to the `Ui` in addition to its default font.
+pub struct Fonts<C> {
+    /// The glyph cache for each font, where the font with id `n` is at index `n - 1`.
+    caches: Vec<C>,
+    /// The fallback chain for each font, indexed by FontId.
+    fallbacks: Vec<Vec<FontId>>,
+    /// The advance width of each glyph measured so far, keyed by the font (prior to fallback),
+    /// font size and character. Cleared whenever the fonts or their fallbacks change.
+    widths: HashMap<(FontId, FontSize, char), f64>,
+}
+
+/// A `CharacterCache` that produces glyphs from some font, falling back to the font's fallback
+/// chain for any glyph that the font is missing. Used to draw each widget's `Element` with the
+/// widget's font.
+pub struct Chain<'a, C: 'a> {
+    default: &'a mut C,
+    fonts: &'a mut Fonts<C>,
+    font: FontId,
+}
+
+/// Widgets whose text may be displayed using some font.
+pub trait Fontable: Sized {
+    /// Set the font with which the widget's text is displayed.
+    fn font(self, font: FontId) -> Self;
+}
+
+
+impl<C> Fonts<C> {
+
+    /// Construct an empty registry.
+    pub fn new() -> Fonts<C> {
+        Fonts { caches: Vec::new(), fallbacks: vec![Vec::new()], widths: HashMap::new() }
+    }
+
+    /// Add the font with the given glyph cache, returning its unique identifier.
+    pub fn add(&mut self, cache: C) -> FontId {
+        self.caches.push(cache);
+        self.fallbacks.push(Vec::new());
+        self.widths.clear();
+        self.caches.len()
+    }
+
+    /// Whether or not a font with the given id exists.
+    pub fn contains(&self, font: FontId) -> bool {
+        font <= self.caches.len()
+    }
+
+    /// Set the fonts that are tried in order whenever the given font has no glyph for some
+    /// character.
+    pub fn set_fallbacks(&mut self, font: FontId, fallbacks: &[FontId]) {
+        if !self.contains(font) {
+            panic!("Tried to set the fallbacks of the font {:?}, however no such font has been \
+                    added to the `Ui`.", font);
+        }
+        self.fallbacks[font] = fallbacks.to_vec();
+        self.widths.clear();
+    }
+
+    /// Forget the measured width of every glyph. This should be called if the glyphs produced by
+    /// any of the fonts' caches change (i.e. the default cache is replaced).
+    pub fn clear_widths(&mut self) {
+        self.widths.clear();
+    }
+
+    /// The glyph cache for the given font, where `default` is the `Ui`'s default font. An unknown
+    /// font produces the default font.
+    pub fn cache_mut<'a>(&'a mut self, default: &'a mut C, font: FontId) -> &'a mut C {
+        match font {
+            DEFAULT_FONT => default,
+            _ => match self.caches.get_mut(font - 1) {
+                Some(cache) => cache,
+                None => default,
+            },
+        }
+    }
+
+    /// The first font within the given font's fallback chain (beginning with the font itself)
+    /// that has a glyph for the given character. If no font has the glyph, the given font is
+    /// returned so that its replacement glyph is drawn.
+    ///
+    /// A glyph is considered missing if it has no advance width, unless the character is
+    /// whitespace.
+    pub fn resolve(&mut self, default: &mut C, font: FontId, size: FontSize, ch: char) -> FontId
+        where
+            C: CharacterCache,
+    {
+        if ch.is_whitespace() || !self.contains(font) {
+            return font;
+        }
+        let chain: Vec<FontId> = Some(font).into_iter()
+            .chain(self.fallbacks[font].iter().map(|&id| id))
+            .collect();
+        for &id in chain.iter() {
+            if self.cache_mut(default, id).character(size, ch).width() > 0.0 {
+                return id;
+            }
+        }
+        font
+    }
+
+    /// The advance width of the glyph for the given character, taken from the first font within
+    /// the given font's fallback chain that has it. Widths are cached after they are first
+    /// measured so that text may be measured without visiting the glyph caches.
+    pub fn width(&mut self, default: &mut C, font: FontId, size: FontSize, ch: char) -> f64
+        where
+            C: CharacterCache,
+    {
+        if let Some(&width) = self.widths.get(&(font, size, ch)) {
+            return width;
+        }
+        let id = self.resolve(default, font, size, ch);
+        let width = self.cache_mut(default, id).character(size, ch).width();
+        self.widths.insert((font, size, ch), width);
+        width
+    }
+
+    /// A `CharacterCache` producing glyphs from the given font and its fallbacks.
+    pub fn chain<'a>(&'a mut self, default: &'a mut C, font: FontId) -> Chain<'a, C> {
+        Chain { default: default, fonts: self, font: font }
+    }
+
+}
+
+
+impl<'a, C> CharacterCache for Chain<'a, C>
+    where
+        C: CharacterCache,
+{
+    type Texture = C::Texture;
+
+    fn character(&mut self, font_size: FontSize, ch: char) -> &Character<C::Texture> {
+        let Chain { ref mut default, ref mut fonts, font } = *self;
+        let id = fonts.resolve(default, font, font_size, ch);
+        fonts.cache_mut(default, id).character(font_size, ch)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use graphics::character::{Character, CharacterCache};
+    use harness::Texture;
+    use label::FontSize;
+    use super::{DEFAULT_FONT, Fonts};
+    use theme::Theme;
+    use ui::Ui;
+
+    /// Produces glyphs of the same advance width regardless of font size, like a cache whose
+    /// glyphs are rasterised at some scale.
+    struct Cache {
+        advance: f64,
+        character: Character<Texture>,
+    }
+
+    impl Cache {
+        fn new(advance: f64) -> Cache {
+            let character = Character { offset: [0.0, 0.0], size: [0.0; 2], texture: Texture };
+            Cache { advance: advance, character: character }
+        }
+    }
+
+    impl CharacterCache for Cache {
+        type Texture = Texture;
+        fn character(&mut self, size: FontSize, _ch: char) -> &Character<Texture> {
+            self.character.size = [self.advance, size as f64];
+            &self.character
+        }
+    }
+
+    #[test]
+    fn widths_are_measured_once_until_cleared() {
+        let mut fonts = Fonts::new();
+        let mut default = Cache::new(5.0);
+        assert_eq!(fonts.width(&mut default, DEFAULT_FONT, 12, 'a'), 5.0);
+        default.advance = 7.0;
+        assert_eq!(fonts.width(&mut default, DEFAULT_FONT, 12, 'a'), 5.0);
+        fonts.clear_widths();
+        assert_eq!(fonts.width(&mut default, DEFAULT_FONT, 12, 'a'), 7.0);
+    }
+
+    #[test]
+    fn replacing_the_character_cache_forgets_glyph_widths() {
+        let mut ui = Ui::new(Cache::new(5.0), Theme::default());
+        assert_eq!(ui.get_character_w(12, 'a'), 5.0);
+        let old = ui.set_character_cache(Cache::new(7.0));
+        assert_eq!(old.advance, 5.0);
+        assert_eq!(ui.get_character_w(12, 'a'), 7.0);
+    }
+
+}
@@ -1,5 +1,10 @@
 
 use color::{Color, hsl, hsla, rgb, rgba};
+use corner::{self, CornerRadii};
+use elmesque::form::{group, polygon, rect, Form};
+use gradient::Gradient;
+use position::{Dimensions, Direction, Point};
+use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
 
 /// To be used as a parameter for defining the aesthetic
 /// of the widget frame.
@@ -11,6 +16,142 @@ pub enum Framing {
     NoFrame,
 }
 
+bitflags! {
+    /// The sides of a widget upon which its frame is drawn, i.e. `FRAME_BOTTOM` alone for an
+    /// underlined text field or `FRAME_LEFT` alone for an accent bar beside a selected item.
+    flags FrameSides: u8 {
+        const FRAME_TOP    = 0b0001,
+        const FRAME_BOTTOM = 0b0010,
+        const FRAME_LEFT   = 0b0100,
+        const FRAME_RIGHT  = 0b1000,
+        const FRAME_ALL    = FRAME_TOP.bits
+                           | FRAME_BOTTOM.bits
+                           | FRAME_LEFT.bits
+                           | FRAME_RIGHT.bits,
+    }
+}
+
+/// The width of a widget's frame upon each of its sides, i.e. a tab whose bottom side is left
+/// open to join the panel beneath it, or the buttons of a joined group sharing a single thin
+/// divider. When given, these take precedence over a widget's `frame` width and `frame_sides`.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct FrameWidths {
+    pub top: f64,
+    pub bottom: f64,
+    pub left: f64,
+    pub right: f64,
+}
+
+/// How a widget's frame is shaded.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum FrameStyle {
+    /// The whole frame is filled with the frame color.
+    Flat,
+    /// The top and left sides are darker and the bottom and right sides lighter, so that the
+    /// widget appears sunken (i.e. a text field).
+    Inset,
+    /// The top and left sides are lighter and the bottom and right sides darker, so that the
+    /// widget appears raised (i.e. a button).
+    Outset,
+}
+
+impl FrameSides {
+
+    /// The area within a frame of the given width drawn upon these sides of a rectangle with the
+    /// given dimensions, given as the offset of its centre from that of the rectangle along with
+    /// its dimensions.
+    ///
+    /// Widgets draw their frame by filling their whole rectangle with the frame color and then
+    /// filling this area with their own color.
+    pub fn inner_rect(&self, dim: Dimensions, width: f64) -> (Point, Dimensions) {
+        FrameWidths::sides(width, *self).inner_rect(dim)
+    }
+
+}
+
+impl FrameWidths {
+
+    /// The same width upon all sides.
+    pub fn all(width: f64) -> FrameWidths {
+        FrameWidths::sides(width, FRAME_ALL)
+    }
+
+    /// The given width upon the given sides and no frame upon the others.
+    pub fn sides(width: f64, sides: FrameSides) -> FrameWidths {
+        let side = |flag: FrameSides| if sides.contains(flag) { width } else { 0.0 };
+        FrameWidths {
+            top: side(FRAME_TOP),
+            bottom: side(FRAME_BOTTOM),
+            left: side(FRAME_LEFT),
+            right: side(FRAME_RIGHT),
+        }
+    }
+
+    /// The area within a frame of these widths drawn upon a rectangle with the given dimensions,
+    /// given as the offset of its centre from that of the rectangle along with its dimensions.
+    pub fn inner_rect(&self, dim: Dimensions) -> (Point, Dimensions) {
+        let FrameWidths { top, bottom, left, right } = *self;
+        let offset = [(left - right) / 2.0, (bottom - top) / 2.0];
+        (offset, [dim[0] - left - right, dim[1] - top - bottom])
+    }
+
+}
+
+impl From<f64> for FrameWidths {
+    fn from(width: f64) -> FrameWidths {
+        FrameWidths::all(width)
+    }
+}
+
+
+/// The Form beneath a widget's inner area, filling a rectangle with the given dimensions and
+/// corners (centred upon the origin) with the frame color, shaded according to the frame style.
+///
+/// Bevelled frames shade each side separately and so are drawn with square corners.
+pub fn form(dim: Dimensions,
+            radii: CornerRadii,
+            widths: FrameWidths,
+            style: FrameStyle,
+            color: Color) -> Form {
+    const BEVEL_SHADE: f32 = 0.35;
+    let (light, dark) = Gradient::Shade(Direction::Down, BEVEL_SHADE).colors(color);
+    let (top_left, bottom_right) = match style {
+        FrameStyle::Flat => return corner::rounded_rect(dim, radii).filled(color),
+        FrameStyle::Inset => (dark, light),
+        FrameStyle::Outset => (light, dark),
+    };
+
+    // The outer and inner corners, joined diagonally so that each side is a trapezoid.
+    let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+    let (outer_tl, outer_tr) = ((-half_w, half_h), (half_w, half_h));
+    let (outer_bl, outer_br) = ((-half_w, -half_h), (half_w, -half_h));
+    let inner_tl = (-half_w + widths.left, half_h - widths.top);
+    let inner_tr = (half_w - widths.right, half_h - widths.top);
+    let inner_bl = (-half_w + widths.left, -half_h + widths.bottom);
+    let inner_br = (half_w - widths.right, -half_h + widths.bottom);
+
+    group(vec![
+        rect(dim[0], dim[1]).filled(color),
+        polygon(vec![outer_tr, outer_tl, inner_tl, inner_tr]).filled(top_left),
+        polygon(vec![outer_tl, outer_bl, inner_bl, inner_tl]).filled(top_left),
+        polygon(vec![outer_bl, outer_br, inner_br, inner_bl]).filled(bottom_right),
+        polygon(vec![outer_br, outer_tr, inner_tr, inner_br]).filled(bottom_right),
+    ])
+}
+
+impl Encodable for FrameSides {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), E::Error> {
+        self.bits().encode(encoder)
+    }
+}
+
+impl Decodable for FrameSides {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<FrameSides, D::Error> {
+        u8::decode(decoder).map(FrameSides::from_bits_truncate)
+    }
+}
+
+
 /// Widgets that may display a frame.
 pub trait Frameable: Sized {
 
@@ -20,6 +161,17 @@ pub trait Frameable: Sized {
     /// Set the color of the widget's frame.
     fn frame_color(self, color: Color) -> Self;
 
+    /// Set the sides upon which the widget's frame is drawn. By default, the frame is drawn upon
+    /// all sides.
+    fn frame_sides(self, sides: FrameSides) -> Self;
+
+    /// Set the width of the widget's frame upon each of its sides, taking precedence over its
+    /// `frame` width and `frame_sides`.
+    fn frame_widths(self, widths: FrameWidths) -> Self;
+
+    /// Set how the widget's frame is shaded. By default, the frame is flat.
+    fn frame_style(self, style: FrameStyle) -> Self;
+
     /// Set the color of the widget's frame with rgba values.
     fn frame_rgba(self, r: f32, g: f32, b: f32, a: f32) -> Self {
         self.frame_color(rgba(r, g, b, a))
@@ -0,0 +1,141 @@
+//!
+//! Linear gradient fills for the rectangles drawn by widgets, as an alternative to a flat color.
+//!
+//! A gradient is described relative to the color with which the widget would otherwise be filled,
+//! so that it still follows the widget's highlighting, clicking and disabling.
+//!
+
+use color::{rgba, Color};
+use corner::{self, CornerRadii};
+use elmesque::form::{self, Form};
+use position::{Dimensions, Direction};
+
+
+/// The number of bands of color with which a gradient is drawn.
+const BANDS: usize = 24;
+
+/// A linear gradient with which a widget's rectangle may be filled in place of its flat color.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum Gradient {
+    /// Shade the widget's color from lighter at the start of the gradient to darker at its end,
+    /// where the amount (from `0.0` to `1.0`) is how far each end is mixed towards white or
+    /// black. The gradient runs in the given direction, i.e. `Direction::Down` is lighter at the
+    /// top.
+    Shade(Direction, f32),
+    /// Blend from the widget's color at the start of the gradient to the given color at its end,
+    /// running in the given direction.
+    To(Direction, Color),
+}
+
+/// Widgets that may be filled with a gradient.
+pub trait Gradientable: Sized {
+
+    /// Fill the widget with the given gradient in place of its flat color.
+    fn gradient(self, gradient: Gradient) -> Self;
+
+}
+
+
+impl Gradient {
+
+    /// The direction in which the gradient runs from its start to its end.
+    pub fn direction(&self) -> Direction {
+        match *self {
+            Gradient::Shade(direction, _) | Gradient::To(direction, _) => direction,
+        }
+    }
+
+    /// The colors at the start and end of the gradient when filling a shape of the given color.
+    pub fn colors(&self, color: Color) -> (Color, Color) {
+        let c = color.to_rgb();
+        match *self {
+            Gradient::Shade(_, amount) => {
+                let amount = amount.max(0.0).min(1.0);
+                (mix(color, rgba(1.0, 1.0, 1.0, c.3), amount),
+                 mix(color, rgba(0.0, 0.0, 0.0, c.3), amount))
+            },
+            Gradient::To(_, end) => (color, end),
+        }
+    }
+
+}
+
+
+/// The color a fraction `t` of the way from `a` to `b`.
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    let (a, b) = (a.to_rgb(), b.to_rgb());
+    rgba(a.0 + (b.0 - a.0) * t,
+         a.1 + (b.1 - a.1) * t,
+         a.2 + (b.2 - a.2) * t,
+         a.3 + (b.3 - a.3) * t)
+}
+
+
+/// A rectangle with the given dimensions and corners, centred upon the origin, filled with the
+/// given color or, if one is given, a gradient of it.
+///
+/// The gradient is drawn as a number of bands across the rectangle, each filled with the color at
+/// its centre.
+pub fn filled_rect(dim: Dimensions,
+                   radii: CornerRadii,
+                   color: Color,
+                   maybe_gradient: Option<Gradient>) -> Form {
+    let gradient = match maybe_gradient {
+        Some(gradient) => gradient,
+        None => return corner::rounded_rect(dim, radii).filled(color),
+    };
+    let (start, end) = gradient.colors(color);
+    let points = corner::rounded_rect_points(dim, radii);
+
+    // The axis along which the gradient runs, along with the side of the origin at which it
+    // starts.
+    let (axis, sign) = match gradient.direction() {
+        Direction::Down => (1, 1.0),
+        Direction::Up => (1, -1.0),
+        Direction::Right => (0, -1.0),
+        Direction::Left => (0, 1.0),
+    };
+    let half = dim[axis] / 2.0;
+    let forms = (0..BANDS).filter_map(|i| {
+        let (t0, t1) = (i as f64 / BANDS as f64, (i + 1) as f64 / BANDS as f64);
+        let (a, b) = (sign * half * (1.0 - 2.0 * t0), sign * half * (1.0 - 2.0 * t1));
+        let band = clip_band(&points, axis, a.min(b), a.max(b));
+        if band.len() < 3 { return None }
+        let t = ((t0 + t1) / 2.0) as f32;
+        Some(form::polygon(band).filled(mix(start, end, t)))
+    }).collect();
+    form::group(forms)
+}
+
+
+/// The part of the given convex polygon lying between `lo` and `hi` along the given axis (`0`
+/// for x, `1` for y).
+fn clip_band(points: &[(f64, f64)], axis: usize, lo: f64, hi: f64) -> Vec<(f64, f64)> {
+    let coord = |p: (f64, f64)| if axis == 0 { p.0 } else { p.1 };
+    let mut polygon = points.to_vec();
+    for &(bound, is_lower) in [(lo, true), (hi, false)].iter() {
+        let is_inside = |p: (f64, f64)| {
+            if is_lower { coord(p) >= bound } else { coord(p) <= bound }
+        };
+        let intersect = |a: (f64, f64), b: (f64, f64)| {
+            let t = (bound - coord(a)) / (coord(b) - coord(a));
+            (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+        };
+        let mut clipped = Vec::with_capacity(polygon.len() + 2);
+        for k in 0..polygon.len() {
+            let (a, b) = (polygon[k], polygon[(k + 1) % polygon.len()]);
+            match (is_inside(a), is_inside(b)) {
+                (true, true) => clipped.push(b),
+                (true, false) => clipped.push(intersect(a, b)),
+                (false, true) => {
+                    clipped.push(intersect(a, b));
+                    clipped.push(b);
+                },
+                (false, false) => (),
+            }
+        }
+        polygon = clipped;
+        if polygon.is_empty() { break }
+    }
+    polygon
+}
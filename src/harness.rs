@@ -0,0 +1,266 @@
+//!
+//! A headless harness for testing `Ui` logic without a window or graphics context.
+//!
+//! The `Harness` owns a `Ui` that measures text with a stub `GlyphCache` (in which every glyph has
+//! the same predictable width) and allows for injecting synthetic input and advancing frames.
+//!
+//! ```ignore
+//! let mut harness = Ui::test_harness([800.0, 600.0]);
+//! let mut value = 0.0;
+//! let set_widgets = |ui: &mut Ui<GlyphCache>| {
+//!     Slider::new(value, 0.0, 1.0)
+//!         .xy(0.0, 0.0)
+//!         .react(|new_value| value = new_value)
+//!         .set(SLIDER, ui);
+//! };
+//! harness.click([50.0, 0.0], set_widgets);
+//! assert!(value > 0.5);
+//! ```
+//!
+
+use graphics::ImageSize;
+use graphics::character::{Character, CharacterCache};
+use label::FontSize;
+use piston::event::{Event, RenderArgs};
+use piston::input::{Button, Input, Motion, MouseButton};
+use piston::input::keyboard::Key;
+use position::{Dimensions, Point};
+use record::Recording;
+use render::{Backend, Primitive, Rgba, Sample};
+use std::collections::HashMap;
+use theme::Theme;
+use ui::Ui;
+
+
+/// The stub texture used for every glyph of the `GlyphCache`. When drawn via
+/// `Ui::draw_to_image`, every glyph is a solid block of the text color.
+#[derive(Copy, Clone, Debug)]
+pub struct Texture;
+
+/// A `CharacterCache` that produces glyphs without loading any font. Each glyph is half as wide
+/// as the font size so that text measurement within tests is predictable.
+pub struct GlyphCache {
+    characters: HashMap<FontSize, Character<Texture>>,
+}
+
+/// Drives a `Ui` with synthetic input, one frame at a time.
+pub struct Harness {
+    /// The `Ui` driven by the harness.
+    pub ui: Ui<GlyphCache>,
+}
+
+/// A `Backend` that discards everything it is given.
+struct Discard;
+
+
+impl ImageSize for Texture {
+    fn get_size(&self) -> (u32, u32) {
+        (0, 0)
+    }
+}
+
+impl Sample for Texture {
+    fn sample(&self, _uv: [f64; 2]) -> Rgba {
+        [1.0; 4]
+    }
+}
+
+
+impl GlyphCache {
+
+    /// Construct a new stub GlyphCache.
+    pub fn new() -> GlyphCache {
+        GlyphCache { characters: HashMap::new() }
+    }
+
+}
+
+impl CharacterCache for GlyphCache {
+    type Texture = Texture;
+
+    fn character(&mut self, font_size: FontSize, _ch: char) -> &Character<Texture> {
+        let size = font_size as f64;
+        self.characters.entry(font_size).or_insert_with(|| {
+            Character { offset: [0.0, size], size: [size / 2.0, size], texture: Texture }
+        })
+    }
+}
+
+
+impl Ui<GlyphCache> {
+
+    /// Construct a `Harness` driving a new `Ui` within a virtual window of the given dimensions.
+    pub fn test_harness(win_dim: Dimensions) -> Harness {
+        let mut harness = Harness { ui: Ui::new(GlyphCache::new(), Theme::default()) };
+        harness.render(win_dim);
+        harness
+    }
+
+}
+
+
+impl Harness {
+
+    /// Move the mouse to the given position, where `[0.0, 0.0]` is the centre of the window.
+    pub fn move_mouse(&mut self, xy: Point) {
+        let scale = self.ui.window_scale();
+        let x = (xy[0] + self.ui.win_w / 2.0) * scale;
+        let y = (self.ui.win_h / 2.0 - xy[1]) * scale;
+        self.ui.handle_event(&Event::Input(Input::Move(Motion::MouseCursor(x, y))));
+    }
+
+    /// Scroll the mouse wheel by the given distance, where positive `y` scrolls up.
+    pub fn scroll_mouse(&mut self, delta: Point) {
+        self.ui.handle_event(&Event::Input(Input::Move(Motion::MouseScroll(delta[0], delta[1]))));
+    }
+
+    /// Press the given mouse button.
+    pub fn press_mouse(&mut self, button: MouseButton) {
+        self.ui.handle_event(&Event::Input(Input::Press(Button::Mouse(button))));
+    }
+
+    /// Release the given mouse button.
+    pub fn release_mouse(&mut self, button: MouseButton) {
+        self.ui.handle_event(&Event::Input(Input::Release(Button::Mouse(button))));
+    }
+
+    /// Press the given key.
+    pub fn press_key(&mut self, key: Key) {
+        self.ui.handle_event(&Event::Input(Input::Press(Button::Keyboard(key))));
+    }
+
+    /// Release the given key.
+    pub fn release_key(&mut self, key: Key) {
+        self.ui.handle_event(&Event::Input(Input::Release(Button::Keyboard(key))));
+    }
+
+    /// Enter the given text, as though it were typed.
+    pub fn enter_text(&mut self, text: &str) {
+        self.ui.handle_event(&Event::Input(Input::Text(text.to_string())));
+    }
+
+    /// Advance a single frame. The given closure should set the widgets exactly as the application
+    /// would each frame, after which the `Ui` is drawn (with all output discarded).
+    pub fn frame<F>(&mut self, mut set_widgets: F)
+        where
+            F: FnMut(&mut Ui<GlyphCache>),
+    {
+        set_widgets(&mut self.ui);
+        self.ui.draw_primitives(&mut Discard);
+        let scale = self.ui.window_scale();
+        let win_dim = [self.ui.win_w * scale, self.ui.win_h * scale];
+        self.render(win_dim);
+    }
+
+    /// Move the mouse to the given position and click the left button, advancing a frame after
+    /// each step so that widgets observe the button both down and up.
+    pub fn click<F>(&mut self, xy: Point, mut set_widgets: F)
+        where
+            F: FnMut(&mut Ui<GlyphCache>),
+    {
+        self.move_mouse(xy);
+        self.frame(&mut set_widgets);
+        self.press_mouse(MouseButton::Left);
+        self.frame(&mut set_widgets);
+        self.release_mouse(MouseButton::Left);
+        self.frame(&mut set_widgets);
+    }
+
+    /// Press and release the given key, advancing a frame after each step.
+    pub fn type_key<F>(&mut self, key: Key, mut set_widgets: F)
+        where
+            F: FnMut(&mut Ui<GlyphCache>),
+    {
+        self.press_key(key);
+        self.frame(&mut set_widgets);
+        self.release_key(key);
+        self.frame(&mut set_widgets);
+    }
+
+    /// Replay the given `Recording` (i.e. one produced by `Ui::stop_recording`), advancing a frame
+    /// via the given closure after the inputs of each of its frames have been handled.
+    pub fn replay<F>(&mut self, recording: &Recording, mut set_widgets: F)
+        where
+            F: FnMut(&mut Ui<GlyphCache>),
+    {
+        self.render(recording.win_dim);
+        for frame in 0..recording.frames() {
+            for recorded in recording.frame_inputs(frame) {
+                self.ui.replay(recorded);
+            }
+            self.frame(&mut set_widgets);
+        }
+    }
+
+    /// End the current input cycle, as a window does once it has been drawn.
+    fn render(&mut self, win_dim: Dimensions) {
+        let (w, h) = (win_dim[0] as u32, win_dim[1] as u32);
+        let args = RenderArgs { ext_dt: 0.0, width: w, height: h, draw_width: w, draw_height: h };
+        self.ui.handle_event(&Event::Render(args));
+    }
+
+}
+
+
+impl Backend for Discard {
+    type Texture = Texture;
+    fn clear(&mut self, _color: Rgba) {}
+    fn primitive(&mut self, _primitive: Primitive<Texture>) {}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use piston::input::keyboard::Key;
+    use position::{Positionable, Sizeable};
+    use super::GlyphCache;
+    use ui::{Ui, UiId};
+    use widget::Widget;
+    use widget::slider::Slider;
+    use widget::text_box::TextBox;
+
+    const WIDGET: UiId = 0;
+
+    #[test]
+    fn clicking_a_slider_reacts_with_the_value_beneath_the_mouse() {
+        let mut harness = Ui::test_harness([400.0, 400.0]);
+        let mut value = 0.0;
+        {
+            let mut set_widgets = |ui: &mut Ui<GlyphCache>| {
+                Slider::new(value, 0.0, 1.0)
+                    .dimensions(200.0, 30.0)
+                    .xy(0.0, 0.0)
+                    .react(|new_value| value = new_value)
+                    .set(WIDGET, ui);
+            };
+            harness.click([0.0, 0.0], &mut set_widgets);
+        }
+        assert_eq!(value, 0.5);
+    }
+
+    #[test]
+    fn typing_into_a_text_box_edits_its_text() {
+        let mut harness = Ui::test_harness([400.0, 400.0]);
+        let mut text = String::new();
+        let mut submitted = String::new();
+        {
+            let mut set_widgets = |ui: &mut Ui<GlyphCache>| {
+                TextBox::new(&mut text)
+                    .dimensions(200.0, 30.0)
+                    .xy(0.0, 0.0)
+                    .react(|text: &mut String| submitted = text.clone())
+                    .set(WIDGET, ui);
+            };
+            harness.click([0.0, 0.0], &mut set_widgets);
+            harness.enter_text("hi");
+            harness.frame(&mut set_widgets);
+            harness.type_key(Key::Backspace, &mut set_widgets);
+            harness.enter_text("ey");
+            harness.frame(&mut set_widgets);
+            harness.type_key(Key::Return, &mut set_widgets);
+        }
+        assert_eq!(text, "hey");
+        assert_eq!(submitted, "hey");
+    }
+
+}
@@ -0,0 +1,173 @@
+//!
+//! An opt-in history of the changes made to value and text widgets, so that they may be undone
+//! and redone.
+//!
+//! Widgets push each change made by the user along with the state before and after it. Undoing
+//! (or redoing) a change hands the earlier (or later) state back to the widget, which adopts it
+//! and reacts with it at its next update as though the user had made the change.
+//!
+
+use std::collections::HashMap;
+use ui::UiId;
+
+
+/// Changes to the same widget pushed within this many nanoseconds of one another are coalesced
+/// into a single change, i.e. the steps of the mouse wheel or the characters of a typed word.
+const COALESCE_NS: u64 = 1_000_000_000;
+
+
+/// A single reversible change to the state of a widget.
+struct Change {
+    ui_id: UiId,
+    kind: &'static str,
+    /// The json encoding of the widget's state before the change.
+    before: String,
+    /// The json encoding of the widget's state after the change.
+    after: String,
+    /// The time at which the change was last pushed, or `0` once it may no longer be coalesced.
+    time_ns: u64,
+}
+
+
+/// The undo and redo stacks, along with the states awaiting adoption by their widgets.
+pub struct History {
+    /// The maximum number of changes that may be undone, where `0` disables the history.
+    limit: usize,
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+    /// The states handed back to each widget by an undo or redo since its last update.
+    applied: HashMap<UiId, (&'static str, String)>,
+}
+
+
+impl History {
+
+    /// Construct a disabled history.
+    pub fn new() -> History {
+        History {
+            limit: 0,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            applied: HashMap::new(),
+        }
+    }
+
+    /// Set the maximum number of changes that may be undone, dropping the oldest changes beyond
+    /// it. A limit of `0` disables the history.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        while self.undo.len() > limit {
+            self.undo.remove(0);
+        }
+        if limit == 0 {
+            self.redo.clear();
+        }
+    }
+
+    /// Whether or not changes are being recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.limit > 0
+    }
+
+    /// Push a change made by the user, coalescing it with the previous change if it was made to
+    /// the same widget moments ago. Any undone changes may no longer be redone.
+    pub fn push(&mut self, ui_id: UiId, kind: &'static str, before: String, after: String,
+                now_ns: u64) {
+        if !self.is_enabled() || before == after { return }
+        self.redo.clear();
+        let is_coalesced = match self.undo.last() {
+            Some(last) => last.ui_id == ui_id && last.kind == kind
+                && now_ns.saturating_sub(last.time_ns) < COALESCE_NS,
+            None => false,
+        };
+        if is_coalesced {
+            let is_cancelled = {
+                let last = self.undo.last_mut().unwrap();
+                last.after = after;
+                last.time_ns = now_ns;
+                last.before == last.after
+            };
+            // The coalesced changes cancelled each other out.
+            if is_cancelled {
+                self.undo.pop();
+            }
+            return;
+        }
+        self.undo.push(Change {
+            ui_id: ui_id,
+            kind: kind,
+            before: before,
+            after: after,
+            time_ns: now_ns,
+        });
+        if self.undo.len() > self.limit {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Undo the most recent change, returning whether or not there was one.
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(change) => {
+                self.applied.insert(change.ui_id, (change.kind, change.before.clone()));
+                self.redo.push(change);
+                self.seal_last();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Redo the most recently undone change, returning whether or not there was one.
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(change) => {
+                self.applied.insert(change.ui_id, (change.kind, change.after.clone()));
+                self.undo.push(change);
+                self.seal_last();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Prevent further changes from being coalesced into the last change that may be undone, so
+    /// that changes made after an undo or redo are undone separately.
+    fn seal_last(&mut self) {
+        if let Some(last) = self.undo.last_mut() {
+            last.time_ns = 0;
+        }
+    }
+
+    /// Whether or not there is a change that may be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether or not there is a change that may be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all changes.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Take the state handed back to the widget of the given kind by an undo or redo, if there is
+    /// one.
+    pub fn take_applied(&mut self, ui_id: UiId, kind: &'static str) -> Option<String> {
+        match self.applied.get(&ui_id) {
+            Some(&(applied_kind, _)) if applied_kind == kind => (),
+            _ => return None,
+        }
+        self.applied.remove(&ui_id).map(|(_, state)| state)
+    }
+
+    /// Forget any state awaiting the widget at the given UiId, i.e. when it is removed.
+    pub fn remove(&mut self, ui_id: UiId) {
+        self.applied.remove(&ui_id);
+    }
+
+}
@@ -0,0 +1,144 @@
+//!
+//! Generation of stable `UiId`s for widgets created within loops, i.e. a dynamic list of
+//! sliders or buttons, so that each list needs only a single hand-assigned `UiId` of its own.
+//!
+//! The ids within a list are namespaced by the list's `UiId` and are allocated by the `Ui`
+//! beyond the range of ids reserved for hand-assigned ids (see `WIDGET_RESERVATION`), so
+//! they never collide with those of other lists or widgets. The same list and index always
+//! yield the same id.
+//!
+//! Where the items of a list may be sorted, filtered, inserted or removed, their widgets should
+//! be identified by some key of the item (see `IdGenerator::key`) rather than by index. As the
+//! state of each widget is cached by its `UiId`, this allows the state (i.e. whether a menu is
+//! open or the text being entered) to follow the item rather than sticking to its position.
+//!
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::Write;
+use ui::{Ui, UiId};
+
+
+/// Generates the `UiId`s for the widgets within a list, namespaced by the list's own `UiId`.
+///
+/// ```ignore
+/// let mut ids = IdGenerator::new(TRACK_LIST);
+/// for track in tracks.iter_mut() {
+///     Slider::new(track.gain, 0.0, 1.0).down(5.0).set(ids.next(ui), ui);
+/// }
+///
+/// // Where the tracks may be reordered, identify each slider by the track's key instead.
+/// let ids = IdGenerator::new(TRACK_LIST);
+/// for track in tracks.iter_mut() {
+///     Slider::new(track.gain, 0.0, 1.0).down(5.0).set(ids.key(ui, track.id), ui);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IdGenerator {
+    namespace: UiId,
+    next_index: usize,
+}
+
+/// The ids allocated within each namespace, owned by the `Ui`.
+pub struct Namespaces {
+    ids: HashMap<UiId, Vec<UiId>>,
+    /// The ids allocated for each key within each namespace, each a `HashMap<K, UiId>` for the
+    /// namespace's key type `K`.
+    keyed_ids: HashMap<UiId, Box<Any>>,
+    next_id: UiId,
+}
+
+
+impl IdGenerator {
+
+    /// Construct a generator for the list with the given `UiId`, starting at index `0`.
+    pub fn new(namespace: UiId) -> IdGenerator {
+        IdGenerator { namespace: namespace, next_index: 0 }
+    }
+
+    /// The `UiId` of the widget at the given index within the list.
+    pub fn index<C>(&self, ui: &mut Ui<C>, index: usize) -> UiId {
+        ui.namespaced_id(self.namespace, index)
+    }
+
+    /// The `UiId` of the widget at the next index within the list.
+    pub fn next<C>(&mut self, ui: &mut Ui<C>) -> UiId {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.index(ui, index)
+    }
+
+    /// The `UiId` of the widget for the item with the given key within the list. The id follows
+    /// the key regardless of the item's index, so that the widget's state follows the item when
+    /// the list is sorted or filtered. All keys within a list should be of the same type.
+    pub fn key<C, K>(&self, ui: &mut Ui<C>, key: K) -> UiId
+        where K: Any + Eq + Hash,
+    {
+        ui.keyed_id(self.namespace, key)
+    }
+
+    /// A generator for a list nested within the widget at the given index, i.e. the widgets
+    /// within each row of a table.
+    pub fn nested<C>(&self, ui: &mut Ui<C>, index: usize) -> IdGenerator {
+        IdGenerator::new(self.index(ui, index))
+    }
+
+}
+
+
+impl Namespaces {
+
+    /// Construct an empty store, allocating ids from `first_id` onwards.
+    pub fn new(first_id: UiId) -> Namespaces {
+        Namespaces { ids: HashMap::new(), keyed_ids: HashMap::new(), next_id: first_id }
+    }
+
+    /// The id at the given index within the given namespace, allocating ids for the namespace up
+    /// to and including the index if necessary. `is_vacant` is used to skip any ids already
+    /// occupied by a hand-assigned id.
+    pub fn id<F>(&mut self, namespace: UiId, index: usize, is_vacant: F) -> UiId
+        where F: Fn(UiId) -> bool,
+    {
+        let Namespaces { ref mut ids, ref mut next_id, .. } = *self;
+        let ids = ids.entry(namespace).or_insert_with(Vec::new);
+        while ids.len() <= index {
+            ids.push(allocate(next_id, &is_vacant));
+        }
+        ids[index]
+    }
+
+    /// The id for the given key within the given namespace, allocating one if necessary.
+    pub fn keyed_id<K, F>(&mut self, namespace: UiId, key: K, is_vacant: F) -> UiId
+        where
+            K: Any + Eq + Hash,
+            F: Fn(UiId) -> bool,
+    {
+        let Namespaces { ref mut keyed_ids, ref mut next_id, .. } = *self;
+        let keys = keyed_ids.entry(namespace)
+            .or_insert_with(|| Box::new(HashMap::<K, UiId>::new()) as Box<Any>);
+        if !keys.is::<HashMap<K, UiId>>() {
+            writeln!(::std::io::stderr(),
+                     "Warning: The list with UiId {:?} was given keys of a different type to \
+                      those it was previously given, so the ids of its previous keys have been \
+                      forgotten.", namespace).unwrap();
+            *keys = Box::new(HashMap::<K, UiId>::new());
+        }
+        let keys = keys.downcast_mut::<HashMap<K, UiId>>().unwrap();
+        *keys.entry(key).or_insert_with(|| allocate(next_id, &is_vacant))
+    }
+
+}
+
+
+/// Allocate the next vacant id, advancing `next_id` beyond it.
+fn allocate<F>(next_id: &mut UiId, is_vacant: &F) -> UiId
+    where F: Fn(UiId) -> bool,
+{
+    while !is_vacant(*next_id) {
+        *next_id += 1;
+    }
+    let id = *next_id;
+    *next_id += 1;
+    id
+}
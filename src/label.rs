@@ -1,18 +1,156 @@
 
-use color::{Color, hsl, hsla, rgb, rgba};
+use color::{black, Color, hsl, hsla, rgb, rgba};
+use elmesque::form::{line, solid, Form};
 use graphics::character::CharacterCache;
+use position::{Dimensions, Padding};
 use ui::Ui;
+use widget::rich_text::{Span, SpannedLabel};
 
 /// Font size used throughout Conrod.
 pub type FontSize = u32;
 
+/// The measured size of some text along with the metrics of each of its lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSize {
+    /// The width of the widest line and the total height of all lines.
+    pub dim: Dimensions,
+    /// The metrics for each line, in order from top to bottom.
+    pub lines: Vec<LineMetrics>,
+}
+
+/// The metrics of a single line of measured text.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LineMetrics {
+    /// The byte index within the text at which the line starts.
+    pub start: usize,
+    /// The byte index within the text at which the line ends (exclusive).
+    pub end: usize,
+    /// The pixel width of the line, excluding any trailing whitespace.
+    pub width: f64,
+}
+
 /// Determine the pixel width of the final text bitmap.
 #[inline]
 pub fn width<C: CharacterCache>(ui: &mut Ui<C>, size: FontSize, text: &str) -> f64 {
-    text.chars().fold(0u32, |a, ch| {
-        let character = ui.get_character(size, ch);
-        a + character.width() as u32
-    }) as f64
+    text.chars().fold(0u32, |a, ch| a + ui.get_character_w(size, ch) as u32) as f64
+}
+
+/// Split the given text into words, keeping each word's trailing whitespace attached to it.
+pub fn words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut prev_was_space = false;
+    for (i, ch) in text.char_indices() {
+        let is_space = ch.is_whitespace();
+        if prev_was_space && !is_space {
+            words.push(&text[start..i]);
+            start = i;
+        }
+        prev_was_space = is_space;
+    }
+    if start < text.len() { words.push(&text[start..]); }
+    words
+}
+
+/// Measure the given text, breaking it into lines at each newline and, if a `max_width` is
+/// given, wherever a word would exceed it.
+pub fn measure<C: CharacterCache>(ui: &mut Ui<C>,
+                                  text: &str,
+                                  size: FontSize,
+                                  maybe_max_width: Option<f64>) -> TextSize {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for paragraph in text.split('\n') {
+        let mut line = LineMetrics { start: offset, end: offset, width: 0.0 };
+        // The width of the line including its trailing whitespace.
+        let mut line_w = 0.0;
+        let mut word_start = offset;
+        for word in words(paragraph).into_iter() {
+            let word_w = width(ui, size, word);
+            let visible_w = width(ui, size, word.trim_right());
+            let exceeds = maybe_max_width.map(|max_w| line_w + visible_w > max_w).unwrap_or(false);
+            if exceeds && line.end > line.start {
+                lines.push(line);
+                line = LineMetrics { start: word_start, end: word_start, width: 0.0 };
+                line_w = 0.0;
+            }
+            line.width = line_w + visible_w;
+            line_w += word_w;
+            word_start += word.len();
+            line.end = word_start;
+        }
+        lines.push(line);
+        offset += paragraph.len() + 1;
+    }
+    let max_w = lines.iter().fold(0.0, |max_w, line| {
+        if line.width > max_w { line.width } else { max_w }
+    });
+    let dim = [max_w, lines.len() as f64 * size as f64];
+    TextSize { dim: dim, lines: lines }
+}
+
+/// Remove the accelerator marker from the given label, returning the text to display along with
+/// the character index of the accelerator (if any).
+///
+/// An accelerator is marked by placing an `&` before the letter, i.e. `"&Save"` displays as
+/// `"Save"` with an accelerator of `S`. A literal `&` may be written as `&&`.
+pub fn strip_mnemonic(label: &str) -> (String, Option<usize>) {
+    let mut text = String::with_capacity(label.len());
+    let mut maybe_idx = None;
+    let mut num_chars = 0;
+    let mut chars = label.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '&' {
+            match chars.peek() {
+                Some(&'&') => { chars.next(); },
+                Some(&next) if maybe_idx.is_none() && next.is_alphanumeric() => {
+                    maybe_idx = Some(num_chars);
+                    continue;
+                },
+                _ => (),
+            }
+        }
+        text.push(ch);
+        num_chars += 1;
+    }
+    (text, maybe_idx)
+}
+
+/// The accelerator character of the given label in lowercase, if it has one.
+pub fn mnemonic_char(label: &str) -> Option<char> {
+    let (text, maybe_idx) = strip_mnemonic(label);
+    maybe_idx.and_then(|idx| text.chars().nth(idx))
+        .and_then(|ch| ch.to_lowercase().next())
+}
+
+/// Construct the Form for the line beneath the accelerator at `idx` within the given (stripped)
+/// text. The Form is positioned relative to the centre of the text.
+pub fn mnemonic_underline<C: CharacterCache>(ui: &mut Ui<C>,
+                                             text: &str,
+                                             idx: usize,
+                                             size: FontSize,
+                                             color: Color) -> Form {
+    let text_w = width(ui, size, text);
+    let prefix_w = text.chars().take(idx).fold(0.0, |w, ch| w + ui.get_character_w(size, ch));
+    let char_w = text.chars().nth(idx).map(|ch| ui.get_character_w(size, ch)).unwrap_or(0.0);
+    let left = prefix_w - text_w / 2.0;
+    let y = -(size as f64) / 2.0;
+    line(solid(color), left, y, left + char_w, y)
+}
+
+/// The dimensions of a widget that fits the given label (or spans, which take precedence) at the
+/// given font size, surrounded by the given padding. Used by widgets that may `auto_size`.
+pub fn fit_dim<C: CharacterCache>(ui: &mut Ui<C>,
+                                  maybe_label: Option<&str>,
+                                  maybe_spans: Option<&SpannedLabel>,
+                                  size: FontSize,
+                                  padding: Padding) -> Dimensions {
+    let label_dim = match (maybe_spans, maybe_label) {
+        (Some(spans), _) => spans.forms(ui, black(), size).0,
+        (None, Some(text)) => [width(ui, size, &strip_mnemonic(text).0), size as f64],
+        (None, None) => [0.0, size as f64],
+    };
+    [label_dim[0] + padding.left + padding.right, label_dim[1] + padding.top + padding.bottom]
 }
 
 /// Widgets that may display some label.
@@ -21,6 +159,11 @@ pub trait Labelable<'a>: Sized {
     /// Set the label for the widget.
     fn label(self, text: &'a str) -> Self;
 
+    /// Set a label made up of differently styled Spans for the widget. Any Span without its own
+    /// color or font size uses the widget's label color and font size. The spans take precedence
+    /// over any label set via `label`.
+    fn label_spans(self, spans: &[Span]) -> Self;
+
     /// Set the color of the widget's label.
     fn label_color(self, color: Color) -> Self;
 
@@ -64,3 +207,31 @@ pub trait Labelable<'a>: Sized {
 
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::{mnemonic_char, strip_mnemonic};
+
+    #[test]
+    fn the_first_marked_character_is_the_mnemonic() {
+        assert_eq!(strip_mnemonic("&File"), ("File".to_string(), Some(0)));
+        assert_eq!(strip_mnemonic("Save &As"), ("Save As".to_string(), Some(5)));
+        // Only the first marker counts, so any later ampersands are kept.
+        assert_eq!(strip_mnemonic("&a&b"), ("a&b".to_string(), Some(0)));
+    }
+
+    #[test]
+    fn ampersands_that_mark_nothing_are_kept() {
+        assert_eq!(strip_mnemonic("Fish && Chips"), ("Fish & Chips".to_string(), None));
+        assert_eq!(strip_mnemonic("A & B"), ("A & B".to_string(), None));
+        assert_eq!(strip_mnemonic("A&"), ("A&".to_string(), None));
+    }
+
+    #[test]
+    fn mnemonic_chars_are_lowercase() {
+        assert_eq!(mnemonic_char("Save &As"), Some('a'));
+        assert_eq!(mnemonic_char("&1st"), Some('1'));
+        assert_eq!(mnemonic_char("Plain"), None);
+    }
+
+}
@@ -10,53 +10,141 @@
 #[macro_use] extern crate bitflags;
 extern crate clock_ticks;
 extern crate elmesque;
+#[cfg(feature = "backend-glium")] extern crate freetype;
+#[cfg(feature = "backend-glium")] #[macro_use] extern crate glium;
 extern crate graphics;
 extern crate num;
 #[macro_use] extern crate piston;
 extern crate rand;
 extern crate rustc_serialize;
+#[cfg(feature = "backend-sdl2")] extern crate sdl2;
 extern crate vecmath;
 
 
 pub use canvas::split::Split;
 
+pub use widget::ab_compare::ABCompare;
 pub use widget::button::Button;
+pub use widget::button::ConfirmMode;
+pub use widget::collapsible_area::CollapsibleArea;
+pub use widget::context_menu::ContextMenu;
+pub use widget::date_picker::DatePicker;
+pub use widget::date_picker::Date;
+pub use widget::draggable::Draggable;
+pub use widget::draw_area::DrawArea;
 pub use widget::drop_down_list::DropDownList;
+pub use widget::editor_canvas::EditorCanvas;
+pub use widget::editor_canvas::Transform as EditorTransform;
+pub use widget::editor_canvas::View as EditorView;
 pub use widget::envelope_editor::EnvelopeEditor;
 pub use widget::envelope_editor::EnvelopePoint;
+pub use widget::event_monitor::EventMonitor;
+pub use widget::file_dialog::FileDialog;
+pub use widget::floating::Floating;
+pub use widget::floating::Event as FloatingEvent;
+pub use widget::flow::Flow;
+pub use widget::flow::{Align as FlowAlign, Child as FlowChild};
 pub use widget::label::Label;
+pub use widget::markdown::Markdown;
+pub use widget::marquee::Marquee;
 pub use widget::matrix::Matrix as WidgetMatrix;
+pub use widget::menu_bar::MenuBar;
+pub use widget::menu_bar::Item as MenuItem;
 pub use widget::number_dialer::NumberDialer;
+pub use widget::number_stepper::NumberStepper;
+pub use widget::perf_hud::PerfHud;
+pub use widget::plot::Plot;
+pub use widget::plot::{Chart as PlotChart, Series as PlotSeries};
+pub use widget::popover::Popover;
+pub use widget::radio_group::RadioGroup;
+pub use widget::radio_group::Orientation as RadioOrientation;
+pub use widget::rich_text::RichText;
+pub use widget::rich_text::{Span, SpannedLabel};
+pub use widget::ruler::Ruler;
+pub use widget::ruler::Orientation as RulerOrientation;
+pub use widget::ruler::snap as snap_to_guides;
+pub use widget::scope::Scope;
 pub use widget::slider::Slider;
+pub use widget::slider::LabelPosition as SliderLabelPosition;
+pub use widget::slider::SliderValue;
+pub use widget::slider::Automation as SliderAutomation;
+pub use widget::split_pane::SplitPane;
+pub use widget::split_pane::Orientation as SplitOrientation;
+pub use widget::switch::Switch;
 pub use widget::text_box::TextBox;
 pub use widget::toggle::Toggle;
 pub use widget::xy_pad::XYPad;
 
+pub use animation::{Animation, Ease, Tween};
 pub use background::Background;
-pub use canvas::{Canvas, CanvasId};
+pub use canvas::{Canvas, CanvasId, Pattern};
+pub use clipboard::{Clipboard, MemoryClipboard};
+pub use corner::{CornerProfile, CornerRadii, Roundable};
+pub use enable::{Disabled, Enableable, ReadOnly};
 pub use elmesque::color;
 pub use elmesque::color::{Color, Colorable};
-pub use frame::{Framing, Frameable};
+pub use font::{DEFAULT_FONT, FontId, Fontable};
+pub use gradient::{Gradient, Gradientable};
+pub use palette::{ColorRole, Palette, Paletted, Shade};
+pub use frame::{Framing, Frameable, FrameSides, FrameStyle, FrameWidths};
+pub use frame::{FRAME_TOP, FRAME_BOTTOM, FRAME_LEFT, FRAME_RIGHT, FRAME_ALL};
 pub use graphics::character::CharacterCache;
-pub use label::{FontSize, Labelable};
+pub use id::IdGenerator;
+pub use label::{FontSize, Labelable, LineMetrics, TextSize};
 pub use mouse::Mouse;
 pub use mouse::ButtonState as MouseButtonState;
+pub use mouse::Cursor as MouseCursor;
+pub use mouse::HoverEvent;
+pub use panel::PanelId;
+pub use persist::WidgetStates;
 pub use position::{align_left_of, align_right_of, align_bottom_of, align_top_of};
-pub use position::{Corner, Depth, Direction, Dimensions, HorizontalAlign, Place, Point, Position,
-                   Positionable, Sizeable, VerticalAlign};
-pub use theme::Theme;
-pub use ui::{Ui, UiId};
-pub use widget::Widget;
+pub use position::{Corner, Depth, Direction, Dimensions, HorizontalAlign, Layer, Margin, Padding,
+                   Place, Point, Position, Positionable, Sizeable, VerticalAlign};
+pub use proxy::UiProxy;
+pub use rate_limit::RateLimit;
+pub use swatch::swatch;
+pub use theme::{Checker, Theme};
+pub use ui::{Ui, UiId, RAISED_DEPTH_STEP, REVEAL_MARGIN, WIDGET_RESERVATION};
+pub use unit::{Conversion, Unit};
+pub use widget::{Lifecycle, Tagged, Widget};
+pub use widget_store::WidgetKey;
 
 
+pub mod accessibility;
+mod animation;
 mod background;
+mod binding;
+pub mod backend;
 mod canvas;
+mod clipboard;
+mod corner;
+mod display_list;
+mod enable;
+pub mod event_log;
+mod font;
+mod gradient;
+mod history;
 mod frame;
+pub mod harness;
+mod id;
 mod label;
 pub mod mouse;
+mod palette;
+mod panel;
+mod persist;
 mod position;
+pub mod profile;
+mod proxy;
+mod rate_limit;
+pub mod record;
+pub mod render;
+pub mod snap;
+mod swatch;
+pub mod text;
 mod theme;
+pub mod touch;
 mod ui;
+mod unit;
 pub mod utils;
 mod widget;
-
+mod widget_store;
@@ -5,6 +5,15 @@
 //!
 
 use position::Point;
+use ui::UiId;
+
+/// The maximum duration in nanoseconds between two presses for them to count as a multi-click.
+pub const MULTI_CLICK_NS: u64 = 500_000_000;
+/// The maximum distance the cursor may travel between two presses for them to count as a
+/// multi-click.
+pub const MULTI_CLICK_DISTANCE: f64 = 4.0;
+/// The greatest number of clicks counted before the count starts again from one.
+pub const MAX_CLICKS: u32 = 3;
 
 /// Represents the current state of a mouse button.
 #[derive(Clone, Copy, Debug)]
@@ -15,19 +24,103 @@ pub enum ButtonState {
     Down,
 }
 
+/// The state of a mouse button along with the number of clicks in its current multi-click.
+#[derive(Clone, Copy, Debug)]
+pub struct Button {
+    /// Whether the button is up or down.
+    pub state: ButtonState,
+    /// The number of presses in quick succession that led to the button's current state, i.e. `1`
+    /// for a single click, `2` for a double click and `3` for a triple click.
+    pub clicks: u32,
+    /// The time and position of the most recent press.
+    maybe_last_press: Option<(u64, Point)>,
+}
+
+/// The shapes of mouse cursor that widgets may request (see `Ui::set_mouse_cursor`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Cursor {
+    /// The default pointer.
+    Arrow,
+    /// A text insertion bar, i.e. over editable text.
+    IBeam,
+    /// A pointing hand, i.e. over something that may be clicked.
+    Hand,
+    /// Arrows in all four directions, i.e. over something that may be dragged.
+    Move,
+    /// Left and right arrows, i.e. over a vertical divider.
+    ResizeHorizontal,
+    /// Up and down arrows, i.e. over a horizontal divider.
+    ResizeVertical,
+    /// Arrows towards the top left and bottom right corners.
+    ResizeTopLeftBottomRight,
+    /// Arrows towards the top right and bottom left corners.
+    ResizeTopRightBottomLeft,
+    /// Something that may not currently be interacted with.
+    NotAllowed,
+}
+
+/// A change in the widget over which the mouse hovers (see `Ui::hover_events`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HoverEvent {
+    /// The mouse began hovering over the widget.
+    Entered(UiId),
+    /// The mouse stopped hovering over the widget.
+    Left(UiId),
+}
+
 /// Represents the current state of the Mouse.
 #[derive(Copy, Clone, Debug)]
 pub struct Mouse {
     /// Position of the mouse cursor.
     pub xy: Point,
     /// Left mouse button state.
-    pub left: ButtonState,
+    pub left: Button,
     /// Middle mouse button state.
-    pub middle: ButtonState,
+    pub middle: Button,
     /// Right mouse button state.
-    pub right: ButtonState,
+    pub right: Button,
     /// Unknown button state.
-    pub unknown: ButtonState,
+    pub unknown: Button,
+    /// The distance scrolled by the mouse wheel (or trackpad) since the end of the last render
+    /// cycle, where positive `y` scrolls up. Widgets should prefer `Ui::get_scroll`, which only
+    /// delivers the scroll to the topmost widget beneath the cursor.
+    pub scroll: Point,
+    /// The time (in nanoseconds) at which the most recent mouse event was received.
+    pub time_ns: u64,
+    /// The `Ui` frame number during which the most recent mouse event was received.
+    pub frame_number: u64,
+}
+
+impl Button {
+
+    /// Constructor for a Button with no click history.
+    pub fn new(state: ButtonState) -> Button {
+        Button { state: state, clicks: 0, maybe_last_press: None }
+    }
+
+    /// Register a press of the button at the given time and position, counting it as part of a
+    /// multi-click if it follows closely enough after the previous press.
+    pub fn press(&mut self, time_ns: u64, xy: Point) {
+        let is_multi_click = match self.maybe_last_press {
+            Some((last_ns, last_xy)) => {
+                let distance = ::vecmath::vec2_len(::vecmath::vec2_sub(xy, last_xy));
+                time_ns >= last_ns
+                    && time_ns - last_ns <= MULTI_CLICK_NS
+                    && distance <= MULTI_CLICK_DISTANCE
+            },
+            None => false,
+        };
+        self.clicks = if is_multi_click && self.clicks < MAX_CLICKS { self.clicks + 1 } else { 1 };
+        self.state = ButtonState::Down;
+        self.maybe_last_press = Some((time_ns, xy));
+    }
+
+    /// Register a release of the button. The click count is retained so that widgets reacting
+    /// upon release may still distinguish double clicks.
+    pub fn release(&mut self) {
+        self.state = ButtonState::Up;
+    }
+
 }
 
 impl Mouse {
@@ -37,7 +130,16 @@ impl Mouse {
                left: ButtonState,
                middle: ButtonState,
                right: ButtonState) -> Mouse {
-        Mouse { xy: xy, left: left, middle: middle, right: right, unknown: ButtonState::Up }
+        Mouse {
+            xy: xy,
+            left: Button::new(left),
+            middle: Button::new(middle),
+            right: Button::new(right),
+            unknown: Button::new(ButtonState::Up),
+            scroll: [0.0, 0.0],
+            time_ns: 0,
+            frame_number: 0,
+        }
     }
 
     /// Return the mouse state with its position relative to the given position.
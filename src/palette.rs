@@ -0,0 +1,188 @@
+//!
+//! A palette of named color roles shared by the widgets of a `Theme`, along with helpers for
+//! adjusting colors in HSL space.
+//!
+//! A widget's style may reference a role (i.e. `ColorRole::Primary`) in place of a Color, so that
+//! changing the theme's palette recolors every widget that uses the role. The highlighted and
+//! clicked colors of a widget are derived from its resolved color, so they follow its role too.
+//!
+
+use color::{hsla, rgb, Color, Hsla};
+use std::ops::{Index, IndexMut};
+
+
+/// The named color roles of a palette.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
+pub enum ColorRole {
+    /// The main accent color, i.e. for the primary action of a form.
+    Primary,
+    /// A secondary accent color, i.e. for less prominent actions.
+    Secondary,
+    /// The color behind all widgets.
+    Background,
+    /// The color of the surfaces upon which content is drawn, i.e. panels and text fields.
+    Surface,
+    /// The color indicating an error or invalid input.
+    Error,
+    /// The color of text and icons.
+    Text,
+}
+
+/// The color of each role.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Palette {
+    pub primary: Color,
+    pub secondary: Color,
+    pub background: Color,
+    pub surface: Color,
+    pub error: Color,
+    pub text: Color,
+}
+
+/// Widgets whose color may be given by a role of the theme's palette.
+pub trait Paletted: Sized {
+
+    /// Color the widget with the given role of the theme's palette. A color given via
+    /// `Colorable::color` takes precedence.
+    fn color_role(self, role: ColorRole) -> Self;
+
+}
+
+/// Adjustments to a color's hue, saturation and lightness.
+pub trait Shade: Sized {
+
+    /// Increase the lightness by the given amount (from `0.0` to `1.0`).
+    fn lighten(self, amount: f32) -> Self;
+
+    /// Decrease the lightness by the given amount (from `0.0` to `1.0`).
+    fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Increase the saturation by the given amount (from `0.0` to `1.0`).
+    fn saturate(self, amount: f32) -> Self;
+
+    /// Decrease the saturation by the given amount (from `0.0` to `1.0`).
+    fn desaturate(self, amount: f32) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Rotate the hue by the given angle in radians.
+    fn rotate_hue(self, radians: f32) -> Self;
+
+}
+
+
+impl Palette {
+
+    /// Construct the default palette.
+    pub fn new() -> Palette {
+        Palette {
+            primary: rgb(0.2, 0.45, 0.85),
+            secondary: rgb(0.45, 0.5, 0.6),
+            background: rgb(0.1, 0.1, 0.12),
+            surface: rgb(0.95, 0.95, 0.95),
+            error: rgb(0.85, 0.2, 0.2),
+            text: rgb(0.0, 0.0, 0.0),
+        }
+    }
+
+}
+
+impl Index<ColorRole> for Palette {
+    type Output = Color;
+    fn index(&self, role: ColorRole) -> &Color {
+        match role {
+            ColorRole::Primary => &self.primary,
+            ColorRole::Secondary => &self.secondary,
+            ColorRole::Background => &self.background,
+            ColorRole::Surface => &self.surface,
+            ColorRole::Error => &self.error,
+            ColorRole::Text => &self.text,
+        }
+    }
+}
+
+impl IndexMut<ColorRole> for Palette {
+    fn index_mut(&mut self, role: ColorRole) -> &mut Color {
+        match role {
+            ColorRole::Primary => &mut self.primary,
+            ColorRole::Secondary => &mut self.secondary,
+            ColorRole::Background => &mut self.background,
+            ColorRole::Surface => &mut self.surface,
+            ColorRole::Error => &mut self.error,
+            ColorRole::Text => &mut self.text,
+        }
+    }
+}
+
+
+impl Shade for Color {
+    fn lighten(self, amount: f32) -> Color {
+        let Hsla(h, s, l, a) = self.to_hsl();
+        hsla(h, s, clamp(l + amount), a)
+    }
+    fn saturate(self, amount: f32) -> Color {
+        let Hsla(h, s, l, a) = self.to_hsl();
+        hsla(h, clamp(s + amount), l, a)
+    }
+    fn rotate_hue(self, radians: f32) -> Color {
+        use std::f32::consts::PI;
+        let Hsla(h, s, l, a) = self.to_hsl();
+        let h = (h + radians) % (2.0 * PI);
+        hsla(if h < 0.0 { h + 2.0 * PI } else { h }, s, l, a)
+    }
+}
+
+/// Clamp the given component to the range `0.0` to `1.0`.
+fn clamp(component: f32) -> f32 {
+    component.max(0.0).min(1.0)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use color::{rgb, Color, Rgba};
+    use std::f32::consts::PI;
+    use super::{ColorRole, Palette, Shade};
+
+    fn assert_rgb(color: Color, (r, g, b): (f32, f32, f32)) {
+        let Rgba(cr, cg, cb, _) = color.to_rgb();
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(close(cr, r) && close(cg, g) && close(cb, b),
+                "{:?} is not close to {:?}", (cr, cg, cb), (r, g, b));
+    }
+
+    #[test]
+    fn lightness_is_adjusted_within_its_range() {
+        let grey = rgb(0.5, 0.5, 0.5);
+        assert_rgb(grey.lighten(0.25), (0.75, 0.75, 0.75));
+        assert_rgb(grey.darken(0.25), (0.25, 0.25, 0.25));
+        assert_rgb(grey.lighten(1.0), (1.0, 1.0, 1.0));
+        assert_rgb(grey.darken(1.0), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn desaturated_colors_become_grey() {
+        assert_rgb(rgb(1.0, 0.0, 0.0).desaturate(1.0), (0.5, 0.5, 0.5));
+        assert_rgb(rgb(1.0, 0.0, 0.0).saturate(1.0), (1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn hues_rotate_in_either_direction() {
+        let red = rgb(1.0, 0.0, 0.0);
+        assert_rgb(red.rotate_hue(2.0 * PI / 3.0), (0.0, 1.0, 0.0));
+        assert_rgb(red.rotate_hue(-2.0 * PI / 3.0), (0.0, 0.0, 1.0));
+        assert_rgb(red.rotate_hue(2.0 * PI), (1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn palettes_are_indexed_by_role() {
+        let mut palette = Palette::new();
+        assert_eq!(palette[ColorRole::Primary], palette.primary);
+        assert_eq!(palette[ColorRole::Error], palette.error);
+        palette[ColorRole::Surface] = rgb(0.0, 1.0, 0.0);
+        assert_eq!(palette.surface, rgb(0.0, 1.0, 0.0));
+    }
+
+}
@@ -0,0 +1,137 @@
+//!
+//! Keyboard panels: groups of value widgets (i.e. Sliders and NumberDialers) between which the
+//! arrow keys move the keyboard focus, so that dense parameter panels may be operated entirely
+//! from the keyboard. The focused widget is adjusted coarsely via PageUp and PageDown (or finely
+//! while Shift is held).
+//!
+//! Each panel remembers the widget that was most recently focused within it, so that the focus
+//! may be returned there via `Ui::focus_panel` after it has moved elsewhere.
+//!
+
+use num::{Float, NumCast};
+use piston::input::keyboard::Key;
+use position::Point;
+use std::collections::HashMap;
+use ui::UiId;
+use utils::clamp;
+
+
+/// Unique identifier for a keyboard panel, chosen by the application.
+pub type PanelId = usize;
+
+/// The widgets within a single panel.
+struct Panel {
+    /// The widgets that have joined the panel, in the order in which they first joined.
+    members: Vec<UiId>,
+    /// The member that was most recently focused.
+    maybe_last_focused: Option<UiId>,
+}
+
+/// Stores the members and focus memory of every keyboard panel.
+pub struct Panels {
+    panels: HashMap<PanelId, Panel>,
+}
+
+
+impl Panels {
+
+    /// Construct an empty store.
+    pub fn new() -> Panels {
+        Panels { panels: HashMap::new() }
+    }
+
+    /// Add the widget to the given panel (removing it from any other), remembering it as the
+    /// panel's most recently focused member if it is focused.
+    pub fn join(&mut self, panel_id: PanelId, ui_id: UiId, is_focused: bool) {
+        if self.panel_of(ui_id).map_or(false, |id| id != panel_id) {
+            self.remove(ui_id);
+        }
+        let panel = self.panels.entry(panel_id).or_insert_with(|| Panel {
+            members: Vec::new(),
+            maybe_last_focused: None,
+        });
+        if !panel.members.contains(&ui_id) {
+            panel.members.push(ui_id);
+        }
+        if is_focused {
+            panel.maybe_last_focused = Some(ui_id);
+        }
+    }
+
+    /// The panel to which the widget belongs, if any.
+    pub fn panel_of(&self, ui_id: UiId) -> Option<PanelId> {
+        self.panels.iter()
+            .find(|&(_, panel)| panel.members.contains(&ui_id))
+            .map(|(&panel_id, _)| panel_id)
+    }
+
+    /// The members of the given panel.
+    pub fn members(&self, panel_id: PanelId) -> &[UiId] {
+        self.panels.get(&panel_id).map(|panel| &panel.members[..]).unwrap_or(&[])
+    }
+
+    /// The most recently focused member of the given panel, if any.
+    pub fn last_focused(&self, panel_id: PanelId) -> Option<UiId> {
+        self.panels.get(&panel_id).and_then(|panel| panel.maybe_last_focused)
+    }
+
+    /// Remove the widget from its panel, i.e. once the widget itself has been removed.
+    pub fn remove(&mut self, ui_id: UiId) {
+        for panel in self.panels.values_mut() {
+            panel.members.retain(|&id| id != ui_id);
+            if panel.maybe_last_focused == Some(ui_id) {
+                panel.maybe_last_focused = None;
+            }
+        }
+    }
+
+}
+
+
+/// The direction in which the given arrow key moves the focus, as a unit vector.
+pub fn arrow_direction(key: Key) -> Option<Point> {
+    match key {
+        Key::Left => Some([-1.0, 0.0]),
+        Key::Right => Some([1.0, 0.0]),
+        Key::Up => Some([0.0, 1.0]),
+        Key::Down => Some([0.0, -1.0]),
+        _ => None,
+    }
+}
+
+/// The candidate nearest to `from` in the given direction, favouring those that are most closely
+/// aligned with it.
+pub fn nearest<I>(from: Point, direction: Point, candidates: I) -> Option<UiId>
+    where
+        I: Iterator<Item=(UiId, Point)>,
+{
+    candidates.filter_map(|(ui_id, xy)| {
+        let (dx, dy) = (xy[0] - from[0], xy[1] - from[1]);
+        let along = dx * direction[0] + dy * direction[1];
+        let across = (dx * direction[1] - dy * direction[0]).abs();
+        if along > 0.0 { Some((ui_id, along + across * 2.0)) } else { None }
+    }).fold(None, |maybe_best, (ui_id, distance)| match maybe_best {
+        Some((_, best)) if best <= distance => maybe_best,
+        _ => Some((ui_id, distance)),
+    }).map(|(ui_id, _)| ui_id)
+}
+
+/// Adjust the value by a tenth of its range for each press of PageUp (or PageDown), or by a
+/// hundredth if `is_fine`, keeping it within the range.
+pub fn page_adjust<T>(value: T, min: T, max: T, keys: &[Key], is_fine: bool) -> T
+    where
+        T: Float + NumCast,
+{
+    let divisions = if is_fine { 100.0 } else { 10.0 };
+    let value_f: f64 = NumCast::from(value).unwrap();
+    let min_f: f64 = NumCast::from(min).unwrap();
+    let max_f: f64 = NumCast::from(max).unwrap();
+    let step = (max_f - min_f) / divisions;
+    let new_value_f = keys.iter().fold(value_f, |value, key| match *key {
+        Key::PageUp => value + step,
+        Key::PageDown => value - step,
+        _ => value,
+    });
+    if new_value_f == value_f { return value }
+    NumCast::from(clamp(new_value_f, min_f.min(max_f), min_f.max(max_f))).unwrap()
+}
@@ -0,0 +1,116 @@
+//!
+//! Persisting the states of widgets across sessions, i.e. so that a tool may save the values of
+//! its Sliders, the contents of its TextBoxes and which of its CollapsibleAreas are expanded when
+//! it exits and restore them when it is next launched.
+//!
+//! Widgets that are made `persistent` record their state within the `Ui` every update. The
+//! recorded states may be retrieved via `Ui::widget_states` and saved to file, then loaded and
+//! given to `Ui::restore_widget_states`, after which each widget adopts its restored state (and
+//! reacts with it) at its next update.
+//!
+
+use rustc_serialize::{json, Decodable, Encodable};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::str;
+use ui::UiId;
+
+
+/// The persisted states of widgets, keyed by the kind and UiId of each widget.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct WidgetStates {
+    /// The json encoding of each widget's state.
+    states: BTreeMap<String, String>,
+}
+
+
+/// The key under which the state of the widget of the given kind and UiId is stored.
+fn key(kind: &str, ui_id: UiId) -> String {
+    format!("{}/{}", kind, ui_id)
+}
+
+
+impl WidgetStates {
+
+    /// Construct an empty set of widget states.
+    pub fn new() -> WidgetStates {
+        WidgetStates { states: BTreeMap::new() }
+    }
+
+    /// Store the state of the widget of the given kind and UiId, replacing its previous state.
+    pub fn insert<T: Encodable>(&mut self, kind: &str, ui_id: UiId, state: &T) {
+        match json::encode(state) {
+            Ok(json_string) => { self.states.insert(key(kind, ui_id), json_string); },
+            Err(e) => {
+                use std::io::Write;
+                writeln!(::std::io::stderr(),
+                         "Warning: Failed to persist the state of the {} with UiId {}: {}",
+                         kind, ui_id, Error::description(&e)).unwrap();
+            },
+        }
+    }
+
+    /// Remove and return the state of the widget of the given kind and UiId, if there is one
+    /// that may be decoded as `T`.
+    pub fn remove<T: Decodable>(&mut self, kind: &str, ui_id: UiId) -> Option<T> {
+        self.states.remove(&key(kind, ui_id)).and_then(|json_string| {
+            json::decode(&json_string).ok()
+        })
+    }
+
+    /// The number of widgets whose states are stored.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Whether or not no widget states are stored.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Load the widget states from file.
+    pub fn load(path: &str) -> Result<WidgetStates, String> {
+        let mut file = match File::open(&Path::new(path)) {
+            Ok(file) => file,
+            Err(e) => return Err(format!("Failed to open file for WidgetStates: {}",
+                                         Error::description(&e))),
+        };
+        let mut contents = Vec::new();
+        if let Err(e) = ::std::io::Read::read_to_end(&mut file, &mut contents) {
+            return Err(format!("Failed to load WidgetStates correctly: {}",
+                               Error::description(&e)));
+        }
+        let json_object = match json::Json::from_str(str::from_utf8(&contents[..]).unwrap()) {
+            Ok(json_object) => json_object,
+            Err(e) => return Err(format!("Failed to construct json_object from str: {}",
+                                         Error::description(&e))),
+        };
+        let mut decoder = json::Decoder::new(json_object);
+        match Decodable::decode(&mut decoder) {
+            Ok(states) => Ok(states),
+            Err(e) => Err(format!("Failed to construct WidgetStates from json decoder: {}",
+                                  Error::description(&e))),
+        }
+    }
+
+    /// Save the widget states to file.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json_string = match json::encode(self) {
+            Ok(x) => x,
+            Err(e) => return Err(e.description().to_owned())
+        };
+        let mut file = match File::create(&Path::new(path)) {
+            Ok(file) => file,
+            Err(e) => return Err(format!("Failed to create a File at the given path: {}",
+                                         Error::description(&e)))
+        };
+        match ::std::io::Write::write_all(&mut file, json_string.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(format!("WidgetStates failed to save correctly: {}",
+                                  Error::description(&e))),
+        }
+    }
+
+}
@@ -5,8 +5,25 @@ use ui::UiId;
 
 /// The depth at which the widget will be rendered. This determines the order of rendering where
 /// widgets with a greater depth will be rendered first. 0.0 is the default depth.
+///
+/// Depth orders widgets within the same `Layer` only.
 pub type Depth = f32;
 
+/// The layers in which widgets are drawn. Every widget in a higher layer is drawn above, and
+/// receives the mouse before, every widget in a lower layer regardless of their depths. Widgets
+/// are in the `Normal` layer unless set within another (see `Ui::with_layer` and `Ui::set_layer`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    /// The layer of most widgets.
+    Normal,
+    /// Windows floating above the normal widgets, i.e. the Floating widget.
+    Floating,
+    /// Transient widgets that open above all windows, i.e. menus and popovers.
+    Popup,
+    /// Hints drawn above everything else. Widgets in this layer never receive the mouse.
+    Tooltip,
+}
+
 /// General use 2D spatial dimensions.
 pub type Dimensions = [Scalar; 2];
 
@@ -24,6 +41,8 @@ pub enum Position {
     Direction(Direction, Scalar, Option<UiId>),
     /// A position at a place on the current Canvas.
     Place(Place, Option<CanvasId>),
+    /// A position at a place within the padded area of the widget with the given UiId.
+    PlaceOn(Place, UiId),
 }
 
 impl Position {
@@ -175,6 +194,15 @@ pub trait Positionable: Sized {
     /// Align the position vertically (only effective for Left or Right `Direction`s).
     fn vertical_align(self, align: VerticalAlign) -> Self;
 
+    /// Set the Margin kept clear around the widget's outer edges. The margin is added to the
+    /// distance between the widget and any widget positioned in some `Direction` from it (and
+    /// vice versa), to the distance between the widget and the edges of the Canvas or widget on
+    /// which it is placed, and to the edges aligned with a relative widget.
+    ///
+    /// A single `Scalar` gives a uniform margin, while a `(left, right, top, bottom)` tuple gives
+    /// the margin for each side.
+    fn margin<M: Into<Margin>>(self, margin: M) -> Self;
+
     /// Align the position to the left (only effective for Up or Down `Direction`s).
     fn align_left(self) -> Self {
         self.horizontal_align(HorizontalAlign::Left)
@@ -212,6 +240,12 @@ pub trait Positionable: Sized {
         self.position(Position::Place(place, maybe_id))
     }
 
+    /// Place the widget at some position within the padded area of the widget with the given
+    /// UiId (i.e. a panel or a Popover hosting it).
+    fn place_on(self, ui_id: UiId, place: Place) -> Self {
+        self.position(Position::PlaceOn(place, ui_id))
+    }
+
     /// Place the widget in the middle of the given Canvas.
     fn middle_of(self, id: CanvasId) -> Self { self.place(Place::Middle, Some(id)) }
 
@@ -290,6 +324,13 @@ pub trait Sizeable: Sized {
         self.dim([width, height])
     }
 
+    /// Set the Padding between the widget's edges and its content, including any widgets placed
+    /// on it via `Positionable::place_on`.
+    ///
+    /// A single `Scalar` gives a uniform padding, while a `(left, right, top, bottom)` tuple gives
+    /// the padding for each side.
+    fn padding<P: Into<Padding>>(self, padding: P) -> Self;
+
 }
 
 /// A corner of a rectangle.
@@ -387,8 +428,118 @@ pub fn mid_right_of(target: Dimensions, dim: Dimensions) -> Point {
     [align_right_of(target[0], dim[0]), 0.0]
 }
 
+/// The position of a rect with `dim` Dimensions at the given Place within the `target`
+/// Dimensions.
+pub fn place_within(place: Place, target: Dimensions, dim: Dimensions) -> Point {
+    match place {
+        Place::Middle      => middle_of(target, dim),
+        Place::TopLeft     => top_left_of(target, dim),
+        Place::TopRight    => top_right_of(target, dim),
+        Place::BottomLeft  => bottom_left_of(target, dim),
+        Place::BottomRight => bottom_right_of(target, dim),
+        Place::MidTop      => mid_top_of(target, dim),
+        Place::MidBottom   => mid_bottom_of(target, dim),
+        Place::MidLeft     => mid_left_of(target, dim),
+        Place::MidRight    => mid_right_of(target, dim),
+    }
+}
+
+/// Whether or not a rect with `dim` Dimensions at `xy` lies entirely within the `bounds`
+/// Dimensions (centred upon the origin, i.e. the window).
+pub fn is_within(xy: Point, dim: Dimensions, bounds: Dimensions) -> bool {
+    let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+    let (half_bounds_w, half_bounds_h) = (bounds[0] / 2.0, bounds[1] / 2.0);
+    xy[0] - half_w >= -half_bounds_w && xy[0] + half_w <= half_bounds_w
+        && xy[1] - half_h >= -half_bounds_h && xy[1] + half_h <= half_bounds_h
+}
+
+/// Slide a rect with `dim` Dimensions at `xy` the shortest distance required for it to lie within
+/// the `bounds` Dimensions (centred upon the origin). A rect larger than the bounds is aligned with
+/// their top left.
+pub fn slide_within(xy: Point, dim: Dimensions, bounds: Dimensions) -> Point {
+    let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+    let (half_bounds_w, half_bounds_h) = (bounds[0] / 2.0, bounds[1] / 2.0);
+    let x = xy[0].min(half_bounds_w - half_w).max(-half_bounds_w + half_w);
+    let y = xy[1].max(-half_bounds_h + half_h).min(half_bounds_h - half_h);
+    [x, y]
+}
+
+/// Place a rect with `dim` Dimensions `gap` pixels beside the given `side` of a target rect,
+/// keeping it within the `bounds` Dimensions (centred upon the origin). If the rect would overflow
+/// the bounds on the given side but not on the opposite side it is flipped, after which it is slid
+/// along the target's edge to remain within the bounds.
+///
+/// Returns the side used and the rect's centre.
+pub fn place_beside(side: Direction,
+                    target_xy: Point,
+                    target_dim: Dimensions,
+                    dim: Dimensions,
+                    gap: Scalar,
+                    bounds: Dimensions) -> (Direction, Point) {
+    let beside = |side: Direction| -> Point {
+        let x_offset = (target_dim[0] + dim[0]) / 2.0 + gap;
+        let y_offset = (target_dim[1] + dim[1]) / 2.0 + gap;
+        match side {
+            Direction::Up    => [target_xy[0], target_xy[1] + y_offset],
+            Direction::Down  => [target_xy[0], target_xy[1] - y_offset],
+            Direction::Left  => [target_xy[0] - x_offset, target_xy[1]],
+            Direction::Right => [target_xy[0] + x_offset, target_xy[1]],
+        }
+    };
+    // Only the axis across the target's edge matters, as the rect may slide along the other.
+    let fits = |side: Direction| -> bool {
+        let xy = beside(side);
+        match side {
+            Direction::Up    => xy[1] + dim[1] / 2.0 <= bounds[1] / 2.0,
+            Direction::Down  => xy[1] - dim[1] / 2.0 >= -bounds[1] / 2.0,
+            Direction::Left  => xy[0] - dim[0] / 2.0 >= -bounds[0] / 2.0,
+            Direction::Right => xy[0] + dim[0] / 2.0 <= bounds[0] / 2.0,
+        }
+    };
+    let opposite = match side {
+        Direction::Up    => Direction::Down,
+        Direction::Down  => Direction::Up,
+        Direction::Left  => Direction::Right,
+        Direction::Right => Direction::Left,
+    };
+    let side = if !fits(side) && fits(opposite) { opposite } else { side };
+    let xy = beside(side);
+    let slid_xy = slide_within(xy, dim, bounds);
+    match side {
+        Direction::Up | Direction::Down    => (side, [slid_xy[0], xy[1]]),
+        Direction::Left | Direction::Right => (side, [xy[0], slid_xy[1]]),
+    }
+}
+
+/// Place a rect with `dim` Dimensions so that one of its corners lies at `point`, with the rect
+/// extending towards the given `Corner`, keeping it within the `bounds` Dimensions (centred upon
+/// the origin). On each axis where the rect would overflow the bounds but would fit if extending
+/// the other way it is flipped, after which it is slid to remain within the bounds. Returns the
+/// rect's centre.
+pub fn place_at_point(point: Point, corner: Corner, dim: Dimensions, bounds: Dimensions) -> Point {
+    let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+    let (half_bounds_w, half_bounds_h) = (bounds[0] / 2.0, bounds[1] / 2.0);
+    let (towards_right, towards_top) = match corner {
+        Corner::TopLeft     => (false, true),
+        Corner::TopRight    => (true, true),
+        Corner::BottomLeft  => (false, false),
+        Corner::BottomRight => (true, false),
+    };
+    let fits_right = point[0] + dim[0] <= half_bounds_w;
+    let fits_left = point[0] - dim[0] >= -half_bounds_w;
+    let fits_top = point[1] + dim[1] <= half_bounds_h;
+    let fits_bottom = point[1] - dim[1] >= -half_bounds_h;
+    let towards_right = if towards_right { fits_right || !fits_left }
+                        else { !fits_left && fits_right };
+    let towards_top = if towards_top { fits_top || !fits_bottom }
+                      else { !fits_bottom && fits_top };
+    let x = if towards_right { point[0] + half_w } else { point[0] - half_w };
+    let y = if towards_top { point[1] + half_h } else { point[1] - half_h };
+    slide_within([x, y], dim, bounds)
+}
+
 /// The distance between the inner edge of a frame and the outer edge of the inner content.
-#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+#[derive(Copy, Debug, Clone, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Padding {
     /// Padding between the top of a Widget and the top of a Canvas.
     pub top: f64,
@@ -407,6 +558,21 @@ impl Padding {
         Padding { top: 0.0, bottom: 0.0, left: 0.0, right: 0.0 }
     }
 
+    /// The same padding on every side.
+    pub fn uniform(pad: Scalar) -> Padding {
+        Padding { top: pad, bottom: pad, left: pad, right: pad }
+    }
+
+    /// The given Dimensions with the padding removed from each side.
+    pub fn inner_dim(&self, dim: Dimensions) -> Dimensions {
+        [dim[0] - self.left - self.right, dim[1] - self.top - self.bottom]
+    }
+
+    /// The offset of the centre of the padded area from the centre of the outer area.
+    pub fn inner_offset(&self) -> Point {
+        [(self.left - self.right) / 2.0, (self.bottom - self.top) / 2.0]
+    }
+
     /// Determine the offset for the given `Place`.
     pub fn offset_from(&self, place: Place) -> Point {
         match place {
@@ -425,7 +591,7 @@ impl Padding {
 }
 
 /// The distance between the dimension bound and the outer edge of the frame.
-#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+#[derive(Copy, Debug, Clone, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Margin {
     /// Margin between the y max Canvas and the outer edge of its frame.
     pub top: f64,
@@ -437,3 +603,105 @@ pub struct Margin {
     pub right: f64,
 }
 
+impl Margin {
+
+    /// No margin.
+    pub fn none() -> Margin {
+        Margin { top: 0.0, bottom: 0.0, left: 0.0, right: 0.0 }
+    }
+
+    /// The same margin on every side.
+    pub fn uniform(margin: Scalar) -> Margin {
+        Margin { top: margin, bottom: margin, left: margin, right: margin }
+    }
+
+    /// Determine the offset for the given `Place`.
+    pub fn offset_from(&self, place: Place) -> Point {
+        Padding { top: self.top, bottom: self.bottom, left: self.left, right: self.right }
+            .offset_from(place)
+    }
+
+}
+
+impl From<Scalar> for Padding {
+    fn from(pad: Scalar) -> Padding {
+        Padding::uniform(pad)
+    }
+}
+
+impl From<(Scalar, Scalar, Scalar, Scalar)> for Padding {
+    fn from((left, right, top, bottom): (Scalar, Scalar, Scalar, Scalar)) -> Padding {
+        Padding { top: top, bottom: bottom, left: left, right: right }
+    }
+}
+
+impl From<Scalar> for Margin {
+    fn from(margin: Scalar) -> Margin {
+        Margin::uniform(margin)
+    }
+}
+
+impl From<(Scalar, Scalar, Scalar, Scalar)> for Margin {
+    fn from((left, right, top, bottom): (Scalar, Scalar, Scalar, Scalar)) -> Margin {
+        Margin { top: top, bottom: bottom, left: left, right: right }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Corner, Dimensions, Direction, Point};
+    use super::{is_within, place_at_point, place_beside, slide_within};
+
+    const BOUNDS: Dimensions = [100.0, 100.0];
+
+    fn beside(side: Direction,
+              target_xy: Point,
+              target_dim: Dimensions,
+              dim: Dimensions) -> (Direction, Point) {
+        place_beside(side, target_xy, target_dim, dim, 0.0, BOUNDS)
+    }
+
+    #[test]
+    fn rects_are_within_only_if_every_edge_is() {
+        assert!(is_within([0.0, 0.0], [10.0, 10.0], BOUNDS));
+        assert!(is_within([45.0, -45.0], [10.0, 10.0], BOUNDS));
+        assert!(!is_within([48.0, 0.0], [10.0, 10.0], BOUNDS));
+        assert!(!is_within([0.0, 0.0], [110.0, 10.0], BOUNDS));
+    }
+
+    #[test]
+    fn overflowing_rects_slide_back_within_the_bounds() {
+        assert_eq!(slide_within([0.0, 0.0], [10.0, 10.0], BOUNDS), [0.0, 0.0]);
+        assert_eq!(slide_within([48.0, -48.0], [10.0, 10.0], BOUNDS), [45.0, -45.0]);
+        // Rects larger than the bounds are aligned with their top left.
+        assert_eq!(slide_within([0.0, 0.0], [200.0, 200.0], BOUNDS), [50.0, -50.0]);
+    }
+
+    #[test]
+    fn rects_beside_a_target_flip_to_the_side_that_fits() {
+        let placed = beside(Direction::Down, [0.0, 0.0], [20.0, 10.0], [30.0, 10.0]);
+        assert_eq!(placed, (Direction::Down, [0.0, -10.0]));
+        let placed = beside(Direction::Down, [0.0, -40.0], [20.0, 10.0], [30.0, 30.0]);
+        assert_eq!(placed, (Direction::Up, [0.0, -20.0]));
+        // If neither side fits, the requested side is kept.
+        let placed = beside(Direction::Right, [0.0, 0.0], [90.0, 10.0], [30.0, 10.0]);
+        assert_eq!(placed, (Direction::Right, [60.0, 0.0]));
+    }
+
+    #[test]
+    fn rects_beside_a_target_slide_along_its_edge() {
+        let placed = beside(Direction::Down, [45.0, 0.0], [10.0, 10.0], [30.0, 10.0]);
+        assert_eq!(placed, (Direction::Down, [35.0, -10.0]));
+    }
+
+    #[test]
+    fn rects_at_a_point_flip_away_from_the_edges_they_would_overflow() {
+        let xy = place_at_point([0.0, 0.0], Corner::BottomRight, [20.0, 20.0], BOUNDS);
+        assert_eq!(xy, [10.0, -10.0]);
+        let xy = place_at_point([40.0, 0.0], Corner::BottomRight, [20.0, 20.0], BOUNDS);
+        assert_eq!(xy, [30.0, -10.0]);
+        let xy = place_at_point([0.0, -40.0], Corner::BottomLeft, [20.0, 20.0], BOUNDS);
+        assert_eq!(xy, [-10.0, -30.0]);
+    }
+
+}
@@ -0,0 +1,144 @@
+//!
+//! A module for measuring where the time within each frame goes: how long widgets spend updating
+//! their state and constructing their Forms, how long the `Ui` spends rendering, how many
+//! widgets were updated and how many of those could reuse their cached Forms.
+//!
+//! Profiling is started via `Ui::enable_profiler` (or simply by setting a `PerfHud` widget, which
+//! displays the results) and the most recent frames are available via `Ui::profiler`.
+//!
+
+use std::collections::VecDeque;
+
+
+/// The number of frames kept by a profiler enabled by a `PerfHud`.
+pub const DEFAULT_HISTORY: usize = 120;
+
+/// The measurements for a single frame, from the end of one `Ui::draw` to the end of the next.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frame {
+    /// The frame number of the `Ui` at which the measurements were taken.
+    pub frame: u64,
+    /// The time in nanoseconds spent within `Widget::update` by all widgets.
+    pub update_ns: u64,
+    /// The time in nanoseconds spent within `Widget::draw` constructing new Forms.
+    pub draw_ns: u64,
+    /// The time in nanoseconds spent rendering the display list within `Ui::draw`.
+    pub render_ns: u64,
+    /// The number of widgets that were set.
+    pub widgets_updated: usize,
+    /// The number of widgets whose state or style changed, requiring new Forms.
+    pub widgets_redrawn: usize,
+    /// The number of allocations made during the frame, if reported via
+    /// `Ui::record_allocations`.
+    pub maybe_allocations: Option<u64>,
+    /// The time in nanoseconds at which `Ui::draw` finished.
+    pub rendered_at_ns: u64,
+}
+
+/// The measurements for the most recent frames, oldest first, along with those for the frame in
+/// progress.
+#[derive(Clone, Debug)]
+pub struct Profiler {
+    capacity: usize,
+    current: Frame,
+    history: VecDeque<Frame>,
+}
+
+
+impl Frame {
+
+    /// A frame with no measurements.
+    pub fn new(frame: u64) -> Frame {
+        Frame {
+            frame: frame,
+            update_ns: 0,
+            draw_ns: 0,
+            render_ns: 0,
+            widgets_updated: 0,
+            widgets_redrawn: 0,
+            maybe_allocations: None,
+            rendered_at_ns: 0,
+        }
+    }
+
+    /// The number of widgets that were set but could reuse their cached Forms.
+    pub fn widgets_cached(&self) -> usize {
+        self.widgets_updated - self.widgets_redrawn
+    }
+
+}
+
+impl Profiler {
+
+    /// Construct a profiler keeping the measurements for at most `capacity` frames.
+    pub fn new(capacity: usize) -> Profiler {
+        Profiler {
+            capacity: capacity,
+            current: Frame::new(0),
+            history: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record that a widget was set, having spent `update_ns` within `Widget::update` and, if it
+    /// constructed new Forms, `maybe_draw_ns` within `Widget::draw`.
+    pub fn widget_updated(&mut self, update_ns: u64, maybe_draw_ns: Option<u64>) {
+        self.current.update_ns += update_ns;
+        self.current.widgets_updated += 1;
+        if let Some(draw_ns) = maybe_draw_ns {
+            self.current.draw_ns += draw_ns;
+            self.current.widgets_redrawn += 1;
+        }
+    }
+
+    /// Record the number of allocations made during the frame in progress.
+    pub fn record_allocations(&mut self, count: u64) {
+        self.current.maybe_allocations = Some(count);
+    }
+
+    /// Record that the frame with the given number finished rendering, having spent `render_ns`
+    /// within `Ui::draw`, and begin measuring the next.
+    pub fn rendered(&mut self, frame: u64, render_ns: u64, now_ns: u64) {
+        self.current.frame = frame;
+        self.current.render_ns = render_ns;
+        self.current.rendered_at_ns = now_ns;
+        if self.capacity > 0 {
+            if self.history.len() == self.capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back(self.current);
+        }
+        self.current = Frame::new(frame + 1);
+    }
+
+    /// The most recently completed frame.
+    pub fn latest(&self) -> Option<&Frame> {
+        self.history.back()
+    }
+
+    /// The completed frames, oldest first.
+    pub fn history(&self) -> ::std::collections::vec_deque::Iter<Frame> {
+        self.history.iter()
+    }
+
+    /// The maximum number of frames kept by the profiler.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The mean number of frames rendered per second over the completed frames.
+    pub fn fps(&self) -> f64 {
+        match (self.history.front(), self.history.back()) {
+            (Some(first), Some(last)) if last.rendered_at_ns > first.rendered_at_ns => {
+                let secs = (last.rendered_at_ns - first.rendered_at_ns) as f64 / 1_000_000_000.0;
+                (self.history.len() - 1) as f64 / secs
+            },
+            _ => 0.0,
+        }
+    }
+
+    /// Discard the measurements for all completed frames.
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+}
@@ -0,0 +1,99 @@
+//!
+//! A thread-safe handle via which other threads (i.e. a worker loading files or a network client)
+//! may send messages to the `Ui`, such as progress updates, newly loaded rows or requests for a
+//! notification to be shown.
+//!
+//! Messages are delivered at the start of the `Ui`'s next input cycle and remain available via
+//! `Ui::take_messages` until the end of that cycle, so the application can apply them before
+//! setting its widgets rather than sharing its state behind a mutex.
+//!
+
+use std::any::Any;
+use std::mem;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+
+/// A message sent via a `UiProxy`.
+pub type Message = Box<Any + Send>;
+
+/// A handle that may be cloned and sent to other threads in order to send messages of any `Send`
+/// type to the `Ui`. Retrieve one via `Ui::proxy`.
+///
+/// ```ignore
+/// let proxy = ui.proxy();
+/// thread::spawn(move || {
+///     for (i, row) in load_rows().enumerate() {
+///         proxy.send(Progress(i));
+///         proxy.send(row);
+///     }
+/// });
+///
+/// // Within the application's update, before setting its widgets.
+/// for row in ui.take_messages::<Row>() {
+///     app.rows.push(row);
+/// }
+/// ```
+///
+/// Messages may also be closures that mutate the application's state, i.e. of the type
+/// `Box<FnMut(&mut App) + Send>`, which the application calls as it takes them.
+#[derive(Clone)]
+pub struct UiProxy {
+    sender: Sender<Message>,
+}
+
+/// The messages received from all `UiProxy`s, owned by the `Ui`.
+pub struct Mailbox {
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+    /// The messages delivered at the start of the current input cycle that have not been taken.
+    delivered: Vec<Message>,
+}
+
+
+impl UiProxy {
+
+    /// Send a message to the `Ui`, to be delivered at the start of its next input cycle. Returns
+    /// `false` if the `Ui` has since been dropped, in which case the message is discarded.
+    pub fn send<T: Any + Send>(&self, message: T) -> bool {
+        self.sender.send(Box::new(message)).is_ok()
+    }
+
+}
+
+
+impl Mailbox {
+
+    /// Construct an empty mailbox.
+    pub fn new() -> Mailbox {
+        let (sender, receiver) = mpsc::channel();
+        Mailbox { sender: sender, receiver: receiver, delivered: Vec::new() }
+    }
+
+    /// A new handle via which messages may be sent to the mailbox.
+    pub fn proxy(&self) -> UiProxy {
+        UiProxy { sender: self.sender.clone() }
+    }
+
+    /// Deliver all messages received since the previous delivery, discarding any delivered
+    /// previously that were not taken.
+    pub fn deliver(&mut self) {
+        self.delivered.clear();
+        while let Ok(message) = self.receiver.try_recv() {
+            self.delivered.push(message);
+        }
+    }
+
+    /// Take the delivered messages of type `T`, in the order in which they were sent.
+    pub fn take<T: Any>(&mut self) -> Vec<T> {
+        let delivered = mem::replace(&mut self.delivered, Vec::new());
+        let mut taken = Vec::new();
+        for message in delivered {
+            match message.downcast::<T>() {
+                Ok(message) => taken.push(*message),
+                Err(message) => self.delivered.push(message),
+            }
+        }
+        taken
+    }
+
+}
@@ -0,0 +1,133 @@
+
+use std::any::Any;
+
+
+/// Limits the rate at which a widget's reaction may be triggered.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RateLimit {
+    /// Only react once the value has stopped changing for the given number of seconds.
+    Debounce(f64),
+    /// React at most once per the given number of seconds. The latest value is always delivered.
+    Throttle(f64),
+}
+
+/// The pending value and timing of a single widget's rate limited reaction.
+pub struct Limiter {
+    last_change_ns: u64,
+    last_react_ns: u64,
+    maybe_pending: Option<Box<Any>>,
+}
+
+
+impl RateLimit {
+    /// The duration of the limit in nanoseconds.
+    fn duration_ns(&self) -> u64 {
+        let secs = match *self { RateLimit::Debounce(secs) | RateLimit::Throttle(secs) => secs };
+        (secs.max(0.0) * 1_000_000_000.0) as u64
+    }
+}
+
+
+impl Limiter {
+
+    /// Construct a Limiter with nothing pending.
+    pub fn new() -> Limiter {
+        Limiter {
+            last_change_ns: 0,
+            last_react_ns: 0,
+            maybe_pending: None,
+        }
+    }
+
+    /// Submit the widget's latest value (if it has produced one) and return the value with which
+    /// the widget should react at the time `now_ns` (if any).
+    ///
+    /// Resubmitting the value that is already pending is not a change, so it neither restarts a
+    /// debounce nor produces a second reaction.
+    pub fn limit<T: Any + PartialEq>(&mut self,
+                                     limit: RateLimit,
+                                     maybe_value: Option<T>,
+                                     now_ns: u64) -> Option<T> {
+        if let Some(value) = maybe_value {
+            let is_pending = self.maybe_pending.as_ref()
+                .and_then(|pending| pending.downcast_ref::<T>())
+                .map_or(false, |pending| *pending == value);
+            if !is_pending {
+                self.last_change_ns = now_ns;
+                self.maybe_pending = Some(Box::new(value));
+            }
+        }
+        let is_due = match limit {
+            RateLimit::Debounce(_) => now_ns >= self.last_change_ns + limit.duration_ns(),
+            RateLimit::Throttle(_) => now_ns >= self.last_react_ns + limit.duration_ns(),
+        };
+        if !is_due || self.maybe_pending.is_none() {
+            return None;
+        }
+        self.last_react_ns = now_ns;
+        self.maybe_pending.take()
+            .and_then(|pending| pending.downcast::<T>().ok())
+            .map(|value| *value)
+    }
+
+    /// Whether or not the widget has submitted a value that is yet to be delivered.
+    pub fn is_pending(&self) -> bool {
+        self.maybe_pending.is_some()
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Limiter, RateLimit};
+
+    const SEC: u64 = 1_000_000_000;
+
+    #[test]
+    fn debounce_waits_for_the_value_to_settle() {
+        let mut limiter = Limiter::new();
+        let limit = RateLimit::Debounce(0.5);
+        assert_eq!(limiter.limit(limit, Some(1.0), SEC), None);
+        assert_eq!(limiter.limit(limit, Some(2.0), SEC + SEC / 4), None);
+        // The second change restarted the debounce.
+        assert_eq!(limiter.limit::<f64>(limit, None, SEC + SEC / 2), None);
+        assert!(limiter.is_pending());
+        assert_eq!(limiter.limit::<f64>(limit, None, SEC + 3 * SEC / 4), Some(2.0));
+        assert!(!limiter.is_pending());
+        assert_eq!(limiter.limit::<f64>(limit, None, 2 * SEC), None);
+    }
+
+    #[test]
+    fn debounce_ignores_resubmission_of_the_pending_value() {
+        let mut limiter = Limiter::new();
+        let limit = RateLimit::Debounce(0.5);
+        assert_eq!(limiter.limit(limit, Some(1.0), SEC), None);
+        // A widget that is held still keeps producing the same value.
+        assert_eq!(limiter.limit(limit, Some(1.0), SEC + SEC / 4), None);
+        assert_eq!(limiter.limit(limit, Some(1.0), SEC + SEC / 2), Some(1.0));
+    }
+
+    #[test]
+    fn release_after_delivery_is_not_a_new_change() {
+        let mut limiter = Limiter::new();
+        let limit = RateLimit::Debounce(0.5);
+        assert_eq!(limiter.limit(limit, Some(3.0), SEC), None);
+        assert_eq!(limiter.limit::<f64>(limit, None, 2 * SEC), Some(3.0));
+        // Releasing the widget submits nothing, so nothing more is delivered.
+        assert_eq!(limiter.limit::<f64>(limit, None, 3 * SEC), None);
+        assert!(!limiter.is_pending());
+    }
+
+    #[test]
+    fn throttle_delivers_the_latest_value_once_per_interval() {
+        let mut limiter = Limiter::new();
+        let limit = RateLimit::Throttle(1.0);
+        assert_eq!(limiter.limit(limit, Some(1), 5 * SEC), Some(1));
+        assert_eq!(limiter.limit(limit, Some(2), 5 * SEC + SEC / 4), None);
+        assert_eq!(limiter.limit(limit, Some(3), 5 * SEC + SEC / 2), None);
+        assert_eq!(limiter.limit::<i32>(limit, None, 6 * SEC), Some(3));
+        assert_eq!(limiter.limit::<i32>(limit, None, 8 * SEC), None);
+    }
+
+}
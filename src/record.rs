@@ -0,0 +1,166 @@
+//!
+//! A module for recording the input received by a `Ui` so that it may be replayed later.
+//!
+//! Recording is started via `Ui::start_recording` and every mouse, keyboard, text and focus event
+//! subsequently given to `Ui::handle_event` is stored along with the frame in which it arrived and
+//! the time at which it arrived, both relative to the start of the recording. The `Recording`
+//! returned by `Ui::stop_recording` may be saved to file and loaded again within a test, where it
+//! may be replayed deterministically via `Harness::replay`.
+//!
+//! Touch input is not recorded.
+//!
+
+use piston::event::{
+    Event,
+    FocusEvent,
+    GenericEvent,
+    MouseCursorEvent,
+    MouseScrollEvent,
+    PressEvent,
+    ReleaseEvent,
+    TextEvent,
+};
+use piston::input::{self, Button, Motion};
+use position::{Dimensions, Point};
+use rustc_serialize::{json, Decodable};
+use std::borrow::ToOwned;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::str;
+
+
+/// A single recordable input event.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum Input {
+    /// The mouse cursor moved to the given position in window coordinates (with the origin at the
+    /// top left of the window).
+    Move(Point),
+    /// The mouse wheel scrolled by the given distance.
+    Scroll(Point),
+    /// A button was pressed.
+    Press(Button),
+    /// A button was released.
+    Release(Button),
+    /// Text was entered.
+    Text(String),
+    /// The window gained or lost focus.
+    Focus(bool),
+}
+
+/// An `Input` along with when it was received.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Recorded {
+    /// The number of frames between the start of the recording and the input.
+    pub frame: u64,
+    /// The time in nanoseconds between the start of the recording and the input.
+    pub time_ns: u64,
+    /// The input itself.
+    pub input: Input,
+}
+
+/// A serializable stream of the input received by a `Ui`.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Recording {
+    /// The dimensions of the window when recording began.
+    pub win_dim: Dimensions,
+    /// Every input received, in the order in which it was received.
+    pub inputs: Vec<Recorded>,
+}
+
+
+impl Input {
+
+    /// The recordable input described by the given event, if there is one.
+    pub fn from_event<E: GenericEvent>(event: &E) -> Option<Input> {
+        let mut maybe_input = None;
+        event.mouse_cursor(|x, y| maybe_input = Some(Input::Move([x, y])));
+        event.mouse_scroll(|x, y| maybe_input = Some(Input::Scroll([x, y])));
+        event.press(|button| maybe_input = Some(Input::Press(button)));
+        event.release(|button| maybe_input = Some(Input::Release(button)));
+        event.text(|text| maybe_input = Some(Input::Text(text.to_owned())));
+        event.focus(|is_focused| maybe_input = Some(Input::Focus(is_focused)));
+        maybe_input
+    }
+
+    /// Convert the input back into the event from which it was recorded.
+    pub fn to_event(&self) -> Event {
+        let input = match *self {
+            Input::Move(xy) => input::Input::Move(Motion::MouseCursor(xy[0], xy[1])),
+            Input::Scroll(xy) => input::Input::Move(Motion::MouseScroll(xy[0], xy[1])),
+            Input::Press(button) => input::Input::Press(button),
+            Input::Release(button) => input::Input::Release(button),
+            Input::Text(ref text) => input::Input::Text(text.clone()),
+            Input::Focus(is_focused) => input::Input::Focus(is_focused),
+        };
+        Event::Input(input)
+    }
+
+}
+
+
+impl Recording {
+
+    /// Construct an empty Recording of a window with the given dimensions.
+    pub fn new(win_dim: Dimensions) -> Recording {
+        Recording { win_dim: win_dim, inputs: Vec::new() }
+    }
+
+    /// The number of frames spanned by the Recording.
+    pub fn frames(&self) -> u64 {
+        self.inputs.last().map(|recorded| recorded.frame + 1).unwrap_or(0)
+    }
+
+    /// The inputs received during the given frame of the Recording.
+    pub fn frame_inputs(&self, frame: u64) -> &[Recorded] {
+        let start = self.inputs.iter().position(|recorded| recorded.frame >= frame)
+            .unwrap_or(self.inputs.len());
+        let len = self.inputs[start..].iter().take_while(|recorded| recorded.frame == frame)
+            .count();
+        &self.inputs[start..start + len]
+    }
+
+    /// Load a Recording from file.
+    pub fn load(path: &str) -> Result<Recording, String> {
+        let mut file = match File::open(&Path::new(path)) {
+            Ok(file) => file,
+            Err(e) => return Err(format!("Failed to open file for Recording: {}",
+                                         Error::description(&e))),
+        };
+        let mut contents = Vec::new();
+        if let Err(e) = ::std::io::Read::read_to_end(&mut file, &mut contents) {
+            return Err(format!("Failed to load Recording correctly: {}",
+                               Error::description(&e)));
+        }
+        let json_object = match json::Json::from_str(str::from_utf8(&contents[..]).unwrap()) {
+            Ok(json_object) => json_object,
+            Err(e) => return Err(format!("Failed to construct json_object from str: {}",
+                                         Error::description(&e))),
+        };
+        let mut decoder = json::Decoder::new(json_object);
+        match Decodable::decode(&mut decoder) {
+            Ok(recording) => Ok(recording),
+            Err(e) => Err(format!("Failed to construct Recording from json decoder: {}",
+                                  Error::description(&e))),
+        }
+    }
+
+    /// Save the Recording to file.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json_string = match json::encode(self) {
+            Ok(x) => x,
+            Err(e) => return Err(e.description().to_owned())
+        };
+        let mut file = match File::create(&Path::new(path)) {
+            Ok(file) => file,
+            Err(e) => return Err(format!("Failed to create a File at the given path: {}",
+                                         Error::description(&e)))
+        };
+        match ::std::io::Write::write_all(&mut file, json_string.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(format!("Recording failed to save correctly: {}",
+                                  Error::description(&e))),
+        }
+    }
+
+}
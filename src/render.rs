@@ -0,0 +1,546 @@
+//!
+//! A module for rendering the `Ui` via any backend, without depending upon piston's `Graphics`.
+//!
+//! The `Ui` describes everything it draws as a list of device-agnostic `Primitive`s which are
+//! delivered, in drawing order, to some `Backend` via `Ui::draw_primitives`. Filled shapes and
+//! outlines (rects, ellipses, lines, etc) are delivered as plain triangles, while glyphs and images
+//! are delivered as textured triangles (i.e. two per textured quad) that sample from the texture
+//! provided by the `Ui`'s `CharacterCache`.
+//!
+//! All positions are given in the same coordinates as the `Ui`'s widgets (with `[0.0, 0.0]` at
+//! the centre of the window and y increasing upwards).
+//!
+//! The `Raster` backend rasterises primitives in software and is used by `Ui::draw_to_image`.
+//!
+//! The `PixelGrid` adaptor snaps everything drawn to whole pixels and is used by `Ui::draw` while
+//! pixel mode is enabled (see `Ui::set_pixel_scale`).
+//!
+//! The `Clip` adaptor crops everything drawn to a rectangle and is used by `Ui::draw` for widgets
+//! set within a clipping scope (see `Ui::with_clip`).
+//!
+
+use graphics::{DrawState, Graphics, ImageSize};
+use position::{Dimensions, Point};
+
+
+/// A color given as `[r, g, b, a]` components in the range `0.0` to `1.0`.
+pub type Rgba = [f32; 4];
+
+/// A single primitive to be rendered by a `Backend`.
+#[derive(Copy, Clone, Debug)]
+pub enum Primitive<'a, T: 'a> {
+    /// A list of triangles (every three vertices form one triangle) filled with a single color.
+    Triangles {
+        /// The color with which the triangles are filled.
+        color: Rgba,
+        /// The position of each vertex.
+        vertices: &'a [Point],
+    },
+    /// A list of triangles textured with some region of the given texture. Used for glyphs and
+    /// images.
+    TexturedTriangles {
+        /// The color by which the texture is multiplied.
+        color: Rgba,
+        /// The texture from which the triangles are sampled.
+        texture: &'a T,
+        /// The position of each vertex.
+        vertices: &'a [Point],
+        /// The texture coordinate of each vertex, where `[0.0, 0.0]` is the top left of the
+        /// texture and `[1.0, 1.0]` is the bottom right.
+        uvs: &'a [[f64; 2]],
+    },
+}
+
+/// Types that can render the `Primitive`s produced by the `Ui`.
+pub trait Backend {
+    /// The texture type used for glyphs and images, as produced by the `Ui`'s `CharacterCache`.
+    type Texture: ImageSize;
+    /// Clear the entire window with the given color.
+    fn clear(&mut self, color: Rgba);
+    /// Render the given primitive above everything that has been rendered before it.
+    fn primitive(&mut self, primitive: Primitive<Self::Texture>);
+}
+
+
+/// Adapts a `Backend` so that it may be used wherever piston's `Graphics` is expected, converting
+/// the normalised vertices produced by the graphics pipeline into `Primitive`s.
+pub struct Primitives<'a, B: 'a> {
+    backend: &'a mut B,
+    win_dim: Dimensions,
+    vertices: Vec<Point>,
+    uvs: Vec<[f64; 2]>,
+}
+
+
+impl<'a, B> Primitives<'a, B> {
+
+    /// Construct an adaptor for the given backend rendering to a window with the given dimensions.
+    pub fn new(backend: &'a mut B, win_dim: Dimensions) -> Primitives<'a, B> {
+        Primitives {
+            backend: backend,
+            win_dim: win_dim,
+            vertices: Vec::new(),
+            uvs: Vec::new(),
+        }
+    }
+
+    /// Convert a flat slice of normalised `x, y` pairs into window coordinates.
+    fn collect_vertices(&mut self, normalised: &[f32]) {
+        let half_w = self.win_dim[0] / 2.0;
+        let half_h = self.win_dim[1] / 2.0;
+        self.vertices.clear();
+        for pair in normalised.chunks(2) {
+            if pair.len() == 2 {
+                self.vertices.push([pair[0] as f64 * half_w, pair[1] as f64 * half_h]);
+            }
+        }
+    }
+
+}
+
+
+impl<'a, B> Graphics for Primitives<'a, B>
+    where
+        B: Backend,
+{
+    type Texture = B::Texture;
+
+    fn clear_color(&mut self, color: Rgba) {
+        self.backend.clear(color);
+    }
+
+    fn tri_list<F>(&mut self, _draw_state: &DrawState, color: &Rgba, mut f: F)
+        where
+            F: FnMut(&mut FnMut(&[f32])),
+    {
+        f(&mut |normalised: &[f32]| {
+            self.collect_vertices(normalised);
+            let Primitives { ref mut backend, ref vertices, .. } = *self;
+            backend.primitive(Primitive::Triangles { color: *color, vertices: vertices });
+        });
+    }
+
+    fn tri_list_uv<F>(&mut self,
+                      _draw_state: &DrawState,
+                      color: &Rgba,
+                      texture: &B::Texture,
+                      mut f: F)
+        where
+            F: FnMut(&mut FnMut(&[f32], &[f32])),
+    {
+        f(&mut |normalised: &[f32], uvs: &[f32]| {
+            self.collect_vertices(normalised);
+            self.uvs.clear();
+            for pair in uvs.chunks(2) {
+                if pair.len() == 2 {
+                    self.uvs.push([pair[0] as f64, pair[1] as f64]);
+                }
+            }
+            let Primitives { ref mut backend, ref vertices, ref uvs, .. } = *self;
+            backend.primitive(Primitive::TexturedTriangles {
+                color: *color,
+                texture: texture,
+                vertices: vertices,
+                uvs: uvs,
+            });
+        });
+    }
+
+}
+
+
+/// Adapts some `Graphics` so that every vertex drawn via it lands upon a whole pixel of the
+/// window, measured from its top left corner. Used for the `Ui`'s pixel mode, in which case the
+/// window dimensions are given in the `Ui`'s (scaled) pixels.
+pub struct PixelGrid<'a, G: 'a> {
+    graphics: &'a mut G,
+    win_dim: Dimensions,
+    vertices: Vec<f32>,
+}
+
+
+impl<'a, G> PixelGrid<'a, G> {
+
+    /// Construct an adaptor for the given graphics rendering to a window with the given
+    /// dimensions.
+    pub fn new(graphics: &'a mut G, win_dim: Dimensions) -> PixelGrid<'a, G> {
+        PixelGrid {
+            graphics: graphics,
+            win_dim: win_dim,
+            vertices: Vec::new(),
+        }
+    }
+
+}
+
+
+/// Snap a flat slice of normalised `x, y` pairs to the nearest whole pixels of a window with the
+/// given dimensions, writing the results to `snapped`.
+fn snap_vertices(win_dim: Dimensions, normalised: &[f32], snapped: &mut Vec<f32>) {
+    let (w, h) = (win_dim[0], win_dim[1]);
+    snapped.clear();
+    for pair in normalised.chunks(2) {
+        if pair.len() == 2 {
+            let x = ((pair[0] as f64 + 1.0) / 2.0 * w).round();
+            let y = ((1.0 - pair[1] as f64) / 2.0 * h).round();
+            snapped.push((x / w * 2.0 - 1.0) as f32);
+            snapped.push((1.0 - y / h * 2.0) as f32);
+        }
+    }
+}
+
+
+impl<'a, G> Graphics for PixelGrid<'a, G>
+    where
+        G: Graphics,
+{
+    type Texture = G::Texture;
+
+    fn clear_color(&mut self, color: Rgba) {
+        self.graphics.clear_color(color);
+    }
+
+    fn tri_list<F>(&mut self, draw_state: &DrawState, color: &Rgba, mut f: F)
+        where
+            F: FnMut(&mut FnMut(&[f32])),
+    {
+        let PixelGrid { ref mut graphics, win_dim, ref mut vertices } = *self;
+        graphics.tri_list(draw_state, color, |sink: &mut FnMut(&[f32])| {
+            f(&mut |normalised: &[f32]| {
+                snap_vertices(win_dim, normalised, vertices);
+                sink(vertices);
+            });
+        });
+    }
+
+    fn tri_list_uv<F>(&mut self,
+                      draw_state: &DrawState,
+                      color: &Rgba,
+                      texture: &G::Texture,
+                      mut f: F)
+        where
+            F: FnMut(&mut FnMut(&[f32], &[f32])),
+    {
+        let PixelGrid { ref mut graphics, win_dim, ref mut vertices } = *self;
+        graphics.tri_list_uv(draw_state, color, texture, |sink: &mut FnMut(&[f32], &[f32])| {
+            f(&mut |normalised: &[f32], uvs: &[f32]| {
+                snap_vertices(win_dim, normalised, vertices);
+                sink(vertices, uvs);
+            });
+        });
+    }
+
+}
+
+
+/// Adapts some `Graphics` so that everything drawn via it is cropped to a rectangle of the window.
+/// Each triangle is clipped against the rectangle's edges, interpolating the texture coordinates
+/// of textured triangles, so that cropping works with any `Graphics` or `Backend`.
+pub struct Clip<'a, G: 'a> {
+    graphics: &'a mut G,
+    /// The left, bottom, right and top edges of the rectangle in normalised coordinates.
+    bounds: [f32; 4],
+    vertices: Vec<f32>,
+    uvs: Vec<f32>,
+}
+
+
+impl<'a, G> Clip<'a, G> {
+
+    /// Construct an adaptor for the given graphics rendering to a window with the given
+    /// dimensions, cropping to the rectangle at `xy` with the given dimensions.
+    pub fn new(graphics: &'a mut G, win_dim: Dimensions, xy: Point, dim: Dimensions)
+        -> Clip<'a, G>
+    {
+        let (half_w, half_h) = (win_dim[0] / 2.0, win_dim[1] / 2.0);
+        let (half_clip_w, half_clip_h) = (dim[0] / 2.0, dim[1] / 2.0);
+        Clip {
+            graphics: graphics,
+            bounds: [((xy[0] - half_clip_w) / half_w) as f32,
+                     ((xy[1] - half_clip_h) / half_h) as f32,
+                     ((xy[0] + half_clip_w) / half_w) as f32,
+                     ((xy[1] + half_clip_h) / half_h) as f32],
+            vertices: Vec::new(),
+            uvs: Vec::new(),
+        }
+    }
+
+}
+
+
+/// Clip a flat slice of normalised `x, y` triangle vertices (along with their `u, v` texture
+/// coordinates, if any are given) to the given bounds, writing the resulting triangles to
+/// `vertices` and `uvs`.
+fn clip_triangles(bounds: [f32; 4],
+                  normalised: &[f32],
+                  uvs_in: &[f32],
+                  vertices: &mut Vec<f32>,
+                  uvs: &mut Vec<f32>) {
+    vertices.clear();
+    uvs.clear();
+    let has_uvs = uvs_in.len() == normalised.len();
+    let mut polygon: Vec<[f32; 4]> = Vec::with_capacity(9);
+    let mut clipped: Vec<[f32; 4]> = Vec::with_capacity(9);
+    for (i, tri) in normalised.chunks(6).enumerate() {
+        if tri.len() < 6 { break }
+        polygon.clear();
+        for j in 0..3 {
+            let (u, v) = match has_uvs {
+                true => (uvs_in[i * 6 + j * 2], uvs_in[i * 6 + j * 2 + 1]),
+                false => (0.0, 0.0),
+            };
+            polygon.push([tri[j * 2], tri[j * 2 + 1], u, v]);
+        }
+
+        // Clip the triangle against the left, bottom, right and top edges in turn.
+        for edge in 0..4 {
+            let (axis, bound) = (edge % 2, bounds[edge]);
+            let is_inside = |p: [f32; 4]| {
+                if edge < 2 { p[axis] >= bound } else { p[axis] <= bound }
+            };
+            let intersect = |a: [f32; 4], b: [f32; 4]| {
+                let t = (bound - a[axis]) / (b[axis] - a[axis]);
+                [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t,
+                 a[2] + (b[2] - a[2]) * t, a[3] + (b[3] - a[3]) * t]
+            };
+            clipped.clear();
+            for k in 0..polygon.len() {
+                let (a, b) = (polygon[k], polygon[(k + 1) % polygon.len()]);
+                match (is_inside(a), is_inside(b)) {
+                    (true, true) => clipped.push(b),
+                    (true, false) => clipped.push(intersect(a, b)),
+                    (false, true) => {
+                        clipped.push(intersect(a, b));
+                        clipped.push(b);
+                    },
+                    (false, false) => (),
+                }
+            }
+            ::std::mem::swap(&mut polygon, &mut clipped);
+            if polygon.is_empty() { break }
+        }
+
+        // Triangulate the remaining convex polygon as a fan.
+        for k in 1..polygon.len().saturating_sub(1) {
+            for p in [polygon[0], polygon[k], polygon[k + 1]].iter() {
+                vertices.push(p[0]);
+                vertices.push(p[1]);
+                if has_uvs {
+                    uvs.push(p[2]);
+                    uvs.push(p[3]);
+                }
+            }
+        }
+    }
+}
+
+
+impl<'a, G> Graphics for Clip<'a, G>
+    where
+        G: Graphics,
+{
+    type Texture = G::Texture;
+
+    fn clear_color(&mut self, color: Rgba) {
+        self.graphics.clear_color(color);
+    }
+
+    fn tri_list<F>(&mut self, draw_state: &DrawState, color: &Rgba, mut f: F)
+        where
+            F: FnMut(&mut FnMut(&[f32])),
+    {
+        let Clip { ref mut graphics, bounds, ref mut vertices, ref mut uvs } = *self;
+        graphics.tri_list(draw_state, color, |sink: &mut FnMut(&[f32])| {
+            f(&mut |normalised: &[f32]| {
+                clip_triangles(bounds, normalised, &[], vertices, uvs);
+                if !vertices.is_empty() {
+                    sink(vertices);
+                }
+            });
+        });
+    }
+
+    fn tri_list_uv<F>(&mut self,
+                      draw_state: &DrawState,
+                      color: &Rgba,
+                      texture: &G::Texture,
+                      mut f: F)
+        where
+            F: FnMut(&mut FnMut(&[f32], &[f32])),
+    {
+        let Clip { ref mut graphics, bounds, ref mut vertices, ref mut uvs } = *self;
+        graphics.tri_list_uv(draw_state, color, texture, |sink: &mut FnMut(&[f32], &[f32])| {
+            f(&mut |normalised: &[f32], uvs_in: &[f32]| {
+                clip_triangles(bounds, normalised, uvs_in, vertices, uvs);
+                if !vertices.is_empty() {
+                    sink(vertices, uvs);
+                }
+            });
+        });
+    }
+
+}
+
+
+/// Textures whose pixels may be read by the software rasteriser used by `Ui::draw_to_image`.
+pub trait Sample {
+    /// The color of the texture at the given texture coordinate, where `[0.0, 0.0]` is the top
+    /// left of the texture and `[1.0, 1.0]` is the bottom right.
+    fn sample(&self, uv: [f64; 2]) -> Rgba;
+}
+
+/// A `Backend` that rasterises primitives in software into an RGBA buffer.
+pub struct Raster<T> {
+    width: u32,
+    height: u32,
+    /// The color of each pixel, row by row from the top left.
+    pixels: Vec<Rgba>,
+    texture: ::std::marker::PhantomData<T>,
+}
+
+
+/// Twice the signed area of the triangle `a`, `b`, `p`.
+fn edge(a: Point, b: Point, p: Point) -> f64 {
+    (a[0] - p[0]) * (b[1] - p[1]) - (b[0] - p[0]) * (a[1] - p[1])
+}
+
+
+impl<T> Raster<T> {
+
+    /// Construct a transparent Raster with the given dimensions in pixels.
+    pub fn new(width: u32, height: u32) -> Raster<T> {
+        Raster {
+            width: width,
+            height: height,
+            pixels: vec![[0.0; 4]; (width * height) as usize],
+            texture: ::std::marker::PhantomData,
+        }
+    }
+
+    /// Consume the Raster, producing 8-bit RGBA components for each pixel row by row from the top
+    /// left.
+    pub fn into_rgba(self) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(self.pixels.len() * 4);
+        for pixel in self.pixels.iter() {
+            for &component in pixel.iter() {
+                rgba.push((component.max(0.0).min(1.0) * 255.0).round() as u8);
+            }
+        }
+        rgba
+    }
+
+    /// Fill the triangles described by `vertices`, where the color of each pixel is given by
+    /// `color_at` with the barycentric weights of the pixel's centre.
+    fn fill<F>(&mut self, vertices: &[Point], mut color_at: F)
+        where
+            F: FnMut([f64; 3]) -> Rgba,
+    {
+        let (half_w, half_h) = (self.width as f64 / 2.0, self.height as f64 / 2.0);
+        for tri in vertices.chunks(3) {
+            if tri.len() < 3 { continue }
+            // Convert to pixel coordinates with the origin at the top left.
+            let to_px = |p: Point| [p[0] + half_w, half_h - p[1]];
+            let (a, b, c) = (to_px(tri[0]), to_px(tri[1]), to_px(tri[2]));
+            let area = edge(a, b, c);
+            if area == 0.0 { continue }
+            let min_x = a[0].min(b[0]).min(c[0]).floor().max(0.0) as u32;
+            let min_y = a[1].min(b[1]).min(c[1]).floor().max(0.0) as u32;
+            let max_x = (a[0].max(b[0]).max(c[0]).ceil() as u32).min(self.width);
+            let max_y = (a[1].max(b[1]).max(c[1]).ceil() as u32).min(self.height);
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    let p = [x as f64 + 0.5, y as f64 + 0.5];
+                    let w_a = edge(b, c, p) / area;
+                    let w_b = edge(c, a, p) / area;
+                    let w_c = 1.0 - w_a - w_b;
+                    if w_a < 0.0 || w_b < 0.0 || w_c < 0.0 { continue }
+                    let src = color_at([w_a, w_b, w_c]);
+                    let dst = &mut self.pixels[(y * self.width + x) as usize];
+                    // Blend the source over the destination.
+                    let alpha = src[3];
+                    let out_alpha = alpha + dst[3] * (1.0 - alpha);
+                    if out_alpha > 0.0 {
+                        for i in 0..3 {
+                            dst[i] = (src[i] * alpha + dst[i] * dst[3] * (1.0 - alpha)) / out_alpha;
+                        }
+                    }
+                    dst[3] = out_alpha;
+                }
+            }
+        }
+    }
+
+}
+
+impl<T> Backend for Raster<T>
+    where
+        T: ImageSize + Sample,
+{
+    type Texture = T;
+
+    fn clear(&mut self, color: Rgba) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = color;
+        }
+    }
+
+    fn primitive(&mut self, primitive: Primitive<T>) {
+        match primitive {
+            Primitive::Triangles { color, vertices } => {
+                self.fill(vertices, |_| color);
+            },
+            Primitive::TexturedTriangles { color, texture, vertices, uvs } => {
+                let mut i = 0;
+                for tri_uvs in uvs.chunks(3) {
+                    if tri_uvs.len() < 3 { break }
+                    let tri = &vertices[i..i + 3];
+                    self.fill(tri, |w| {
+                        let u = tri_uvs[0][0] * w[0] + tri_uvs[1][0] * w[1] + tri_uvs[2][0] * w[2];
+                        let v = tri_uvs[0][1] * w[0] + tri_uvs[1][1] * w[1] + tri_uvs[2][1] * w[2];
+                        let texel = texture.sample([u, v]);
+                        [color[0] * texel[0], color[1] * texel[1],
+                         color[2] * texel[2], color[3] * texel[3]]
+                    });
+                    i += 3;
+                }
+            },
+        }
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::snap_vertices;
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((a - b).abs() < 1.0e-6, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn vertices_snap_to_the_nearest_whole_pixel() {
+        let mut snapped = Vec::new();
+        // Within a 100x100 window each pixel spans 0.02 of the normalised range.
+        snap_vertices([100.0, 100.0], &[0.0, 0.0, 0.013, -0.013, -0.009, 0.009], &mut snapped);
+        assert_close(&snapped, &[0.0, 0.0, 0.02, -0.02, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn window_edges_are_unchanged() {
+        let mut snapped = Vec::new();
+        snap_vertices([101.0, 37.0], &[-1.0, 1.0, 1.0, -1.0], &mut snapped);
+        assert_close(&snapped, &[-1.0, 1.0, 1.0, -1.0]);
+    }
+
+    #[test]
+    fn previous_vertices_and_unpaired_values_are_discarded() {
+        let mut snapped = vec![0.5; 4];
+        snap_vertices([100.0, 100.0], &[0.0, 0.0, 0.5], &mut snapped);
+        assert_close(&snapped, &[0.0, 0.0]);
+    }
+
+}
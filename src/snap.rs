@@ -0,0 +1,121 @@
+//!
+//! Snapping of dragged rectangles to a grid and to the edges and centres of their siblings, along
+//! with the guides that show the user which alignments have been found.
+//!
+//! This is used by the `Draggable` widget, though it may be used by any widget that moves
+//! rectangles around (i.e. the nodes of a graph upon an EditorCanvas).
+//!
+
+use color::Color;
+use elmesque::form::{line, solid, Form};
+use position::{Dimensions, Point};
+
+
+/// The default distance (in pixels) within which an edge or centre snaps to that of a sibling.
+pub const DEFAULT_DISTANCE: f64 = 6.0;
+
+/// Two positions closer than this are considered aligned.
+const EPSILON: f64 = 1.0e-6;
+
+/// A line showing that the edge or centre of a dragged rectangle is aligned with a sibling's.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Guide {
+    /// A vertical line at `x`, spanning both rectangles from `bottom` to `top`.
+    Vertical { x: f64, bottom: f64, top: f64 },
+    /// A horizontal line at `y`, spanning both rectangles from `left` to `right`.
+    Horizontal { y: f64, left: f64, right: f64 },
+}
+
+
+/// The low edge, centre and high edge of a rectangle along a single axis.
+fn anchors(centre: f64, length: f64) -> [f64; 3] {
+    [centre - length / 2.0, centre, centre + length / 2.0]
+}
+
+/// Snap the centre of a rectangle along a single axis.
+///
+/// The rectangle is shifted by the smallest amount (within `distance`) that aligns one of its
+/// anchors with one of the siblings' anchors. Failing that, the given edge of the rectangle
+/// (`-1.0` for the low edge, `1.0` for the high edge) is snapped to the grid, if there is one.
+fn snap_axis(centre: f64,
+             length: f64,
+             siblings: &[(f64, f64)],
+             distance: f64,
+             maybe_grid: Option<f64>,
+             grid_edge: f64) -> f64 {
+    let own = anchors(centre, length);
+    let mut maybe_shift = None;
+    let mut nearest = distance;
+    for &(s_centre, s_length) in siblings.iter() {
+        for &target in anchors(s_centre, s_length).iter() {
+            for &anchor in own.iter() {
+                let shift = target - anchor;
+                if shift.abs() <= nearest {
+                    maybe_shift = Some(shift);
+                    nearest = shift.abs();
+                }
+            }
+        }
+    }
+    match (maybe_shift, maybe_grid) {
+        (Some(shift), _) => centre + shift,
+        (None, Some(spacing)) if spacing > 0.0 => {
+            let edge = centre + grid_edge * length / 2.0;
+            (edge / spacing).round() * spacing - grid_edge * length / 2.0
+        },
+        _ => centre,
+    }
+}
+
+/// Snap a rectangle with the given centre and dimensions.
+///
+/// Along each axis, the rectangle's edges and centre snap to the edges and centres of the given
+/// siblings (each described by its centre and dimensions) that lie within `distance`. Where no
+/// sibling is close enough, the rectangle's left and top edges snap to the grid with the given
+/// spacing, if there is one.
+///
+/// Returns the snapped centre along with the guides for every alignment with a sibling.
+pub fn snap_rect(xy: Point,
+                 dim: Dimensions,
+                 siblings: &[(Point, Dimensions)],
+                 distance: f64,
+                 maybe_grid: Option<f64>) -> (Point, Vec<Guide>) {
+    let xs: Vec<(f64, f64)> = siblings.iter().map(|&(s_xy, s_dim)| (s_xy[0], s_dim[0])).collect();
+    let ys: Vec<(f64, f64)> = siblings.iter().map(|&(s_xy, s_dim)| (s_xy[1], s_dim[1])).collect();
+    let x = snap_axis(xy[0], dim[0], &xs, distance, maybe_grid, -1.0);
+    let y = snap_axis(xy[1], dim[1], &ys, distance, maybe_grid, 1.0);
+    let snapped = [x, y];
+    (snapped, guides(snapped, dim, siblings))
+}
+
+/// The guides for every edge or centre of the given rectangle that is aligned with an edge or
+/// centre of one of the given siblings.
+pub fn guides(xy: Point, dim: Dimensions, siblings: &[(Point, Dimensions)]) -> Vec<Guide> {
+    let (own_x, own_y) = (anchors(xy[0], dim[0]), anchors(xy[1], dim[1]));
+    let mut guides = Vec::new();
+    for &(s_xy, s_dim) in siblings.iter() {
+        let (s_x, s_y) = (anchors(s_xy[0], s_dim[0]), anchors(s_xy[1], s_dim[1]));
+        for &x in own_x.iter() {
+            if s_x.iter().any(|&s| (s - x).abs() < EPSILON) {
+                let (bottom, top) = (own_y[0].min(s_y[0]), own_y[2].max(s_y[2]));
+                guides.push(Guide::Vertical { x: x, bottom: bottom, top: top });
+            }
+        }
+        for &y in own_y.iter() {
+            if s_y.iter().any(|&s| (s - y).abs() < EPSILON) {
+                let (left, right) = (own_x[0].min(s_x[0]), own_x[2].max(s_x[2]));
+                guides.push(Guide::Horizontal { y: y, left: left, right: right });
+            }
+        }
+    }
+    guides
+}
+
+/// Construct the Forms for the given guides, drawn as lines with the given color and width.
+pub fn guide_forms(guides: &[Guide], color: Color, width: f64) -> Vec<Form> {
+    let line_style = solid(color).width(width);
+    guides.iter().map(|guide| match *guide {
+        Guide::Vertical { x, bottom, top } => line(line_style.clone(), x, bottom, x, top),
+        Guide::Horizontal { y, left, right } => line(line_style.clone(), left, y, right, y),
+    }).collect()
+}
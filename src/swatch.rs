@@ -0,0 +1,41 @@
+//!
+//! Drawing of color swatches, i.e. a preview of a chosen color, with a checkerboard beneath any
+//! color with transparency so that its transparency is visible.
+//!
+
+use color::Color;
+use elmesque::form::{rect, Form};
+use position::Dimensions;
+use theme::Checker;
+
+
+/// The Forms for a swatch of the given color and dimensions, centred upon the origin.
+///
+/// Where the color is not fully opaque, the color is drawn over a checkerboard in the given style
+/// (usually `ui.theme.checker`). The squares of the checkerboard begin from the top left corner and
+/// those along the right and bottom edges are cut short to fit within the swatch.
+pub fn swatch(dim: Dimensions, color: Color, checker: &Checker) -> Vec<Form> {
+    let (w, h) = (dim[0].max(0.0), dim[1].max(0.0));
+    let color_form = rect(w, h).filled(color);
+    let alpha = color.to_rgb().3;
+    if alpha >= 1.0 || checker.size <= 0.0 {
+        return vec![color_form];
+    }
+
+    let mut forms = vec![rect(w, h).filled(checker.light)];
+    let cols = (w / checker.size).ceil() as usize;
+    let rows = (h / checker.size).ceil() as usize;
+    for row in 0..rows {
+        for col in (0..cols).filter(|col| (col + row) % 2 == 1) {
+            let left = col as f64 * checker.size;
+            let top = row as f64 * checker.size;
+            let square_w = checker.size.min(w - left);
+            let square_h = checker.size.min(h - top);
+            let x = -w / 2.0 + left + square_w / 2.0;
+            let y = h / 2.0 - top - square_h / 2.0;
+            forms.push(rect(square_w, square_h).filled(checker.dark).shift(x, y));
+        }
+    }
+    forms.push(color_form);
+    forms
+}
@@ -0,0 +1,145 @@
+//!
+//! Helpers for hit-testing a single line of text and rendering its caret and selection.
+//!
+//! These are the same functions used internally by the `TextBox` and are intended for use within
+//! custom widgets that allow for text editing. All indices are character indices (rather than byte
+//! indices) and all x positions are given relative to the same origin as `start_x`.
+//!
+
+use elmesque::form::{line, rect, solid, Form};
+use graphics::character::CharacterCache;
+use label::FontSize;
+use color::Color;
+use ui::Ui;
+
+
+/// A character index into some text.
+pub type Idx = usize;
+
+/// The duration (in seconds) for which a blinking caret is alternately shown and hidden.
+pub const CARET_BLINK_SECS: f64 = 0.5;
+
+
+/// Find the x position of the caret that sits before the character at `idx`, where `start_x` is
+/// the x position of the left edge of the text.
+pub fn caret_x<C: CharacterCache>(ui: &mut Ui<C>,
+                                  idx: Idx,
+                                  start_x: f64,
+                                  font_size: FontSize,
+                                  text: &str) -> f64 {
+    text.chars().take(idx).fold(start_x, |x, ch| x + ui.get_character_w(font_size, ch))
+}
+
+/// Find the caret index that is closest to the given `x` position, along with the caret's x
+/// position.
+pub fn closest_idx<C: CharacterCache>(ui: &mut Ui<C>,
+                                      x: f64,
+                                      start_x: f64,
+                                      font_size: FontSize,
+                                      text: &str) -> (Idx, f64) {
+    if x <= start_x { return (0, start_x) }
+    let mut left_x = start_x;
+    let mut char_x = start_x;
+    let mut num_chars = 0;
+    for (i, ch) in text.chars().enumerate() {
+        let char_w = ui.get_character_w(font_size, ch);
+        let right_x = char_x + char_w / 2.0;
+        if x > left_x && x <= right_x { return (i, char_x) }
+        char_x += char_w;
+        left_x = right_x;
+        num_chars = i + 1;
+    }
+    (num_chars, char_x)
+}
+
+/// Find the left and right x positions of the selection spanning the characters from `start` up
+/// to (but not including) `end`.
+pub fn selection_x<C: CharacterCache>(ui: &mut Ui<C>,
+                                      start: Idx,
+                                      end: Idx,
+                                      start_x: f64,
+                                      font_size: FontSize,
+                                      text: &str) -> (f64, f64) {
+    let (start, end) = if start <= end { (start, end) } else { (end, start) };
+    let left = caret_x(ui, start, start_x, font_size, text);
+    let right = text.chars().skip(start).take(end - start)
+        .fold(left, |x, ch| x + ui.get_character_w(font_size, ch));
+    (left, right)
+}
+
+/// Whether or not a blinking caret that last moved at `moved_ns` is shown at `now_ns`. The caret
+/// is always shown as soon as it moves so that it may be followed while typing. Times should be
+/// taken from `Ui::time_ns` so that the caret blinks on the `Ui`'s clock.
+pub fn is_caret_shown(moved_ns: u64, now_ns: u64) -> bool {
+    let blink_ns = (CARET_BLINK_SECS * 1.0e9) as u64;
+    (now_ns.saturating_sub(moved_ns) / blink_ns) % 2 == 0
+}
+
+/// Construct the Form for a caret of the given height at the given x position. The caret is
+/// vertically centred upon `0.0`.
+pub fn caret_form(x: f64, height: f64, color: Color) -> Form {
+    let half_h = height / 2.0;
+    line(solid(color), 0.0, half_h, 0.0, -half_h).shift_x(x)
+}
+
+/// Construct the Form for a selection highlight spanning from `left` to `right` with the given
+/// height. The highlight is vertically centred upon `0.0`.
+pub fn selection_form(left: f64, right: f64, height: f64, color: Color) -> Form {
+    rect(right - left, height).filled(color).shift_x(left + (right - left) / 2.0)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use label::FontSize;
+    use super::{caret_x, closest_idx, is_caret_shown, selection_x};
+    use ui::Ui;
+
+    const SEC: u64 = 1_000_000_000;
+
+    // Each glyph of the test harness' cache is half as wide as the font size, so each of these
+    // characters is 5.0 wide.
+    const FONT_SIZE: FontSize = 10;
+
+    #[test]
+    fn caret_x_sums_the_widths_of_the_preceding_characters() {
+        let mut harness = Ui::test_harness([100.0, 100.0]);
+        let ui = &mut harness.ui;
+        assert_eq!(caret_x(ui, 0, 10.0, FONT_SIZE, "abc"), 10.0);
+        assert_eq!(caret_x(ui, 2, 10.0, FONT_SIZE, "abc"), 20.0);
+        // Indices beyond the end of the text stop at the end.
+        assert_eq!(caret_x(ui, 9, 10.0, FONT_SIZE, "abc"), 25.0);
+    }
+
+    #[test]
+    fn closest_idx_splits_each_character_at_its_middle() {
+        let mut harness = Ui::test_harness([100.0, 100.0]);
+        let ui = &mut harness.ui;
+        assert_eq!(closest_idx(ui, -5.0, 0.0, FONT_SIZE, "abc"), (0, 0.0));
+        assert_eq!(closest_idx(ui, 2.5, 0.0, FONT_SIZE, "abc"), (0, 0.0));
+        assert_eq!(closest_idx(ui, 3.0, 0.0, FONT_SIZE, "abc"), (1, 5.0));
+        assert_eq!(closest_idx(ui, 12.0, 0.0, FONT_SIZE, "abc"), (2, 10.0));
+        assert_eq!(closest_idx(ui, 100.0, 0.0, FONT_SIZE, "abc"), (3, 15.0));
+    }
+
+    #[test]
+    fn selection_x_is_ordered_from_left_to_right() {
+        let mut harness = Ui::test_harness([100.0, 100.0]);
+        let ui = &mut harness.ui;
+        assert_eq!(selection_x(ui, 1, 3, 0.0, FONT_SIZE, "abcd"), (5.0, 15.0));
+        assert_eq!(selection_x(ui, 3, 1, 0.0, FONT_SIZE, "abcd"), (5.0, 15.0));
+        assert_eq!(selection_x(ui, 2, 2, 0.0, FONT_SIZE, "abcd"), (10.0, 10.0));
+    }
+
+    #[test]
+    fn caret_is_shown_as_soon_as_it_moves_and_then_blinks() {
+        assert!(is_caret_shown(SEC, SEC));
+        assert!(is_caret_shown(SEC, SEC + SEC / 4));
+        assert!(!is_caret_shown(SEC, SEC + SEC / 2));
+        assert!(!is_caret_shown(SEC, SEC + 3 * SEC / 4));
+        assert!(is_caret_shown(SEC, 2 * SEC));
+        // A clock that hasn't caught up with the move still shows the caret.
+        assert!(is_caret_shown(SEC, 0));
+    }
+
+}
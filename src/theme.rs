@@ -1,18 +1,27 @@
 
+use animation::Ease;
 use canvas;
-use color::{Color, black, white};
+use enable::Disabled;
+use font::{self, FontId};
+use gradient::Gradient;
+use color::{Color, black, rgb, white};
+use corner::CornerRadii;
+use frame::FrameStyle;
+use palette::Palette;
 use position::{Margin, Padding, Position, HorizontalAlign, VerticalAlign};
 use rustc_serialize::{json, Encodable, Decodable};
 use std::borrow::ToOwned;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs::File;
 use std::path::Path;
 use std::str;
+use unit::Conversion;
 use widget;
 
 
 /// A serializable collection of widget styling defaults.
-#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+#[derive(Debug, Clone, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Theme {
     /// A name for the theme used for identification.
     pub name: String,
@@ -32,26 +41,94 @@ pub struct Theme {
     pub frame_color: Color,
     /// A default width for widget frames.
     pub frame_width: f64,
+    /// A default style with which widget frames are shaded.
+    pub frame_style: FrameStyle,
+    /// A default radius for the corners of the rectangles of widgets that may be rounded.
+    pub corner_radii: CornerRadii,
+    /// A default gradient with which the rectangles of widgets that support gradients are filled
+    /// in place of their flat colors.
+    pub maybe_gradient: Option<Gradient>,
     /// A default color for widget labels.
     pub label_color: Color,
+    /// The colors of the named roles that widget styles may reference (see `Paletted`).
+    pub palette: Palette,
     /// A default "large" font size.
     pub font_size_large: u32,
     /// A default "medium" font size.
     pub font_size_medium: u32,
     /// A default "small" font size.
     pub font_size_small: u32,
+    /// The default font for widget text, as returned by `Ui::add_font`.
+    pub font: FontId,
+    /// The duration in seconds over which widgets animate changes in their appearance. A duration
+    /// of `0.0` disables animation.
+    pub animation_duration: f64,
+    /// The easing curve used for widget animations.
+    pub animation_ease: Ease,
+    /// The treatment with which the colors of disabled widgets are drawn.
+    pub disabled: Disabled,
+    /// Whether or not read-only widgets are drawn with a lock icon.
+    pub read_only_lock: bool,
+    /// The checkerboard drawn beneath colors with transparency, i.e. within color swatches.
+    pub checker: Checker,
+    /// The conversion with which value widgets of each named quantity (i.e. `"temperature"`)
+    /// display and edit their values, as given to the widgets via `.quantity`. Values of
+    /// quantities without a conversion are displayed in their canonical unit.
+    pub display_units: HashMap<String, Conversion>,
     /// Optional style defaults for a Canvas split.
     pub maybe_canvas_split: Option<canvas::split::Style>,
+    /// Optional style defaults for an ABCompare.
+    pub maybe_ab_compare: Option<widget::ab_compare::Style>,
     /// Optional style defaults for a Button widget.
     pub maybe_button: Option<widget::button::Style>,
+    /// Optional style defaults for a CollapsibleArea.
+    pub maybe_collapsible_area: Option<widget::collapsible_area::Style>,
+    /// Optional style defaults for a ContextMenu.
+    pub maybe_context_menu: Option<widget::context_menu::Style>,
+    /// Optional style defaults for a DatePicker.
+    pub maybe_date_picker: Option<widget::date_picker::Style>,
+    /// Optional style defaults for a Draggable.
+    pub maybe_draggable: Option<widget::draggable::Style>,
+    /// Optional style defaults for a DrawArea.
+    pub maybe_draw_area: Option<widget::draw_area::Style>,
     /// Optional style defaults for a DropDownList.
     pub maybe_drop_down_list: Option<widget::drop_down_list::Style>,
+    /// Optional style defaults for an EditorCanvas.
+    pub maybe_editor_canvas: Option<widget::editor_canvas::Style>,
     /// Optional style defaults for an EnvelopeEditor.
     pub maybe_envelope_editor: Option<widget::envelope_editor::Style>,
+    /// Optional style defaults for an EventMonitor.
+    pub maybe_event_monitor: Option<widget::event_monitor::Style>,
+    /// Optional style defaults for a FileDialog.
+    pub maybe_file_dialog: Option<widget::file_dialog::Style>,
+    /// Optional style defaults for a Floating window.
+    pub maybe_floating: Option<widget::floating::Style>,
+    /// Optional style defaults for a Marquee.
+    pub maybe_marquee: Option<widget::marquee::Style>,
+    /// Optional style defaults for a MenuBar.
+    pub maybe_menu_bar: Option<widget::menu_bar::Style>,
     /// Optional style defaults for a NumberDialer.
     pub maybe_number_dialer: Option<widget::number_dialer::Style>,
+    /// Optional style defaults for a NumberStepper.
+    pub maybe_number_stepper: Option<widget::number_stepper::Style>,
+    /// Optional style defaults for a PerfHud.
+    pub maybe_perf_hud: Option<widget::perf_hud::Style>,
+    /// Optional style defaults for a Plot.
+    pub maybe_plot: Option<widget::plot::Style>,
+    /// Optional style defaults for a Popover.
+    pub maybe_popover: Option<widget::popover::Style>,
+    /// Optional style defaults for a RadioGroup.
+    pub maybe_radio_group: Option<widget::radio_group::Style>,
+    /// Optional style defaults for a Ruler.
+    pub maybe_ruler: Option<widget::ruler::Style>,
+    /// Optional style defaults for a Scope.
+    pub maybe_scope: Option<widget::scope::Style>,
     /// Optional style defaults for a Slider.
     pub maybe_slider: Option<widget::slider::Style>,
+    /// Optional style defaults for a SplitPane.
+    pub maybe_split_pane: Option<widget::split_pane::Style>,
+    /// Optional style defaults for a Switch.
+    pub maybe_switch: Option<widget::switch::Style>,
     /// Optional style defaults for a TextBox.
     pub maybe_text_box: Option<widget::text_box::Style>,
     /// Optional style defaults for a Toggle.
@@ -61,7 +138,7 @@ pub struct Theme {
 }
 
 /// The alignment of an element's dimensions with another's.
-#[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
+#[derive(Debug, Clone, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Align {
     /// Positioning relative to an elements width and position on the x axis.
     pub horizontal: HorizontalAlign,
@@ -69,6 +146,18 @@ pub struct Align {
     pub vertical: VerticalAlign,
 }
 
+/// The style of the checkerboard drawn beneath colors with transparency so that their
+/// transparency is visible.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Checker {
+    /// The width of each square of the checkerboard.
+    pub size: f64,
+    /// The color of the lighter squares.
+    pub light: Color,
+    /// The color of the darker squares.
+    pub dark: Color,
+}
+
 impl Theme {
 
     /// The default theme if not loading from file.
@@ -96,16 +185,55 @@ impl Theme {
             shape_color: white(),
             frame_color: black(),
             frame_width: 1.0,
+            frame_style: FrameStyle::Flat,
+            corner_radii: CornerRadii::none(),
+            maybe_gradient: None,
             label_color: black(),
+            palette: Palette::new(),
             font_size_large: 26,
             font_size_medium: 18,
             font_size_small: 12,
+            font: font::DEFAULT_FONT,
+            animation_duration: 0.12,
+            animation_ease: Ease::QuadOut,
+            disabled: Disabled {
+                saturation: 0.0,
+                fade: 0.5,
+            },
+            read_only_lock: true,
+            checker: Checker {
+                size: 6.0,
+                light: rgb(0.8, 0.8, 0.8),
+                dark: rgb(0.55, 0.55, 0.55),
+            },
+            display_units: HashMap::new(),
             maybe_canvas_split: None,
+            maybe_ab_compare: None,
             maybe_button: None,
+            maybe_collapsible_area: None,
+            maybe_context_menu: None,
+            maybe_date_picker: None,
+            maybe_draggable: None,
+            maybe_draw_area: None,
             maybe_drop_down_list: None,
+            maybe_editor_canvas: None,
             maybe_envelope_editor: None,
+            maybe_event_monitor: None,
+            maybe_file_dialog: None,
+            maybe_floating: None,
+            maybe_marquee: None,
+            maybe_menu_bar: None,
             maybe_number_dialer: None,
+            maybe_number_stepper: None,
+            maybe_perf_hud: None,
+            maybe_plot: None,
+            maybe_popover: None,
+            maybe_radio_group: None,
+            maybe_ruler: None,
+            maybe_scope: None,
             maybe_slider: None,
+            maybe_split_pane: None,
+            maybe_switch: None,
             maybe_text_box: None,
             maybe_toggle: None,
             maybe_xy_pad: None,
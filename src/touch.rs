@@ -0,0 +1,151 @@
+//!
+//! A module for describing touch input and the gestures recognised from it.
+//!
+//! Touch events are passed to the `Ui` via `Ui::handle_touch`. The first finger to touch down
+//! becomes the "primary" touch and drives the `Ui`'s `Mouse` so that widgets continue to work
+//! as usual, while two-finger movements are recognised as pinch and scroll gestures.
+//!
+
+use position::Point;
+
+
+/// A unique identifier for a single finger for the duration of its contact with the surface.
+pub type Id = u64;
+
+/// The stage of a finger's contact with the surface.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Phase {
+    /// The finger has just touched down.
+    Start,
+    /// The finger has moved while touching the surface.
+    Move,
+    /// The finger has been lifted from the surface.
+    End,
+    /// The touch has been interrupted by the system.
+    Cancel,
+}
+
+/// A single touch event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Touch {
+    /// The finger that produced the event.
+    pub id: Id,
+    /// The stage of the finger's contact with the surface.
+    pub phase: Phase,
+    /// The position of the finger.
+    pub xy: Point,
+}
+
+/// A gesture recognised from the movement of two fingers.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gesture {
+    /// The fingers have moved toward (`scale < 1.0`) or away from (`scale > 1.0`) one another.
+    Pinch {
+        /// The point midway between the two fingers.
+        centre: Point,
+        /// The ratio between the new and the previous distance between the fingers.
+        scale: f64,
+    },
+    /// The fingers have moved together in some direction.
+    Scroll {
+        /// The point midway between the two fingers.
+        centre: Point,
+        /// The distance travelled by the point midway between the fingers.
+        delta: Point,
+    },
+}
+
+/// Tracks the fingers currently touching the surface.
+pub struct Touches {
+    active: Vec<(Id, Point)>,
+    maybe_primary: Option<Id>,
+}
+
+
+impl Gesture {
+
+    /// The point midway between the two fingers performing the gesture.
+    pub fn centre(&self) -> Point {
+        match *self {
+            Gesture::Pinch { centre, .. } | Gesture::Scroll { centre, .. } => centre,
+        }
+    }
+
+}
+
+
+impl Touches {
+
+    /// Construct a tracker with no active touches.
+    pub fn new() -> Touches {
+        Touches { active: Vec::with_capacity(10), maybe_primary: None }
+    }
+
+    /// The id of the primary touch, if there is one.
+    pub fn primary(&self) -> Option<Id> {
+        self.maybe_primary
+    }
+
+    /// The id and position of each finger currently touching the surface.
+    pub fn active(&self) -> &[(Id, Point)] {
+        &self.active
+    }
+
+    /// Update the tracked touches with the given event, returning any gestures that the event
+    /// completes.
+    pub fn update(&mut self, touch: Touch) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+        match touch.phase {
+            Phase::Start => {
+                self.active.retain(|&(id, _)| id != touch.id);
+                self.active.push((touch.id, touch.xy));
+                if self.maybe_primary.is_none() {
+                    self.maybe_primary = Some(touch.id);
+                }
+            },
+            Phase::Move => {
+                let before = self.pair();
+                for entry in self.active.iter_mut() {
+                    if entry.0 == touch.id { entry.1 = touch.xy; }
+                }
+                if let (Some((a, b)), Some((new_a, new_b))) = (before, self.pair()) {
+                    let centre = midpoint(a, b);
+                    let new_centre = midpoint(new_a, new_b);
+                    let distance = ::vecmath::vec2_len(::vecmath::vec2_sub(b, a));
+                    let new_distance = ::vecmath::vec2_len(::vecmath::vec2_sub(new_b, new_a));
+                    if distance > 0.0 && new_distance != distance {
+                        let scale = new_distance / distance;
+                        gestures.push(Gesture::Pinch { centre: new_centre, scale: scale });
+                    }
+                    let delta = ::vecmath::vec2_sub(new_centre, centre);
+                    if delta != [0.0, 0.0] {
+                        gestures.push(Gesture::Scroll { centre: new_centre, delta: delta });
+                    }
+                }
+            },
+            Phase::End | Phase::Cancel => {
+                self.active.retain(|&(id, _)| id != touch.id);
+                if self.maybe_primary == Some(touch.id) {
+                    self.maybe_primary = None;
+                }
+            },
+        }
+        gestures
+    }
+
+    /// The positions of the two fingers touching the surface, if exactly two are.
+    fn pair(&self) -> Option<(Point, Point)> {
+        if self.active.len() == 2 {
+            Some((self.active[0].1, self.active[1].1))
+        } else {
+            None
+        }
+    }
+
+}
+
+
+/// The point midway between `a` and `b`.
+fn midpoint(a: Point, b: Point) -> Point {
+    [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0]
+}
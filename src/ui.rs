@@ -1,24 +1,50 @@
 
+use accessibility::{self, Accessibility};
+use animation::{Ease, Tween};
+use binding::Bindings;
 use canvas::{Canvas, CanvasId};
+use clipboard::{Clipboard, MemoryClipboard};
+use display_list::DisplayList;
 use canvas::Kind as CanvasKind;
 use elmesque::Element;
+use elmesque::form::Form;
+use font::{self, FontId, Fonts};
 use graphics::Graphics;
 use graphics::character::{Character, CharacterCache};
-use label::FontSize;
-use mouse::{ButtonState, Mouse};
+use history::History;
+use label::{self, FontSize, TextSize};
+use mouse::{self, ButtonState, Mouse};
+use panel::{self, PanelId, Panels};
+use persist::WidgetStates;
 use piston::input;
 use piston::event::{
+    FocusEvent,
     GenericEvent,
     MouseCursorEvent,
+    MouseScrollEvent,
     PressEvent,
     ReleaseEvent,
     RenderEvent,
+    ResizeEvent,
     TextEvent,
+    UpdateEvent,
 };
-use position::{Depth, Dimensions, HorizontalAlign, Padding, Point, Position, VerticalAlign};
-use std::any::Any;
+use position::{Depth, Dimensions, HorizontalAlign, Layer, Margin, Padding, Point, Position,
+               VerticalAlign};
+use rate_limit::RateLimit;
+use event_log::{self, Entry, EventLog};
+use id::Namespaces;
+use profile::Profiler;
+use proxy::{Mailbox, UiProxy};
+use record::{self, Recorded, Recording};
+use render;
+use rustc_serialize::{json, Decodable, Encodable};
+use std::any::{Any, TypeId};
+use std::cmp::Ordering;
 use theme::Theme;
+use touch::{self, Gesture, Touch, Touches};
 use widget::{self, Widget};
+use widget_store::{WidgetKey, WidgetStore};
 use ::std::io::Write;
 
 /// User interface identifier. Each widget must use a unique `UiId` so that it's state can be
@@ -26,6 +52,23 @@ use ::std::io::Write;
 /// a `Vec`, which is limited to a size of `usize` elements.
 pub type UiId = usize;
 
+/// The number of `UiId`s reserved for hand-assigned ids. Ids generated via `IdGenerator` are
+/// allocated beyond these, so hand-assigned ids should remain below this.
+pub const WIDGET_RESERVATION: usize = 512;
+
+/// The difference in depth between consecutive raised widgets (see `Ui::bring_to_front`), leaving
+/// room for the children of each raised widget to be drawn between it and the widget raised above
+/// it.
+pub const RAISED_DEPTH_STEP: Depth = 100.0;
+
+/// The distance beyond an auto-hiding canvas within which pointer activity reveals it, so that a
+/// hidden toolbar at the edge of the window may be revealed by moving to (or resting upon) that
+/// edge.
+pub const REVEAL_MARGIN: f64 = 8.0;
+
+/// The duration in seconds over which an idle auto-hiding canvas fades out.
+const AUTO_HIDE_FADE_SECS: f64 = 0.5;
+
 /// Indicates whether or not the Mouse has been captured by a widget.
 #[derive(Copy, Clone, Debug)]
 enum Capturing {
@@ -35,6 +78,32 @@ enum Capturing {
     JustReleased,
 }
 
+/// A payload that is being dragged from one widget so that it may be dropped upon another.
+struct Drag {
+    /// The UiId of the widget from which the drag began.
+    source: UiId,
+    /// The dragged data.
+    payload: Box<Any>,
+    /// The `TypeId` of the dragged data, by which drop targets check whether they accept it.
+    payload_type: TypeId,
+    /// An optional Form to be drawn beneath the mouse cursor for the duration of the drag.
+    maybe_ghost: Option<Form>,
+    /// Whether or not the mouse has been released, in which case the payload may be dropped.
+    released: bool,
+}
+
+/// The most recent movement of a group of selected widgets, to be followed by each of the
+/// selected widgets other than the one being dragged.
+#[derive(Copy, Clone, Debug)]
+struct GroupMove {
+    /// Uniquely identifies the movement, so that each widget follows it exactly once.
+    id: u64,
+    /// The UiId of the widget being dragged.
+    leader: UiId,
+    /// The distance by which the group has moved.
+    delta: Point,
+}
+
 /// `Ui` is the most important type within Conrod and is necessary for rendering and maintaining
 /// widget state.
 /// # Ui Handles the following:
@@ -46,8 +115,9 @@ enum Capturing {
 pub struct Ui<C> {
     /// Stores the state of all canvasses.
     canvas_cache: Vec<Canvas>,
-    /// The Widget cache, storing state for all widgets.
-    widget_cache: Vec<widget::Cached>,
+    /// The Widget cache, storing state for all widgets along with their animations, rate
+    /// limiters, layers and removal hooks.
+    widget_cache: WidgetStore,
     /// The theme used to set default styling for widgets.
     pub theme: Theme,
     /// The latest received mouse state.
@@ -56,23 +126,134 @@ pub struct Ui<C> {
     pub keys_just_pressed: Vec<input::keyboard::Key>,
     /// Keys that have been released since the end of the last render cycle.
     pub keys_just_released: Vec<input::keyboard::Key>,
+    /// Keys that are currently held down.
+    pub keys_down: Vec<input::keyboard::Key>,
+    /// Mouse buttons that have been released since the end of the last render cycle.
+    pub mouse_buttons_just_released: Vec<input::mouse::MouseButton>,
+    /// Gestures that have been performed via touch input since the end of the last render cycle.
+    pub gestures: Vec<Gesture>,
     /// Text that has been entered since the end of the last render cycle.
     pub text_just_entered: Vec<String>,
-    /// Cache for character textures, used for label width calculation and glyph rendering.
+    /// Text that is currently being composed via an input method editor (IME) but that has not
+    /// yet been committed.
+    pub maybe_composition: Option<String>,
+    /// Cache for character textures, used for label width calculation and glyph rendering. This
+    /// is the default font, identified by `font::DEFAULT_FONT`. Replace it via
+    /// `set_character_cache` so that the glyph widths measured with the old cache are forgotten.
     pub character_cache: C,
+    /// The fonts added via `add_font` along with the fallback chain of each font.
+    fonts: Fonts<C>,
+    /// The font with which text is currently measured, being that of the widget currently being
+    /// set (or the default font between widgets).
+    current_font: FontId,
+    /// The Margin and Padding of the widget currently being set, as given to
+    /// `get_xy_with_spacing`.
+    current_spacing: (Margin, Padding),
     prev_event_was_render: bool,
+    /// The number of render cycles that have begun since the `Ui` was constructed. This can be
+    /// used to guard against reacting more than once within the same frame.
+    pub frame_number: u64,
+    /// The time (in nanoseconds) at which the most recent event was received by `handle_event`.
+    /// Widgets should measure time via `time_ns` instead, so that they follow the `Ui`'s clock
+    /// once it is driven by `update`.
+    pub event_time_ns: u64,
+    /// The time (in nanoseconds) accumulated via `update`, once the application has begun driving
+    /// the `Ui`'s clock.
+    maybe_clock_ns: Option<u64>,
+    /// The duration (in seconds) passed to the most recent call to `update`.
+    delta_time: f64,
     /// Window width.
     pub win_w: f64,
     /// Window height.
     pub win_h: f64,
+    /// The whole number of window pixels spanned by each of the `Ui`'s pixels while pixel mode
+    /// is enabled. `win_w` and `win_h` are given in the `Ui`'s pixels.
+    maybe_pixel_scale: Option<u32>,
+    /// The number of physical window pixels spanned by each of the `Ui`'s units, i.e. `2.0` on
+    /// high-DPI displays.
+    scale_factor: f64,
     /// The UiId of the previously drawn Widget.
     maybe_prev_ui_id: Option<UiId>,
+    /// The number of widgets set so far, used to order widgets by when they were set.
+    widgets_set: u64,
     /// The Id of the current canvas.
     maybe_current_canvas_id: Option<CanvasId>,
     /// The captured Mouse and the UiId of the widget who has captured it.
     maybe_captured_mouse: Option<(Capturing, Mouse)>,
     /// The UiId of the widget currently keyboard input if there is one.
-    maybe_captured_keyboard: Option<Capturing>
+    maybe_captured_keyboard: Option<Capturing>,
+    /// The UiId of the widget with keyboard focus if there is one.
+    maybe_focused: Option<UiId>,
+    /// The UiId of the widget to which the mouse wheel's scroll is delivered, being the topmost
+    /// widget beneath the cursor (or the widget capturing the mouse) when it was last scrolled.
+    maybe_scrolled: Option<UiId>,
+    /// The widget over which the mouse hovers, along with the time (in nanoseconds) at which the
+    /// hover began.
+    maybe_hovered: Option<(UiId, u64)>,
+    /// The changes in the hovered widget since the end of the last render cycle.
+    hover_events: Vec<mouse::HoverEvent>,
+    /// The mouse cursors requested by widgets since the end of the last render cycle.
+    cursor_requests: Vec<(UiId, mouse::Cursor)>,
+    /// The keyboard panels between whose widgets the arrow keys move the focus.
+    panels: Panels,
+    /// The learning state and pending values of the external control bindings.
+    bindings: Bindings,
+    /// The states recorded by persistent widgets, to be saved when the application exits.
+    persisted: WidgetStates,
+    /// The restored states not yet adopted by their persistent widgets.
+    restored: WidgetStates,
+    /// The undo and redo history of the changes made to widgets.
+    history: History,
+    /// The widgets brought to the front of their layers (i.e. Floating windows), in the order in
+    /// which they were last brought to the front.
+    raised: Vec<UiId>,
+    /// The layer of the widgets set within the current layer scope (see `with_layer`).
+    current_layer: Layer,
+    /// The topmost widget beneath the left mouse button when it was last pressed, which receives
+    /// the mouse for as long as the button is held.
+    maybe_pressed: Option<UiId>,
+    /// The widgets to be drawn this frame, retained so that its buffers are reused.
+    display_list: DisplayList,
+    /// The open transient widgets and their rectangles, in the order in which they were opened.
+    transients: Vec<(UiId, Point, Dimensions)>,
+    /// The transient widgets dismissed since the end of the last render cycle.
+    dismissed: Vec<UiId>,
+    /// The payload currently being dragged between widgets, if there is one.
+    maybe_drag: Option<Drag>,
+    /// The currently selected widgets, in the order in which they were selected.
+    selection: Vec<UiId>,
+    /// The most recent movement of the selected widgets, if they have been moved.
+    maybe_group_move: Option<GroupMove>,
+    /// The clipboard used by widgets for copying and pasting text.
+    clipboard: Box<Clipboard>,
+    /// The ids generated for the widgets within each list via `IdGenerator`.
+    namespaces: Namespaces,
+    /// The fingers currently touching the surface.
+    touches: Touches,
+    /// The frame number and time at which recording began, along with the input recorded since.
+    maybe_recording: Option<(u64, u64, Recording)>,
+    /// The time (relative to the start of its recording) at which the input currently being
+    /// replayed was originally received.
+    maybe_replay_time_ns: Option<u64>,
+    /// The most recent input and interactions, if logging is enabled.
+    maybe_event_log: Option<EventLog>,
+    /// The per-frame timing and widget counts, if profiling is enabled.
+    maybe_profiler: Option<Profiler>,
+    /// The id of the style scope currently applied to the theme via `with_style`, or `0`.
+    theme_scope: u64,
+    /// The most recently used scoped themes along with their ids, so that widgets within a scope
+    /// only need redrawing when the scope's styling actually changes.
+    scoped_themes: Vec<(u64, Theme)>,
+    /// The id to be given to the next distinct scoped theme.
+    next_theme_scope: u64,
+    /// The rectangle to which widgets set within the current clipping scope (see `with_clip`) are
+    /// cropped.
+    maybe_clip: Option<(Point, Dimensions)>,
+    /// The messages sent from other threads via `UiProxy`s.
+    mailbox: Mailbox,
+    /// The number of consecutive draws for which a widget may go unset before it is removed, if
+    /// unset widgets are removed at all (see `enable_widget_removal`).
+    maybe_removal_grace: Option<u32>,
 }
 
 impl<C> Ui<C> {
@@ -80,30 +261,133 @@ impl<C> Ui<C> {
     /// Constructor for a UiContext.
     pub fn new(character_cache: C, theme: Theme) -> Ui<C> {
         const CANVAS_RESERVATION: usize = 64;
-        const WIDGET_RESERVATION: usize = 512;
         Ui {
             canvas_cache: (0..CANVAS_RESERVATION).map(|_| Canvas::empty()).collect(),
-            widget_cache: (0..WIDGET_RESERVATION).map(|_| widget::Cached::empty()).collect(),
+            widget_cache: WidgetStore::new(WIDGET_RESERVATION),
             theme: theme,
             mouse: Mouse::new([0.0, 0.0], ButtonState::Up, ButtonState::Up, ButtonState::Up),
             keys_just_pressed: Vec::with_capacity(10),
             keys_just_released: Vec::with_capacity(10),
+            keys_down: Vec::with_capacity(10),
+            mouse_buttons_just_released: Vec::with_capacity(4),
+            gestures: Vec::with_capacity(4),
             text_just_entered: Vec::with_capacity(10),
+            maybe_composition: None,
             character_cache: character_cache,
+            fonts: Fonts::new(),
+            current_font: font::DEFAULT_FONT,
+            current_spacing: (Margin::none(), Padding::none()),
             prev_event_was_render: false,
+            frame_number: 0,
+            event_time_ns: ::clock_ticks::precise_time_ns(),
+            maybe_clock_ns: None,
+            delta_time: 0.0,
             win_w: 0.0,
             win_h: 0.0,
+            maybe_pixel_scale: None,
+            scale_factor: 1.0,
             maybe_prev_ui_id: None,
+            widgets_set: 0,
             maybe_current_canvas_id: None,
             maybe_captured_mouse: None,
             maybe_captured_keyboard: None,
+            maybe_focused: None,
+            maybe_scrolled: None,
+            maybe_hovered: None,
+            hover_events: Vec::new(),
+            cursor_requests: Vec::new(),
+            panels: Panels::new(),
+            bindings: Bindings::new(),
+            persisted: WidgetStates::new(),
+            restored: WidgetStates::new(),
+            history: History::new(),
+            raised: Vec::new(),
+            current_layer: Layer::Normal,
+            maybe_pressed: None,
+            display_list: DisplayList::new(),
+            transients: Vec::new(),
+            dismissed: Vec::new(),
+            maybe_drag: None,
+            selection: Vec::new(),
+            maybe_group_move: None,
+            clipboard: Box::new(MemoryClipboard::new()),
+            namespaces: Namespaces::new(WIDGET_RESERVATION),
+            touches: Touches::new(),
+            maybe_recording: None,
+            maybe_replay_time_ns: None,
+            maybe_event_log: None,
+            maybe_profiler: None,
+            theme_scope: 0,
+            scoped_themes: Vec::new(),
+            next_theme_scope: 1,
+            maybe_clip: None,
+            mailbox: Mailbox::new(),
+            maybe_removal_grace: None,
         }
     }
 
+    /// Return the position of the centre of a widget.
+    pub fn widget_xy(&self, ui_id: UiId) -> Point {
+        self.widget_cache[ui_id].xy
+    }
+
     /// Return the dimensions of a Canvas.
     pub fn widget_size(&self, ui_id: UiId) -> Dimensions {
-        let (w, h) = self.widget_cache[ui_id].element.get_size();
-        [w as f64, h as f64]
+        self.widget_cache[ui_id].dim
+    }
+
+    /// Return the centre and dimensions of the widget at the given UiId as of its most recent
+    /// update, or `None` if no widget has been set there. This may be used to position overlays
+    /// relative to a widget or to scroll a widget into view.
+    pub fn rect_of(&self, ui_id: UiId) -> Option<(Point, Dimensions)> {
+        self.widget_cache.get(ui_id)
+            .and_then(|widget| if !widget.is_empty() { Some((widget.xy, widget.dim)) }
+                               else { None })
+    }
+
+    /// Return the centre and dimensions of the area of the `Ui` that is visible within the
+    /// window, i.e. for determining whether or not the rectangle of some widget is on screen.
+    ///
+    /// Within a clipping scope (see `with_clip`), only the area within the clipping rectangle is
+    /// visible.
+    pub fn visible_area(&self) -> (Point, Dimensions) {
+        let window = ([0.0, 0.0], [self.win_w, self.win_h]);
+        match self.maybe_clip {
+            Some(clip) => intersect_rects(window, clip),
+            None => window,
+        }
+    }
+
+    /// Return the UiIds of all widgets whose rectangles overlap the rectangle described by `xy`
+    /// and `dim`, as of each widget's most recent update.
+    pub fn widgets_overlapping(&self, xy: Point, dim: Dimensions) -> Vec<UiId> {
+        use utils::rects_overlap;
+        self.widget_cache.widgets().iter().enumerate()
+            .filter(|&(_, widget)| !widget.is_empty())
+            .filter(|&(_, widget)| rects_overlap(widget.xy, widget.dim, xy, dim))
+            .map(|(ui_id, _)| ui_id)
+            .collect()
+    }
+
+    /// Return the UiId of the topmost widget whose rectangle contains the given point as of each
+    /// widget's most recent update, i.e. the widget that is drawn above all others at that point.
+    /// Widgets within the `Tooltip` layer are passed over.
+    pub fn topmost_widget_at(&self, xy: Point) -> Option<UiId> {
+        use utils::is_over_rect;
+        self.widget_cache.widgets().iter().enumerate()
+            .filter(|&(_, widget)| !widget.is_empty() && widget.layer != Layer::Tooltip)
+            .filter(|&(_, widget)| is_over_rect(widget.xy, xy, widget.dim))
+            .filter(|&(_, widget)| match widget.maybe_clip {
+                Some((clip_xy, clip_dim)) => is_over_rect(clip_xy, xy, clip_dim),
+                None => true,
+            })
+            .fold(None, |maybe_top: Option<(UiId, &widget::Cached)>, (ui_id, widget)| {
+                match maybe_top {
+                    Some((_, top)) if is_drawn_above(top, widget) => maybe_top,
+                    _ => Some((ui_id, widget)),
+                }
+            })
+            .map(|(ui_id, _)| ui_id)
     }
 
     /// Return the dimensions of a Canvas.
@@ -112,10 +396,535 @@ impl<C> Ui<C> {
         [w as f64, h as f64]
     }
 
+    /// Called by auto-hiding canvasses when set, returning the opacity with which the canvas with
+    /// the given rectangle should be drawn. The canvas remains opaque while the pointer moves or
+    /// a button is held within `REVEAL_MARGIN` of it, or while the pointer rests at the edge of
+    /// the window there. Once `hide_after_secs` pass without such activity, it fades out.
+    pub fn auto_hide_canvas(&mut self,
+                            id: CanvasId,
+                            xy: Point,
+                            dim: Dimensions,
+                            hide_after_secs: f64) -> f32 {
+        use utils::is_over_rect;
+        let now_ns = self.time_ns();
+        let region_dim = [dim[0] + REVEAL_MARGIN * 2.0, dim[1] + REVEAL_MARGIN * 2.0];
+        let mouse = self.mouse;
+        let is_near = is_over_rect(xy, mouse.xy, region_dim);
+        let is_at_window_edge = mouse.xy[0].abs() >= self.win_w / 2.0 - REVEAL_MARGIN
+            || mouse.xy[1].abs() >= self.win_h / 2.0 - REVEAL_MARGIN;
+        let is_down = match (mouse.left.state, mouse.middle.state, mouse.right.state) {
+            (ButtonState::Up, ButtonState::Up, ButtonState::Up) => false,
+            _ => true,
+        };
+        let (mut last_active_ns, _) = self.canvas_cache[id].maybe_activity.unwrap_or((now_ns, 1.0));
+        if is_near && (is_at_window_edge || is_down) {
+            last_active_ns = now_ns;
+        } else if is_near && mouse.time_ns > last_active_ns {
+            last_active_ns = mouse.time_ns;
+        }
+        let idle_secs = now_ns.saturating_sub(last_active_ns) as f64 / 1.0e9;
+        let fade = (idle_secs - hide_after_secs) / AUTO_HIDE_FADE_SECS;
+        let opacity = 1.0 - fade.max(0.0).min(1.0) as f32;
+        self.canvas_cache[id].maybe_activity = Some((last_active_ns, opacity));
+        opacity
+    }
+
+    /// The opacity of the auto-hiding canvas with the given CanvasId as of the last time it was
+    /// set, i.e. so that the widgets upon a hidden toolbar may be faded or left unset along with
+    /// it. Canvasses that do not auto-hide are always `1.0`.
+    pub fn canvas_opacity(&self, id: CanvasId) -> f32 {
+        self.canvas_cache.get(id)
+            .and_then(|canvas| canvas.maybe_activity)
+            .map_or(1.0, |(_, opacity)| opacity)
+    }
+
     /// Handle game events and update the state.
     pub fn handle_event<E: GenericEvent>(&mut self, event: &E) {
+        self.begin_event();
+
+        if let Some((start_frame, start_ns, ref mut recording)) = self.maybe_recording {
+            if let Some(input) = record::Input::from_event(event) {
+                let time_ns = if self.event_time_ns > start_ns { self.event_time_ns - start_ns }
+                              else { 0 };
+                recording.inputs.push(Recorded {
+                    frame: self.frame_number - start_frame,
+                    time_ns: time_ns,
+                    input: input,
+                });
+            }
+        }
+
+        if self.maybe_event_log.is_some() {
+            if let Some(input) = record::Input::from_event(event) {
+                self.log_event(event_log::Event::Input(input));
+            }
+        }
+
+        event.update(|args| self.update(args.dt));
+
+        event.render(|args| {
+            let scale = self.window_scale();
+            self.win_w = args.width as f64 / scale;
+            self.win_h = args.height as f64 / scale;
+            self.prev_event_was_render = true;
+        });
+
+        // Adopt the new window dimensions immediately rather than waiting for the next render.
+        event.resize(|width, height| {
+            let scale = self.window_scale();
+            self.win_w = width as f64 / scale;
+            self.win_h = height as f64 / scale;
+        });
+
+        // If the window loses focus mid-drag the release may never arrive, so release all buttons.
+        event.focus(|is_focused| {
+            if !is_focused {
+                self.mouse.left.release();
+                self.mouse.middle.release();
+                self.mouse.right.release();
+                self.mouse.unknown.release();
+            }
+        });
+
+        event.mouse_cursor(|x, y| {
+            // Convert mouse coords to (0, 0) origin.
+            self.mouse.xy = self.window_to_ui([x, y]);
+            self.mouse.time_ns = self.time_ns();
+            self.mouse.frame_number = self.frame_number;
+            self.update_hover();
+        });
+
+        event.mouse_scroll(|x, y| {
+            self.mouse.scroll = [self.mouse.scroll[0] + x, self.mouse.scroll[1] + y];
+            self.maybe_scrolled = match self.maybe_captured_mouse {
+                Some((Capturing::Captured(ui_id), _)) => Some(ui_id),
+                _ => self.topmost_widget_at(self.mouse.xy),
+            };
+            self.mouse.time_ns = self.time_ns();
+            self.mouse.frame_number = self.frame_number;
+        });
+
+        event.press(|button_type| {
+            use piston::input::Button;
+            use piston::input::MouseButton::{Left, Middle, Right};
+
+            match button_type {
+                Button::Mouse(button) => {
+                    let (time_ns, xy) = (self.time_ns(), self.mouse.xy);
+                    if let Left = button {
+                        self.left_pressed_at(xy);
+                    }
+                    match button {
+                        Left => &mut self.mouse.left,
+                        Right => &mut self.mouse.right,
+                        Middle => &mut self.mouse.middle,
+                        _ => &mut self.mouse.unknown,
+                    }.press(time_ns, xy);
+                    self.mouse.time_ns = self.time_ns();
+                    self.mouse.frame_number = self.frame_number;
+                },
+                Button::Keyboard(key) => {
+                    // Escape dismisses the most recently opened transient widget, in which case
+                    // the key is consumed rather than reaching the widgets.
+                    let mut is_consumed = false;
+                    if let input::keyboard::Key::Escape = key {
+                        if let Some((ui_id, _, _)) = self.transients.pop() {
+                            self.dismissed.push(ui_id);
+                            self.log_event(event_log::Event::Dismissed(ui_id));
+                            is_consumed = true;
+                        }
+                    }
+                    // Arrow keys move the focus within a keyboard panel rather than reaching
+                    // the widgets.
+                    if !is_consumed && !self.move_panel_focus(key) {
+                        self.keys_just_pressed.push(key);
+                    }
+                    if !self.keys_down.contains(&key) {
+                        self.keys_down.push(key);
+                    }
+                },
+            }
+        });
+
+        event.release(|button_type| {
+            use piston::input::Button;
+            use piston::input::MouseButton::{Left, Middle, Right};
+            match button_type {
+                Button::Mouse(button) => {
+                    if let (Left, Some(ref mut drag)) = (button, self.maybe_drag.as_mut()) {
+                        drag.released = true;
+                    }
+                    self.mouse_buttons_just_released.push(button);
+                    match button {
+                        Left => &mut self.mouse.left,
+                        Right => &mut self.mouse.right,
+                        Middle => &mut self.mouse.middle,
+                        _ => &mut self.mouse.unknown,
+                    }.release();
+                    self.mouse.time_ns = self.time_ns();
+                    self.mouse.frame_number = self.frame_number;
+                },
+                Button::Keyboard(key) => {
+                    self.keys_just_released.push(key);
+                    self.keys_down.retain(|&k| k != key);
+                },
+            }
+        });
+
+        event.text(|text| {
+            self.text_just_entered.push(text.to_string())
+        });
+    }
+
+    /// Convert a position in window coordinates (with the origin at the top left) to the `Ui`'s
+    /// coordinates (with the origin at the centre and y increasing upwards).
+    fn window_to_ui(&self, xy: Point) -> Point {
+        let scale = self.window_scale();
+        [xy[0] / scale - self.win_w / 2.0, -(xy[1] / scale - self.win_h / 2.0)]
+    }
+
+    /// Enable or disable pixel mode, in which all widget geometry, text and frames are constrained
+    /// to whole pixels and the `Ui` is scaled by the given whole number factor (i.e. `Some(3)`
+    /// draws each of the `Ui`'s pixels as a 3x3 block of window pixels). This keeps the edges of
+    /// pixel-art UIs crisp rather than shimmering as widgets move.
+    ///
+    /// While enabled, `win_w` and `win_h` are given in the `Ui`'s pixels, widgets are positioned
+    /// so that their edges lie on whole pixels and every vertex drawn is snapped to the nearest
+    /// whole pixel. Glyphs and images should be sampled without interpolation (i.e. by creating
+    /// their textures with nearest-neighbour filtering) for the result to remain crisp.
+    pub fn set_pixel_scale(&mut self, maybe_scale: Option<u32>) {
+        let old_scale = self.window_scale();
+        self.maybe_pixel_scale = maybe_scale.map(|scale| ::std::cmp::max(scale, 1));
+        self.rescale_window(old_scale);
+    }
+
+    /// The factor by which the `Ui` is scaled if pixel mode is enabled.
+    pub fn pixel_scale(&self) -> Option<u32> {
+        self.maybe_pixel_scale
+    }
+
+    /// Set the number of physical window pixels spanned by each of the `Ui`'s units, i.e. the
+    /// DPI scale factor reported by the windowing backend (`2.0` on most high-DPI displays).
+    ///
+    /// Widget dimensions, positions, frame widths and font sizes are all given in the `Ui`'s
+    /// units, so the whole `Ui` is scaled consistently without callers multiplying any sizes.
+    /// `win_w` and `win_h` are given in the `Ui`'s units, and mouse coordinates received in
+    /// window pixels are converted to them. Character caches may rasterise glyphs at the
+    /// physical size (see `window_scale`) to keep text crisp.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        let old_scale = self.window_scale();
+        self.scale_factor = if scale_factor > 0.0 { scale_factor } else { 1.0 };
+        self.rescale_window(old_scale);
+    }
+
+    /// The number of physical window pixels spanned by each of the `Ui`'s units.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// The total number of window pixels spanned by each of the `Ui`'s units, combining the scale
+    /// factor with the pixel scale if pixel mode is enabled.
+    pub fn window_scale(&self) -> f64 {
+        self.scale_factor * self.maybe_pixel_scale.unwrap_or(1) as f64
+    }
+
+    /// Convert the window dimensions from the given previous window scale to the current one.
+    fn rescale_window(&mut self, old_scale: f64) {
+        let new_scale = self.window_scale();
+        self.win_w = self.win_w * old_scale / new_scale;
+        self.win_h = self.win_h * old_scale / new_scale;
+    }
+
+    /// Begin recording all input subsequently given to `handle_event`, discarding any recording
+    /// already in progress.
+    pub fn start_recording(&mut self) {
+        let recording = Recording::new([self.win_w, self.win_h]);
+        let start_ns = ::clock_ticks::precise_time_ns();
+        self.maybe_recording = Some((self.frame_number, start_ns, recording));
+    }
+
+    /// Stop recording, returning the input recorded since `start_recording` was called.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.maybe_recording.take().map(|(_, _, recording)| recording)
+    }
+
+    /// Whether or not input is currently being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.maybe_recording.is_some()
+    }
+
+    /// Begin logging input and interactions, keeping the given number of the most recent entries.
+    /// Any log already in progress is kept if it has the same capacity.
+    pub fn enable_event_log(&mut self, capacity: usize) {
+        let has_capacity = self.maybe_event_log.as_ref()
+            .map(|log| log.capacity() == capacity)
+            .unwrap_or(false);
+        if !has_capacity {
+            self.maybe_event_log = Some(EventLog::new(capacity));
+        }
+    }
+
+    /// Stop logging input and interactions, discarding the log.
+    pub fn disable_event_log(&mut self) {
+        self.maybe_event_log = None;
+    }
+
+    /// The most recent input and interactions, if logging is enabled.
+    pub fn event_log(&self) -> Option<&EventLog> {
+        self.maybe_event_log.as_ref()
+    }
+
+    /// Add the given event to the log (if logging is enabled), stamped with the current frame
+    /// and time. Custom widgets may use this to log their own interactions.
+    pub fn log_event(&mut self, event: event_log::Event) {
+        let (frame, time_ns) = (self.frame_number, self.event_time_ns);
+        if let Some(ref mut log) = self.maybe_event_log {
+            log.push(Entry { frame: frame, time_ns: time_ns, event: event });
+        }
+    }
+
+    /// The `UiId` of the widget at the given index within the list with the given `UiId`. The same
+    /// list and index always yield the same id, which never collides with that of any other
+    /// list or hand-assigned id. `IdGenerator` provides a more convenient interface to this.
+    pub fn namespaced_id(&mut self, namespace: UiId, index: usize) -> UiId {
+        let Ui { ref mut namespaces, ref widget_cache, .. } = *self;
+        namespaces.id(namespace, index, |ui_id| {
+            widget_cache.get(ui_id).map(|widget| widget.is_empty()).unwrap_or(true)
+        })
+    }
+
+    /// The `UiId` of the widget for the item with the given key within the list with the given
+    /// `UiId`. The same list and key always yield the same id regardless of the item's index.
+    /// `IdGenerator::key` provides a more convenient interface to this.
+    pub fn keyed_id<K>(&mut self, namespace: UiId, key: K) -> UiId
+        where K: Any + Eq + ::std::hash::Hash,
+    {
+        let Ui { ref mut namespaces, ref widget_cache, .. } = *self;
+        namespaces.keyed_id(namespace, key, |ui_id| {
+            widget_cache.get(ui_id).map(|widget| widget.is_empty()).unwrap_or(true)
+        })
+    }
+
+    /// Set the widgets within `scope` using a temporarily modified theme, so that a block of
+    /// widgets may share some styling (i.e. an "error" section tinted red) without repeating
+    /// builder methods on each widget.
+    ///
+    /// `style` modifies a copy of the current theme, which is used until `scope` returns. Only the
+    /// theme's defaults are affected, so any styling given to a widget directly still takes
+    /// precedence. Scopes may be nested, each modifying the theme of the scope around it.
+    ///
+    /// ```ignore
+    /// ui.with_style(|theme| theme.shape_color = rgb(0.8, 0.2, 0.2), |ui| {
+    ///     Button::new().label("Retry").set(RETRY, ui);
+    ///     Button::new().label("Cancel").set(CANCEL, ui);
+    /// });
+    /// ```
+    pub fn with_style<S, F>(&mut self, style: S, scope: F)
+        where
+            S: FnOnce(&mut Theme),
+            F: FnOnce(&mut Ui<C>),
+    {
+        const MAX_SCOPED_THEMES: usize = 64;
+        let mut theme = self.theme.clone();
+        style(&mut theme);
+
+        // Reuse the id of an identical scoped theme so that its widgets aren't redrawn needlessly.
+        let theme_scope = match self.scoped_themes.iter().find(|&&(_, ref t)| *t == theme) {
+            Some(&(id, _)) => id,
+            None => {
+                let id = self.next_theme_scope;
+                self.next_theme_scope += 1;
+                if self.scoped_themes.len() == MAX_SCOPED_THEMES {
+                    self.scoped_themes.remove(0);
+                }
+                self.scoped_themes.push((id, theme.clone()));
+                id
+            },
+        };
+
+        let prev_theme = ::std::mem::replace(&mut self.theme, theme);
+        let prev_theme_scope = self.theme_scope;
+        self.theme_scope = theme_scope;
+        scope(self);
+        self.theme = prev_theme;
+        self.theme_scope = prev_theme_scope;
+    }
+
+    /// Crop the rendering of all widgets set within `scope` to the rectangle at `xy` with the given
+    /// dimensions, i.e. so that the contents of a container don't draw beyond its bounds when
+    /// offset. Scopes may be nested, in which case widgets are cropped to the intersection of
+    /// the rectangles. The mouse is only considered to be over a cropped widget while it is
+    /// within the rectangle.
+    ///
+    /// ```ignore
+    /// ui.with_clip(list_xy, list_dim, |ui| {
+    ///     for (i, item) in items.iter().enumerate() {
+    ///         Label::new(item).xy(0.0, scroll - i as f64 * 20.0).set(ITEMS + i, ui);
+    ///     }
+    /// });
+    /// ```
+    pub fn with_clip<F>(&mut self, xy: Point, dim: Dimensions, scope: F)
+        where
+            F: FnOnce(&mut Ui<C>),
+    {
+        let clip = match self.maybe_clip {
+            Some(outer) => intersect_rects(outer, (xy, dim)),
+            None => (xy, dim),
+        };
+        let prev_clip = ::std::mem::replace(&mut self.maybe_clip, Some(clip));
+        scope(self);
+        self.maybe_clip = prev_clip;
+    }
+
+    /// Set the widgets within the given scope in the given layer, so that they are drawn above
+    /// (and receive the mouse before) the widgets of all lower layers regardless of depth.
+    ///
+    /// ```ignore
+    /// ui.with_layer(Layer::Floating, |ui| {
+    ///     Floating::new(window_xy, window_dim).set(WINDOW, ui);
+    ///     Button::new().label("OK").relative_to(WINDOW, [0.0, 0.0]).set(OK, ui);
+    /// });
+    /// ```
+    pub fn with_layer<F>(&mut self, layer: Layer, scope: F)
+        where
+            F: FnOnce(&mut Ui<C>),
+    {
+        let prev_layer = ::std::mem::replace(&mut self.current_layer, layer);
+        scope(self);
+        self.current_layer = prev_layer;
+    }
+
+    /// Place the widget with the given UiId within the given layer, regardless of the layer scope
+    /// within which it is set (see `with_layer`). This is called by widgets that open above all
+    /// others, i.e. a DropDownList while its list is open.
+    pub fn set_layer(&mut self, ui_id: UiId, layer: Layer) {
+        self.widget_cache.set_layer_override(ui_id, Some(layer));
+    }
+
+    /// Return the widget with the given UiId to the layer of the scope within which it is set.
+    pub fn reset_layer(&mut self, ui_id: UiId) {
+        self.widget_cache.set_layer_override(ui_id, None);
+    }
+
+    /// The layer within which the widget with the given UiId was most recently drawn.
+    pub fn layer(&self, ui_id: UiId) -> Layer {
+        self.widget_cache.get(ui_id).map_or(Layer::Normal, |widget| widget.layer)
+    }
+
+    /// Whether or not the widget with the given UiId was last drawn within a different style
+    /// scope (see `with_style`) to the current one. This is called by `Widget::set`.
+    pub fn has_theme_changed(&self, ui_id: UiId) -> bool {
+        self.widget_cache.get(ui_id)
+            .map(|widget| widget.theme_scope != self.theme_scope)
+            .unwrap_or(false)
+    }
+
+    /// Begin measuring the time spent updating, drawing and rendering widgets each frame, keeping
+    /// the measurements for the given number of the most recent frames. Any profiler already in
+    /// progress is kept if it has the same capacity.
+    pub fn enable_profiler(&mut self, capacity: usize) {
+        let has_capacity = self.maybe_profiler.as_ref()
+            .map(|profiler| profiler.capacity() == capacity)
+            .unwrap_or(false);
+        if !has_capacity {
+            self.maybe_profiler = Some(Profiler::new(capacity));
+        }
+    }
+
+    /// Stop profiling, discarding the measurements.
+    pub fn disable_profiler(&mut self) {
+        self.maybe_profiler = None;
+    }
+
+    /// The measurements for the most recent frames, if profiling is enabled.
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.maybe_profiler.as_ref()
+    }
+
+    /// Whether or not profiling is enabled.
+    pub fn is_profiling(&self) -> bool {
+        self.maybe_profiler.is_some()
+    }
+
+    /// Record that a widget was set, having spent `update_ns` updating its state and, if it
+    /// constructed new Forms, `maybe_draw_ns` drawing. This is called by `Widget::set`.
+    pub fn profile_widget(&mut self, update_ns: u64, maybe_draw_ns: Option<u64>) {
+        if let Some(ref mut profiler) = self.maybe_profiler {
+            profiler.widget_updated(update_ns, maybe_draw_ns);
+        }
+    }
+
+    /// Report the number of allocations made during the current frame so that they may be shown
+    /// by the profiler (i.e. as counted by the application's allocator). Rust provides no hook
+    /// through which conrod could count these itself.
+    pub fn record_allocations(&mut self, count: u64) {
+        if let Some(ref mut profiler) = self.maybe_profiler {
+            profiler.record_allocations(count);
+        }
+    }
+
+    /// A handle that may be sent to other threads in order to send messages to the `Ui`. Messages
+    /// are delivered at the start of the next input cycle (see `take_messages`).
+    pub fn proxy(&self) -> UiProxy {
+        self.mailbox.proxy()
+    }
+
+    /// Take the messages of type `T` that were sent via a `UiProxy` and delivered at the start of
+    /// the current input cycle, in the order in which they were sent. Messages of other types are
+    /// left to be taken separately, while any left untaken by the end of the cycle are discarded.
+    pub fn take_messages<T: Any>(&mut self) -> Vec<T> {
+        self.mailbox.take()
+    }
+
+    /// Handle an input from some `Recording` as though it were received at the time at which it
+    /// was recorded (relative to the start of the recording), so that time sensitive interactions
+    /// such as double clicks are reproduced exactly.
+    pub fn replay(&mut self, recorded: &Recorded) {
+        self.maybe_replay_time_ns = Some(recorded.time_ns);
+        self.handle_event(&recorded.input.to_event());
+        self.maybe_replay_time_ns = None;
+    }
+
+    /// Advance the `Ui`'s clock by `dt` seconds. This is called for each update event given to
+    /// `handle_event`, but may also be called directly by applications that manage their own
+    /// timing (i.e. to step the `Ui` at a fixed rate, or to pause it).
+    ///
+    /// Once the clock is driven by `update`, widget animations and time-dependent behaviours
+    /// (such as the auto-repeat of a held button, debouncing, hover delays and the blinking of a
+    /// TextBox's caret) follow it rather than the system clock.
+    pub fn update(&mut self, dt: f64) {
+        let dt = dt.max(0.0);
+        let clock_ns = self.maybe_clock_ns.unwrap_or(self.event_time_ns);
+        self.maybe_clock_ns = Some(clock_ns + (dt * 1.0e9) as u64);
+        self.delta_time = dt;
+    }
+
+    /// The current time in nanoseconds as seen by widgets. This is the time accumulated via
+    /// `update` if it has been called, otherwise the time at which the most recent event was
+    /// received.
+    pub fn time_ns(&self) -> u64 {
+        self.maybe_clock_ns.unwrap_or(self.event_time_ns)
+    }
+
+    /// The duration in seconds passed to the most recent call to `update`, or `0.0` if the
+    /// clock is not yet being driven.
+    pub fn delta_time(&self) -> f64 {
+        self.delta_time
+    }
+
+    /// Stamp the time of a newly received event and, if it is the first event since the last
+    /// render, begin a new input cycle.
+    fn begin_event(&mut self) {
+        self.event_time_ns = match self.maybe_replay_time_ns {
+            Some(time_ns) => time_ns,
+            None => ::clock_ticks::precise_time_ns(),
+        };
         if self.prev_event_was_render {
+            self.frame_number += 1;
             self.flush_input();
+            // Widgets may have moved beneath the mouse since the previous cycle.
+            self.update_hover();
+            self.mailbox.deliver();
             self.maybe_prev_ui_id = None;
             self.prev_event_was_render = false;
             if let Some((Capturing::JustReleased, _)) = self.maybe_captured_mouse {
@@ -124,109 +933,684 @@ impl<C> Ui<C> {
             if let Some(Capturing::JustReleased) = self.maybe_captured_keyboard {
                 self.maybe_captured_keyboard = None;
             }
+            // If the payload was released without being dropped on a target, cancel the drag.
+            if self.maybe_drag.as_ref().map(|drag| drag.released).unwrap_or(false) {
+                self.maybe_drag = None;
+            }
         }
+    }
 
-        event.render(|args| {
-            self.win_w = args.width as f64;
-            self.win_h = args.height as f64;
-            self.prev_event_was_render = true;
-        });
+    /// Called whenever the left mouse button (or primary touch) is pressed at the given position.
+    fn left_pressed_at(&mut self, xy: Point) {
+        use utils::is_over_rect;
 
-        event.mouse_cursor(|x, y| {
-            // Convert mouse coords to (0, 0) origin.
-            self.mouse.xy = [x - self.win_w / 2.0, -(y - self.win_h / 2.0)];
+        self.maybe_pressed = self.topmost_widget_at(xy);
+
+        if self.maybe_event_log.is_some() {
+            let pressed_over = self.widgets_overlapping(xy, [0.0, 0.0]);
+            self.log_event(event_log::Event::PressedOver(pressed_over));
+        }
+
+        // Clicking moves the focus, so the clicked widget must claim it again.
+        if self.maybe_focused.take().is_some() {
+            self.log_event(event_log::Event::Focused(None));
+        }
+
+        // Dismiss the most recently opened transients until one is found beneath the press.
+        while let Some(&(ui_id, t_xy, t_dim)) = self.transients.last() {
+            if is_over_rect(t_xy, xy, t_dim) { break }
+            self.transients.pop();
+            self.dismissed.push(ui_id);
+            self.log_event(event_log::Event::Dismissed(ui_id));
+        }
+    }
+
+    /// Handle a touch event, where the position is given in window coordinates with the origin
+    /// at the top left (as with mouse cursor events).
+    ///
+    /// The primary touch (the first finger to touch down) drives the left button and position of
+    /// the `Mouse` so that widgets respond to it as they would to a mouse. Movements of two
+    /// fingers are recognised as pinch and scroll `Gesture`s.
+    pub fn handle_touch(&mut self, touch: Touch) {
+        use piston::input::MouseButton::Left;
+        self.begin_event();
+        let xy = self.window_to_ui(touch.xy);
+        let touch = Touch { xy: xy, ..touch };
+        let is_primary = match touch.phase {
+            touch::Phase::Start => self.touches.primary().is_none(),
+            _ => self.touches.primary() == Some(touch.id),
+        };
+        let gestures = self.touches.update(touch);
+        self.gestures.extend(gestures.into_iter());
+        if is_primary {
+            self.mouse.xy = xy;
+            match touch.phase {
+                touch::Phase::Start => {
+                    let now_ns = self.time_ns();
+                    self.left_pressed_at(xy);
+                    self.mouse.left.press(now_ns, xy);
+                },
+                touch::Phase::Move => (),
+                touch::Phase::End | touch::Phase::Cancel => {
+                    if let Some(ref mut drag) = self.maybe_drag {
+                        drag.released = true;
+                    }
+                    self.mouse_buttons_just_released.push(Left);
+                    self.mouse.left.release();
+                },
+            }
+            self.mouse.time_ns = self.time_ns();
+            self.mouse.frame_number = self.frame_number;
+        }
+    }
+
+    /// Return the gestures performed since the end of the last render cycle whose centre lies
+    /// within the rectangle described by `xy` and `dim`. This is useful for containers that wish
+    /// to pan or zoom their contents.
+    pub fn gestures_over(&self, xy: Point, dim: Dimensions) -> Vec<Gesture> {
+        use utils::is_over_rect;
+        self.gestures.iter()
+            .filter(|gesture| is_over_rect(xy, gesture.centre(), dim))
+            .map(|gesture| *gesture)
+            .collect()
+    }
+
+    /// Add a font to the `Ui` with the given glyph cache, returning the id by which widgets may
+    /// refer to it via `Fontable::font`.
+    pub fn add_font(&mut self, character_cache: C) -> FontId {
+        self.fonts.add(character_cache)
+    }
+
+    /// Set the fonts that are tried in order whenever the given font has no glyph for some
+    /// character, i.e. a font covering CJK characters or symbols. Panics if the font has not
+    /// been added.
+    pub fn set_font_fallbacks(&mut self, font: FontId, fallbacks: &[FontId]) {
+        self.fonts.set_fallbacks(font, fallbacks);
+    }
+
+    /// The font with which text is currently measured by `get_character`, `text_width`, etc.
+    pub fn current_font(&self) -> FontId {
+        self.current_font
+    }
+
+    /// Set the font with which text is measured. This is done automatically for each widget
+    /// while it is being set, after which the default font is restored.
+    pub fn set_current_font(&mut self, font: FontId) {
+        if !self.fonts.contains(font) {
+            writeln!(::std::io::stderr(),
+                     "Warning: The font {:?} has not been added to the `Ui`, so the default font \
+                      will be used instead.", font).unwrap();
+        }
+        self.current_font = font;
+    }
+
+    /// Return a reference to a `Character` from the current font (or the first font within its
+    /// fallback chain that has a glyph for the character).
+    pub fn get_character(&mut self,
+                         size: FontSize,
+                         ch: char) -> &Character<C::Texture>
+        where
+            C: CharacterCache
+    {
+        let Ui { ref mut character_cache, ref mut fonts, current_font, .. } = *self;
+        let font = fonts.resolve(character_cache, current_font, size, ch);
+        fonts.cache_mut(character_cache, font).character(size, ch)
+    }
+
+    /// Return the width of a 'Character' from the current font (or its fallbacks). Widths are
+    /// cached per font, font size and character, so this is much cheaper than `get_character`.
+    pub fn get_character_w(&mut self, size: FontSize, ch: char) -> f64
+        where
+            C: CharacterCache
+    {
+        let Ui { ref mut character_cache, ref mut fonts, current_font, .. } = *self;
+        fonts.width(character_cache, current_font, size, ch)
+    }
+
+    /// Forget the cached width of every glyph. This must be called after changing the glyphs
+    /// produced by the `character_cache` in place (i.e. rasterising them at a new scale).
+    /// Replacing the cache via `set_character_cache`, adding fonts and setting fallbacks all do so
+    /// automatically.
+    pub fn clear_glyph_widths(&mut self) {
+        self.fonts.clear_widths();
+    }
+
+    /// Replace the default font's `character_cache`, returning the old one. The cached width of
+    /// every glyph is forgotten so that text is measured with the new cache from now on.
+    pub fn set_character_cache(&mut self, character_cache: C) -> C {
+        self.fonts.clear_widths();
+        ::std::mem::replace(&mut self.character_cache, character_cache)
+    }
+
+    /// Return the pixel width of the given text at the given font size.
+    pub fn text_width(&mut self, size: FontSize, text: &str) -> f64
+        where
+            C: CharacterCache
+    {
+        label::width(self, size, text)
+    }
+
+    /// Measure the given text at the given font size, wrapping lines wherever a word would exceed
+    /// `maybe_max_width` (if given). This is useful for sizing containers to fit some text prior
+    /// to instantiating the widgets that display it.
+    pub fn text_size(&mut self,
+                     text: &str,
+                     size: FontSize,
+                     maybe_max_width: Option<f64>) -> TextSize
+        where
+            C: CharacterCache
+    {
+        label::measure(self, text, size, maybe_max_width)
+    }
+
+    /// Is the given key currently held down?
+    pub fn is_key_down(&self, key: input::keyboard::Key) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    /// Is either of the shift keys currently held down?
+    pub fn is_shift_down(&self) -> bool {
+        use piston::input::keyboard::Key::{LShift, RShift};
+        self.is_key_down(LShift) || self.is_key_down(RShift)
+    }
+
+    /// Smoothly transition the named property of the widget at `ui_id` toward `target` over
+    /// `duration` seconds, returning the property's current value.
+    ///
+    /// As widgets only redraw when their `State` changes, the returned value should be stored
+    /// within the widget's `State` so that each step of the animation is drawn.
+    pub fn animate<T>(&mut self,
+                      ui_id: UiId,
+                      property: &'static str,
+                      target: T,
+                      duration: f64,
+                      ease: Ease) -> T
+        where
+            T: Tween + Any,
+    {
+        let now_ns = self.time_ns();
+        self.widget_cache.animations_mut(ui_id).animate(property, target, duration, ease, now_ns)
+    }
+
+    /// Animate the named property using the `Theme`'s animation duration and easing.
+    pub fn animate_with_theme<T>(&mut self, ui_id: UiId, property: &'static str, target: T) -> T
+        where
+            T: Tween + Any,
+    {
+        let (duration, ease) = (self.theme.animation_duration, self.theme.animation_ease);
+        let now_ns = self.time_ns();
+        self.widget_cache.animations_mut(ui_id).animate(property, target, duration, ease, now_ns)
+    }
+
+    /// Whether or not any of the widget's properties are still being animated.
+    pub fn is_animating(&self, ui_id: UiId) -> bool {
+        self.widget_cache.is_animating(ui_id, self.time_ns())
+    }
+
+    /// Is either of the control keys currently held down?
+    pub fn is_ctrl_down(&self) -> bool {
+        use piston::input::keyboard::Key::{LCtrl, RCtrl};
+        self.is_key_down(LCtrl) || self.is_key_down(RCtrl)
+    }
+
+    /// Whether or not either of the Alt keys are currently held down.
+    pub fn is_alt_down(&self) -> bool {
+        use piston::input::keyboard::Key::{LAlt, RAlt};
+        self.is_key_down(LAlt) || self.is_key_down(RAlt)
+    }
+
+    /// Whether or not the accelerator for the given character (Alt + the character's key) was
+    /// pressed since the end of the last render cycle. The accelerator is unavailable to widgets
+    /// while some other widget has captured the keyboard.
+    pub fn is_mnemonic_pressed(&self, ui_id: UiId, ch: char) -> bool {
+        self.is_alt_down() && key_for_char(ch).map_or(false, |key| {
+            self.get_pressed_keys(ui_id).contains(&key)
+        })
+    }
+
+    /// Pass a widget's newly produced value (if any) through the given rate limit, returning the
+    /// value with which the widget should react now (if any). Widgets should call this every
+    /// update so that pending values are delivered once they fall due.
+    pub fn rate_limit<T: Any + PartialEq>(&mut self,
+                                          ui_id: UiId,
+                                          limit: RateLimit,
+                                          maybe_value: Option<T>) -> Option<T> {
+        let now_ns = self.time_ns();
+        self.widget_cache.limiter_mut(ui_id).limit(limit, maybe_value, now_ns)
+    }
+
+    /// Whether or not the widget at the given UiId has a rate limited value that is yet to be
+    /// delivered to its reaction.
+    pub fn is_rate_limit_pending(&self, ui_id: UiId) -> bool {
+        self.widget_cache.is_rate_limit_pending(ui_id)
+    }
+
+    /// Replace the clipboard backend used for copying and pasting text.
+    pub fn set_clipboard(&mut self, clipboard: Box<Clipboard>) {
+        self.clipboard = clipboard;
+    }
+
+    /// The clipboard used for copying and pasting text.
+    pub fn clipboard(&mut self) -> &mut Clipboard {
+        &mut *self.clipboard
+    }
+
+    /// Flush all stored keys.
+    pub fn flush_input(&mut self) {
+        self.keys_just_pressed.clear();
+        self.keys_just_released.clear();
+        self.text_just_entered.clear();
+        self.mouse_buttons_just_released.clear();
+        self.gestures.clear();
+        self.dismissed.clear();
+        self.mouse.scroll = [0.0, 0.0];
+        self.maybe_scrolled = None;
+        self.cursor_requests.clear();
+        self.hover_events.clear();
+    }
+
+    /// Return the current mouse state. If the Ui has been captured and the given ui_id doesn't
+    /// match the captured ui_id, return the captured mouse state. Otherwise, if the widget at the
+    /// given ui_id is occluded by the topmost widget beneath the mouse, the mouse is out of reach.
+    pub fn get_mouse_state(&self, ui_id: UiId) -> Mouse {
+        use utils::is_over_rect;
+        // A widget within a clipping scope is out of the mouse's reach beyond the clipped rect.
+        let is_captured = match self.maybe_captured_mouse {
+            Some((Capturing::Captured(captured_ui_id), _)) => captured_ui_id == ui_id,
+            _ => false,
+        };
+        if let Some((clip_xy, clip_dim)) = self.maybe_clip {
+            if !is_captured && !is_over_rect(clip_xy, self.mouse.xy, clip_dim) {
+                let unreachable = ::std::f64::MAX / 4.0;
+                return Mouse { xy: [unreachable, unreachable], ..self.mouse };
+            }
+        }
+        // Widgets are out of the mouse's reach beneath the topmost widget under the cursor (or
+        // under the left button's press for as long as the button is held) if that widget is in a
+        // higher layer. Widgets within the same layer all receive the mouse, as containers and
+        // passive widgets (i.e. Labels) commonly overlap the widgets upon them.
+        let maybe_target = match self.mouse.left.state {
+            ButtonState::Down => self.maybe_pressed,
+            ButtonState::Up => self.hovered_widget(),
+        };
+        let layer = self.widget_cache.layer_override(ui_id).unwrap_or(self.current_layer);
+        let is_occluded = maybe_target.map_or(false, |id| {
+            id != ui_id && self.widget_cache.get(id).map_or(false, |top| top.layer > layer)
         });
+        if self.maybe_captured_mouse.is_none() && is_occluded {
+            let unreachable = ::std::f64::MAX / 4.0;
+            return Mouse { xy: [unreachable, unreachable], ..self.mouse };
+        }
+        match self.maybe_captured_mouse {
+            Some((Capturing::Captured(captured_ui_id), captured_mouse)) => {
+                match ui_id == captured_ui_id {
+                    true => self.mouse,
+                    false => captured_mouse,
+                }
+            },
+            Some((Capturing::JustReleased, captured_mouse)) => captured_mouse,
+            None => self.mouse,
+        }
+    }
+
+    /// Return the distance scrolled by the mouse wheel since the end of the last render cycle if
+    /// the scroll is delivered to the widget at the given UiId, otherwise `[0.0, 0.0]`. The scroll
+    /// is delivered only to the topmost widget beneath the cursor (or to the widget capturing the
+    /// mouse) so that overlapping widgets do not all respond to it.
+    pub fn get_scroll(&self, ui_id: UiId) -> Point {
+        match self.maybe_scrolled == Some(ui_id) {
+            true => self.mouse.scroll,
+            false => [0.0, 0.0],
+        }
+    }
+
+    /// Determine the topmost widget beneath the mouse, recording a `HoverEvent` for each widget
+    /// that the mouse enters or leaves.
+    fn update_hover(&mut self) {
+        let maybe_over = self.topmost_widget_at(self.mouse.xy);
+        let maybe_prev = self.maybe_hovered.map(|(ui_id, _)| ui_id);
+        if maybe_over != maybe_prev {
+            if let Some(prev) = maybe_prev {
+                self.hover_events.push(mouse::HoverEvent::Left(prev));
+            }
+            if let Some(over) = maybe_over {
+                self.hover_events.push(mouse::HoverEvent::Entered(over));
+            }
+            let now_ns = self.time_ns();
+            self.maybe_hovered = maybe_over.map(|ui_id| (ui_id, now_ns));
+        }
+    }
+
+    /// The widget over which the mouse hovers, being the topmost widget beneath it.
+    pub fn hovered_widget(&self) -> Option<UiId> {
+        self.maybe_hovered.map(|(ui_id, _)| ui_id)
+    }
+
+    /// The number of seconds for which the mouse has hovered over the widget at the given UiId,
+    /// or `None` if it is not hovering over it. Useful for delaying tooltips and hover-to-open
+    /// menus.
+    pub fn hover_duration(&self, ui_id: UiId) -> Option<f64> {
+        match self.maybe_hovered {
+            Some((id, since_ns)) if id == ui_id =>
+                Some(self.time_ns().saturating_sub(since_ns) as f64 / 1.0e9),
+            _ => None,
+        }
+    }
+
+    /// Whether or not the mouse began hovering over the widget at the given UiId since the end
+    /// of the last render cycle.
+    pub fn has_hover_entered(&self, ui_id: UiId) -> bool {
+        self.hover_events.contains(&mouse::HoverEvent::Entered(ui_id))
+    }
+
+    /// Whether or not the mouse stopped hovering over the widget at the given UiId since the end
+    /// of the last render cycle.
+    pub fn has_hover_left(&self, ui_id: UiId) -> bool {
+        self.hover_events.contains(&mouse::HoverEvent::Left(ui_id))
+    }
+
+    /// The changes in the hovered widget since the end of the last render cycle, in the order in
+    /// which they occurred.
+    pub fn hover_events(&self) -> &[mouse::HoverEvent] {
+        &self.hover_events
+    }
+
+    /// Request the given shape of mouse cursor on behalf of the widget at the given UiId, i.e. an
+    /// IBeam while the mouse is over a TextBox. Widgets should only request a cursor while the
+    /// mouse is over them (or while they are dragging it).
+    pub fn set_mouse_cursor(&mut self, ui_id: UiId, cursor: mouse::Cursor) {
+        self.cursor_requests.retain(|&(id, _)| id != ui_id);
+        self.cursor_requests.push((ui_id, cursor));
+    }
+
+    /// The shape of mouse cursor that the application (or backend) should display, being that
+    /// requested by the widget capturing the mouse, or otherwise by the topmost of the widgets
+    /// that requested one. This is `Cursor::Arrow` if no widget requested a cursor.
+    pub fn mouse_cursor(&self) -> mouse::Cursor {
+        let maybe_captured = match self.maybe_captured_mouse {
+            Some((Capturing::Captured(ui_id), _)) => Some(ui_id),
+            _ => None,
+        };
+        let widget_cache = &self.widget_cache;
+        self.cursor_requests.iter()
+            .filter(|&&(ui_id, _)| maybe_captured.map_or(true, |captured| captured == ui_id))
+            .fold(None, |maybe_top: Option<(UiId, mouse::Cursor)>, &(ui_id, cursor)| {
+                match maybe_top {
+                    Some((top_id, _)) if is_drawn_above(&widget_cache[top_id],
+                                                        &widget_cache[ui_id]) => maybe_top,
+                    _ => Some((ui_id, cursor)),
+                }
+            })
+            .map(|(_, cursor)| cursor)
+            .unwrap_or(mouse::Cursor::Arrow)
+    }
+
+    /// Whether or not the right mouse button was released over the rectangle described by `xy`
+    /// and `dim` since the end of the last render cycle. If the mouse is captured by some other
+    /// widget, this will always return false.
+    pub fn is_right_clicked(&self, ui_id: UiId, xy: Point, dim: Dimensions) -> bool {
+        use piston::input::MouseButton::Right;
+        use utils::is_over_rect;
+        let is_captured_by_other = match self.maybe_captured_mouse {
+            Some((Capturing::Captured(captured_ui_id), _)) => captured_ui_id != ui_id,
+            Some((Capturing::JustReleased, _)) => true,
+            None => false,
+        };
+        !is_captured_by_other
+            && self.mouse_buttons_just_released.contains(&Right)
+            && is_over_rect(xy, self.mouse.xy, dim)
+    }
+
+    /// Return the vector of recently pressed keys.
+    pub fn get_pressed_keys(&self, ui_id: UiId) -> &[input::keyboard::Key] {
+        match self.maybe_captured_keyboard {
+            Some(Capturing::Captured(captured_ui_id)) => if ui_id == captured_ui_id {
+                &self.keys_just_pressed
+            } else {
+                &[]
+            },
+            Some(Capturing::JustReleased) => &[],
+            None => &self.keys_just_pressed,
+        }
+    }
+
+    /// Give keyboard focus to the widget with the given UiId, taking it from whichever widget had
+    /// it previously. Focus is lost when the left mouse button is next pressed, so widgets should
+    /// claim it each time they are clicked.
+    pub fn focus(&mut self, ui_id: UiId) {
+        if self.maybe_focused != Some(ui_id) {
+            self.log_event(event_log::Event::Focused(Some(ui_id)));
+        }
+        self.maybe_focused = Some(ui_id);
+    }
+
+    /// Whether or not the widget with the given UiId has keyboard focus.
+    pub fn is_focused(&self, ui_id: UiId) -> bool {
+        self.maybe_focused == Some(ui_id)
+    }
+
+    /// Add the widget with the given UiId to the given keyboard panel. Value widgets call this
+    /// each update when they are given a panel (i.e. via `Slider::panel`). While a member of a
+    /// panel has focus, the arrow keys move the focus to the nearest member in that direction.
+    pub fn join_panel(&mut self, panel_id: PanelId, ui_id: UiId) {
+        let is_focused = self.is_focused(ui_id);
+        self.panels.join(panel_id, ui_id, is_focused);
+    }
+
+    /// Give keyboard focus to the member of the given panel that was most recently focused, or to
+    /// its first member if none has been focused yet.
+    pub fn focus_panel(&mut self, panel_id: PanelId) {
+        let maybe_ui_id = self.panels.last_focused(panel_id)
+            .or_else(|| self.panels.members(panel_id).first().cloned());
+        if let Some(ui_id) = maybe_ui_id {
+            self.focus(ui_id);
+        }
+    }
+
+    /// The member of the given panel that was most recently focused, if any.
+    pub fn panel_focus(&self, panel_id: PanelId) -> Option<UiId> {
+        self.panels.last_focused(panel_id)
+    }
+
+    /// Bring the widget with the given UiId to the front of its layer, above all widgets brought
+    /// to the front before it, i.e. when a Floating window is clicked. The widget's depth is
+    /// offset by its `raised_depth`.
+    pub fn bring_to_front(&mut self, ui_id: UiId) {
+        self.raised.retain(|&id| id != ui_id);
+        self.raised.push(ui_id);
+    }
+
+    /// The offset to the depth of the widget with the given UiId brought to the front of its layer
+    /// (see `bring_to_front`), such that it is drawn above the widgets that were brought to the
+    /// front before it and beneath those brought to the front after it. The widget's children
+    /// should be given a depth of up to `RAISED_DEPTH_STEP` less than this. Widgets that have
+    /// never been brought to the front are offset by `0.0`.
+    pub fn raised_depth(&self, ui_id: UiId) -> Depth {
+        match self.raised.iter().position(|&id| id == ui_id) {
+            Some(idx) => -RAISED_DEPTH_STEP * (idx + 1) as Depth,
+            None => 0.0,
+        }
+    }
+
+    /// Begin learning an external control binding. The next value widget (i.e. a Slider,
+    /// NumberDialer or Toggle) with which the user interacts is passed to `on_learn`, after which
+    /// learning ends. Values for that widget may then be given via `push_control_value`.
+    pub fn learn_binding<F>(&mut self, on_learn: F) where F: FnMut(UiId) + 'static {
+        self.bindings.learn(Box::new(on_learn));
+    }
+
+    /// Stop learning an external control binding without reporting a widget.
+    pub fn cancel_learn_binding(&mut self) {
+        self.bindings.cancel_learn();
+    }
+
+    /// Whether or not an external control binding is currently being learned.
+    pub fn is_learning_binding(&self) -> bool {
+        self.bindings.is_learning()
+    }
+
+    /// Called by value widgets when the user interacts with them, reporting the widget to the
+    /// application if a binding is being learned.
+    pub fn binding_interacted(&mut self, ui_id: UiId) {
+        if self.bindings.interacted(ui_id) {
+            self.log_event(event_log::Event::BindingLearned(ui_id));
+        }
+    }
+
+    /// Push a value from an external control into the widget at the given UiId, normalised so
+    /// that `0.0` and `1.0` are the ends of the widget's range. The widget adopts the most recently
+    /// pushed value at its next update, reacting with it as though the user had changed it.
+    pub fn push_control_value(&mut self, ui_id: UiId, value: f64) {
+        self.bindings.push(ui_id, value);
+    }
+
+    /// Take the external control value pushed to the widget at the given UiId since its previous
+    /// update, if there is one.
+    pub fn take_control_value(&mut self, ui_id: UiId) -> Option<f64> {
+        self.bindings.take(ui_id)
+    }
+
+    /// The states recorded by all persistent widgets, i.e. to be saved via `WidgetStates::save`
+    /// when the application exits.
+    pub fn widget_states(&self) -> &WidgetStates {
+        &self.persisted
+    }
+
+    /// Restore widget states saved during a previous session, i.e. as loaded via
+    /// `WidgetStates::load` at launch. Each persistent widget adopts its restored state at its
+    /// next update.
+    pub fn restore_widget_states(&mut self, states: WidgetStates) {
+        self.restored = states;
+    }
+
+    /// Called by persistent widgets to record their state for the widget of the given kind at
+    /// the given UiId.
+    pub fn persist_widget_state<T: Encodable>(&mut self, ui_id: UiId, kind: &'static str,
+                                              state: &T) {
+        self.persisted.insert(kind, ui_id, state);
+    }
+
+    /// Take the restored state of the widget of the given kind at the given UiId, if it has one
+    /// that has not yet been adopted.
+    pub fn take_restored_widget_state<T: Decodable>(&mut self, ui_id: UiId,
+                                                    kind: &'static str) -> Option<T> {
+        if self.restored.is_empty() { return None }
+        self.restored.remove(kind, ui_id)
+    }
+
+    /// Record the changes made by the user to value and text widgets (i.e. Sliders, Toggles and
+    /// TextBoxes) so that they may be undone, keeping at most `limit` changes. A limit of `0`
+    /// disables the history, which is the default.
+    pub fn enable_undo(&mut self, limit: usize) {
+        self.history.set_limit(limit);
+    }
 
-        event.press(|button_type| {
-            use piston::input::Button;
-            use piston::input::MouseButton::{Left, Middle, Right};
+    /// Undo the most recent change, returning whether or not there was one. The widget adopts its
+    /// previous state at its next update, reacting with it as though the user had changed it.
+    pub fn undo(&mut self) -> bool {
+        self.history.undo()
+    }
 
-            match button_type {
-                Button::Mouse(button) => {
-                    *match button {
-                        Left => &mut self.mouse.left,
-                        Right => &mut self.mouse.right,
-                        Middle => &mut self.mouse.middle,
-                        _ => &mut self.mouse.unknown,
-                    } = ButtonState::Down;
-                },
-                Button::Keyboard(key) => self.keys_just_pressed.push(key),
-            }
-        });
+    /// Redo the most recently undone change, returning whether or not there was one.
+    pub fn redo(&mut self) -> bool {
+        self.history.redo()
+    }
 
-        event.release(|button_type| {
-            use piston::input::Button;
-            use piston::input::MouseButton::Left;
-            match button_type {
-                Button::Mouse(button) => {
-                    *match button {
-                        Left => &mut self.mouse.left,
-                        _/*input::mouse::Right*/ => &mut self.mouse.right,
-                        //Middle => &mut self.mouse.middle,
-                    } = ButtonState::Up;
-                },
-                Button::Keyboard(key) => self.keys_just_released.push(key),
-            }
-        });
+    /// Whether or not there is a change that may be undone.
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
 
-        event.text(|text| {
-            self.text_just_entered.push(text.to_string())
-        });
+    /// Whether or not there is a change that may be redone.
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
     }
 
-    /// Return a reference to a `Character` from the GlyphCache.
-    pub fn get_character(&mut self,
-                         size: FontSize,
-                         ch: char) -> &Character<C::Texture>
-        where
-            C: CharacterCache
-    {
-        self.character_cache.character(size, ch)
+    /// Forget all recorded changes, i.e. after loading a new document.
+    pub fn clear_undo_history(&mut self) {
+        self.history.clear();
     }
 
-    /// Return the width of a 'Character'.
-    pub fn get_character_w(&mut self, size: FontSize, ch: char) -> f64
-        where
-            C: CharacterCache
-    {
-        self.get_character(size, ch).width()
+    /// Called by widgets to record a change made by the user to the widget of the given kind at
+    /// the given UiId. Changes made to the same widget in quick succession are coalesced.
+    pub fn push_change<T: Encodable>(&mut self, ui_id: UiId, kind: &'static str,
+                                     before: &T, after: &T) {
+        if !self.history.is_enabled() { return }
+        if let (Ok(before), Ok(after)) = (json::encode(before), json::encode(after)) {
+            let now_ns = self.time_ns();
+            self.history.push(ui_id, kind, before, after, now_ns);
+        }
     }
 
-    /// Flush all stored keys.
-    pub fn flush_input(&mut self) {
-        self.keys_just_pressed.clear();
-        self.keys_just_released.clear();
-        self.text_just_entered.clear();
+    /// Take the state handed back to the widget of the given kind at the given UiId by an undo or
+    /// redo since its previous update, if there is one.
+    pub fn take_undone_state<T: Decodable>(&mut self, ui_id: UiId,
+                                           kind: &'static str) -> Option<T> {
+        self.history.take_applied(ui_id, kind).and_then(|state| json::decode(&state).ok())
     }
 
-    /// Return the current mouse state. If the Ui has been captured and the given ui_id doesn't
-    /// match the captured ui_id, return the captured mouse state.
-    pub fn get_mouse_state(&self, ui_id: UiId) -> Mouse {
-        match self.maybe_captured_mouse {
-            Some((Capturing::Captured(captured_ui_id), captured_mouse)) => {
-                match ui_id == captured_ui_id {
-                    true => self.mouse,
-                    false => captured_mouse,
-                }
-            },
-            Some((Capturing::JustReleased, captured_mouse)) => captured_mouse,
-            None => self.mouse,
+    /// If the focused widget belongs to a keyboard panel and the key is an arrow key, move the
+    /// focus to the nearest member of the panel in that direction. Returns whether or not the key
+    /// was used, in which case it should not be delivered to the widgets.
+    fn move_panel_focus(&mut self, key: input::keyboard::Key) -> bool {
+        let (focused, direction) = match (self.maybe_focused, panel::arrow_direction(key)) {
+            (Some(focused), Some(direction)) => (focused, direction),
+            _ => return false,
+        };
+        let panel_id = match (self.maybe_captured_keyboard, self.panels.panel_of(focused)) {
+            (None, Some(panel_id)) => panel_id,
+            _ => return false,
+        };
+        let from = self.widget_cache[focused].xy;
+        let maybe_nearest = {
+            let widget_cache = &self.widget_cache;
+            let candidates = self.panels.members(panel_id).iter()
+                .filter(|&&ui_id| ui_id != focused)
+                .map(|&ui_id| (ui_id, widget_cache[ui_id].xy));
+            panel::nearest(from, direction, candidates)
+        };
+        if let Some(ui_id) = maybe_nearest {
+            self.focus(ui_id);
         }
+        true
     }
 
-    /// Return the vector of recently pressed keys.
-    pub fn get_pressed_keys(&self, ui_id: UiId) -> &[input::keyboard::Key] {
-        match self.maybe_captured_keyboard {
-            Some(Capturing::Captured(captured_ui_id)) => if ui_id == captured_ui_id {
-                &self.keys_just_pressed
-            } else {
-                &[]
-            },
-            Some(Capturing::JustReleased) => &[],
-            None => &self.keys_just_pressed,
+    /// Return the recently pressed keys if the widget with the given UiId has keyboard focus and
+    /// no widget has captured the keyboard. Unlike `get_pressed_keys`, this yields nothing to
+    /// widgets without focus, so that keys like Space and Enter only affect a single widget.
+    pub fn get_focused_keys(&self, ui_id: UiId) -> &[input::keyboard::Key] {
+        match (self.maybe_focused, self.maybe_captured_keyboard) {
+            (Some(focused_ui_id), None) if focused_ui_id == ui_id => &self.keys_just_pressed,
+            _ => &[],
+        }
+    }
+
+    /// Indicate that the widget with the given UiId is an open transient (i.e. an open menu or
+    /// popup) occupying the rectangle described by `xy` and `dim`. Transients should call this
+    /// every update while they are open.
+    ///
+    /// The `Ui` dismisses open transients in the reverse order in which they were opened, one at a
+    /// time when Escape is pressed, or all of those above the press when the left mouse button is
+    /// pressed outside of their rectangles. See `Ui::is_dismissed`. An Escape press that dismisses
+    /// a transient is not reported to any widget as a pressed key.
+    pub fn transient_opened_by(&mut self, ui_id: UiId, xy: Point, dim: Dimensions) {
+        match self.transients.iter().position(|&(id, _, _)| id == ui_id) {
+            Some(idx) => self.transients[idx] = (ui_id, xy, dim),
+            None => self.transients.push((ui_id, xy, dim)),
         }
     }
 
+    /// Indicate that the transient widget with the given UiId has closed.
+    pub fn transient_closed_by(&mut self, ui_id: UiId) {
+        self.transients.retain(|&(id, _, _)| id != ui_id);
+    }
+
+    /// Whether or not the transient widget with the given UiId has been dismissed since the end
+    /// of the last render cycle, in which case it should close.
+    pub fn is_dismissed(&self, ui_id: UiId) -> bool {
+        self.dismissed.contains(&ui_id)
+    }
+
     /// Return the vector of recently entered text.
     pub fn get_entered_text(&self, ui_id: UiId) -> &[String] {
         match self.maybe_captured_keyboard {
@@ -240,6 +1624,30 @@ impl<C> Ui<C> {
         }
     }
 
+    /// Update the text currently being composed by an input method editor (IME). Backends that
+    /// support IME should call this whenever the pre-edit string changes, passing `None` if the
+    /// composition is cancelled.
+    pub fn set_composition(&mut self, maybe_text: Option<String>) {
+        self.maybe_composition = maybe_text;
+    }
+
+    /// Commit the given composed text, ending the current composition. The text will be received
+    /// by widgets in the same manner as regular text input.
+    pub fn commit_composition(&mut self, text: &str) {
+        self.maybe_composition = None;
+        self.text_just_entered.push(text.to_string());
+    }
+
+    /// Return the text currently being composed if the widget with the given UiId has captured
+    /// the keyboard.
+    pub fn get_composition(&self, ui_id: UiId) -> Option<&str> {
+        match self.maybe_captured_keyboard {
+            Some(Capturing::Captured(captured_ui_id)) if ui_id == captured_ui_id =>
+                self.maybe_composition.as_ref().map(|text| &text[..]),
+            _ => None,
+        }
+    }
+
 
     /// Get the state of a widget with the given type and UiId.
     ///
@@ -255,45 +1663,164 @@ impl<C> Ui<C> {
     {
 
         // If the cache is not big enough, extend it.
-        if self.widget_cache.len() <= ui_id {
-            let num_to_extend = ui_id + 1 - self.widget_cache.len();
-            let extension = (0..num_to_extend).map(|_| widget::Cached::empty());
-            self.widget_cache.extend(extension);
-        }
+        self.widget_cache.reserve(ui_id + 1);
 
-        // If the cache is empty, return None.
-        if self.widget_cache[ui_id].kind == "EMPTY" {
-            None
-        }
+        // The kind of a widget is checked by the type of its stored state and style, which is far
+        // cheaper than comparing its `unique_kind` string.
+        let type_id = TypeId::of::<widget::Store<W::State, W::Style>>();
 
-        // Else if the cache is already initialised for a widget of a different kind, warn the user.
-        else if self.widget_cache[ui_id].kind != kind {
-            writeln!(::std::io::stderr(),
-                     "A widget of a different kind already exists at the given UiId ({:?}).
-                      You tried to insert a {:?}, however the existing widget is a {:?}.
-                      Check your widgets' `UiId`s for errors.",
-                      ui_id, kind, &self.widget_cache[ui_id].kind).unwrap();
-            None
-        }
+        match self.widget_cache[ui_id].maybe_type_id {
 
-        // Otherwise we've successfully found our state!
-        else {
-            let cached_widget = &mut self.widget_cache[ui_id];
-            if let Some(any_state) = cached_widget.maybe_state.take() {
-                let dim = cached_widget.dim;
-                let xy = cached_widget.xy;
-                let depth = cached_widget.depth;
-                let store: Box<widget::Store<W::State, W::Style>> = any_state.downcast()
-                    .ok().expect("Failed to downcast from Box<Any> to required widget::Store.");
-                let store: widget::Store<W::State, W::Style> = *store;
-                let widget::Store { state, style } = store;
-                Some(widget::PrevState { state: state, style: style, dim: dim, xy: xy, depth: depth })
-            } else {
+            // If the cache is empty, return None.
+            None => None,
+
+            // If the cache is already initialised for a widget of a different kind, warn the user.
+            Some(cached_type_id) if cached_type_id != type_id => {
+                writeln!(::std::io::stderr(),
+                         "A widget of a different kind already exists at the given UiId ({:?}).
+                          You tried to insert a {:?}, however the existing widget is a {:?}.
+                          Check your widgets' `UiId`s for errors.",
+                          ui_id, kind, &self.widget_cache[ui_id].kind).unwrap();
                 None
+            },
+
+            // Otherwise we've successfully found our state!
+            Some(_) => {
+                let cached_widget = &mut self.widget_cache[ui_id];
+                if let Some(any_state) = cached_widget.maybe_state.take() {
+                    let dim = cached_widget.dim;
+                    let xy = cached_widget.xy;
+                    let depth = cached_widget.depth;
+                    let store: Box<widget::Store<W::State, W::Style>> = any_state.downcast()
+                        .ok().expect("Failed to downcast from Box<Any> to required widget::Store.");
+                    let store: widget::Store<W::State, W::Style> = *store;
+                    let widget::Store { state, style } = store;
+                    Some(widget::PrevState {
+                        state: state,
+                        style: style,
+                        dim: dim,
+                        xy: xy,
+                        depth: depth,
+                    })
+                } else {
+                    None
+                }
+            },
+
+        }
+    }
+
+
+    /// Whether or not a widget's state currently exists at the given UiId, having been set since
+    /// the `Ui` last removed the widget there.
+    pub fn has_widget_state(&self, ui_id: UiId) -> bool {
+        self.widget_cache.get(ui_id).map(|widget| !widget.is_empty()).unwrap_or(false)
+    }
+
+    /// The key of the widget currently at the given UiId. Unlike the UiId, which is reused by
+    /// whichever widget is next set there, the key only ever refers to this widget, so it may be
+    /// held across frames (i.e. by a background task) and checked via `contains_widget`.
+    pub fn widget_key(&self, ui_id: UiId) -> WidgetKey {
+        self.widget_cache.key(ui_id)
+    }
+
+    /// Whether or not the widget to which the given key refers still exists, having not been
+    /// removed since the key was taken.
+    pub fn contains_widget(&self, key: WidgetKey) -> bool {
+        self.widget_cache.contains_key(key)
+    }
+
+    /// Set the function to be called with the given UiId once the widget there is removed,
+    /// replacing any function given previously.
+    pub fn set_widget_removal_hook(&mut self, ui_id: UiId, hook: Box<FnMut(UiId)>) {
+        self.widget_cache.set_removal_hook(ui_id, hook);
+    }
+
+    /// Remove the widgets that go unset for more than the given number of consecutive calls to
+    /// `Ui::draw`, dropping their state, animations, captures and focus and calling their
+    /// removal hooks (see `Widget::on_remove`). A `grace` of `0` removes every widget that is not
+    /// set between two draws, while a larger grace allows widgets that are only set on some
+    /// frames (i.e. the contents of a collapsed area) to keep their state.
+    ///
+    /// Widgets are never removed by default, in which case their state is kept for as long as
+    /// the `Ui` and removal hooks are never called.
+    pub fn enable_widget_removal(&mut self, grace: u32) {
+        self.maybe_removal_grace = Some(grace);
+    }
+
+    /// Stop removing unset widgets, keeping the state of every widget for as long as the `Ui`.
+    pub fn disable_widget_removal(&mut self) {
+        self.maybe_removal_grace = None;
+    }
+
+    /// Remove every widget that has gone unset for more than the removal grace, along with its
+    /// animations, captures and focus, calling its removal hook if it has one. Nothing is removed
+    /// unless removal has been enabled via `enable_widget_removal`.
+    fn remove_unset_widgets(&mut self) {
+        let grace = match self.maybe_removal_grace {
+            Some(grace) => grace,
+            None => return,
+        };
+        let unset = self.widget_cache.unset_for_more_than(grace);
+        for ui_id in unset {
+            let removed = self.widget_cache.remove(ui_id);
+            if let Some((Capturing::Captured(id), _)) = self.maybe_captured_mouse {
+                if id == ui_id { self.maybe_captured_mouse = None; }
+            }
+            if let Some(Capturing::Captured(id)) = self.maybe_captured_keyboard {
+                if id == ui_id { self.maybe_captured_keyboard = None; }
+            }
+            if self.maybe_focused == Some(ui_id) {
+                self.maybe_focused = None;
+            }
+            self.panels.remove(ui_id);
+            if self.maybe_hovered.map_or(false, |(id, _)| id == ui_id) {
+                self.maybe_hovered = None;
+            }
+            self.bindings.remove(ui_id);
+            self.history.remove(ui_id);
+            self.raised.retain(|&id| id != ui_id);
+            if self.maybe_pressed == Some(ui_id) {
+                self.maybe_pressed = None;
+            }
+            if let Some(mut hook) = removed.maybe_removal_hook {
+                hook(ui_id);
             }
         }
     }
 
+    /// Store the given user data alongside the widget at the given UiId.
+    pub fn set_widget_tag<T: Any>(&mut self, ui_id: UiId, tag: T) {
+        self.widget_cache[ui_id].maybe_tag = Some(Box::new(tag));
+    }
+
+    /// Return the user data attached to the widget at the given UiId if there is some of type `T`.
+    pub fn widget_tag<T: Any>(&self, ui_id: UiId) -> Option<&T> {
+        self.widget_cache.get(ui_id)
+            .and_then(|widget| widget.maybe_tag.as_ref())
+            .and_then(|tag| tag.downcast_ref())
+    }
+
+    /// Store the description of the widget at the given UiId for assistive technologies.
+    pub fn set_widget_accessibility(&mut self, ui_id: UiId, accessibility: Accessibility) {
+        self.widget_cache[ui_id].maybe_accessibility = Some(accessibility);
+    }
+
+    /// Return a description of every widget that has been set within the `Ui` for assistive
+    /// technologies (i.e. screen readers), in the order of their UiIds. Each node describes the
+    /// widget's role, label, value and bounds as of its most recent update.
+    pub fn accessibility_nodes(&self) -> Vec<accessibility::Node> {
+        self.widget_cache.widgets().iter().enumerate().filter_map(|(ui_id, widget)| {
+            widget.maybe_accessibility.as_ref().map(|accessibility| accessibility::Node {
+                ui_id: ui_id,
+                role: accessibility.role,
+                maybe_label: accessibility.maybe_label.clone(),
+                maybe_value: accessibility.maybe_value.clone(),
+                xy: widget.xy,
+                dim: widget.dim,
+            })
+        }).collect()
+    }
 
     /// Update the given canvas.
     pub fn update_canvas(&mut self,
@@ -338,13 +1865,13 @@ impl<C> Ui<C> {
                                    dim: Dimensions,
                                    xy: Point,
                                    depth: Depth,
-                                   maybe_new_element: Option<Element>)
+                                   maybe_new_forms: Option<Vec<Form>>)
         where
             Sta: Any + ::std::fmt::Debug + 'static,
             Sty: Any + ::std::fmt::Debug + 'static,
     {
-        if self.widget_cache[ui_id].kind == kind
-        || self.widget_cache[ui_id].kind == "EMPTY" {
+        let type_id = TypeId::of::<widget::Store<Sta, Sty>>();
+        if self.widget_cache[ui_id].maybe_type_id.map_or(true, |cached| cached == type_id) {
             if self.widget_cache[ui_id].has_updated {
                 writeln!(::std::io::stderr(),
                          "Warning: The widget with UiId {:?} has already been set within the `Ui` \
@@ -353,15 +1880,34 @@ impl<C> Ui<C> {
                           `Ui::draw` after constructing your widgets and that you haven't \
                           accidentally set the same widget twice.", ui_id).unwrap();
             }
+            let font = self.current_font;
+            let depth = depth + self.raised_depth(ui_id);
+            let layer = self.widget_cache.layer_override(ui_id).unwrap_or(self.current_layer);
+            let (margin, padding) = self.current_spacing;
+            let theme_scope = self.theme_scope;
+            let maybe_clip = self.maybe_clip;
+            self.current_spacing = (Margin::none(), Padding::none());
+            self.widgets_set += 1;
             let cached_widget = &mut self.widget_cache[ui_id];
             let state: Box<Any> = Box::new(store);
             cached_widget.maybe_state = Some(state);
+            cached_widget.maybe_type_id = Some(type_id);
             cached_widget.kind = kind;
             cached_widget.xy = xy;
             cached_widget.dim = dim;
             cached_widget.depth = depth;
-            if let Some(new_element) = maybe_new_element {
-                cached_widget.element = new_element;
+            cached_widget.layer = layer;
+            cached_widget.set_order = self.widgets_set;
+            cached_widget.font = font;
+            cached_widget.margin = margin;
+            cached_widget.padding = padding;
+            cached_widget.maybe_clip = maybe_clip;
+            if maybe_new_forms.is_some() {
+                cached_widget.theme_scope = theme_scope;
+            }
+            cached_widget.maybe_tag = None;
+            if let Some(new_forms) = maybe_new_forms {
+                cached_widget.forms = new_forms;
             }
             cached_widget.has_updated = true;
             self.maybe_prev_ui_id = Some(ui_id);
@@ -380,7 +1926,42 @@ impl<C> Ui<C> {
                   dim: Dimensions,
                   h_align: HorizontalAlign,
                   v_align: VerticalAlign) -> Point {
-        match position {
+        self.get_xy_with_margin(position, dim, Margin::none(), h_align, v_align)
+    }
+
+    /// Get the centred xy coords for some given `Dimension`s, `Position` and alignment, keeping
+    /// the given Margin clear around the widget.
+    ///
+    /// The Margin and Padding are also recorded for the widget currently being set, so that
+    /// widgets later positioned relative to it (or placed on it) respect them in turn.
+    pub fn get_xy_with_spacing(&mut self,
+                               position: Position,
+                               dim: Dimensions,
+                               margin: Margin,
+                               padding: Padding,
+                               h_align: HorizontalAlign,
+                               v_align: VerticalAlign) -> Point {
+        self.set_current_spacing(margin, padding);
+        self.get_xy_with_margin(position, dim, margin, h_align, v_align)
+    }
+
+    /// Record the Margin and Padding of the widget currently being set. This is done by
+    /// `get_xy_with_spacing`, though widgets that determine their own position (i.e. the
+    /// `Popover`) may call this directly. The spacing is reset once the widget has been set.
+    pub fn set_current_spacing(&mut self, margin: Margin, padding: Padding) {
+        self.current_spacing = (margin, padding);
+    }
+
+    /// Get the centred xy coords for some given `Dimension`s, `Position` and alignment, keeping
+    /// the given Margin clear around the widget, without recording it. This is useful for
+    /// layouts that are not themselves cached within the `Ui` (i.e. the `WidgetMatrix`).
+    pub fn get_xy_with_margin(&self,
+                              position: Position,
+                              dim: Dimensions,
+                              margin: Margin,
+                              h_align: HorizontalAlign,
+                              v_align: VerticalAlign) -> Point {
+        let xy = match position {
 
             Position::Absolute(x, y) => [x, y],
 
@@ -398,48 +1979,50 @@ impl<C> Ui<C> {
                     Some(rel_ui_id) => {
                         use position::Direction;
                         let rel_xy = self.widget_cache[rel_ui_id].xy;
-                        let element = &self.widget_cache[rel_ui_id].element;
-                        let (rel_w, rel_h) = element.get_size();
-                        let (rel_w, rel_h) = (rel_w as f64, rel_h as f64);
+                        let rel_margin = self.widget_cache[rel_ui_id].margin;
+                        let rel_dim = self.widget_cache[rel_ui_id].dim;
+                        let (rel_w, rel_h) = (rel_dim[0], rel_dim[1]);
+                        let x_align = || match h_align {
+                            HorizontalAlign::Middle => 0.0,
+                            HorizontalAlign::Left   =>
+                                align_left_of(rel_w, dim[0]) + margin.left,
+                            HorizontalAlign::Right  =>
+                                align_right_of(rel_w, dim[0]) - margin.right,
+                        };
+                        let y_align = || match v_align {
+                            VerticalAlign::Middle => 0.0,
+                            VerticalAlign::Bottom =>
+                                align_bottom_of(rel_h, dim[1]) + margin.bottom,
+                            VerticalAlign::Top    =>
+                                align_top_of(rel_h, dim[1]) - margin.top,
+                        };
                         match direction {
 
                             Direction::Up => {
-                                let x = rel_xy[0] + match h_align {
-                                    HorizontalAlign::Middle => 0.0,
-                                    HorizontalAlign::Left   => align_left_of(rel_w, dim[0]),
-                                    HorizontalAlign::Right  => align_right_of(rel_w, dim[0]),
-                                };
-                                let y = rel_xy[1] + rel_h / 2.0 + dim[1] / 2.0 + px;
+                                let x = rel_xy[0] + x_align();
+                                let gap = px + rel_margin.top + margin.bottom;
+                                let y = rel_xy[1] + rel_h / 2.0 + dim[1] / 2.0 + gap;
                                 [x, y]
                             },
 
                             Direction::Down => {
-                                let x = rel_xy[0] + match h_align {
-                                    HorizontalAlign::Middle => 0.0,
-                                    HorizontalAlign::Left   => align_left_of(rel_w, dim[0]),
-                                    HorizontalAlign::Right  => align_right_of(rel_w, dim[0]),
-                                };
-                                let y = rel_xy[1] - rel_h / 2.0 - dim[1] / 2.0 - px;
+                                let x = rel_xy[0] + x_align();
+                                let gap = px + rel_margin.bottom + margin.top;
+                                let y = rel_xy[1] - rel_h / 2.0 - dim[1] / 2.0 - gap;
                                 [x, y]
                             },
 
                             Direction::Left => {
-                                let y = rel_xy[1] + match v_align {
-                                    VerticalAlign::Middle => 0.0,
-                                    VerticalAlign::Bottom => align_bottom_of(rel_h, dim[1]),
-                                    VerticalAlign::Top    => align_top_of(rel_h, dim[1]),
-                                };
-                                let x = rel_xy[0] - rel_w / 2.0 - dim[0] / 2.0 - px;
+                                let y = rel_xy[1] + y_align();
+                                let gap = px + rel_margin.left + margin.right;
+                                let x = rel_xy[0] - rel_w / 2.0 - dim[0] / 2.0 - gap;
                                 [x, y]
                             },
 
                             Direction::Right => {
-                                let y = rel_xy[1] + match v_align {
-                                    VerticalAlign::Middle => 0.0,
-                                    VerticalAlign::Bottom => align_bottom_of(rel_h, dim[1]),
-                                    VerticalAlign::Top    => align_top_of(rel_h, dim[1]),
-                                };
-                                let x = rel_xy[0] + rel_w / 2.0 + dim[0] / 2.0 + px;
+                                let y = rel_xy[1] + y_align();
+                                let gap = px + rel_margin.right + margin.left;
+                                let x = rel_xy[0] + rel_w / 2.0 + dim[0] / 2.0 + gap;
                                 [x, y]
                             },
 
@@ -449,7 +2032,7 @@ impl<C> Ui<C> {
             },
 
             Position::Place(place, maybe_canvas_id) => {
-                use position::{self, Place};
+                use position;
                 let (xy, target_dim, pad) = match maybe_canvas_id.or(self.maybe_current_canvas_id) {
                     Some(canvas_id) => {
                         let canvas = &self.canvas_cache[canvas_id];
@@ -458,21 +2041,36 @@ impl<C> Ui<C> {
                     },
                     None => ([0.0, 0.0], [self.win_w, self.win_h], Padding::none()),
                 };
-                let place_xy = match place {
-                    Place::Middle      => position::middle_of(target_dim, dim),
-                    Place::TopLeft     => position::top_left_of(target_dim, dim),
-                    Place::TopRight    => position::top_right_of(target_dim, dim),
-                    Place::BottomLeft  => position::bottom_left_of(target_dim, dim),
-                    Place::BottomRight => position::bottom_right_of(target_dim, dim),
-                    Place::MidTop      => position::mid_top_of(target_dim, dim),
-                    Place::MidBottom   => position::mid_bottom_of(target_dim, dim),
-                    Place::MidLeft     => position::mid_left_of(target_dim, dim),
-                    Place::MidRight    => position::mid_right_of(target_dim, dim),
-                };
-                let relative_xy = ::vecmath::vec2_add(place_xy, pad.offset_from(place));
+                let place_xy = position::place_within(place, target_dim, dim);
+                let offset = ::vecmath::vec2_add(pad.offset_from(place), margin.offset_from(place));
+                let relative_xy = ::vecmath::vec2_add(place_xy, offset);
                 ::vecmath::vec2_add(xy, relative_xy)
             },
 
+            Position::PlaceOn(place, ui_id) => {
+                use position;
+                let target = &self.widget_cache[ui_id];
+                let place_xy = position::place_within(place, target.dim, dim);
+                let offset = ::vecmath::vec2_add(target.padding.offset_from(place),
+                                                 margin.offset_from(place));
+                let relative_xy = ::vecmath::vec2_add(place_xy, offset);
+                ::vecmath::vec2_add(target.xy, relative_xy)
+            },
+
+        };
+        self.snap_to_pixels(xy, dim)
+    }
+
+    /// While pixel mode is enabled, shift the rectangle with the given centre and dimensions so
+    /// that its left and top edges lie on whole pixels of the window.
+    fn snap_to_pixels(&self, xy: Point, dim: Dimensions) -> Point {
+        match self.maybe_pixel_scale {
+            Some(_) => {
+                let left = (xy[0] - dim[0] / 2.0 + self.win_w / 2.0).round();
+                let top = (self.win_h / 2.0 - xy[1] - dim[1] / 2.0).round();
+                [left - self.win_w / 2.0 + dim[0] / 2.0, self.win_h / 2.0 - top - dim[1] / 2.0]
+            },
+            None => xy,
         }
     }
 
@@ -483,13 +2081,32 @@ impl<C> Ui<C> {
                 writeln!(::std::io::stderr(),
                         "Warning: Widget {:?} tried to capture the mouse, however it is \
                          already captured by {:?}.", ui_id, captured_ui_id).unwrap();
+                self.log_event(event_log::Event::MouseCaptureRefused(ui_id));
             },
             Some((Capturing::JustReleased, _)) => {
                 writeln!(::std::io::stderr(),
                         "Warning: Widget {:?} tried to capture the mouse, however it was \
                          already captured.", ui_id).unwrap();
+                self.log_event(event_log::Event::MouseCaptureRefused(ui_id));
+            },
+            None => {
+                self.maybe_captured_mouse = Some((Capturing::Captured(ui_id), self.mouse));
+                self.log_event(event_log::Event::MouseCaptured(ui_id));
             },
-            None => self.maybe_captured_mouse = Some((Capturing::Captured(ui_id), self.mouse)),
+        }
+    }
+
+    /// Capture the mouse for the given widget for the duration of a drag, so that the widget
+    /// retains exclusive delivery of the mouse until the drag ends, even once the cursor leaves
+    /// the widget's rectangle (or the window, where the backend continues to report the cursor).
+    ///
+    /// Widgets should call this every update with whether or not they were being dragged as of
+    /// their previous update and whether or not they are being dragged now.
+    pub fn mouse_dragged_by(&mut self, ui_id: UiId, was_dragging: bool, is_dragging: bool) {
+        match (was_dragging, is_dragging) {
+            (false, true) => self.mouse_captured_by(ui_id),
+            (true, false) => self.mouse_uncaptured_by(ui_id),
+            _ => (),
         }
     }
 
@@ -502,6 +2119,7 @@ impl<C> Ui<C> {
                          actually captured by {:?}.", ui_id, captured_ui_id).unwrap();
             } else {
                 self.maybe_captured_mouse = Some((Capturing::JustReleased, mouse));
+                self.log_event(event_log::Event::MouseUncaptured(ui_id));
             },
             Some((Capturing::JustReleased, _)) => {
                 writeln!(::std::io::stderr(),
@@ -523,13 +2141,18 @@ impl<C> Ui<C> {
                 writeln!(::std::io::stderr(),
                         "Warning: Widget {:?} tried to capture the keyboard, however it is \
                          already captured by {:?}.", ui_id, captured_ui_id).unwrap();
+                self.log_event(event_log::Event::KeyboardCaptureRefused(ui_id));
             },
             Some(Capturing::JustReleased) => {
                 writeln!(::std::io::stderr(),
                         "Warning: Widget {:?} tried to capture the keyboard, however it was \
                          already captured.", ui_id).unwrap();
+                self.log_event(event_log::Event::KeyboardCaptureRefused(ui_id));
+            },
+            None => {
+                self.maybe_captured_keyboard = Some(Capturing::Captured(ui_id));
+                self.log_event(event_log::Event::KeyboardCaptured(ui_id));
             },
-            None => self.maybe_captured_keyboard = Some(Capturing::Captured(ui_id)),
         }
     }
 
@@ -542,6 +2165,7 @@ impl<C> Ui<C> {
                          actually captured by {:?}.", ui_id, captured_ui_id).unwrap();
             } else {
                 self.maybe_captured_keyboard = Some(Capturing::JustReleased);
+                self.log_event(event_log::Event::KeyboardUncaptured(ui_id));
             },
             Some(Capturing::JustReleased) => {
                 writeln!(::std::io::stderr(),
@@ -556,96 +2180,398 @@ impl<C> Ui<C> {
         }
     }
 
+    /// Begin dragging the given payload from the widget with the given UiId. The optional `ghost`
+    /// Form will be drawn centred beneath the mouse cursor until the payload is dropped or the
+    /// mouse is released.
+    pub fn start_drag<T: Any>(&mut self, source: UiId, payload: T, maybe_ghost: Option<Form>) {
+        if let Some(ref drag) = self.maybe_drag {
+            writeln!(::std::io::stderr(),
+                    "Warning: Widget {:?} tried to start a drag, however widget {:?} is \
+                     already dragging.", source, drag.source).unwrap();
+            return;
+        }
+        self.log_event(event_log::Event::DragStarted(source));
+        self.maybe_drag = Some(Drag {
+            source: source,
+            payload: Box::new(payload),
+            payload_type: TypeId::of::<T>(),
+            maybe_ghost: maybe_ghost,
+            released: false,
+        });
+    }
+
+    /// Whether or not some payload is currently being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.maybe_drag.is_some()
+    }
+
+    /// The UiId of the widget from which the current drag began, if there is one.
+    pub fn drag_source(&self) -> Option<UiId> {
+        self.maybe_drag.as_ref().map(|drag| drag.source)
+    }
+
+    /// If a payload of type `T` is being dragged over the rectangle described by `xy` and `dim`,
+    /// return a reference to it. Drop targets may use this to highlight themselves.
+    pub fn drag_over<T: Any>(&self, xy: Point, dim: Dimensions) -> Option<&T> {
+        use utils::is_over_rect;
+        match self.maybe_drag {
+            Some(ref drag) if is_over_rect(xy, self.mouse.xy, dim) => drag.payload.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether or not a payload with the given `TypeId` is being dragged over the rectangle
+    /// described by `xy` and `dim`. This allows widgets to accept a payload type chosen by their
+    /// user without being generic over it.
+    pub fn is_drag_over(&self, xy: Point, dim: Dimensions, payload_type: TypeId) -> bool {
+        use utils::is_over_rect;
+        match self.maybe_drag {
+            Some(ref drag) => drag.payload_type == payload_type
+                && is_over_rect(xy, self.mouse.xy, dim),
+            None => false,
+        }
+    }
+
+    /// If a payload of type `T` was released over the rectangle described by `xy` and `dim`, take
+    /// it, ending the drag.
+    pub fn take_drop<T: Any>(&mut self, xy: Point, dim: Dimensions) -> Option<T> {
+        self.take_drop_of_type(xy, dim, TypeId::of::<T>())
+            .and_then(|payload| payload.downcast::<T>().ok())
+            .map(|payload| *payload)
+    }
+
+    /// If a payload with the given `TypeId` was released over the rectangle described by `xy`
+    /// and `dim`, take it, ending the drag.
+    pub fn take_drop_of_type(&mut self,
+                             xy: Point,
+                             dim: Dimensions,
+                             payload_type: TypeId) -> Option<Box<Any>> {
+        let is_dropped = match self.maybe_drag {
+            Some(ref drag) => drag.released && self.is_drag_over(xy, dim, payload_type),
+            None => false,
+        };
+        if !is_dropped { return None }
+        self.log_event(event_log::Event::Dropped);
+        self.maybe_drag.take().map(|drag| drag.payload)
+    }
+
+    /// The currently selected widgets, in the order in which they were selected. Widgets are
+    /// selected by clicking on a selectable Draggable (holding Shift to add to the selection) or
+    /// via a Marquee using `Marquee::ui_selection`.
+    pub fn selection(&self) -> &[UiId] {
+        &self.selection
+    }
+
+    /// Whether or not the widget with the given UiId is selected.
+    pub fn is_selected(&self, ui_id: UiId) -> bool {
+        self.selection.contains(&ui_id)
+    }
+
+    /// Replace the selection with the given widgets.
+    pub fn set_selection(&mut self, selection: &[UiId]) {
+        self.selection = selection.to_vec();
+    }
+
+    /// Add the widget with the given UiId to the selection.
+    pub fn select(&mut self, ui_id: UiId) {
+        if !self.selection.contains(&ui_id) {
+            self.selection.push(ui_id);
+        }
+    }
+
+    /// Remove the widget with the given UiId from the selection.
+    pub fn deselect(&mut self, ui_id: UiId) {
+        self.selection.retain(|&id| id != ui_id);
+    }
+
+    /// Select the widget with the given UiId if it is not selected, otherwise deselect it.
+    pub fn toggle_selected(&mut self, ui_id: UiId) {
+        if self.is_selected(ui_id) { self.deselect(ui_id) } else { self.select(ui_id) }
+    }
+
+    /// Deselect all widgets.
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+    }
+
+    /// Whether or not Delete or Backspace was pressed since the end of the last render cycle
+    /// while some widgets are selected and no widget has captured the keyboard. The application
+    /// should then remove the selected widgets' items and call `clear_selection`.
+    pub fn is_selection_deleted(&self) -> bool {
+        use input::keyboard::Key;
+        !self.selection.is_empty()
+            && self.maybe_captured_keyboard.is_none()
+            && self.keys_just_pressed.iter().any(|&key| key == Key::Delete || key == Key::Backspace)
+    }
+
+    /// Indicate that the selected widget with the given UiId has been dragged by the given
+    /// distance, so that the rest of the selection should follow it. See `group_move`.
+    pub fn move_selection(&mut self, leader: UiId, delta: Point) {
+        let id = self.maybe_group_move.map(|group_move| group_move.id).unwrap_or(0) + 1;
+        self.maybe_group_move = Some(GroupMove { id: id, leader: leader, delta: delta });
+    }
+
+    /// The most recent movement of the selection as a unique, increasing id (zero if the
+    /// selection has never moved) along with the distance by which the widget with the given
+    /// UiId should follow it. The distance is zero for the widget that was dragged.
+    ///
+    /// As a movement is reported until the next begins, widgets should remember the id of the
+    /// last movement that they saw and only follow those with a greater id (and only while they
+    /// are selected). A widget set before the dragged widget follows each movement one update
+    /// later.
+    pub fn group_move(&self, ui_id: UiId) -> (u64, Point) {
+        match self.maybe_group_move {
+            Some(group_move) if group_move.leader != ui_id => (group_move.id, group_move.delta),
+            Some(group_move) => (group_move.id, [0.0, 0.0]),
+            None => (0, [0.0, 0.0]),
+        }
+    }
+
     /// Draw the `Ui` in it's current state.
     /// - The order of drawing is as follows:
     ///     1. Canvas splits.
     ///     2. Widgets on Canvas splits.
     ///     3. Floating Canvasses.
     ///     4. Widgets on Floating Canvasses.
-    /// - Widgets are sorted by capturing and then render depth (depth first).
-    /// - Construct the elmesque `Renderer` for rendering the elm `Element`s.
-    /// - Render all widgets.
+    ///     5. The ghost of any payload being dragged.
+    /// - If widget removal is enabled, widgets that have gone unset for more than the removal
+    /// grace are removed (see `enable_widget_removal` and `Widget::on_remove`).
+    /// - The Forms of every widget are appended to a single display list, sorted by layer,
+    /// capturing and then render depth (depth first).
+    /// - Render the Forms of each batch of consecutive widgets that share a font and clipping
+    /// scope (see `with_clip`) as a single collage with a single elmesque `Renderer`.
+    /// - While pixel mode is enabled, snap every vertex to a whole pixel (see `set_pixel_scale`).
     pub fn draw<G>(&mut self, graphics: &mut G)
         where
             C: CharacterCache,
             G: Graphics<Texture = C::Texture>,
+    {
+        let start_ns = ::clock_ticks::precise_time_ns();
+        self.remove_unset_widgets();
+        match self.maybe_pixel_scale {
+            Some(_) => {
+                let win_dim = [self.win_w, self.win_h];
+                self.render(&mut render::PixelGrid::new(graphics, win_dim));
+            },
+            None => self.render(graphics),
+        }
+
+        if let Some(ref mut profiler) = self.maybe_profiler {
+            let now_ns = ::clock_ticks::precise_time_ns();
+            profiler.rendered(self.frame_number, now_ns - start_ns, now_ns);
+        }
+    }
+
+    /// Render the canvasses, widgets and any dragged ghost in the order described by `draw`.
+    fn render<G>(&mut self, graphics: &mut G)
+        where
+            C: CharacterCache,
+            G: Graphics<Texture = C::Texture>,
     {
         use elmesque::Renderer;
-        use std::cmp::Ordering;
+        use elmesque::form::collage;
 
         let Ui {
             ref mut canvas_cache,
             ref mut widget_cache,
+            ref mut display_list,
             ref win_w, ref win_h,
             ref mut character_cache,
+            ref mut fonts,
             ..
         } = *self;
 
-        // Collect references to the widgets so that we can sort them without changing cache order.
-        let mut widgets: Vec<_> = widget_cache.iter_mut()
-            .filter(|widget| widget.has_updated)
-            .collect();
-
-        for widget in widgets.iter_mut() {
-            widget.has_updated = false;
-        }
-
-        // Check for captured widgets and take them from the Vec (we want to draw them last).
-        let maybe_mouse = self.maybe_captured_mouse.map(|(capturing, _)| capturing);
-        let maybe_keyboard = self.maybe_captured_keyboard;
-        let (maybe_mouse_widget, maybe_keyboard_widget) = match (maybe_mouse, maybe_keyboard) {
-            (Some(Capturing::Captured(mouse_ui_id)), Some(Capturing::Captured(keyboard_ui_id))) => {
-                if mouse_ui_id == keyboard_ui_id {
-                    (Some(widgets.swap_remove(mouse_ui_id)), None)
-                } else if mouse_ui_id > keyboard_ui_id {
-                    let mouse_widget = widgets.swap_remove(mouse_ui_id);
-                    let keyboard_widget = widgets.swap_remove(keyboard_ui_id);
-                    (Some(mouse_widget), Some(keyboard_widget))
-                }
-                else {
-                    let keyboard_widget = widgets.swap_remove(keyboard_ui_id);
-                    let mouse_widget = widgets.swap_remove(mouse_ui_id);
-                    (Some(mouse_widget), Some(keyboard_widget))
-                }
-            },
-            (Some(Capturing::Captured(mouse_ui_id)), _) => {
-                (Some(widgets.swap_remove(mouse_ui_id)), None)
-            },
-            (_, Some(Capturing::Captured(keyboard_ui_id))) => {
-                (None, Some(widgets.swap_remove(keyboard_ui_id)))
-            },
-            (_, _) => (None, None),
+        // Append the Forms of the widgets updated since the last frame to the display list from
+        // back to front, drawing the widgets that capture the keyboard and mouse above all others.
+        let maybe_keyboard = match self.maybe_captured_keyboard {
+            Some(Capturing::Captured(ui_id)) => Some(ui_id),
+            _ => None,
         };
+        let maybe_mouse = match self.maybe_captured_mouse {
+            Some((Capturing::Captured(ui_id), _)) => Some(ui_id),
+            _ => None,
+        };
+        display_list.build(widget_cache.widgets(), maybe_keyboard, maybe_mouse);
 
-        // Sort the rest of the widgets by rendering depth.
-        widgets.sort_by(|a, b| if      a.depth < b.depth { Ordering::Greater }
-                               else if a.depth > b.depth { Ordering::Less }
-                               else                      { Ordering::Equal });
-
-        // Construct the elmesque Renderer for rendering the Elements.
-        let mut renderer = Renderer::new(*win_w, *win_h, graphics).character_cache(character_cache);
+        // Draw all Canvas Splits with the default font.
+        {
+            let mut chain = fonts.chain(character_cache, font::DEFAULT_FONT);
+            let mut renderer = Renderer::new(*win_w, *win_h, graphics).character_cache(&mut chain);
+            for canvas in canvas_cache.iter().filter(|canvas| canvas.has_updated) {
+                canvas.element.draw(&mut renderer);
+            }
+        }
 
-        // Chain our widgets with the captured widgets and take their Elements.
-        let elements = widgets.iter()
-            .chain(maybe_keyboard_widget.iter())
-            .chain(maybe_mouse_widget.iter())
-            .map(|widget| &widget.element);
+        // Draw the Forms of each batch as a single collage with a single elmesque Renderer that
+        // uses the batch's font and crops to the batch's clipping rectangle, if it has one.
+        for (batch, forms) in display_list.take_batches() {
+            let mut chain = fonts.chain(character_cache, batch.font);
+            let element = collage(*win_w as i32, *win_h as i32, forms);
+            match batch.maybe_clip {
+                Some((clip_xy, clip_dim)) => {
+                    let win_dim = [*win_w, *win_h];
+                    let mut clip = render::Clip::new(graphics, win_dim, clip_xy, clip_dim);
+                    let mut renderer = Renderer::new(*win_w, *win_h, &mut clip)
+                        .character_cache(&mut chain);
+                    element.draw(&mut renderer);
+                },
+                None => {
+                    let mut renderer = Renderer::new(*win_w, *win_h, graphics)
+                        .character_cache(&mut chain);
+                    element.draw(&mut renderer);
+                },
+            }
+        }
 
-        // Draw all Canvas Splits.
-        for canvas in canvas_cache.iter().filter(|canvas| canvas.has_updated) {
-            canvas.element.draw(&mut renderer);
+        for widget in widget_cache.widgets_mut().iter_mut() {
+            widget.has_updated = false;
         }
 
-        // Draw all Elements.
-        for element in elements {
-            element.draw(&mut renderer);
+        // Draw the ghost of any dragged payload above everything else.
+        if let Some(Drag { maybe_ghost: Some(ref ghost), .. }) = self.maybe_drag {
+            let mut chain = fonts.chain(character_cache, font::DEFAULT_FONT);
+            let mut renderer = Renderer::new(*win_w, *win_h, graphics).character_cache(&mut chain);
+            let ghost = ghost.clone().shift(self.mouse.xy[0], self.mouse.xy[1]);
+            collage(*win_w as i32, *win_h as i32, vec![ghost]).draw(&mut renderer);
         }
 
         // Indicate that the canvasses and widgets have now been drawn since the last time it was set.
         for canvas in canvas_cache.iter_mut() {
             canvas.has_updated = false;
         }
+    }
+
+    /// Draw the `Ui` in it's current state by delivering device-agnostic `Primitive`s to the
+    /// given `Backend`, rather than via some piston `Graphics` implementation. The order of drawing
+    /// is the same as for `Ui::draw`.
+    pub fn draw_primitives<B>(&mut self, backend: &mut B)
+        where
+            C: CharacterCache,
+            B: render::Backend<Texture = C::Texture>,
+    {
+        let win_dim = [self.win_w, self.win_h];
+        let mut primitives = render::Primitives::new(backend, win_dim);
+        self.draw(&mut primitives);
+    }
+
+    /// Draw the `Ui` in its current state into an RGBA image with the given dimensions in pixels
+    /// using a software rasteriser. This is useful for golden-image tests and for producing
+    /// screenshots for documentation.
+    ///
+    /// The `Ui` is scaled to fill the image. The result contains four 8-bit components for each
+    /// pixel, row by row from the top left. Only the pixels drawn by the `Ui` are filled, so any
+    /// background drawn by the application separately is not included.
+    pub fn draw_to_image(&mut self, width: u32, height: u32) -> Vec<u8>
+        where
+            C: CharacterCache,
+            C::Texture: render::Sample,
+    {
+        let mut raster = render::Raster::new(width, height);
+        {
+            let image_dim = [width as f64, height as f64];
+            let mut primitives = render::Primitives::new(&mut raster, image_dim);
+            self.draw(&mut primitives);
+        }
+        raster.into_rgba()
+    }
+
+}
+
+
+/// Whether or not the widget `a` is drawn above the widget `b`. Higher layers are drawn above lower
+/// ones, and within a layer, lesser depths are drawn above greater ones. Of widgets within the
+/// same layer and at the same depth, those set later are drawn above.
+fn is_drawn_above(a: &widget::Cached, b: &widget::Cached) -> bool {
+    match a.layer.cmp(&b.layer) {
+        Ordering::Equal => match b.depth.partial_cmp(&a.depth) {
+            Some(Ordering::Equal) | None => a.set_order > b.set_order,
+            Some(ordering) => ordering == Ordering::Greater,
+        },
+        ordering => ordering == Ordering::Greater,
+    }
+}
+
+/// The keyboard key on which the given (lowercase) letter or digit resides.
+fn key_for_char(ch: char) -> Option<input::keyboard::Key> {
+    use piston::input::keyboard::Key::*;
+    const LETTERS: [input::keyboard::Key; 26] = [
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    ];
+    const DIGITS: [input::keyboard::Key; 10] = [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9];
+    match ch {
+        'a'...'z' => Some(LETTERS[ch as usize - 'a' as usize]),
+        '0'...'9' => Some(DIGITS[ch as usize - '0' as usize]),
+        _ => None,
+    }
+}
+
+/// The intersection of two rectangles given by their centres and dimensions. Rectangles that
+/// don't overlap produce an empty rectangle.
+fn intersect_rects(a: (Point, Dimensions), b: (Point, Dimensions)) -> (Point, Dimensions) {
+    let ((a_xy, a_dim), (b_xy, b_dim)) = (a, b);
+    let left = (a_xy[0] - a_dim[0] / 2.0).max(b_xy[0] - b_dim[0] / 2.0);
+    let right = (a_xy[0] + a_dim[0] / 2.0).min(b_xy[0] + b_dim[0] / 2.0);
+    let bottom = (a_xy[1] - a_dim[1] / 2.0).max(b_xy[1] - b_dim[1] / 2.0);
+    let top = (a_xy[1] + a_dim[1] / 2.0).min(b_xy[1] + b_dim[1] / 2.0);
+    let (w, h) = ((right - left).max(0.0), (top - bottom).max(0.0));
+    ([left + w / 2.0, bottom + h / 2.0], [w, h])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use piston::input::keyboard::Key;
+    use position::{Depth, Layer};
+    use super::{is_drawn_above, key_for_char};
+    use widget;
+
+    fn cached(layer: Layer, depth: Depth, set_order: u64) -> widget::Cached {
+        let mut cached = widget::Cached::empty();
+        cached.layer = layer;
+        cached.depth = depth;
+        cached.set_order = set_order;
+        cached
+    }
+
+    #[test]
+    fn letters_and_digits_map_to_their_keys() {
+        assert_eq!(key_for_char('a'), Some(Key::A));
+        assert_eq!(key_for_char('z'), Some(Key::Z));
+        assert_eq!(key_for_char('0'), Some(Key::D0));
+        assert_eq!(key_for_char('9'), Some(Key::D9));
+    }
+
+    #[test]
+    fn other_characters_have_no_key() {
+        // Mnemonic characters are lowercased before their key is found.
+        assert_eq!(key_for_char('A'), None);
+        assert_eq!(key_for_char('&'), None);
+        assert_eq!(key_for_char('\u{e9}'), None);
+    }
+
+    #[test]
+    fn higher_layers_are_drawn_above_regardless_of_depth() {
+        let popup = cached(Layer::Popup, 10.0, 0);
+        let normal = cached(Layer::Normal, -10.0, 1);
+        assert!(is_drawn_above(&popup, &normal));
+        assert!(!is_drawn_above(&normal, &popup));
+    }
+
+    #[test]
+    fn lesser_depths_are_drawn_above_within_a_layer() {
+        let front = cached(Layer::Normal, -1.0, 0);
+        let back = cached(Layer::Normal, 1.0, 1);
+        assert!(is_drawn_above(&front, &back));
+        assert!(!is_drawn_above(&back, &front));
+    }
 
+    #[test]
+    fn ties_are_drawn_in_the_order_in_which_they_were_set() {
+        let first = cached(Layer::Normal, 0.0, 0);
+        let second = cached(Layer::Normal, 0.0, 1);
+        assert!(is_drawn_above(&second, &first));
+        assert!(!is_drawn_above(&first, &second));
+        assert!(!is_drawn_above(&first, &first));
     }
 
 }
@@ -0,0 +1,199 @@
+
+use num::NumCast;
+use theme::Theme;
+
+
+/// A unit of measurement with which a widget's value may be displayed.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum Unit {
+    /// Gain in decibels. The minimum of the range is displayed as `-inf dB`, as is common for
+    /// audio gain controls whose minimum represents silence.
+    Decibels,
+    /// A percentage, where the value is already given in the range `0.0` to `100.0`.
+    Percent,
+    /// A frequency in hertz. Frequencies of 1000 Hz or greater are displayed in kHz.
+    Hertz,
+    /// A duration in seconds. Durations of less than a second are displayed in ms.
+    Seconds,
+    /// Some custom suffix, displayed with the given number of decimal places.
+    Suffix(String, u8),
+}
+
+/// A linear conversion from the canonical unit in which a widget's value is given and reported
+/// (i.e. °C, mm or seconds) into a unit in which it is displayed and edited (i.e. °F, inches or
+/// beats), such that `display = canonical * scale + offset`.
+///
+/// Value widgets use a conversion either when given one directly via `.conversion`, or when given
+/// a quantity via `.quantity` for which the theme's `display_units` holds a conversion. The latter
+/// allows the display unit of every widget of some quantity to be switched at runtime.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Conversion {
+    /// The unit with which the converted value is displayed.
+    pub unit: Unit,
+    /// The factor by which the canonical value is multiplied. This must be positive so that the
+    /// order of the widget's range is preserved.
+    pub scale: f64,
+    /// The amount added to the canonical value once scaled.
+    pub offset: f64,
+}
+
+
+impl Unit {
+
+    /// The symbol used to display the unit (without any magnitude prefix).
+    pub fn symbol(&self) -> &str {
+        match *self {
+            Unit::Decibels => "dB",
+            Unit::Percent => "%",
+            Unit::Hertz => "Hz",
+            Unit::Seconds => "s",
+            Unit::Suffix(ref suffix, _) => suffix,
+        }
+    }
+
+    /// Format the given value with the unit and a precision suitable for its magnitude. `min` is
+    /// the minimum of the range within which the value lies.
+    pub fn format(&self, value: f64, min: f64) -> String {
+        match *self {
+            Unit::Decibels => {
+                if value <= min || value == ::std::f64::NEG_INFINITY {
+                    "-inf dB".to_string()
+                } else {
+                    format!("{:.1} dB", value)
+                }
+            },
+            Unit::Percent => format!("{:.0}%", value),
+            Unit::Hertz => {
+                if value.abs() >= 1000.0 { format!("{:.2} kHz", value / 1000.0) }
+                else if value.abs() >= 100.0 { format!("{:.0} Hz", value) }
+                else { format!("{:.1} Hz", value) }
+            },
+            Unit::Seconds => {
+                if value.abs() < 1.0 { format!("{:.0} ms", value * 1000.0) }
+                else { format!("{:.2} s", value) }
+            },
+            Unit::Suffix(ref suffix, precision) => {
+                format!("{:.*} {}", precision as usize, value, suffix)
+            },
+        }
+    }
+
+}
+
+impl Conversion {
+
+    /// Construct a conversion into the given unit.
+    pub fn new(unit: Unit, scale: f64, offset: f64) -> Conversion {
+        Conversion { unit: unit, scale: scale, offset: offset }
+    }
+
+    /// Display degrees Celsius as degrees Fahrenheit.
+    pub fn celsius_to_fahrenheit() -> Conversion {
+        Conversion::new(Unit::Suffix("°F".to_string(), 1), 9.0 / 5.0, 32.0)
+    }
+
+    /// Display millimetres as inches.
+    pub fn mm_to_inches() -> Conversion {
+        Conversion::new(Unit::Suffix("in".to_string(), 2), 1.0 / 25.4, 0.0)
+    }
+
+    /// Display seconds as beats at the given tempo in beats per minute.
+    pub fn seconds_to_beats(bpm: f64) -> Conversion {
+        Conversion::new(Unit::Suffix("beats".to_string(), 2), bpm / 60.0, 0.0)
+    }
+
+    /// Convert the given canonical value into the display unit.
+    pub fn to_display(&self, canonical: f64) -> f64 {
+        canonical * self.scale + self.offset
+    }
+
+    /// Convert the given value in the display unit back into the canonical unit.
+    pub fn from_display(&self, display: f64) -> f64 {
+        (display - self.offset) / self.scale
+    }
+
+}
+
+
+/// The conversion with which a widget displays its value, preferring the widget's own conversion
+/// over that which the theme holds for the widget's quantity.
+pub fn active_conversion(maybe_conversion: Option<&Conversion>,
+                         maybe_quantity: Option<&str>,
+                         theme: &Theme) -> Option<Conversion> {
+    maybe_conversion.cloned().or_else(|| {
+        maybe_quantity.and_then(|quantity| theme.display_units.get(quantity).cloned())
+    })
+}
+
+/// Convert the given canonical value into the display unit via the conversion, if there is one.
+pub fn to_display<T: NumCast + Copy>(maybe_conversion: Option<&Conversion>, value: T) -> T {
+    match maybe_conversion {
+        Some(conversion) => {
+            let value_f: f64 = NumCast::from(value).unwrap();
+            NumCast::from(conversion.to_display(value_f)).unwrap_or(value)
+        },
+        None => value,
+    }
+}
+
+/// Convert the given value in the display unit back into the canonical unit via the conversion,
+/// if there is one.
+pub fn from_display<T: NumCast + Copy>(maybe_conversion: Option<&Conversion>, value: T) -> T {
+    match maybe_conversion {
+        Some(conversion) => {
+            let value_f: f64 = NumCast::from(value).unwrap();
+            NumCast::from(conversion.from_display(value_f)).unwrap_or(value)
+        },
+        None => value,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Conversion, Unit, active_conversion, from_display, to_display};
+    use theme::Theme;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} is not close to {}", a, b);
+    }
+
+    #[test]
+    fn units_are_formatted_for_their_magnitude() {
+        assert_eq!(Unit::Decibels.format(-60.0, -60.0), "-inf dB");
+        assert_eq!(Unit::Decibels.format(-6.0, -60.0), "-6.0 dB");
+        assert_eq!(Unit::Hertz.format(1500.0, 20.0), "1.50 kHz");
+        assert_eq!(Unit::Seconds.format(0.25, 0.0), "250 ms");
+    }
+
+    #[test]
+    fn conversions_round_trip_through_the_display_unit() {
+        let fahrenheit = Conversion::celsius_to_fahrenheit();
+        assert_close(fahrenheit.to_display(100.0), 212.0);
+        assert_close(fahrenheit.from_display(32.0), 0.0);
+        let inches = Conversion::mm_to_inches();
+        assert_close(inches.to_display(25.4), 1.0);
+        assert_close(inches.from_display(inches.to_display(42.0)), 42.0);
+    }
+
+    #[test]
+    fn values_without_a_conversion_are_unchanged() {
+        assert_eq!(to_display(None, 7i32), 7);
+        assert_eq!(from_display(None, 0.5f64), 0.5);
+        let fahrenheit = Conversion::celsius_to_fahrenheit();
+        assert_eq!(to_display(Some(&fahrenheit), 100i32), 212);
+    }
+
+    #[test]
+    fn a_widget_conversion_is_preferred_over_the_theme() {
+        let mut theme = Theme::default();
+        theme.display_units.insert("temperature".to_string(), Conversion::celsius_to_fahrenheit());
+        let inches = Conversion::mm_to_inches();
+        assert_eq!(active_conversion(None, Some("temperature"), &theme),
+                   Some(Conversion::celsius_to_fahrenheit()));
+        assert_eq!(active_conversion(Some(&inches), Some("temperature"), &theme), Some(inches));
+        assert_eq!(active_conversion(None, Some("length"), &theme), None);
+        assert_eq!(active_conversion(None, None, &theme), None);
+    }
+
+}
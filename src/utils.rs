@@ -19,6 +19,12 @@ pub fn is_over_rect(rect_point: Point, mouse_point: Point, rect_dim: Dimensions)
     else { false }
 }
 
+/// Return whether or not two rectangles, each described by its centre and dimensions, overlap.
+pub fn rects_overlap(a_xy: Point, a_dim: Dimensions, b_xy: Point, b_dim: Dimensions) -> bool {
+    (a_xy[0] - b_xy[0]).abs() * 2.0 < a_dim[0] + b_dim[0]
+        && (a_xy[1] - b_xy[1]).abs() * 2.0 < a_dim[1] + b_dim[1]
+}
+
 /// Get value percentage between max and min.
 pub fn percentage<T: Float + NumCast>(value: T, min: T, max: T) -> f32 {
     let v: f32 = NumCast::from(value).unwrap();
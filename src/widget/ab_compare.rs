@@ -0,0 +1,506 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::{self, Enableable};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::FontSize;
+use mouse::Mouse;
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
+use std::any::Any;
+use std::fmt::Debug;
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+
+
+/// An A/B comparison of the values of one or more parameters, as is common within audio plugins.
+///
+/// The ABCompare holds two snapshots of the given value, A and B, the active one of which follows
+/// the value as it changes. Clicking the left button switches to the other snapshot, and its
+/// reaction is triggered with that snapshot's value, which the application should then apply to
+/// its parameters. Clicking the right button copies the active snapshot over the other (i.e.
+/// A→B).
+///
+/// The value may be a single parameter (i.e. that of a Slider or an XYPad) or a struct or tuple of
+/// every parameter to be compared, so that the application need not duplicate each of them.
+pub struct ABCompare<T, F> {
+    value: T,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the ABCompare, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// One of the two snapshots.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Snapshot {
+    A,
+    B,
+}
+
+/// The buttons of which the ABCompare is made up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Elem {
+    /// Switches to the other snapshot.
+    Switch,
+    /// Copies the active snapshot over the other.
+    Copy,
+}
+
+/// The current interaction with the ABCompare.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted(Elem),
+    Clicked(Elem),
+}
+
+/// The state of the ABCompare.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State<T> {
+    a: T,
+    b: T,
+    active: Snapshot,
+    interaction: Interaction,
+    enabled: bool,
+}
+
+
+impl Snapshot {
+    /// The other snapshot.
+    fn other(self) -> Snapshot {
+        match self {
+            Snapshot::A => Snapshot::B,
+            Snapshot::B => Snapshot::A,
+        }
+    }
+
+    /// The letter by which the snapshot is displayed.
+    fn letter(self) -> &'static str {
+        match self {
+            Snapshot::A => "A",
+            Snapshot::B => "B",
+        }
+    }
+}
+
+impl Interaction {
+    /// Alter the color of the given button depending on the interaction.
+    fn color(&self, elem: Elem, color: Color) -> Color {
+        match *self {
+            Interaction::Highlighted(e) if e == elem => color.highlighted(),
+            Interaction::Clicked(e) if e == elem => color.clicked(),
+            _ => color,
+        }
+    }
+}
+
+
+/// The button over which the mouse is, if any. The Switch button fills the left half of the
+/// ABCompare and the Copy button the right.
+fn is_over(mouse_xy: Point, dim: Dimensions) -> Option<Elem> {
+    use utils::is_over_rect;
+    if !is_over_rect([0.0, 0.0], mouse_xy, dim) { None }
+    else if mouse_xy[0] < 0.0 { Some(Elem::Switch) }
+    else { Some(Elem::Copy) }
+}
+
+/// Check the current interaction with the ABCompare.
+fn get_new_interaction(is_over_elem: Option<Elem>, prev: Interaction, mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (is_over_elem, prev, mouse.left.state) {
+        (Some(_),    Normal,         Down) => Normal,
+        (Some(elem), _,              Up)   => Highlighted(elem),
+        (Some(elem), Highlighted(_), Down) => Clicked(elem),
+        (_,          Clicked(elem),  Down) => Clicked(elem),
+        _                                  => Normal,
+    }
+}
+
+
+impl<T, F> ABCompare<T, F> {
+
+    /// Construct a new ABCompare following the current value of the compared parameters.
+    pub fn new(value: T) -> ABCompare<T, F> {
+        ABCompare {
+            value: value,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the reaction for the ABCompare. It is triggered with the value of the snapshot that is
+    /// switched to, which the application should apply to the compared parameters.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+}
+
+impl<T, F> Widget for ABCompare<T, F>
+    where
+        F: FnMut(T),
+        T: Any + Clone + Debug + PartialEq,
+{
+    type State = State<T>;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "ABCompare" }
+    fn init_state(&self) -> State<T> {
+        State {
+            a: self.value.clone(),
+            b: self.value.clone(),
+            active: Snapshot::A,
+            interaction: Interaction::Normal,
+            enabled: self.enabled,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the ABCompare.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State<T>>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State<T>>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+        let new_interaction = match self.enabled {
+            true => get_new_interaction(is_over(mouse.xy, dim), state.interaction, mouse),
+            false => Interaction::Normal,
+        };
+
+        // The active snapshot follows the value.
+        let (mut a, mut b) = match state.active {
+            Snapshot::A => (self.value.clone(), state.b.clone()),
+            Snapshot::B => (state.a.clone(), self.value.clone()),
+        };
+        let mut active = state.active;
+
+        // Switch or copy upon release of the respective button.
+        match (state.interaction, new_interaction) {
+            (Interaction::Clicked(Elem::Switch), Interaction::Highlighted(Elem::Switch)) => {
+                active = active.other();
+                let value = match active { Snapshot::A => a.clone(), Snapshot::B => b.clone() };
+                if let Some(ref mut react) = self.maybe_react { react(value) }
+            },
+            (Interaction::Clicked(Elem::Copy), Interaction::Highlighted(Elem::Copy)) => {
+                match active {
+                    Snapshot::A => b = a.clone(),
+                    Snapshot::B => a = b.clone(),
+                }
+            },
+            _ => (),
+        }
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.interaction != new_interaction
+            || state.active != active
+            || state.a != a
+            || state.b != b
+            || state.enabled != self.enabled;
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed {
+            Some(State {
+                a: a,
+                b: b,
+                active: active,
+                interaction: new_interaction,
+                enabled: self.enabled,
+            })
+        } else {
+            None
+        };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given ABCompare State.
+    fn draw<C>(new_state: &widget::State<State<T>>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{rect, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let frame = style.frame(&ui.theme);
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let frame_color = enable::color(style.frame_color(&ui.theme), state.enabled, &ui.theme);
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+
+        // The two buttons share the inner area, separated by the width of the frame.
+        let button_w = ((inner_dim[0] - frame) / 2.0).max(0.0);
+        let button_dim = [button_w, inner_dim[1]];
+        let switch_x = inner_offset[0] - inner_dim[0] / 2.0 + button_w / 2.0;
+        let copy_x = inner_offset[0] + inner_dim[0] / 2.0 - button_w / 2.0;
+
+        // The Switch button displays the active snapshot and the Copy button its direction.
+        let color = enable::color(style.color(&ui.theme), state.enabled, &ui.theme);
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let size = style.label_font_size(&ui.theme);
+        let copy_label = format!("{}→{}", state.active.letter(), state.active.other().letter());
+        let buttons = [
+            (Elem::Switch, switch_x, state.active.letter().to_string()),
+            (Elem::Copy, copy_x, copy_label),
+        ];
+        let mut forms = vec![frame_form];
+        for &(elem, x, ref button_label) in buttons.iter() {
+            let button_color = state.interaction.color(elem, color);
+            forms.push(rect(button_dim[0], button_dim[1]).filled(button_color)
+                           .shift(x, inner_offset[1]));
+            forms.push(text(Text::from_string(button_label.clone())
+                                .color(label_color)
+                                .height(size as f64))
+                           .shift(x.floor(), inner_offset[1].floor()));
+        }
+
+        // Shift the Forms into position and collect them for drawing.
+        let forms = forms.into_iter().map(|form| form.shift(xy[0].floor(), xy[1].floor()));
+        forms.collect()
+    }
+
+    /// Describe the ABCompare for assistive technologies.
+    fn accessibility(state: &State<T>) -> Accessibility {
+        Accessibility::new(Role::Toggle)
+            .label(Some("A/B comparison"))
+            .value(state.active.letter())
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_ab_compare.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_ab_compare.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [96.0, 24.0];
+        let width = self.maybe_width.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_ab_compare.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<T, F> Enableable for ABCompare<T, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<T, F> Colorable for ABCompare<T, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<T, F> Paletted for ABCompare<T, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<T, F> Fontable for ABCompare<T, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<T, F> Frameable for ABCompare<T, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<T, F> position::Positionable for ABCompare<T, F> {
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        ABCompare { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        ABCompare { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+}
+
+impl<T, F> position::Sizeable for ABCompare<T, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -1,44 +1,118 @@
 
-use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use accessibility::{Accessibility, Role};
+use color::{black, Color, Colorable};
+use corner::{CornerRadii, Roundable};
+use gradient::{self, Gradient, Gradientable};
+use elmesque::form::Form;
+use enable::{self, Enableable};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::character::CharacterCache;
-use label::{FontSize, Labelable};
-use mouse::Mouse;
-use position::{Depth, Dimensions, HorizontalAlign, Position, Positionable, VerticalAlign};
+use label::{self, FontSize, Labelable};
+use mouse::{Cursor as MouseCursor, Mouse};
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Place, Position,
+               Positionable, VerticalAlign};
+use std::any::{Any, TypeId};
 use theme::Theme;
 use ui::{UiId, Ui};
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// A pressable button widget whose reaction is triggered upon release.
+///
+/// If the label marks an accelerator with an `&` (i.e. `"&Save"`), pressing Alt and the marked
+/// letter will also trigger the reaction.
+///
+/// Buttons for destructive actions may require their reaction to be confirmed via `confirm`.
+///
+/// A Button may also act as a drop target for payloads dragged from other widgets (see
+/// `drop_target`).
 pub struct Button<'a, F> {
     pos: Position,
-    dim: Dimensions,
+    margin: Margin,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
     depth: Depth,
     maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
     maybe_react: Option<F>,
+    maybe_confirm: Option<ConfirmMode>,
+    confirm_label: &'a str,
+    maybe_drop_target: Option<DropTarget<'a>>,
     style: Style,
     enabled: bool,
 }
 
+/// The type of payload accepted by a Button that is a drop target, along with the function to be
+/// called with each payload dropped on it.
+struct DropTarget<'a> {
+    payload_type: TypeId,
+    on_drop: Box<FnMut(Box<Any>) + 'a>,
+}
+
 /// Styling for the Button, necessary for constructing its renderable Element.
 #[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_corner_radii: Option<CornerRadii>,
+    pub maybe_gradient: Option<Gradient>,
     pub maybe_label_color: Option<Color>,
     pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_padding: Option<Padding>,
+    pub maybe_label_position: Option<Place>,
+    pub maybe_auto_size: Option<bool>,
+    pub maybe_font: Option<FontId>,
 }
 
 /// Represents the state of the Button widget.
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
     interaction: Interaction,
+    /// The current color of the pressable area, animated between interactions.
+    color: Color,
+    /// Whether or not the label's accelerator should be underlined.
+    show_mnemonic: bool,
+    /// The progress of the confirmation of the reaction, if the Button requires one.
+    confirmation: Confirmation,
+    /// Whether or not the Button accepts user input.
+    enabled: bool,
+}
+
+/// How the reaction of a Button must be confirmed, i.e. for destructive actions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ConfirmMode {
+    /// The Button must be held down for the given number of seconds, after which the reaction is
+    /// triggered without waiting for the release. A progress arc fills while it is held.
+    Hold(f64),
+    /// The Button must be clicked twice, the first click replacing the label with the confirm
+    /// label (see `Button::confirm_label`). Moving the mouse away from the Button cancels it.
+    Twice,
+}
+
+/// The progress of the confirmation of a Button's reaction.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Confirmation {
+    /// No confirmation is in progress.
+    Idle,
+    /// The Button has been held since the given time (in nanoseconds), completing the given
+    /// fraction of the hold.
+    Holding(u64, f64),
+    /// The hold completed and the reaction was triggered, awaiting the release.
+    Held,
+    /// The Button was clicked once and awaits its second click.
+    Armed,
 }
 
 /// Represents an interaction with the Button widget.
@@ -50,10 +124,10 @@ pub enum Interaction {
 }
 
 
-impl State {
-    /// Alter the widget color depending on the state.
+impl Interaction {
+    /// Alter the widget color depending on the interaction.
     fn color(&self, color: Color) -> Color {
-        match self.interaction {
+        match *self {
             Interaction::Normal => color,
             Interaction::Highlighted => color.highlighted(),
             Interaction::Clicked => color.clicked(),
@@ -66,7 +140,7 @@ impl State {
 fn get_new_interaction(is_over: bool, prev: Interaction, mouse: Mouse) -> Interaction {
     use mouse::ButtonState::{Down, Up};
     use self::Interaction::{Normal, Highlighted, Clicked};
-    match (is_over, prev, mouse.left) {
+    match (is_over, prev, mouse.left.state) {
         (true,  Normal,  Down) => Normal,
         (true,  _,       Down) => Clicked,
         (true,  _,       Up)   => Highlighted,
@@ -81,13 +155,17 @@ impl<'a, F> Button<'a, F> {
     /// Create a button context to be built upon.
     pub fn new() -> Button<'a, F> {
         Button {
-            dim: [64.0, 64.0],
             pos: Position::default(),
+            margin: Margin::none(),
             maybe_h_align: None,
             maybe_v_align: None,
             depth: 0.0,
             maybe_react: None,
             maybe_label: None,
+            maybe_label_spans: None,
+            maybe_confirm: None,
+            confirm_label: "Click again to confirm",
+            maybe_drop_target: None,
             style: Style::new(),
             enabled: true,
         }
@@ -99,9 +177,50 @@ impl<'a, F> Button<'a, F> {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
+    /// Require the reaction to be confirmed, either by holding the Button or by clicking it twice.
+    /// Label accelerators are ignored by Buttons that must be held.
+    pub fn confirm(mut self, mode: ConfirmMode) -> Self {
+        self.maybe_confirm = Some(mode);
+        self
+    }
+
+    /// Set the label shown while a Button with `ConfirmMode::Twice` awaits its second click.
+    pub fn confirm_label(mut self, text: &'a str) -> Self {
+        self.confirm_label = text;
+        self
+    }
+
+    /// Set the Place of the label within the widget.
+    #[inline]
+    pub fn label_position(mut self, place: Place) -> Self {
+        self.style.maybe_label_position = Some(place);
+        self
+    }
+
+    /// Size the Button to fit its label (plus its padding) rather than using its default
+    /// dimensions. A width or height that is set explicitly is still used.
+    #[inline]
+    pub fn auto_size(mut self) -> Self {
+        self.style.maybe_auto_size = Some(true);
+        self
+    }
+
+    /// Accept payloads of type `T` dragged from other widgets (see `Ui::start_drag`). The Button
+    /// is highlighted while such a payload is dragged over it, and `on_drop` is called with each
+    /// payload released over it. Disabled Buttons accept nothing.
+    pub fn drop_target<T, D>(mut self, mut on_drop: D) -> Self
+        where
+            T: Any,
+            D: FnMut(T) + 'a,
+    {
+        self.maybe_drop_target = Some(DropTarget {
+            payload_type: TypeId::of::<T>(),
+            on_drop: Box::new(move |payload: Box<Any>| {
+                if let Ok(payload) = payload.downcast::<T>() {
+                    on_drop(*payload);
+                }
+            }),
+        });
         self
     }
 
@@ -116,14 +235,23 @@ impl<'a, F> Widget for Button<'a, F>
     type Style = Style;
     fn unique_kind(&self) -> &'static str { "Button" }
     fn init_state(&self) -> State {
-        State { maybe_label: None, interaction: Interaction::Normal }
+        State {
+            maybe_label: None,
+            maybe_label_spans: None,
+            interaction: Interaction::Normal,
+            color: black(),
+            show_mnemonic: false,
+            confirmation: Confirmation::Idle,
+            enabled: self.enabled,
+        }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the Button.
     fn update<C>(mut self,
                  prev_state: &widget::State<State>,
-                 _style: &Style,
+                 style: &Style,
                  ui_id: UiId,
                  ui: &mut Ui<C>) -> widget::State<Option<State>>
         where
@@ -131,10 +259,19 @@ impl<'a, F> Widget for Button<'a, F>
     {
         use utils::is_over_rect;
         let widget::State { ref state, .. } = *prev_state;
-        let dim = self.dim;
+        let dim = if style.auto_size(&ui.theme) {
+            let size = style.label_font_size(&ui.theme);
+            let padding = style.padding(&ui.theme);
+            let maybe_spans = self.maybe_label_spans.as_ref();
+            let fit = label::fit_dim(ui, self.maybe_label, maybe_spans, size, padding);
+            [style.maybe_width.unwrap_or(fit[0]), style.maybe_height.unwrap_or(fit[1])]
+        } else {
+            style.dim(&ui.theme)
+        };
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
+        let padding = style.padding(&ui.theme);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, padding, h_align, v_align);
         let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
         let is_over = is_over_rect([0.0, 0.0], mouse.xy, dim);
         let new_interaction =
@@ -145,23 +282,96 @@ impl<'a, F> Widget for Button<'a, F>
                 Interaction::Normal
             };
 
-        // If the mouse was released over button, react.
-        if let (true, Interaction::Clicked, Interaction::Highlighted) =
-            (is_over, state.interaction, new_interaction) {
+        if self.enabled && is_over {
+            ui.set_mouse_cursor(ui_id, MouseCursor::Hand);
+        }
+
+        // The Button is pressed when the mouse is released over it or via the label's accelerator.
+        let is_released_over = match (is_over, state.interaction, new_interaction) {
+            (true, Interaction::Clicked, Interaction::Highlighted) => true,
+            _ => false,
+        };
+        let maybe_mnemonic = self.maybe_label.and_then(label::mnemonic_char);
+        let show_mnemonic = self.enabled && maybe_mnemonic.is_some() && ui.is_alt_down();
+        let is_mnemonic_pressed =
+            self.enabled && maybe_mnemonic.map_or(false, |ch| ui.is_mnemonic_pressed(ui_id, ch));
+        let is_pressed = is_released_over || is_mnemonic_pressed;
+
+        // React to a press, or to the completion of its confirmation if one is required.
+        let (should_react, confirmation) = match self.maybe_confirm {
+            None => (is_pressed, Confirmation::Idle),
+            Some(ConfirmMode::Hold(secs)) => match (new_interaction, state.confirmation) {
+                (Interaction::Clicked, Confirmation::Held) => (false, Confirmation::Held),
+                (Interaction::Clicked, Confirmation::Holding(start_ns, _)) => {
+                    let elapsed_ns = ui.time_ns().saturating_sub(start_ns);
+                    let progress = if secs > 0.0 { elapsed_ns as f64 / (secs * 1.0e9) }
+                                   else { 1.0 };
+                    if progress >= 1.0 { (true, Confirmation::Held) }
+                    else { (false, Confirmation::Holding(start_ns, progress)) }
+                },
+                (Interaction::Clicked, _) =>
+                    (false, Confirmation::Holding(ui.time_ns(), 0.0)),
+                _ => (false, Confirmation::Idle),
+            },
+            Some(ConfirmMode::Twice) => match (is_pressed, state.confirmation) {
+                (true, Confirmation::Armed) => (true, Confirmation::Idle),
+                (true, _) => (false, Confirmation::Armed),
+                (false, Confirmation::Armed) if new_interaction == Interaction::Normal
+                                             && state.interaction != Interaction::Normal =>
+                    (false, Confirmation::Idle),
+                (false, confirmation) => (false, confirmation),
+            },
+        };
+        if should_react {
             if let Some(ref mut react) = self.maybe_react { react() }
         }
 
+        // Show the confirm label while awaiting the second click.
+        let (maybe_label, maybe_label_spans) = match confirmation {
+            Confirmation::Armed => (Some(self.confirm_label), None),
+            _ => (self.maybe_label, self.maybe_label_spans.clone()),
+        };
+
+        // Take any accepted payload dropped on the Button, which is highlighted while one is
+        // dragged over it.
+        let is_drop_hovered = match (self.enabled, self.maybe_drop_target.as_mut()) {
+            (true, Some(target)) => {
+                if let Some(payload) = ui.take_drop_of_type(xy, dim, target.payload_type) {
+                    (target.on_drop)(payload);
+                }
+                ui.is_drag_over(xy, dim, target.payload_type)
+            },
+            _ => false,
+        };
+
+        // Fade the color towards that of the new interaction.
+        let color_interaction = if is_drop_hovered { Interaction::Highlighted }
+                                else { new_interaction };
+        let target_color = color_interaction.color(style.color(&ui.theme));
+        let target_color = enable::color(target_color, self.enabled, &ui.theme);
+        let color = ui.animate_with_theme(ui_id, "color", target_color);
+
         // A function for constructing a new state.
         let new_state = || {
             State {
-                maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label: maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: maybe_label_spans.clone(),
                 interaction: new_interaction,
+                color: color,
+                show_mnemonic: show_mnemonic,
+                confirmation: confirmation,
+                enabled: self.enabled,
             }
         };
 
         // Check whether or not the state has changed since the previous update.
         let state_has_changed = state.interaction != new_interaction
-            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label;
+            || state.color != color
+            || state.show_mnemonic != show_mnemonic
+            || state.confirmation != confirmation
+            || state.enabled != self.enabled
+            || state.maybe_label.as_ref().map(|string| &string[..]) != maybe_label
+            || state.maybe_label_spans != maybe_label_spans;
 
         // Construct the new state if there was a change.
         let maybe_new_state = if state_has_changed { Some(new_state()) } else { None };
@@ -169,42 +379,98 @@ impl<'a, F> Widget for Button<'a, F>
         widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
     }
 
-    /// Construct an Element from the given Button State.
-    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given Button State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{collage, rect, text};
+        use elmesque::form::{polygon, text};
         let widget::State { ref state, dim, xy, .. } = *new_state;
-        let theme = &ui.theme;
 
         // Retrieve the styling for the Element..
-        let color = state.color(style.color(theme));
-        let frame = style.frame(theme);
-        let frame_color = style.frame_color(theme);
+        let color = state.color;
+        let frame = style.frame(&ui.theme);
+        let frame_color = enable::color(style.frame_color(&ui.theme), state.enabled, &ui.theme);
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let size = style.label_font_size(&ui.theme);
 
         // Construct the frame and inner rectangle forms.
-        let frame_form = rect(dim[0], dim[1]).filled(frame_color);
-        let (inner_w, inner_h) = (dim[0] - frame * 2.0, dim[1] - frame * 2.0);
-        let pressable_form = rect(inner_w, inner_h).filled(color);
+        let radii = style.corner_radii(&ui.theme);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let maybe_gradient = style.gradient(&ui.theme);
+        let pressable_form = gradient::filled_rect(inner_dim, radii.inset(frame), color,
+                                                   maybe_gradient)
+            .shift(inner_offset[0], inner_offset[1]);
+
+        // Fill a wedge of a disc as the hold confirming the reaction progresses.
+        let maybe_progress_form = match state.confirmation {
+            Confirmation::Holding(_, progress) if progress > 0.0 => {
+                use std::f64::consts::PI;
+                const SEGMENTS: f64 = 32.0;
+                let radius = inner_dim[0].min(inner_dim[1]) * 0.4;
+                let end = progress.min(1.0) * 2.0 * PI;
+                let n = (SEGMENTS * progress.min(1.0)).ceil() as usize;
+                let arc = (0..n + 1).map(|i| {
+                    let angle = end * i as f64 / n as f64;
+                    (angle.sin() * radius, angle.cos() * radius)
+                });
+                let points = Some((0.0, 0.0)).into_iter().chain(arc).collect();
+                Some(polygon(points).filled(label_color.alpha(0.35))
+                         .shift(inner_offset[0], inner_offset[1]))
+            },
+            _ => None,
+        };
 
-        // Construct the label's Form.
-        let maybe_label_form = state.maybe_label.as_ref().map(|label_text| {
+        // Construct the label's Forms.
+        let mut label_forms = Vec::new();
+        let mut label_dim = [0.0, size as f64];
+        if let Some(ref spans) = state.maybe_label_spans {
+            let (spans_dim, spans_forms) = spans.forms(ui, label_color, size);
+            label_dim = spans_dim;
+            label_forms = spans_forms;
+        } else if let Some(ref label_text) = state.maybe_label {
             use elmesque::text::Text;
-            let label_color = style.label_color(theme);
-            let size = style.label_font_size(theme);
-            text(Text::from_string(label_text.to_string()).color(label_color).height(size as f64))
-                .shift(xy[0].floor(), xy[1].floor())
-        });
+            let (label_text, maybe_mnemonic_idx) = label::strip_mnemonic(label_text);
+            if let (true, Some(idx)) = (state.show_mnemonic, maybe_mnemonic_idx) {
+                let underline = label::mnemonic_underline(ui, &label_text, idx, size, label_color);
+                label_forms.push(underline);
+            }
+            label_dim[0] = label::width(ui, size, &label_text);
+            label_forms.push(text(Text::from_string(label_text)
+                                      .color(label_color)
+                                      .height(size as f64)));
+        }
+
+        // Shift the label into its place within the padded area of the button.
+        if !label_forms.is_empty() {
+            let padding = style.padding(&ui.theme);
+            let place = style.label_position(&ui.theme);
+            let padded_dim = padding.inner_dim(dim);
+            let l_pos = ::vecmath::vec2_add(position::place_within(place, padded_dim, label_dim),
+                                            padding.inner_offset());
+            label_forms = label_forms.into_iter()
+                .map(|form| form.shift(l_pos[0].floor(), l_pos[1].floor()))
+                .collect();
+        }
 
         // Construct the button's Form.
         let form_chain = Some(frame_form).into_iter()
             .chain(Some(pressable_form).into_iter())
+            .chain(maybe_progress_form.into_iter())
             .map(|form| form.shift(xy[0], xy[1]))
-            .chain(maybe_label_form.into_iter());
+            .chain(label_forms.into_iter().map(|form| form.shift(xy[0].floor(), xy[1].floor())));
 
-        // Turn the form into a renderable Element.
-        collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+        // Turn the form for drawing.
+        form_chain.collect()
+    }
+
+    /// Describe the Button for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::Button).label(maybe_label.map(|l| &l[..]))
     }
 
 }
@@ -216,18 +482,32 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
+            maybe_gradient: None,
             maybe_label_color: None,
             maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_padding: None,
+            maybe_label_position: None,
+            maybe_auto_size: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_button.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_button.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -244,6 +524,41 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_button.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of an Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
+    /// Get the Gradient with which the Element is filled, if there is one.
+    pub fn gradient(&self, theme: &Theme) -> Option<Gradient> {
+        self.maybe_gradient
+            .or(theme.maybe_button.as_ref().and_then(|style| style.maybe_gradient))
+            .or(theme.maybe_gradient)
+    }
+
     /// Get the label Color for an Element.
     pub fn label_color(&self, theme: &Theme) -> Color {
         self.maybe_label_color.or(theme.maybe_button.as_ref().map(|style| {
@@ -258,9 +573,57 @@ impl Style {
         })).unwrap_or(theme.font_size_medium)
     }
 
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [64.0, 64.0];
+        let width = self.maybe_width.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the padding between the edge of the Element and its text.
+    pub fn padding(&self, theme: &Theme) -> Padding {
+        const DEFAULT_PADDING: f64 = 10.0;
+        self.maybe_padding.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_padding.unwrap_or(Padding::uniform(DEFAULT_PADDING))
+        })).unwrap_or(Padding::uniform(DEFAULT_PADDING))
+    }
+
+    /// Whether or not the Element is sized to fit its label.
+    pub fn auto_size(&self, theme: &Theme) -> bool {
+        self.maybe_auto_size.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_auto_size.unwrap_or(false)
+        })).unwrap_or(false)
+    }
+
+    /// Get the Place of the label within the Element.
+    pub fn label_position(&self, theme: &Theme) -> Place {
+        self.maybe_label_position.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_label_position.unwrap_or(Place::Middle)
+        })).unwrap_or(Place::Middle)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_button.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
 }
 
 
+impl<'a, F> Enableable for Button<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
 impl<'a, F> Colorable for Button<'a, F> {
     fn color(mut self, color: Color) -> Self {
         self.style.maybe_color = Some(color);
@@ -268,6 +631,20 @@ impl<'a, F> Colorable for Button<'a, F> {
     }
 }
 
+impl<'a, F> Paletted for Button<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for Button<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, F> Frameable for Button<'a, F> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -277,6 +654,32 @@ impl<'a, F> Frameable for Button<'a, F> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Roundable for Button<'a, F> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
+}
+
+impl<'a, F> Gradientable for Button<'a, F> {
+    fn gradient(mut self, gradient: Gradient) -> Self {
+        self.style.maybe_gradient = Some(gradient);
+        self
+    }
 }
 
 impl<'a, F> Labelable<'a> for Button<'a, F> {
@@ -285,6 +688,11 @@ impl<'a, F> Labelable<'a> for Button<'a, F> {
         self
     }
 
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
     fn label_color(mut self, color: Color) -> Self {
         self.style.maybe_label_color = Some(color);
         self
@@ -309,18 +717,28 @@ impl<'a, F> Positionable for Button<'a, F> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         Button { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl<'a, F> ::position::Sizeable for Button<'a, F> {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        Button { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        Button { dim: [w, h], ..self }
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.style.maybe_padding = Some(padding.into());
+        self
     }
 }
 
@@ -0,0 +1,593 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize, Labelable};
+use mouse::Mouse;
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
+
+
+/// The centre and dimensions of the area within which a CollapsibleArea's content should be
+/// placed.
+pub type Content = (Point, Dimensions);
+
+/// A section with a clickable header that shows or hides the content beneath it, i.e. a single
+/// section of an accordion.
+///
+/// Whether or not the section is expanded is owned by the CollapsibleArea and persists within its
+/// `State`. The area's height animates between that of the header alone and that of the header
+/// plus its content, so that widgets positioned `down` from the CollapsibleArea follow it. The
+/// reaction is called every update with the padded area beneath the header while the section is
+/// expanded and `None` while it is collapsed, so that the application only sets the content's
+/// widgets while they are visible.
+pub struct CollapsibleArea<'a, F> {
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    initially_expanded: bool,
+    maybe_react: Option<F>,
+    maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
+    style: Style,
+    enabled: bool,
+    persistent: bool,
+}
+
+/// Styling for the CollapsibleArea, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_header_height: Option<f64>,
+    pub maybe_content_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The way in which the CollapsibleArea's header is being interacted with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted,
+    Clicked,
+}
+
+/// The state of the CollapsibleArea.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    is_expanded: bool,
+    /// The visible share of the content's height, animated as the section expands and collapses.
+    open_perc: f64,
+    interaction: Interaction,
+    maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+}
+
+
+impl Interaction {
+    /// Alter the widget color depending on the interaction.
+    fn color(&self, color: Color) -> Color {
+        match *self {
+            Interaction::Normal => color,
+            Interaction::Highlighted => color.highlighted(),
+            Interaction::Clicked => color.clicked(),
+        }
+    }
+}
+
+
+/// Check the current interaction with the header.
+fn get_new_interaction(is_over: bool,
+                       prev: Interaction,
+                       mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (is_over, prev, mouse.left.state) {
+        (true,  Normal,  Down) => Normal,
+        (true,  _,       Down) => Clicked,
+        (true,  _,       Up)   => Highlighted,
+        (false, Clicked, Down) => Clicked,
+        _                      => Normal,
+    }
+}
+
+
+/// The centre of the header, given the centre and dimensions of the whole area.
+fn header_xy(xy: Point, dim: Dimensions, header_h: f64) -> Point {
+    [xy[0], xy[1] + dim[1] / 2.0 - header_h / 2.0]
+}
+
+
+impl<'a, F> CollapsibleArea<'a, F> {
+
+    /// Construct a new, collapsed CollapsibleArea.
+    pub fn new() -> CollapsibleArea<'a, F> {
+        CollapsibleArea {
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            initially_expanded: false,
+            maybe_react: None,
+            maybe_label: None,
+            maybe_label_spans: None,
+            style: Style::new(),
+            enabled: true,
+            persistent: false,
+        }
+    }
+
+    /// Whether or not the section is expanded when the CollapsibleArea is first set.
+    pub fn initially_expanded(mut self, expanded: bool) -> Self {
+        self.initially_expanded = expanded;
+        self
+    }
+
+    /// Set the height of the clickable header.
+    pub fn header_height(mut self, height: f64) -> Self {
+        self.style.maybe_header_height = Some(height);
+        self
+    }
+
+    /// Set the height of the content beneath the header while the section is expanded.
+    pub fn content_height(mut self, height: f64) -> Self {
+        self.style.maybe_content_height = Some(height);
+        self
+    }
+
+    /// Set the reaction for the CollapsibleArea. It is called every update with the area within
+    /// which the content should be placed, or `None` while the section is collapsed.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the depth of the CollapsibleArea.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Record whether or not the section is expanded within the `Ui`'s widget states so that it
+    /// may be saved and restored in a later session.
+    pub fn persistent(mut self) -> Self {
+        self.persistent = true;
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for CollapsibleArea<'a, F>
+    where
+        F: FnMut(Option<Content>),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "CollapsibleArea" }
+    fn init_state(&self) -> State {
+        State {
+            is_expanded: self.initially_expanded,
+            open_perc: if self.initially_expanded { 1.0 } else { 0.0 },
+            interaction: Interaction::Normal,
+            maybe_label: None,
+            maybe_label_spans: None,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the CollapsibleArea.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use utils::is_over_rect;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let width = style.width(&ui.theme);
+        let header_h = style.header_height(&ui.theme);
+        let content_h = style.content_height(&ui.theme);
+
+        // The header is clicked against the area's position during the previous update, as the
+        // area's height (and so its position) depends upon whether or not it is expanded.
+        let prev_dim = [width, header_h + content_h * state.open_perc];
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let prev_xy = ui.get_xy_with_margin(self.pos, prev_dim, self.margin, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id);
+        let is_over = is_over_rect(header_xy(prev_xy, prev_dim, header_h), mouse.xy,
+                                   [width, header_h]);
+        let new_interaction = if self.enabled {
+            get_new_interaction(is_over, state.interaction, mouse)
+        } else {
+            Interaction::Normal
+        };
+        let is_expanded = match (is_over, state.interaction, new_interaction) {
+            (true, Interaction::Clicked, Interaction::Highlighted) => !state.is_expanded,
+            _ => state.is_expanded,
+        };
+
+        // Adopt the expansion restored from a previous session and record it for the next.
+        let is_expanded = match self.persistent {
+            true => ui.take_restored_widget_state(ui_id, "CollapsibleArea").unwrap_or(is_expanded),
+            false => is_expanded,
+        };
+        if self.persistent {
+            ui.persist_widget_state(ui_id, "CollapsibleArea", &is_expanded);
+        }
+
+        // Grow and shrink the content smoothly rather than snapping it open or closed.
+        let open_target = if is_expanded { 1.0 } else { 0.0 };
+        let open_perc = ui.animate_with_theme(ui_id, "open_perc", open_target);
+        let dim = [width, header_h + content_h * open_perc];
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+
+        if let Some(ref mut react) = self.maybe_react {
+            let maybe_content = if is_expanded {
+                let visible_h = dim[1] - header_h;
+                let content_dim = self.padding.inner_dim([width, visible_h]);
+                let content_xy = [xy[0], xy[1] - dim[1] / 2.0 + visible_h / 2.0];
+                let content_xy = ::vecmath::vec2_add(content_xy, self.padding.inner_offset());
+                Some((content_xy, content_dim))
+            } else {
+                None
+            };
+            react(maybe_content);
+        }
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.interaction != new_interaction
+            || state.is_expanded != is_expanded
+            || state.open_perc != open_perc
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed {
+            Some(State {
+                is_expanded: is_expanded,
+                open_perc: open_perc,
+                interaction: new_interaction,
+                maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+            })
+        } else {
+            None
+        };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given CollapsibleArea State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{polygon, rect, text};
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let header_h = style.header_height(&ui.theme);
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let color = style.color(&ui.theme);
+        let h_xy = header_xy(xy, dim, header_h);
+
+        // The content's background, drawn beneath the header while any of it is visible.
+        let mut forms = Vec::new();
+        let visible_h = dim[1] - header_h;
+        if visible_h > 0.0 {
+            let content_y = xy[1] - dim[1] / 2.0 + visible_h / 2.0;
+            let (inner_w, inner_h) = (dim[0] - frame * 2.0, (visible_h - frame).max(0.0));
+            forms.push(rect(dim[0], visible_h).filled(frame_color).shift(xy[0], content_y));
+            forms.push(rect(inner_w, inner_h).filled(color.with_luminance(0.1))
+                           .shift(xy[0], content_y - frame / 2.0));
+        }
+
+        // The header's frame and pressable area.
+        let header_dim = [dim[0], header_h];
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(header_dim);
+        forms.push(frame::form(header_dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                               style.frame_style(&ui.theme), frame_color)
+                       .shift(h_xy[0], h_xy[1]));
+        forms.push(rect(inner_dim[0], inner_dim[1]).filled(state.interaction.color(color))
+                       .shift(h_xy[0] + inner_offset[0], h_xy[1] + inner_offset[1]));
+
+        // The arrow points right while collapsed and turns to point down as the area expands.
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let arrow_r = font_size as f64 / 3.0;
+        let arrow_xy = [h_xy[0] - dim[0] / 2.0 + header_h / 2.0, h_xy[1]];
+        let angle = -state.open_perc * ::std::f64::consts::PI / 2.0;
+        let arrow_points = (0..3).map(|i| {
+            let a = angle + i as f64 * ::std::f64::consts::PI * 2.0 / 3.0;
+            (arrow_xy[0] + a.cos() * arrow_r, arrow_xy[1] + a.sin() * arrow_r)
+        }).collect();
+        forms.push(polygon(arrow_points).filled(label_color));
+
+        // Construct the label's Forms, left aligned just beyond the arrow.
+        let mut label_forms = Vec::new();
+        let mut label_dim = [0.0, font_size as f64];
+        if let Some(ref spans) = state.maybe_label_spans {
+            let (spans_dim, spans_forms) = spans.forms(ui, label_color, font_size);
+            label_dim = spans_dim;
+            label_forms = spans_forms;
+        } else if let Some(ref label_text) = state.maybe_label {
+            use elmesque::text::Text;
+            label_dim[0] = label::width(ui, font_size, label_text);
+            label_forms.push(text(Text::from_string(label_text.clone())
+                                      .color(label_color)
+                                      .height(font_size as f64)));
+        }
+        let l_x = h_xy[0] - dim[0] / 2.0 + header_h + label_dim[0] / 2.0;
+        forms.extend(label_forms.into_iter().map(|form| form.shift(l_x.floor(), h_xy[1].floor())));
+
+        // Collect the Forms for drawing.
+        forms
+    }
+
+    /// Describe the CollapsibleArea for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::Disclosure)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(if state.is_expanded { "expanded" } else { "collapsed" })
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_header_height: None,
+            maybe_content_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_collapsible_area.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_collapsible_area.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_medium)
+        })).unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the width of the Element.
+    pub fn width(&self, theme: &Theme) -> f64 {
+        const DEFAULT_WIDTH: f64 = 256.0;
+        self.maybe_width.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_WIDTH)
+        })).unwrap_or(DEFAULT_WIDTH)
+    }
+
+    /// Get the height of the header.
+    pub fn header_height(&self, theme: &Theme) -> f64 {
+        const DEFAULT_HEADER_HEIGHT: f64 = 32.0;
+        self.maybe_header_height.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_header_height.unwrap_or(DEFAULT_HEADER_HEIGHT)
+        })).unwrap_or(DEFAULT_HEADER_HEIGHT)
+    }
+
+    /// Get the height of the content while expanded.
+    pub fn content_height(&self, theme: &Theme) -> f64 {
+        const DEFAULT_CONTENT_HEIGHT: f64 = 128.0;
+        self.maybe_content_height.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_content_height.unwrap_or(DEFAULT_CONTENT_HEIGHT)
+        })).unwrap_or(DEFAULT_CONTENT_HEIGHT)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_collapsible_area.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for CollapsibleArea<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for CollapsibleArea<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for CollapsibleArea<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for CollapsibleArea<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Frameable for CollapsibleArea<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Labelable<'a> for CollapsibleArea<'a, F> {
+    fn label(mut self, text: &'a str) -> Self {
+        self.maybe_label = Some(text);
+        self
+    }
+
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
+    fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+}
+
+impl<'a, F> position::Positionable for CollapsibleArea<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        CollapsibleArea { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        CollapsibleArea { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> position::Sizeable for CollapsibleArea<'a, F> {
+    /// Set the width of the CollapsibleArea.
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    /// Set the height of the content while expanded, as the header's height is set via
+    /// `header_height`.
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_content_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
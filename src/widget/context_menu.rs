@@ -0,0 +1,491 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize};
+use mouse::Mouse;
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, Layer, Point};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+
+
+/// Index of a ContextMenu entry.
+pub type Idx = usize;
+
+
+/// A popup menu that opens at the mouse cursor when the right mouse button is clicked within the
+/// menu's area. Its reaction is triggered upon selection of an entry, after which the menu closes.
+/// The open menu is a transient, so pressing Escape or clicking anywhere outside of it closes it
+/// without reacting.
+pub struct ContextMenu<'a, F> {
+    entries: &'a [&'a str],
+    maybe_area: Option<(Point, Dimensions)>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the ContextMenu, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_item_width: Option<f64>,
+    pub maybe_item_height: Option<f64>,
+    pub maybe_padding: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// Represents the state of the ContextMenu.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    menu_state: MenuState,
+    entries: Vec<String>,
+}
+
+/// Whether the ContextMenu is currently open (at the point at which it was opened) or closed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MenuState {
+    Closed,
+    Open(Point, Interaction),
+}
+
+/// Describes how the entries of an open ContextMenu are being interacted with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted(Idx),
+    Clicked(Idx),
+}
+
+
+/// The xy position and dimensions of a menu opened at the given point. The menu extends down and
+/// to the right of the point, flipping on either axis if it would otherwise overflow the window.
+fn menu_rect(point: Point,
+             item_dim: Dimensions,
+             frame: f64,
+             len: usize,
+             win_dim: Dimensions) -> (Point, Dimensions) {
+    let item_h = item_dim[1] - frame;
+    let dim = [item_dim[0], item_h * len as f64 + frame];
+    (position::place_at_point(point, position::Corner::BottomRight, dim, win_dim), dim)
+}
+
+/// Which entry of the open menu is the mouse over, if any?
+fn is_over_idx(mouse_xy: Point, dim: Dimensions, item_h: f64, len: usize) -> Option<Idx> {
+    use utils::is_over_rect;
+    if len == 0 || !is_over_rect([0.0, 0.0], mouse_xy, dim) { return None }
+    let idx = ((dim[1] / 2.0 - mouse_xy[1]) / item_h) as usize;
+    Some(if idx < len { idx } else { len - 1 })
+}
+
+/// Determine the new Interaction with an open menu.
+fn get_new_interaction(is_over_idx: Option<Idx>, prev: Interaction, mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (is_over_idx, prev, mouse.left.state) {
+        (Some(_),   Clicked(idx), Down) => Clicked(idx),
+        (Some(idx), _,            Down) => Clicked(idx),
+        (Some(idx), _,            Up)   => Highlighted(idx),
+        _                               => Normal,
+    }
+}
+
+
+impl<'a, F> ContextMenu<'a, F> {
+
+    /// Construct a new ContextMenu with the given entries.
+    pub fn new(entries: &'a [&'a str]) -> ContextMenu<'a, F> {
+        ContextMenu {
+            entries: entries,
+            maybe_area: None,
+            depth: -1.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// The area within which a right click will open the menu, given as the xy position of its
+    /// centre and its dimensions. By default, the menu may be opened anywhere within the window.
+    #[inline]
+    pub fn area(mut self, xy: Point, dim: Dimensions) -> ContextMenu<'a, F> {
+        self.maybe_area = Some((xy, dim));
+        self
+    }
+
+    /// Set the dimensions of each entry within the menu.
+    #[inline]
+    pub fn item_dimensions(mut self, dim: Dimensions) -> ContextMenu<'a, F> {
+        self.style.maybe_item_width = Some(dim[0]);
+        self.style.maybe_item_height = Some(dim[1]);
+        self
+    }
+
+    /// Set the rendering depth of the open menu.
+    #[inline]
+    pub fn depth(mut self, depth: Depth) -> ContextMenu<'a, F> {
+        self.depth = depth;
+        self
+    }
+
+    /// Set the color of the entries' text.
+    #[inline]
+    pub fn label_color(mut self, color: Color) -> ContextMenu<'a, F> {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    /// Set the font size of the entries' text.
+    #[inline]
+    pub fn label_font_size(mut self, size: FontSize) -> ContextMenu<'a, F> {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+
+    /// Set the reaction for the ContextMenu. It will be triggered upon selection of an entry.
+    pub fn react(mut self, reaction: F) -> ContextMenu<'a, F> {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for ContextMenu<'a, F>
+    where
+        F: FnMut(Idx, &str),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "ContextMenu" }
+    fn init_state(&self) -> State {
+        State { menu_state: MenuState::Closed, entries: Vec::new() }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the ContextMenu.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let frame = style.frame(&ui.theme);
+        let item_dim = style.item_dim(&ui.theme);
+        let win_dim = [ui.win_w, ui.win_h];
+        let num_entries = self.entries.len();
+        let (area_xy, area_dim) = self.maybe_area.unwrap_or(([0.0, 0.0], win_dim));
+
+        // A right click within the area (re)opens the menu at the cursor.
+        let is_right_clicked = self.enabled && ui.is_right_clicked(ui_id, area_xy, area_dim);
+        let new_menu_state = if is_right_clicked && num_entries > 0 {
+            MenuState::Open(ui.mouse.xy, Interaction::Normal)
+        } else {
+            match state.menu_state {
+                MenuState::Closed => MenuState::Closed,
+                MenuState::Open(_, _) if !self.enabled || ui.is_dismissed(ui_id) =>
+                    MenuState::Closed,
+                MenuState::Open(point, interaction) => {
+                    let (xy, dim) = menu_rect(point, item_dim, frame, num_entries, win_dim);
+                    let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+                    let item_h = item_dim[1] - frame;
+                    let is_over_idx = is_over_idx(mouse.xy, dim, item_h, num_entries);
+                    let new_interaction = get_new_interaction(is_over_idx, interaction, mouse);
+                    match (interaction, new_interaction) {
+                        // Releasing the mouse over the clicked entry selects it.
+                        (Interaction::Clicked(idx), Interaction::Highlighted(new_idx))
+                            if idx == new_idx => {
+                            if let Some(ref mut react) = self.maybe_react {
+                                react(idx, self.entries[idx]);
+                            }
+                            MenuState::Closed
+                        },
+                        _ => MenuState::Open(point, new_interaction),
+                    }
+                },
+            }
+        };
+
+        // Capture the mouse while the menu is open so that the widgets beneath don't react.
+        match (state.menu_state, new_menu_state) {
+            (MenuState::Closed, MenuState::Open(_, _)) => ui.mouse_captured_by(ui_id),
+            (MenuState::Open(_, _), MenuState::Closed) => ui.mouse_uncaptured_by(ui_id),
+            _ => (),
+        }
+
+        let (xy, dim) = match new_menu_state {
+            MenuState::Open(point, _) =>
+                menu_rect(point, item_dim, frame, num_entries, win_dim),
+            MenuState::Closed => ([0.0, 0.0], [0.0, 0.0]),
+        };
+
+        // Register the open menu as a transient so that the `Ui` may dismiss it, drawing it within
+        // the popup layer above all other widgets.
+        match (state.menu_state, new_menu_state) {
+            (_, MenuState::Open(_, _)) => {
+                ui.transient_opened_by(ui_id, xy, dim);
+                ui.set_layer(ui_id, Layer::Popup);
+            },
+            (MenuState::Open(_, _), MenuState::Closed) => {
+                ui.transient_closed_by(ui_id);
+                ui.reset_layer(ui_id);
+            },
+            _ => (),
+        }
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.menu_state != new_menu_state
+            || state.entries.len() != num_entries
+            || state.entries.iter().zip(self.entries.iter()).any(|(a, b)| &a[..] != *b);
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed {
+            Some(State {
+                menu_state: new_menu_state,
+                entries: self.entries.iter().map(|entry| entry.to_string()).collect(),
+            })
+        } else {
+            None
+        };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given ContextMenu State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{rect, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let interaction = match state.menu_state {
+            MenuState::Open(_, interaction) => interaction,
+            MenuState::Closed => return Vec::new(),
+        };
+
+        // Retrieve the styling for the Element.
+        let color = style.color(&ui.theme);
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let padding = style.padding(&ui.theme);
+        let item_h = if state.entries.len() > 0 {
+            (dim[1] - frame) / state.entries.len() as f64 + frame
+        } else {
+            dim[1]
+        };
+        let (inner_offset, inner_dim) =
+            style.frame_widths(&ui.theme).inner_rect([dim[0], item_h]);
+        let top_y = xy[1] + dim[1] / 2.0 - item_h / 2.0;
+
+        let mut forms = Vec::new();
+        for (i, entry) in state.entries.iter().enumerate() {
+            let item_color = match interaction {
+                Interaction::Highlighted(idx) if idx == i => color.highlighted(),
+                Interaction::Clicked(idx) if idx == i => color.clicked(),
+                _ => color,
+            };
+            let y = top_y - i as f64 * (item_h - frame);
+            let text_w = label::width(ui, font_size, entry);
+            let text_x = position::align_left_of(inner_dim[0], text_w) + padding;
+            forms.push(rect(dim[0], item_h).filled(frame_color).shift(xy[0], y));
+            forms.push(rect(inner_dim[0], inner_dim[1]).filled(item_color)
+                           .shift(xy[0] + inner_offset[0], y + inner_offset[1]));
+            forms.push(text(Text::from_string(entry.clone())
+                                .color(label_color)
+                                .height(font_size as f64))
+                           .shift((xy[0] + text_x).floor(), y.floor()));
+        }
+
+        // Collect the Forms for drawing.
+        forms
+    }
+
+    /// Describe the ContextMenu for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::Menu)
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_item_width: None,
+            maybe_item_height: None,
+            maybe_padding: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_context_menu.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_context_menu.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_medium)
+        })).unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the Dimensions of each entry within the menu.
+    pub fn item_dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_ITEM_DIM: Dimensions = [160.0, 28.0];
+        let width = self.maybe_item_width.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_item_width.unwrap_or(DEFAULT_ITEM_DIM[0])
+        })).unwrap_or(DEFAULT_ITEM_DIM[0]);
+        let height = self.maybe_item_height.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_item_height.unwrap_or(DEFAULT_ITEM_DIM[1])
+        })).unwrap_or(DEFAULT_ITEM_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the padding between the edge of the Element and its text.
+    pub fn padding(&self, theme: &Theme) -> f64 {
+        const DEFAULT_PADDING: f64 = 8.0;
+        self.maybe_padding.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_padding.unwrap_or(DEFAULT_PADDING)
+        })).unwrap_or(DEFAULT_PADDING)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_context_menu.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for ContextMenu<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for ContextMenu<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for ContextMenu<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for ContextMenu<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Frameable for ContextMenu<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
@@ -0,0 +1,699 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::FontSize;
+use mouse::Mouse;
+use palette::{ColorRole, Paletted};
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               Sizeable, VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+
+
+/// The names of the months, from January.
+const MONTH_NAMES: [&'static str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// The abbreviated names of the days of the week, from Monday.
+const WEEKDAY_NAMES: [&'static str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// The number of rows within the DatePicker: the header, the names of the weekdays and up to six
+/// weeks.
+const NUM_ROWS: usize = 8;
+
+/// A day within the (proleptic) Gregorian calendar. Dates are ordered chronologically.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    /// The month of the year, from `1` (January) to `12` (December).
+    pub month: u32,
+    /// The day of the month, from `1`.
+    pub day: u32,
+}
+
+/// A calendar showing a single month as a grid of days, from which a date may be chosen.
+///
+/// The arrows to either side of the month's name step to the previous and next months. The
+/// chosen date and today's date are highlighted, while days outside the optional minimum and
+/// maximum dates are dimmed and may not be chosen. The reaction is triggered with the year, month
+/// and day of the chosen date.
+pub struct DatePicker<F> {
+    date: Date,
+    maybe_min: Option<Date>,
+    maybe_max: Option<Date>,
+    maybe_today: Option<Date>,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the DatePicker, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// Represents the specific elements that the DatePicker is made up of. This is used to specify
+/// which element is Highlighted or Clicked when storing State.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Elem {
+    /// The arrow stepping to the previous month.
+    Prev,
+    /// The arrow stepping to the next month.
+    Next,
+    /// The given day of the shown month.
+    Day(u32),
+}
+
+/// The current interaction with the DatePicker.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted(Elem),
+    Clicked(Elem),
+}
+
+/// The state of the DatePicker.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    /// The chosen date, as most recently given.
+    date: Date,
+    /// The year and month currently shown.
+    shown: (i32, u32),
+    today: Date,
+    maybe_min: Option<Date>,
+    maybe_max: Option<Date>,
+    interaction: Interaction,
+}
+
+
+/// Whether or not the given year has a 29th of February.
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// The number of days within the given month of the given year.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The year and month before the given year and month.
+fn prev_month(year: i32, month: u32) -> (i32, u32) {
+    if month <= 1 { (year - 1, 12) } else { (year, month - 1) }
+}
+
+/// The year and month after the given year and month.
+fn next_month(year: i32, month: u32) -> (i32, u32) {
+    if month >= 12 { (year + 1, 1) } else { (year, month + 1) }
+}
+
+
+impl Date {
+
+    /// Construct a Date, clamping the month and day to those that exist.
+    pub fn new(year: i32, month: u32, day: u32) -> Date {
+        let month = ::std::cmp::max(1, ::std::cmp::min(month, 12));
+        let day = ::std::cmp::max(1, ::std::cmp::min(day, days_in_month(year, month)));
+        Date { year: year, month: month, day: day }
+    }
+
+    /// Today's date in UTC, according to the system clock.
+    pub fn today() -> Date {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Date::from_days((secs / 86_400) as i64)
+    }
+
+    /// The Date the given number of days after the 1st of January 1970.
+    pub fn from_days(days: i64) -> Date {
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = (if z >= 0 { z } else { z - 146_096 }) / 146_097;
+        let day_of_era = z - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date { year: year as i32, month: month as u32, day: day as u32 }
+    }
+
+    /// The number of days from the 1st of January 1970 until the Date.
+    pub fn days(&self) -> i64 {
+        // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = (if year >= 0 { year } else { year - 399 }) / 400;
+        let year_of_era = year - era * 400;
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The day of the week upon which the Date falls, from `0` (Monday) to `6` (Sunday).
+    pub fn weekday(&self) -> u32 {
+        // The 1st of January 1970 was a Thursday.
+        ((self.days() % 7 + 7 + 3) % 7) as u32
+    }
+
+}
+
+
+/// The rectangle of the cell at the given column and row, relative to the DatePicker's centre.
+fn cell_rect(dim: Dimensions, frame: f64, col: usize, row: usize) -> (Point, Dimensions) {
+    let (inner_w, inner_h) = (dim[0] - frame * 2.0, dim[1] - frame * 2.0);
+    let (cell_w, cell_h) = (inner_w / 7.0, inner_h / NUM_ROWS as f64);
+    let x = -inner_w / 2.0 + cell_w * (col as f64 + 0.5);
+    let y = inner_h / 2.0 - cell_h * (row as f64 + 0.5);
+    ([x, y], [cell_w, cell_h])
+}
+
+/// The column and row of the cell of the given day within the month that begins upon the given
+/// weekday.
+fn day_cell(first_weekday: u32, day: u32) -> (usize, usize) {
+    let idx = (first_weekday + day - 1) as usize;
+    (idx % 7, 2 + idx / 7)
+}
+
+/// Which element of the DatePicker (if any) lies beneath the given point, relative to its centre.
+fn is_over_elem(xy: Point, dim: Dimensions, frame: f64, year: i32, month: u32) -> Option<Elem> {
+    let (inner_w, inner_h) = (dim[0] - frame * 2.0, dim[1] - frame * 2.0);
+    let x = (xy[0] + inner_w / 2.0) / (inner_w / 7.0);
+    let y = (inner_h / 2.0 - xy[1]) / (inner_h / NUM_ROWS as f64);
+    if x < 0.0 || y < 0.0 || x >= 7.0 || y >= NUM_ROWS as f64 {
+        return None;
+    }
+    let (col, row) = (x as u32, y as u32);
+    match (col, row) {
+        (0, 0) => Some(Elem::Prev),
+        (6, 0) => Some(Elem::Next),
+        (_, 0) | (_, 1) => None,
+        _ => {
+            let first_weekday = Date::new(year, month, 1).weekday();
+            let idx = (row - 2) * 7 + col;
+            if idx < first_weekday || idx - first_weekday >= days_in_month(year, month) {
+                None
+            } else {
+                Some(Elem::Day(idx - first_weekday + 1))
+            }
+        },
+    }
+}
+
+/// Whether or not the given element may currently be pressed.
+fn is_elem_enabled(elem: Elem,
+                   (year, month): (i32, u32),
+                   maybe_min: Option<Date>,
+                   maybe_max: Option<Date>) -> bool {
+    let is_after_min = |date: Date| maybe_min.map_or(true, |min| date >= min);
+    let is_before_max = |date: Date| maybe_max.map_or(true, |max| date <= max);
+    match elem {
+        Elem::Prev => {
+            let (year, month) = prev_month(year, month);
+            is_after_min(Date::new(year, month, days_in_month(year, month)))
+        },
+        Elem::Next => {
+            let (year, month) = next_month(year, month);
+            is_before_max(Date::new(year, month, 1))
+        },
+        Elem::Day(day) => {
+            let date = Date::new(year, month, day);
+            is_after_min(date) && is_before_max(date)
+        },
+    }
+}
+
+/// Check and return the current state of the DatePicker.
+fn get_new_interaction(is_over_elem: Option<Elem>, prev: Interaction, mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (is_over_elem, prev, mouse.left.state) {
+        (Some(_),    Normal,          Down) => Normal,
+        (Some(elem), _,               Up)   => Highlighted(elem),
+        (Some(elem), Highlighted(_),  Down) => Clicked(elem),
+        (_,          Clicked(p_elem), Down) => Clicked(p_elem),
+        _                                   => Normal,
+    }
+}
+
+
+impl<F> DatePicker<F> {
+
+    /// Construct a new DatePicker with the given date chosen, initially showing its month.
+    pub fn new(year: i32, month: u32, day: u32) -> DatePicker<F> {
+        DatePicker {
+            date: Date::new(year, month, day),
+            maybe_min: None,
+            maybe_max: None,
+            maybe_today: None,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the earliest date that may be chosen.
+    pub fn min_date(mut self, date: Date) -> Self {
+        self.maybe_min = Some(date);
+        self
+    }
+
+    /// Set the latest date that may be chosen.
+    pub fn max_date(mut self, date: Date) -> Self {
+        self.maybe_max = Some(date);
+        self
+    }
+
+    /// Set the date highlighted as today. By default, this is today's date in UTC.
+    pub fn today(mut self, date: Date) -> Self {
+        self.maybe_today = Some(date);
+        self
+    }
+
+    /// Set the reaction for the DatePicker. It is triggered with the year, month and day of a
+    /// date upon it being chosen.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the depth of the DatePicker.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl<F> Widget for DatePicker<F>
+    where
+        F: FnMut(i32, u32, u32),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "DatePicker" }
+    fn init_state(&self) -> State {
+        State {
+            date: self.date,
+            shown: (self.date.year, self.date.month),
+            today: self.maybe_today.unwrap_or_else(Date::today),
+            maybe_min: None,
+            maybe_max: None,
+            interaction: Interaction::Normal,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the DatePicker.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let frame = style.frame(&ui.theme);
+
+        // Show the month of the chosen date whenever the application changes it.
+        let mut shown = if self.date != state.date {
+            (self.date.year, self.date.month)
+        } else {
+            state.shown
+        };
+
+        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+        let (min, max) = (self.maybe_min, self.maybe_max);
+        let is_over_elem = is_over_elem(mouse.xy, dim, frame, shown.0, shown.1)
+            .and_then(|elem| {
+                if is_elem_enabled(elem, shown, min, max) { Some(elem) } else { None }
+            });
+        let new_interaction = if self.enabled {
+            get_new_interaction(is_over_elem, state.interaction, mouse)
+        } else {
+            Interaction::Normal
+        };
+
+        // Step between months or choose a day upon release over the element that was pressed.
+        if let (Interaction::Clicked(elem), Interaction::Highlighted(new_elem)) =
+            (state.interaction, new_interaction) {
+            if elem == new_elem {
+                match elem {
+                    Elem::Prev => shown = prev_month(shown.0, shown.1),
+                    Elem::Next => shown = next_month(shown.0, shown.1),
+                    Elem::Day(day) => if let Some(ref mut react) = self.maybe_react {
+                        react(shown.0, shown.1, day)
+                    },
+                }
+            }
+        }
+
+        // The shown month's days may no longer be where the interaction was.
+        let new_interaction =
+            if shown != state.shown { Interaction::Normal } else { new_interaction };
+
+        let new_state = State {
+            date: self.date,
+            shown: shown,
+            today: self.maybe_today.unwrap_or_else(Date::today),
+            maybe_min: min,
+            maybe_max: max,
+            interaction: new_interaction,
+        };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given DatePicker State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{rect, solid, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let color = style.color(&ui.theme);
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let (year, month) = state.shown;
+        let label_form = |string: &str, color: Color| {
+            text(Text::from_string(string.to_string()).color(color).height(font_size as f64))
+        };
+
+        // The frame and background.
+        let mut forms = Vec::new();
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        forms.push(frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                               style.frame_style(&ui.theme), frame_color));
+        forms.push(rect(inner_dim[0], inner_dim[1]).filled(color)
+                       .shift(inner_offset[0], inner_offset[1]));
+
+        // Fill the cell of an element that is being interacted with.
+        let interacted = match state.interaction {
+            Interaction::Normal => None,
+            Interaction::Highlighted(elem) => Some((elem, color.highlighted())),
+            Interaction::Clicked(elem) => Some((elem, color.clicked())),
+        };
+        let fill_for = |elem: Elem| interacted.and_then(|(interacted_elem, fill)| {
+            if interacted_elem == elem { Some(fill) } else { None }
+        });
+
+        // The header, with the arrows either side of the shown month's name.
+        let arrows = [(Elem::Prev, 0, "<"), (Elem::Next, 6, ">")];
+        for &(elem, col, arrow) in arrows.iter() {
+            let (c_xy, c_dim) = cell_rect(dim, frame, col, 0);
+            if let Some(fill) = fill_for(elem) {
+                forms.push(rect(c_dim[0], c_dim[1]).filled(fill).shift(c_xy[0], c_xy[1]));
+            }
+            let is_enabled = is_elem_enabled(elem, state.shown, state.maybe_min, state.maybe_max);
+            let arrow_color = if is_enabled { label_color } else { label_color.alpha(0.3) };
+            forms.push(label_form(arrow, arrow_color).shift(c_xy[0].floor(), c_xy[1].floor()));
+        }
+        let title = format!("{} {}", MONTH_NAMES[month as usize - 1], year);
+        let (title_xy, _) = cell_rect(dim, frame, 3, 0);
+        forms.push(label_form(&title, label_color).shift(title_xy[0].floor(), title_xy[1].floor()));
+
+        // The names of the weekdays.
+        for (col, name) in WEEKDAY_NAMES.iter().enumerate() {
+            let (c_xy, _) = cell_rect(dim, frame, col, 1);
+            forms.push(label_form(name, label_color.alpha(0.6))
+                           .shift(c_xy[0].floor(), c_xy[1].floor()));
+        }
+
+        // The days of the shown month.
+        let first_weekday = Date::new(year, month, 1).weekday();
+        for day in 1..days_in_month(year, month) + 1 {
+            let (col, row) = day_cell(first_weekday, day);
+            let (c_xy, c_dim) = cell_rect(dim, frame, col, row);
+            let date = Date::new(year, month, day);
+            let maybe_fill = if date == state.date {
+                Some(label_color.alpha(0.35))
+            } else {
+                fill_for(Elem::Day(day))
+            };
+            if let Some(fill) = maybe_fill {
+                forms.push(rect(c_dim[0], c_dim[1]).filled(fill).shift(c_xy[0], c_xy[1]));
+            }
+            if date == state.today {
+                let line_style = solid(label_color);
+                forms.push(rect(c_dim[0] - 2.0, c_dim[1] - 2.0).outlined(line_style)
+                               .shift(c_xy[0], c_xy[1]));
+            }
+            let is_enabled =
+                is_elem_enabled(Elem::Day(day), state.shown, state.maybe_min, state.maybe_max);
+            let day_color = if is_enabled { label_color } else { label_color.alpha(0.3) };
+            forms.push(label_form(&day.to_string(), day_color)
+                           .shift(c_xy[0].floor(), c_xy[1].floor()));
+        }
+
+        // Collect the Forms for drawing.
+        let forms = forms.into_iter().map(|form| form.shift(xy[0].floor(), xy[1].floor()));
+        forms.collect()
+    }
+
+    /// Describe the DatePicker for assistive technologies, giving the chosen date.
+    fn accessibility(state: &State) -> Accessibility {
+        let Date { year, month, day } = state.date;
+        Accessibility::new(Role::Calendar).value(format!("{:04}-{:02}-{:02}", year, month, day))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_date_picker.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_date_picker.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [224.0, 208.0];
+        let width = self.maybe_width.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_date_picker.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<F> Enableable for DatePicker<F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<F> Colorable for DatePicker<F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<F> Paletted for DatePicker<F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<F> Fontable for DatePicker<F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<F> Frameable for DatePicker<F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<F> Positionable for DatePicker<F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        DatePicker { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        DatePicker { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<F> Sizeable for DatePicker<F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -0,0 +1,502 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::Enableable;
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use palette::{ColorRole, Paletted};
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               VerticalAlign};
+use snap::{self, Guide};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::is_over_rect;
+use widget::{self, Widget};
+
+
+/// A rectangle that may be dragged around by the mouse, i.e. the title bar of a panel within an
+/// editor. Other widgets may be positioned relative to it in order to move along with it.
+///
+/// As with the value of a Slider, the position is owned by the application. The Draggable is
+/// positioned as usual each update, and its reaction is called with its new centre while it is
+/// being dragged.
+///
+/// While dragging, the Draggable snaps to the grid given via `snap_to_grid` and to the edges and
+/// centres of the widgets given via `snap_to`, drawing a guide for each alignment found. Snapping
+/// is suspended while Ctrl is held.
+///
+/// A `selectable` Draggable joins the `Ui`'s selection (see `Ui::selection`) when clicked, or is
+/// added to or removed from it when Shift is held. Dragging any of the selected Draggables moves
+/// the rest of the selection along with it, each reacting with its own new centre.
+pub struct Draggable<'a, F> {
+    siblings: &'a [UiId],
+    selectable: bool,
+    maybe_grid: Option<f64>,
+    snap_distance: f64,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the Draggable, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_guide_color: Option<Color>,
+    pub maybe_selection_color: Option<Color>,
+}
+
+/// The state of the Draggable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    drag: Drag,
+    guides: Vec<Guide>,
+    interaction: Interaction,
+    is_selected: bool,
+    /// The id of the last movement of the selection seen by the Draggable.
+    maybe_group_move: Option<u64>,
+}
+
+/// Whether or not the Draggable is being dragged by the mouse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Drag {
+    /// The left mouse button is up.
+    Idle,
+    /// The left mouse button was pressed away from the Draggable and is still held.
+    Blocked,
+    /// The Draggable is being dragged, with the given offset of its centre from the mouse.
+    Dragging(Point),
+}
+
+/// Represents an interaction with the Draggable widget.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Interaction {
+    Normal,
+    Highlighted,
+    Clicked,
+}
+
+
+impl Interaction {
+    /// Alter the widget color depending on the state.
+    fn color(&self, color: Color) -> Color {
+        match *self {
+            Interaction::Normal => color,
+            Interaction::Highlighted => color.highlighted(),
+            Interaction::Clicked => color.clicked(),
+        }
+    }
+}
+
+
+impl<'a, F> Draggable<'a, F> {
+
+    /// Construct a new Draggable.
+    pub fn new() -> Draggable<'a, F> {
+        Draggable {
+            siblings: &[],
+            selectable: false,
+            maybe_grid: None,
+            snap_distance: snap::DEFAULT_DISTANCE,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the reaction for the Draggable. It is called with the new centre of the Draggable
+    /// whenever it is dragged.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Snap the edges and centre of the Draggable to those of the given widgets.
+    pub fn snap_to(mut self, siblings: &'a [UiId]) -> Self {
+        self.siblings = siblings;
+        self
+    }
+
+    /// Snap the left and top edges of the Draggable to a grid with the given spacing.
+    pub fn snap_to_grid(mut self, spacing: f64) -> Self {
+        self.maybe_grid = Some(spacing);
+        self
+    }
+
+    /// Set the distance (in pixels) within which the Draggable snaps to its siblings.
+    pub fn snap_distance(mut self, distance: f64) -> Self {
+        self.snap_distance = distance;
+        self
+    }
+
+    /// Whether or not the Draggable may be selected and dragged along with the rest of the `Ui`'s
+    /// selection.
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    /// Set the color of the frame drawn around the Draggable while it is selected.
+    pub fn selection_color(mut self, color: Color) -> Self {
+        self.style.maybe_selection_color = Some(color);
+        self
+    }
+
+    /// Set the color of the guides drawn for each alignment with a sibling.
+    pub fn guide_color(mut self, color: Color) -> Self {
+        self.style.maybe_guide_color = Some(color);
+        self
+    }
+
+    /// Set the depth of the Draggable.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for Draggable<'a, F>
+    where
+        F: FnMut(Point),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Draggable" }
+    fn init_state(&self) -> State {
+        State {
+            drag: Drag::Idle,
+            guides: Vec::new(),
+            interaction: Interaction::Normal,
+            is_selected: false,
+            maybe_group_move: None,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+
+    /// Update the state of the Draggable.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::Down;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id);
+        let is_over = is_over_rect(xy, mouse.xy, dim);
+
+        // Alt with the left button is reserved for panning an EditorCanvas.
+        let is_down = self.enabled && mouse.left.state == Down && !ui.is_alt_down();
+        let drag = match (is_down, state.drag) {
+            (false, _) => Drag::Idle,
+            (true, Drag::Dragging(offset)) => Drag::Dragging(offset),
+            (true, Drag::Idle) if is_over =>
+                Drag::Dragging([xy[0] - mouse.xy[0], xy[1] - mouse.xy[1]]),
+            (true, _) => Drag::Blocked,
+        };
+        let was_dragging = if let Drag::Dragging(_) = state.drag { true } else { false };
+        let is_dragging = if let Drag::Dragging(_) = drag { true } else { false };
+        ui.mouse_dragged_by(ui_id, was_dragging, is_dragging);
+
+        // Clicking selects the Draggable alone, unless it is already part of the selection in
+        // which case the whole selection is dragged. Shift-clicking toggles its selection.
+        if self.selectable && is_dragging && !was_dragging {
+            if ui.is_shift_down() {
+                ui.toggle_selected(ui_id);
+            } else if !ui.is_selected(ui_id) {
+                ui.set_selection(&[ui_id]);
+            }
+        }
+        let is_selected = self.selectable && ui.is_selected(ui_id);
+
+        // Snap the dragged position and react with it.
+        let mut guides = Vec::new();
+        if let Drag::Dragging(offset) = drag {
+            let dragged = [mouse.xy[0] + offset[0], mouse.xy[1] + offset[1]];
+            let new_xy = if ui.is_ctrl_down() {
+                dragged
+            } else {
+                let siblings: Vec<(Point, Dimensions)> = self.siblings.iter()
+                    .filter(|&&id| id != ui_id)
+                    .map(|&id| (ui.widget_xy(id), ui.widget_size(id)))
+                    .collect();
+                let (snapped, snap_guides) =
+                    snap::snap_rect(dragged, dim, &siblings, self.snap_distance, self.maybe_grid);
+                guides = snap_guides;
+                snapped
+            };
+            if new_xy != xy {
+                if is_selected {
+                    ui.move_selection(ui_id, [new_xy[0] - xy[0], new_xy[1] - xy[1]]);
+                }
+                if let Some(ref mut react) = self.maybe_react {
+                    react(new_xy)
+                }
+            }
+        }
+
+        // Follow the movement of the selection when another of its widgets is being dragged.
+        let (group_move, delta) = ui.group_move(ui_id);
+        let is_new = state.maybe_group_move.map(|last| group_move > last).unwrap_or(false);
+        if is_selected && is_new && !is_dragging {
+            if let Some(ref mut react) = self.maybe_react {
+                react([xy[0] + delta[0], xy[1] + delta[1]])
+            }
+        }
+
+        let interaction = match drag {
+            Drag::Dragging(_) => Interaction::Clicked,
+            Drag::Idle if is_over => Interaction::Highlighted,
+            _ => Interaction::Normal,
+        };
+
+        let new_state = State {
+            drag: drag,
+            guides: guides,
+            interaction: interaction,
+            is_selected: is_selected,
+            maybe_group_move: Some(group_move),
+        };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given Draggable State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::rect;
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+
+        let color = state.interaction.color(style.color(&ui.theme));
+        let frame_color = if state.is_selected { style.selection_color(&ui.theme) }
+                          else { style.frame_color(&ui.theme) };
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color)
+            .shift(xy[0], xy[1]);
+        let inner_form = rect(inner_dim[0], inner_dim[1]).filled(color)
+            .shift(xy[0] + inner_offset[0], xy[1] + inner_offset[1]);
+
+        // The guides are drawn above the Draggable, as they usually run along its edges.
+        let guide_forms = snap::guide_forms(&state.guides, style.guide_color(&ui.theme), 1.0);
+
+        Some(frame_form).into_iter()
+            .chain(Some(inner_form).into_iter())
+            .chain(guide_forms.into_iter())
+            .collect()
+    }
+
+    /// Describe the Draggable for assistive technologies.
+    fn accessibility(_state: &State) -> Accessibility {
+        Accessibility::new(Role::Draggable)
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_width: None,
+            maybe_height: None,
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_guide_color: None,
+            maybe_selection_color: None,
+        }
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [128.0, 24.0];
+        let width = self.maybe_width.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_draggable.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_draggable.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the Color of the snap guides.
+    pub fn guide_color(&self, theme: &Theme) -> Color {
+        self.maybe_guide_color.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_guide_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the Color of the frame while the Draggable is selected.
+    pub fn selection_color(&self, theme: &Theme) -> Color {
+        self.maybe_selection_color.or(theme.maybe_draggable.as_ref().map(|style| {
+            style.maybe_selection_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+}
+
+
+impl<'a, F> Enableable for Draggable<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for Draggable<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for Draggable<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Frameable for Draggable<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Positionable for Draggable<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        Draggable { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        Draggable { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> ::position::Sizeable for Draggable<'a, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -0,0 +1,249 @@
+use accessibility::{Accessibility, Role};
+use elmesque::form::Form;
+use enable::Enableable;
+use graphics::character::CharacterCache;
+use mouse::Mouse;
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+
+
+/// Reserves a rectangle within the layout, tracks the mouse's interaction with it and calls the
+/// given closure to produce the Forms to be drawn within it. This allows for drawing arbitrary
+/// graphics amongst other widgets without implementing the `Widget` trait.
+///
+/// The closure is called with the area's dimensions and its current `State` during every update,
+/// and the returned Forms (positioned relative to the centre of the area) are redrawn each time.
+pub struct DrawArea<F> {
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    draw: F,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the DrawArea.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+}
+
+/// The state of the DrawArea, given to the draw closure.
+#[derive(Clone, Debug)]
+pub struct State {
+    /// How the mouse is currently interacting with the area.
+    pub interaction: Interaction,
+    /// The position of the mouse relative to the centre of the area.
+    pub mouse_xy: Point,
+    /// Whether or not the mouse was released over the area after clicking it since the last
+    /// update.
+    pub was_clicked: bool,
+    /// The Forms most recently produced by the draw closure.
+    forms: Vec<Form>,
+}
+
+/// The ways in which the mouse may interact with the DrawArea.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted,
+    Clicked,
+}
+
+
+/// Forms cannot be compared, so only the interaction with the area is considered.
+impl PartialEq for State {
+    fn eq(&self, other: &State) -> bool {
+        self.interaction == other.interaction
+            && self.mouse_xy == other.mouse_xy
+            && self.was_clicked == other.was_clicked
+    }
+}
+
+
+/// Check the current interaction with the area.
+fn get_new_interaction(is_over: bool, prev: Interaction, mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (is_over, prev, mouse.left.state) {
+        (true,  Normal,  Down) => Normal,
+        (true,  _,       Down) => Clicked,
+        (true,  _,       Up)   => Highlighted,
+        (false, Clicked, Down) => Clicked,
+        _                      => Normal,
+    }
+}
+
+
+impl<F> DrawArea<F> {
+
+    /// Construct a new DrawArea that will draw the Forms returned by the given closure.
+    pub fn new(draw: F) -> DrawArea<F> {
+        DrawArea {
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            draw: draw,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+}
+
+
+impl<F> Widget for DrawArea<F>
+    where
+        F: FnMut(Dimensions, &State) -> Vec<Form>,
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "DrawArea" }
+    fn init_state(&self) -> State {
+        State {
+            interaction: Interaction::Normal,
+            mouse_xy: [0.0, 0.0],
+            was_clicked: false,
+            forms: Vec::new(),
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+
+    /// Update the state of the DrawArea and collect the Forms produced by its draw closure.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use utils::is_over_rect;
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+        let is_over = is_over_rect([0.0, 0.0], mouse.xy, dim);
+        let new_interaction = if self.enabled {
+            get_new_interaction(is_over, state.interaction, mouse)
+        } else {
+            Interaction::Normal
+        };
+        let was_clicked = match (is_over, state.interaction, new_interaction) {
+            (true, Interaction::Clicked, Interaction::Highlighted) => true,
+            _ => false,
+        };
+
+        // Produce the new Forms from the user's closure.
+        let mut new_state = State {
+            interaction: new_interaction,
+            mouse_xy: mouse.xy,
+            was_clicked: was_clicked,
+            forms: Vec::new(),
+        };
+        new_state.forms = (self.draw)(dim, &new_state);
+
+        // The closure may draw anything, so the area is redrawn on every update.
+        widget::State { state: Some(new_state), dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Collect the Forms produced by the draw closure.
+    fn draw<C>(new_state: &widget::State<State>, _style: &Style, _ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, xy, .. } = *new_state;
+        state.forms.iter().map(|form| form.clone().shift(xy[0], xy[1])).collect()
+    }
+
+    /// Describe the DrawArea for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::Graphic)
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_width: None,
+            maybe_height: None,
+        }
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [128.0, 128.0];
+        let width = self.maybe_width.or(theme.maybe_draw_area.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_draw_area.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+}
+
+
+impl<F> Enableable for DrawArea<F> {
+    /// If true, will track the mouse's interaction with the area. If false, the interaction will
+    /// always be `Normal`.
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<F> Positionable for DrawArea<F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        DrawArea { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        DrawArea { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<F> ::position::Sizeable for DrawArea<F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
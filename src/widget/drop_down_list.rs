@@ -1,14 +1,22 @@
 
+use accessibility::{Accessibility, Role};
 use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use corner::{self, CornerRadii, Roundable};
+use elmesque::form::Form;
+use enable::{self, Enableable};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::character::CharacterCache;
-use label::{FontSize, Labelable};
+use label::{self, FontSize, Labelable};
 use mouse::Mouse;
-use position::{Depth, Dimensions, HorizontalAlign, Point, Position, Positionable, VerticalAlign};
+use palette::{ColorRole, Paletted};
+use piston::input::keyboard::Key;
+use position::{Depth, Dimensions, HorizontalAlign, Layer, Margin, Padding, Point, Position,
+               Positionable, VerticalAlign};
 use theme::Theme;
 use ui::{UiId, Ui};
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// Tuple / React params.
@@ -17,16 +25,28 @@ pub type Len = usize;
 
 /// Displays a given `Vec<String>` as a selectable drop down menu. It's reaction is triggered upon
 /// selection of a list item.
+///
+/// If the label marks an accelerator with an `&` (i.e. `"&Font"`), pressing Alt and the marked
+/// letter will open the list.
+///
+/// Clicking the DropDownList gives it keyboard focus. While focused, Enter or Space opens the
+/// list, the Up and Down arrows move the highlighted item and Enter or Space selects it. Scrolling
+/// the mouse wheel over the closed list selects the previous or next item.
+///
+/// The open list is a transient, so pressing Escape or clicking outside of it closes it without
+/// making a selection.
 pub struct DropDownList<'a, F> {
     strings: &'a mut Vec<String>,
     selected: &'a mut Option<Idx>,
     pos: Position,
-    dim: Dimensions,
+    margin: Margin,
+    padding: Padding,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
     depth: Depth,
     maybe_react: Option<F>,
     maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
     style: Style,
     enabled: bool,
 }
@@ -35,10 +55,18 @@ pub struct DropDownList<'a, F> {
 #[derive(PartialEq, Clone, Debug, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_corner_radii: Option<CornerRadii>,
     pub maybe_label_color: Option<Color>,
     pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
 }
 
 /// Represents the state of the DropDownList.
@@ -46,8 +74,19 @@ pub struct Style {
 pub struct State {
     menu_state: MenuState,
     maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
     strings: Vec<String>,
     maybe_selected: Option<Idx>,
+    /// How far the menu has expanded, from 0.0 (closed) to 1.0 (fully open).
+    open_perc: f64,
+    /// Whether or not the label's accelerator should be underlined.
+    show_mnemonic: bool,
+    /// Whether or not the list opens upwards to remain within the window.
+    opens_upward: bool,
+    /// Whether or not the DropDownList has keyboard focus.
+    is_focused: bool,
+    /// Whether or not the DropDownList accepts user input.
+    enabled: bool,
 }
 
 /// Whether the DropDownList is currently open or closed.
@@ -77,12 +116,34 @@ impl Interaction {
 }
 
 
+/// The centre and dimensions of the open list relative to the DropDownList's centre, along with
+/// whether or not the list opens upwards. The list only opens upwards if opening downwards would
+/// overflow the window while opening upwards would not.
+fn open_rect(xy: Point,
+             frame_w: f64,
+             dim: Dimensions,
+             len: Len,
+             win_dim: Dimensions) -> (Point, Dimensions, bool) {
+    use position::is_within;
+    let item_h = dim[1] - frame_w;
+    let total_h = item_h * len as f64;
+    let open_dim = [dim[0], total_h];
+    let offset = (total_h - item_h) / 2.0;
+    let fits_below = is_within([xy[0], xy[1] - offset], open_dim, win_dim);
+    let fits_above = is_within([xy[0], xy[1] + offset], open_dim, win_dim);
+    match !fits_below && fits_above {
+        true => ([0.0, offset], open_dim, true),
+        false => ([0.0, -offset], open_dim, false),
+    }
+}
+
 /// Is the cursor currently over the widget? If so which item?
 fn is_over(mouse_pos: Point,
            frame_w: f64,
            dim: Dimensions,
            menu_state: MenuState,
-           len: Len) -> Option<Idx> {
+           len: Len,
+           opens_upward: bool) -> Option<Idx> {
     use utils::is_over_rect;
     match menu_state {
         MenuState::Closed(_) => match is_over_rect([0.0, 0.0], mouse_pos, dim) {
@@ -92,10 +153,14 @@ fn is_over(mouse_pos: Point,
         MenuState::Open(_) => {
             let item_h = dim[1] - frame_w;
             let total_h = item_h * len as f64;
-            let open_centre_y = -(total_h - item_h) / 2.0;
+            let open_centre_y = (total_h - item_h) / 2.0;
+            let (open_centre_y, first_edge_y) = match opens_upward {
+                true => (open_centre_y, -item_h / 2.0),
+                false => (-open_centre_y, item_h / 2.0),
+            };
             match is_over_rect([0.0, open_centre_y], mouse_pos, [dim[0], total_h]) {
                 false => None,
-                true => Some(((mouse_pos[1] - item_h / 2.0).abs() / item_h) as usize),
+                true => Some(((mouse_pos[1] - first_edge_y).abs() / item_h) as usize),
             }
         },
     }
@@ -114,7 +179,7 @@ fn get_new_menu_state(is_over_idx: Option<Idx>,
         MenuState::Closed(draw_state) => {
             match is_over_idx {
                 Some(_) => {
-                    match (draw_state, mouse.left) {
+                    match (draw_state, mouse.left.state) {
                         (Normal,            Down) => MenuState::Closed(Normal),
                         (Normal,            Up)   |
                         (Highlighted(_, _), Up)   => MenuState::Closed(Highlighted(0, len)),
@@ -129,7 +194,7 @@ fn get_new_menu_state(is_over_idx: Option<Idx>,
         MenuState::Open(draw_state) => {
             match is_over_idx {
                 Some(idx) => {
-                    match (draw_state, mouse.left) {
+                    match (draw_state, mouse.left.state) {
                         (Normal,            Down) => MenuState::Open(Normal),
                         (Normal,            Up)   |
                         (Highlighted(_, _), Up)   => MenuState::Open(Highlighted(idx, len)),
@@ -139,7 +204,7 @@ fn get_new_menu_state(is_over_idx: Option<Idx>,
                     }
                 },
                 None => {
-                    match (draw_state, mouse.left) {
+                    match (draw_state, mouse.left.state) {
                         (Highlighted(p_idx, _), Up) => MenuState::Open(Highlighted(p_idx, len)),
                         _ => MenuState::Closed(Normal),
                     }
@@ -149,6 +214,45 @@ fn get_new_menu_state(is_over_idx: Option<Idx>,
     }
 }
 
+/// Determine the new MenuState from the keys pressed while the DropDownList has keyboard focus,
+/// along with the index of the item chosen via the keyboard (if any).
+fn get_key_menu_state(keys: &[Key],
+                      len: Len,
+                      menu_state: MenuState,
+                      selected: Option<Idx>) -> (MenuState, Option<Idx>) {
+    use piston::input::keyboard::Key::{Down, Return, Space, Up};
+    use self::Interaction::{Normal, Clicked, Highlighted};
+    keys.iter().fold((menu_state, None), |(menu_state, maybe_chosen), &key| {
+        match menu_state {
+            MenuState::Closed(_) => match key {
+                Return | Space => {
+                    let idx = selected.unwrap_or(0);
+                    (MenuState::Open(Highlighted(idx, len)), maybe_chosen)
+                },
+                _ => (menu_state, maybe_chosen),
+            },
+            MenuState::Open(draw_state) => {
+                let idx = match draw_state {
+                    Highlighted(idx, _) | Clicked(idx, _) => idx,
+                    Normal => selected.unwrap_or(0),
+                };
+                match key {
+                    Up => {
+                        let idx = if idx > 0 { idx - 1 } else { idx };
+                        (MenuState::Open(Highlighted(idx, len)), maybe_chosen)
+                    },
+                    Down => {
+                        let idx = if idx + 1 < len { idx + 1 } else { idx };
+                        (MenuState::Open(Highlighted(idx, len)), maybe_chosen)
+                    },
+                    Return | Space => (MenuState::Closed(Normal), Some(idx)),
+                    _ => (menu_state, maybe_chosen),
+                }
+            },
+        }
+    })
+}
+
 impl<'a, F> DropDownList<'a, F> {
 
     /// Construct a new DropDownList.
@@ -157,12 +261,14 @@ impl<'a, F> DropDownList<'a, F> {
             strings: strings,
             selected: selected,
             pos: Position::default(),
-            dim: [128.0, 32.0],
+            margin: Margin::none(),
+            padding: Padding::none(),
             maybe_h_align: None,
             maybe_v_align: None,
             depth: 0.0,
             maybe_react: None,
             maybe_label: None,
+            maybe_label_spans: None,
             style: Style::new(),
             enabled: true,
         }
@@ -174,12 +280,6 @@ impl<'a, F> DropDownList<'a, F> {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
-        self
-    }
-
 }
 
 
@@ -195,10 +295,17 @@ impl<'a, F> Widget for DropDownList<'a, F>
             menu_state: MenuState::Closed(Interaction::Normal),
             strings: Vec::new(),
             maybe_label: None,
+            maybe_label_spans: None,
             maybe_selected: None,
+            open_perc: 0.0,
+            show_mnemonic: false,
+            opens_upward: false,
+            is_focused: false,
+            enabled: self.enabled,
         }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the DropDownList.
     fn update<C>(mut self,
@@ -211,23 +318,63 @@ impl<'a, F> Widget for DropDownList<'a, F>
     {
 
         let widget::State { ref state, .. } = *prev_state;
-        let dim = self.dim;
+        let dim = style.dim(&ui.theme);
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
         let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
         let frame = style.frame(&ui.theme);
         let num_strings = self.strings.len();
-        let is_over_idx = is_over(mouse.xy, frame, dim, state.menu_state, num_strings);
+        let win_dim = [ui.win_w, ui.win_h];
+        let (open_xy, open_dim, opens_upward) = open_rect(xy, frame, dim, num_strings, win_dim);
+        let is_over_idx =
+            is_over(mouse.xy, frame, dim, state.menu_state, num_strings, opens_upward);
+        let maybe_mnemonic = self.maybe_label.and_then(label::mnemonic_char);
+        let show_mnemonic = self.enabled && maybe_mnemonic.is_some() && ui.is_alt_down();
+        let is_mnemonic_pressed =
+            self.enabled && maybe_mnemonic.map_or(false, |ch| ui.is_mnemonic_pressed(ui_id, ch));
         let new_menu_state = 
-            if self.enabled {
-                get_new_menu_state(is_over_idx, num_strings, state.menu_state, mouse)
-            } else {
+            if !self.enabled || ui.is_dismissed(ui_id) {
                 MenuState::Closed(Interaction::Normal)
+            } else if is_mnemonic_pressed && num_strings > 0 {
+                // The accelerator opens the list.
+                match state.menu_state {
+                    MenuState::Closed(_) => MenuState::Open(Interaction::Normal),
+                    menu_state => menu_state,
+                }
+            } else {
+                get_new_menu_state(is_over_idx, num_strings, state.menu_state, mouse)
             };
         let selected = self.selected.and_then(|idx| if idx < num_strings { Some(idx) }
                                                     else { None });
 
+        // Claim keyboard focus when clicked, after which the keyboard may also be used to open
+        // the list, move the highlighted item and choose it.
+        match new_menu_state {
+            MenuState::Closed(Interaction::Clicked(_, _)) |
+            MenuState::Open(Interaction::Clicked(_, _)) => if self.enabled { ui.focus(ui_id) },
+            _ => (),
+        }
+        let is_focused = self.enabled && ui.is_focused(ui_id);
+        let (new_menu_state, maybe_key_choice) = if is_focused && num_strings > 0 {
+            get_key_menu_state(ui.get_focused_keys(ui_id), num_strings, new_menu_state, selected)
+        } else {
+            (new_menu_state, None)
+        };
+
+        // Scrolling the mouse wheel over the closed list chooses the previous or next item.
+        let scroll = ui.get_scroll(ui_id);
+        let maybe_wheel_choice = match new_menu_state {
+            MenuState::Closed(_) if self.enabled && scroll[1] != 0.0 && num_strings > 0 => {
+                match (selected, scroll[1] > 0.0) {
+                    (Some(idx), true) => if idx > 0 { Some(idx - 1) } else { None },
+                    (Some(idx), false) => if idx + 1 < num_strings { Some(idx + 1) } else { None },
+                    (None, _) => Some(0),
+                }
+            },
+            _ => None,
+        };
+
         // Check whether or not we need to capture or uncapture the mouse.
         // We need to capture the cursor if the DropDownList has just been opened.
         // We need to uncapture the cursor if the DropDownList has just been closed.
@@ -237,32 +384,70 @@ impl<'a, F> Widget for DropDownList<'a, F>
             _ => (),
         }
 
-        // Call the `react` closure if mouse was released on one of the DropDownList items.
+        // Register the open list as a transient so that the `Ui` may dismiss it, and draw it within
+        // the popup layer so that it is drawn above (and hit-tested before) all other widgets.
+        match new_menu_state {
+            MenuState::Open(_) => {
+                let open_xy = ::vecmath::vec2_add(xy, open_xy);
+                ui.transient_opened_by(ui_id, open_xy, open_dim);
+                ui.set_layer(ui_id, Layer::Popup);
+            },
+            MenuState::Closed(_) => if let MenuState::Open(_) = state.menu_state {
+                ui.transient_closed_by(ui_id);
+                ui.reset_layer(ui_id);
+            },
+        }
+
+        // Call the `react` closure if mouse was released on one of the DropDownList items or if
+        // an item was chosen via the keyboard or the mouse wheel.
         if let Some(ref mut react) = self.maybe_react {
-            if let (MenuState::Open(o_d_state), MenuState::Closed(c_d_state)) =
-                (state.menu_state, new_menu_state) {
-                if let (Interaction::Clicked(idx, _), Interaction::Normal) = (o_d_state, c_d_state) {
-                    *self.selected = selected;
-                    react(self.selected, idx, self.strings[idx].clone())
-                }
+            let maybe_chosen = match (state.menu_state, new_menu_state) {
+                _ if maybe_key_choice.is_some() => maybe_key_choice,
+                _ if maybe_wheel_choice.is_some() => maybe_wheel_choice,
+                (MenuState::Open(Interaction::Clicked(idx, _)),
+                 MenuState::Closed(Interaction::Normal)) => Some(idx),
+                _ => None,
+            };
+            if let Some(idx) = maybe_chosen {
+                *self.selected = selected;
+                react(self.selected, idx, self.strings[idx].clone())
             }
         }
 
+        // Expand the menu smoothly rather than snapping it open.
+        let open_target = match new_menu_state {
+            MenuState::Open(_) => 1.0,
+            MenuState::Closed(_) => 0.0,
+        };
+        let open_perc = ui.animate_with_theme(ui_id, "open_perc", open_target);
+
         // Function for constructing a new DropDownList State.
         let construct_new_state = || {
             State {
                 menu_state: new_menu_state,
                 maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
                 strings: self.strings.clone(),
                 maybe_selected: *self.selected,
+                open_perc: open_perc,
+                show_mnemonic: show_mnemonic,
+                opens_upward: opens_upward,
+                is_focused: is_focused,
+                enabled: self.enabled,
             }
         };
 
         // Check whether or not the state has changed since the previous update.
         let state_has_changed = state.menu_state != new_menu_state
+            || state.open_perc != open_perc
+            || state.show_mnemonic != show_mnemonic
+            || state.opens_upward != opens_upward
+            || state.is_focused != is_focused
+            || state.enabled != self.enabled
             || &state.strings[..] != &(*self.strings)[..]
             || state.maybe_selected != *self.selected
-            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label;
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
 
         // Construct the new state if there was a change.
         let maybe_new_state = if state_has_changed { Some(construct_new_state()) }
@@ -271,55 +456,73 @@ impl<'a, F> Widget for DropDownList<'a, F>
         widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
     }
 
-    /// Construct an Element from the given DropDownList State.
-    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given DropDownList State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{collage, rect, text};
+        use elmesque::form::{rect, text};
         use elmesque::text::Text;
 
         let widget::State { ref state, dim, xy, .. } = *new_state;
-        let theme = &ui.theme;
 
         // Retrieve the styling for the Element..
-        let color = style.color(theme);
-        let frame = style.frame(theme);
-        let frame_color = style.frame_color(theme);
-        let label_color = style.label_color(theme);
-        let font_size = style.label_font_size(theme);
-        let pad_dim = ::vecmath::vec2_sub(dim, [frame * 2.0; 2]);
+        let color = enable::color(style.color(&ui.theme), state.enabled, &ui.theme);
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let frame_color = if state.is_focused { frame_color.highlighted() } else { frame_color };
+        let frame_color = enable::color(frame_color, state.enabled, &ui.theme);
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
 
         // Construct the DropDownList's Element.
         match state.menu_state {
 
             MenuState::Closed(draw_state) => {
-                let string = match state.maybe_selected {
-                    Some(idx) => state.strings[idx].clone(),
-                    None => match state.maybe_label {
-                        Some(ref label) => label.clone(),
-                        None => state.strings[0].clone(),
-                    },
-                };
-                let frame_form = rect(dim[0], dim[1]).filled(frame_color);
-                let inner_form = rect(pad_dim[0], pad_dim[1]).filled(draw_state.color(color));
-                let text_form = text(Text::from_string(string)
-                                         .color(label_color)
-                                         .height(font_size as f64));
+                // Show the selected string, otherwise the label or the first string.
+                let mut text_forms = Vec::new();
+                let maybe_spans = state.maybe_label_spans.as_ref();
+                if let (None, Some(spans)) = (state.maybe_selected, maybe_spans) {
+                    text_forms = spans.forms(ui, label_color, font_size).1;
+                } else {
+                    let (string, maybe_mnemonic_idx) = match state.maybe_selected {
+                        Some(idx) => (state.strings[idx].clone(), None),
+                        None => match state.maybe_label {
+                            Some(ref label) => label::strip_mnemonic(label),
+                            None => (state.strings[0].clone(), None),
+                        },
+                    };
+                    if let (true, Some(idx)) = (state.show_mnemonic, maybe_mnemonic_idx) {
+                        let underline =
+                            label::mnemonic_underline(ui, &string, idx, font_size, label_color);
+                        text_forms.push(underline);
+                    }
+                    text_forms.push(text(Text::from_string(string)
+                                             .color(label_color)
+                                             .height(font_size as f64)));
+                }
+                let radii = style.corner_radii(&ui.theme);
+                let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                             style.frame_style(&ui.theme), frame_color);
+                let inner_form = corner::rounded_rect(pad_dim, radii.inset(frame))
+                    .filled(draw_state.color(color))
+                    .shift(pad_offset[0], pad_offset[1]);
 
                 // Chain and shift the Forms into position.
                 let form_chain = Some(frame_form).into_iter()
                     .chain(Some(inner_form).into_iter())
-                    .chain(Some(text_form).into_iter())
+                    .chain(text_forms.into_iter())
                     .map(|form| form.shift(xy[0].floor(), xy[1].floor()));
 
-                // Collect the Form's into a renderable Element.
-                collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+                // Collect the Form's for drawing.
+                form_chain.collect()
             },
 
             MenuState::Open(draw_state) => {
-                // Chain and shift the Forms into position.
-                let form_chain = state.strings.iter().enumerate().flat_map(|(i, string)| {
+                // Chain and shift the Forms into position. Items are drawn in reverse so that
+                // each slides out from beneath the one above it while expanding.
+                let form_chain = state.strings.iter().enumerate().rev().flat_map(|(i, string)| {
                     let color = match state.maybe_selected {
                         None => match draw_state {
                             Interaction::Normal => color,
@@ -349,9 +552,12 @@ impl<'a, F> Widget for DropDownList<'a, F>
                             }
                         },
                     };
-                    let shift_amt = -(i as f64 * dim[1] - i as f64 * frame).floor();
+                    let item_y = i as f64 * dim[1] - i as f64 * frame;
+                    let shift_amt = (item_y * state.open_perc).floor();
+                    let shift_amt = if state.opens_upward { shift_amt } else { -shift_amt };
                     let frame_form = rect(dim[0], dim[1]).filled(frame_color);
-                    let inner_form = rect(pad_dim[0], pad_dim[1]).filled(color);
+                    let inner_form = rect(pad_dim[0], pad_dim[1]).filled(color)
+                        .shift(pad_offset[0], pad_offset[1]);
                     let text_form = text(Text::from_string(string.clone())
                                              .color(label_color)
                                              .height(font_size as f64));
@@ -360,14 +566,27 @@ impl<'a, F> Widget for DropDownList<'a, F>
                         .chain(Some(text_form.shift_y(shift_amt.floor())).into_iter())
                 }).map(|form| form.shift(xy[0].floor(), xy[1].floor()));
 
-                // Collect the Form's into a renderable Element.
-                collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+                // Collect the Form's for drawing.
+                form_chain.collect()
             },
 
         }
 
     }
 
+    /// Describe the DropDownList for assistive technologies, with the selected string as its value.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_selected = state.maybe_selected.and_then(|idx| state.strings.get(idx));
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        let accessibility = Accessibility::new(Role::DropDownList)
+            .label(maybe_label.map(|l| &l[..]));
+        match maybe_selected {
+            Some(selected) => accessibility.value(&selected[..]),
+            None => accessibility,
+        }
+    }
+
 }
 
 
@@ -377,18 +596,28 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
             maybe_label_color: None,
             maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_drop_down_list.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_drop_down_list.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -405,6 +634,34 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_drop_down_list.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_drop_down_list.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_drop_down_list.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of an Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_drop_down_list.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
     /// Get the label Color for an Element.
     pub fn label_color(&self, theme: &Theme) -> Color {
         self.maybe_label_color.or(theme.maybe_drop_down_list.as_ref().map(|style| {
@@ -419,9 +676,35 @@ impl Style {
         })).unwrap_or(theme.font_size_medium)
     }
 
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [128.0, 32.0];
+        let width = self.maybe_width.or(theme.maybe_drop_down_list.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_drop_down_list.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_drop_down_list.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
 }
 
 
+impl<'a, F> Enableable for DropDownList<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
 impl<'a, F> Colorable for DropDownList<'a, F> {
     fn color(mut self, color: Color) -> Self {
         self.style.maybe_color = Some(color);
@@ -429,6 +712,20 @@ impl<'a, F> Colorable for DropDownList<'a, F> {
     }
 }
 
+impl<'a, F> Paletted for DropDownList<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for DropDownList<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, F> Frameable for DropDownList<'a, F> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -438,6 +735,25 @@ impl<'a, F> Frameable for DropDownList<'a, F> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Roundable for DropDownList<'a, F> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
 }
 
 impl<'a, F> Labelable<'a> for DropDownList<'a, F> {
@@ -446,6 +762,11 @@ impl<'a, F> Labelable<'a> for DropDownList<'a, F> {
         self
     }
 
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
     fn label_color(mut self, color: Color) -> Self {
         self.style.maybe_label_color = Some(color);
         self
@@ -470,18 +791,28 @@ impl<'a, F> Positionable for DropDownList<'a, F> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         DropDownList { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl<'a, F> ::position::Sizeable for DropDownList<'a, F> {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        DropDownList { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        DropDownList { dim: [w, h], ..self }
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
     }
 }
 
@@ -0,0 +1,684 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize};
+use palette::{ColorRole, Paletted};
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               VerticalAlign};
+use theme::Theme;
+use touch::Gesture;
+use ui::{UiId, Ui};
+use utils::{clamp, is_over_rect};
+use widget::{self, Widget};
+
+
+/// The factor by which the zoom changes for each step of the mouse wheel.
+const ZOOM_PER_SCROLL: f64 = 1.1;
+
+/// An unbounded 2D space that may be panned and zoomed, drawn with a grid whose spacing adapts to
+/// the zoom and with rulers along its top and left edges. It is intended as the surface upon which
+/// editors (node graphs, timelines, diagrams, etc) lay out their content.
+///
+/// Drag with the middle mouse button (or with the left button while Alt is held) or scroll with
+/// two fingers to pan. Use the mouse wheel or pinch to zoom about the cursor.
+///
+/// As with the value of a Slider, the `View` is owned by the application. The EditorCanvas is
+/// given the current `View` each update, and its reaction is called with the new `View` whenever
+/// it is panned or zoomed. Content should be set after the EditorCanvas (so that it is drawn above
+/// it) and positioned via the `Transform` returned by `View::transform`, i.e.
+/// `view.transform(ui.widget_xy(EDITOR), ui.widget_size(EDITOR))`.
+pub struct EditorCanvas<F> {
+    view: View,
+    min_zoom: f64,
+    max_zoom: f64,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the EditorCanvas, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_grid_color: Option<Color>,
+    pub maybe_grid_spacing: Option<f64>,
+    pub maybe_ruler_width: Option<f64>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<FontSize>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The region of the world displayed by an EditorCanvas.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct View {
+    /// The point in the world displayed at the centre of the canvas.
+    pub centre: Point,
+    /// The number of pixels per unit of the world.
+    pub zoom: f64,
+}
+
+/// Converts between world coordinates and `Ui` coordinates for an EditorCanvas with some
+/// position and dimensions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    /// The region of the world displayed by the canvas.
+    pub view: View,
+    /// The centre of the canvas within the `Ui`.
+    pub xy: Point,
+    /// The dimensions of the canvas.
+    pub dim: Dimensions,
+}
+
+/// The state of the EditorCanvas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    view: View,
+    pan: Pan,
+}
+
+/// Whether or not the EditorCanvas is being panned by the mouse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Pan {
+    /// The panning button is up.
+    Idle,
+    /// The panning button was pressed outside of the canvas and is still held.
+    Blocked,
+    /// The canvas is being panned, with the position of the mouse at the previous update.
+    Panning(Point),
+}
+
+
+impl View {
+
+    /// A View of the world's origin at a zoom of one pixel per unit.
+    pub fn new() -> View {
+        View { centre: [0.0, 0.0], zoom: 1.0 }
+    }
+
+    /// The Transform for an EditorCanvas displaying this View at the given position with the
+    /// given dimensions.
+    pub fn transform(&self, xy: Point, dim: Dimensions) -> Transform {
+        Transform { view: *self, xy: xy, dim: dim }
+    }
+
+    /// The View zoomed by the given factor (clamped to the given limits) about the given point in
+    /// the world, so that the point remains at the same position within the canvas.
+    pub fn zoomed_about(&self, world: Point, factor: f64, min_zoom: f64, max_zoom: f64) -> View {
+        let zoom = clamp(self.zoom * factor, min_zoom, max_zoom);
+        let ratio = self.zoom / zoom;
+        let centre = [world[0] + (self.centre[0] - world[0]) * ratio,
+                      world[1] + (self.centre[1] - world[1]) * ratio];
+        View { centre: centre, zoom: zoom }
+    }
+
+}
+
+impl Transform {
+
+    /// Convert a point in the world to a point within the `Ui`.
+    pub fn to_ui(&self, world: Point) -> Point {
+        [self.xy[0] + (world[0] - self.view.centre[0]) * self.view.zoom,
+         self.xy[1] + (world[1] - self.view.centre[1]) * self.view.zoom]
+    }
+
+    /// Convert a point within the `Ui` (i.e. the mouse position) to a point in the world.
+    pub fn to_world(&self, xy: Point) -> Point {
+        [self.view.centre[0] + (xy[0] - self.xy[0]) / self.view.zoom,
+         self.view.centre[1] + (xy[1] - self.xy[1]) / self.view.zoom]
+    }
+
+    /// Convert a length in the world to a length within the `Ui`.
+    pub fn len_to_ui(&self, len: f64) -> f64 {
+        len * self.view.zoom
+    }
+
+    /// Convert a length within the `Ui` to a length in the world.
+    pub fn len_to_world(&self, len: f64) -> f64 {
+        len / self.view.zoom
+    }
+
+    /// The bottom left and top right corners of the region of the world visible within the canvas.
+    pub fn visible_world(&self) -> (Point, Point) {
+        let (half_w, half_h) = (self.dim[0] / 2.0, self.dim[1] / 2.0);
+        (self.to_world([self.xy[0] - half_w, self.xy[1] - half_h]),
+         self.to_world([self.xy[0] + half_w, self.xy[1] + half_h]))
+    }
+
+    /// Whether or not the given point within the `Ui` lies within the canvas. Useful for culling
+    /// content that has been panned out of view.
+    pub fn contains(&self, xy: Point) -> bool {
+        is_over_rect(self.xy, xy, self.dim)
+    }
+
+}
+
+
+/// The smallest step of the form `{1, 2, 5} * 10^n` that is no smaller than `min`.
+pub fn nice_step(min: f64) -> f64 {
+    let magnitude = 10.0f64.powf(min.log10().floor());
+    for &multiple in [1.0, 2.0, 5.0].iter() {
+        if magnitude * multiple >= min { return magnitude * multiple }
+    }
+    magnitude * 10.0
+}
+
+/// The grid lines visible along one axis, each given as its offset from the centre of the canvas
+/// in pixels, its value in the world and whether or not it is a major line.
+pub fn grid_lines(centre: f64,
+                  zoom: f64,
+                  half_len: f64,
+                  step: f64,
+                  major: f64) -> Vec<(f64, f64, bool)> {
+    let lo = centre - half_len / zoom;
+    let hi = centre + half_len / zoom;
+    let per_major = (major / step).round() as i64;
+    let first = (lo / step).ceil() as i64;
+    let last = (hi / step).floor() as i64;
+    (first..last + 1).map(|i| {
+        let value = i as f64 * step;
+        ((value - centre) * zoom, value, i % per_major == 0)
+    }).collect()
+}
+
+/// Format a ruler label for the given value, with as many decimal places as the step requires.
+pub fn ruler_label(value: f64, step: f64) -> String {
+    let decimals = if step >= 1.0 { 0 } else { (-step.log10().floor()) as usize };
+    format!("{:.*}", decimals, value)
+}
+
+
+impl<F> EditorCanvas<F> {
+
+    /// Construct an EditorCanvas displaying the given View.
+    pub fn new(view: View) -> EditorCanvas<F> {
+        EditorCanvas {
+            view: view,
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the reaction for the EditorCanvas. It will be triggered with the new View whenever the
+    /// canvas is panned or zoomed.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the minimum and maximum zoom in pixels per unit of the world.
+    pub fn zoom_limits(mut self, min: f64, max: f64) -> Self {
+        self.min_zoom = min;
+        self.max_zoom = max;
+        self
+    }
+
+    /// Set the color of the grid lines and ruler ticks.
+    pub fn grid_color(mut self, color: Color) -> Self {
+        self.style.maybe_grid_color = Some(color);
+        self
+    }
+
+    /// Set the minimum spacing between grid lines in pixels. The grid steps between multiples of
+    /// 1, 2 and 5 as the canvas is zoomed so that its lines are never closer than this.
+    pub fn grid_spacing(mut self, spacing: f64) -> Self {
+        self.style.maybe_grid_spacing = Some(spacing);
+        self
+    }
+
+    /// Set the width of the rulers. A width of zero hides them.
+    pub fn ruler_width(mut self, width: f64) -> Self {
+        self.style.maybe_ruler_width = Some(width);
+        self
+    }
+
+    /// Set the color of the ruler labels.
+    pub fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    /// Set the font size of the ruler labels.
+    pub fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+
+}
+
+
+impl<F> Widget for EditorCanvas<F>
+    where
+        F: FnMut(View),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "EditorCanvas" }
+    fn init_state(&self) -> State {
+        State { view: View::new(), pan: Pan::Idle }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the EditorCanvas.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::Down;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id);
+        let is_over = is_over_rect(xy, mouse.xy, dim);
+        let (min_zoom, max_zoom) = (self.min_zoom, self.max_zoom);
+        let mut view = self.view;
+
+        // Pan while the middle button (or the left button with Alt) is held after being pressed
+        // over the canvas.
+        let is_pan_down = match (mouse.middle.state, mouse.left.state) {
+            (Down, _) => true,
+            (_, Down) => ui.is_alt_down(),
+            _ => false,
+        };
+        let pan = match (self.enabled && is_pan_down, state.pan) {
+            (false, _) => Pan::Idle,
+            (true, Pan::Panning(prev_xy)) => {
+                view.centre[0] -= (mouse.xy[0] - prev_xy[0]) / view.zoom;
+                view.centre[1] -= (mouse.xy[1] - prev_xy[1]) / view.zoom;
+                Pan::Panning(mouse.xy)
+            },
+            (true, Pan::Idle) if is_over => Pan::Panning(mouse.xy),
+            (true, _) => Pan::Blocked,
+        };
+        let was_panning = if let Pan::Panning(_) = state.pan { true } else { false };
+        let is_panning = if let Pan::Panning(_) = pan { true } else { false };
+        ui.mouse_dragged_by(ui_id, was_panning, is_panning);
+
+        if self.enabled {
+            // Zoom about the cursor with the mouse wheel.
+            let scroll = ui.get_scroll(ui_id);
+            if is_over && scroll[1] != 0.0 {
+                let cursor = view.transform(xy, dim).to_world(mouse.xy);
+                let factor = ZOOM_PER_SCROLL.powf(scroll[1]);
+                view = view.zoomed_about(cursor, factor, min_zoom, max_zoom);
+            }
+
+            // Pan and zoom with two finger touch gestures.
+            for gesture in ui.gestures_over(xy, dim) {
+                match gesture {
+                    Gesture::Pinch { centre, scale } => {
+                        let about = view.transform(xy, dim).to_world(centre);
+                        view = view.zoomed_about(about, scale, min_zoom, max_zoom);
+                    },
+                    Gesture::Scroll { delta, .. } => {
+                        view.centre[0] -= delta[0] / view.zoom;
+                        view.centre[1] -= delta[1] / view.zoom;
+                    },
+                }
+            }
+        }
+
+        // React with the new View.
+        if view != self.view {
+            if let Some(ref mut react) = self.maybe_react { react(view) }
+        }
+
+        let new_state = State { view: view, pan: pan };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given EditorCanvas State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{line, rect, solid, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let View { centre, zoom } = state.view;
+
+        // Retrieve the styling for the Element.
+        let color = style.color(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let grid_color = style.grid_color(&ui.theme);
+        let spacing = style.grid_spacing(&ui.theme);
+        let ruler = style.ruler_width(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let (half_w, half_h) = (inner_dim[0] / 2.0, inner_dim[1] / 2.0);
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let inner_form = rect(inner_dim[0], inner_dim[1]).filled(color)
+            .shift(inner_offset[0], inner_offset[1]);
+
+        // Every major line is also labelled upon the rulers.
+        let step = nice_step(spacing.max(1.0) / zoom);
+        let major = nice_step(step * 4.5);
+        let xs = grid_lines(centre[0], zoom, half_w, step, major);
+        let ys = grid_lines(centre[1], zoom, half_h, step, major);
+
+        // Construct the grid line Forms.
+        let line_style = |is_major: bool| solid(grid_color).width(if is_major { 2.0 } else { 1.0 });
+        let vert_forms = xs.iter().map(|&(x, _, is_major)| {
+            line(line_style(is_major), 0.0, -half_h, 0.0, half_h).shift_x(x.floor())
+        });
+        let hori_forms = ys.iter().map(|&(y, _, is_major)| {
+            line(line_style(is_major), -half_w, 0.0, half_w, 0.0).shift_y(y.floor())
+        });
+        let mut forms: Vec<Form> = Some(frame_form).into_iter()
+            .chain(Some(inner_form).into_iter())
+            .chain(vert_forms)
+            .chain(hori_forms)
+            .collect();
+
+        // Construct the ruler Forms along the top and left edges.
+        if ruler > 0.0 {
+            let ruler_color = color.highlighted();
+            let tick_style = solid(label_color);
+            let top_y = half_h - ruler / 2.0;
+            let left_x = -half_w + ruler / 2.0;
+            forms.push(rect(inner_dim[0], ruler).filled(ruler_color).shift_y(top_y));
+            forms.push(rect(ruler, inner_dim[1]).filled(ruler_color).shift_x(left_x));
+            let label_h = font_size as f64;
+            for &(x, value, is_major) in xs.iter() {
+                if x < -half_w + ruler { continue }
+                let tick_len = if is_major { ruler / 2.0 } else { ruler / 4.0 };
+                let bottom = half_h - ruler;
+                forms.push(line(tick_style.clone(), 0.0, bottom, 0.0, bottom + tick_len)
+                    .shift_x(x.floor()));
+                if is_major {
+                    let string = ruler_label(value, step);
+                    let w = label::width(ui, font_size, &string);
+                    if x + 2.0 + w > half_w { continue }
+                    let label = Text::from_string(string).color(label_color).height(label_h);
+                    forms.push(text(label).shift(x.floor() + 2.0 + w / 2.0, top_y));
+                }
+            }
+            for &(y, value, is_major) in ys.iter() {
+                if y > half_h - ruler { continue }
+                let tick_len = if is_major { ruler / 2.0 } else { ruler / 4.0 };
+                let right = -half_w + ruler;
+                forms.push(line(tick_style.clone(), right - tick_len, 0.0, right, 0.0)
+                    .shift_y(y.floor()));
+                if is_major {
+                    let string = ruler_label(value, step);
+                    let w = label::width(ui, font_size, &string);
+                    if y + 2.0 + w > half_h - ruler { continue }
+                    let label = Text::from_string(string).color(label_color).height(label_h);
+                    forms.push(text(label).rotate(::std::f64::consts::PI / 2.0)
+                        .shift(left_x, y.floor() + 2.0 + w / 2.0));
+                }
+            }
+            forms.push(rect(ruler, ruler).filled(ruler_color).shift(left_x, top_y));
+        }
+
+        // Shift the Forms into position and collect them for drawing.
+        forms.into_iter().map(|form| form.shift(xy[0], xy[1])).collect()
+    }
+
+    /// Describe the EditorCanvas for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::EditorCanvas)
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_width: None,
+            maybe_height: None,
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_grid_color: None,
+            maybe_grid_spacing: None,
+            maybe_ruler_width: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [256.0, 256.0];
+        let width = self.maybe_width.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_editor_canvas.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_editor_canvas.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the Color of the grid lines and ruler ticks.
+    pub fn grid_color(&self, theme: &Theme) -> Color {
+        let default = self.color(theme).plain_contrast().alpha(0.15);
+        self.maybe_grid_color.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_grid_color.unwrap_or(default)
+        })).unwrap_or(default)
+    }
+
+    /// Get the minimum spacing between grid lines in pixels.
+    pub fn grid_spacing(&self, theme: &Theme) -> f64 {
+        const DEFAULT_SPACING: f64 = 16.0;
+        self.maybe_grid_spacing.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_grid_spacing.unwrap_or(DEFAULT_SPACING)
+        })).unwrap_or(DEFAULT_SPACING)
+    }
+
+    /// Get the width of the rulers.
+    pub fn ruler_width(&self, theme: &Theme) -> f64 {
+        const DEFAULT_RULER_WIDTH: f64 = 20.0;
+        self.maybe_ruler_width.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_ruler_width.unwrap_or(DEFAULT_RULER_WIDTH)
+        })).unwrap_or(DEFAULT_RULER_WIDTH)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_editor_canvas.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<F> Enableable for EditorCanvas<F> {
+    /// If true, the canvas may be panned and zoomed by the user. If false, it only displays the
+    /// given View.
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<F> Colorable for EditorCanvas<F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<F> Paletted for EditorCanvas<F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<F> Fontable for EditorCanvas<F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<F> Frameable for EditorCanvas<F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<F> Positionable for EditorCanvas<F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        EditorCanvas { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        EditorCanvas { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<F> ::position::Sizeable for EditorCanvas<F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -1,13 +1,19 @@
 
+use accessibility::{Accessibility, Role};
 use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::math::Scalar;
 use graphics::character::CharacterCache;
 use label::{self, FontSize, Labelable};
 use mouse::Mouse;
 use num::Float;
-use position::{self, Corner, Depth, Dimensions, HorizontalAlign, Point, Position, VerticalAlign};
+use palette::{ColorRole, Paletted};
+use position::{self, Corner, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
 use std::any::Any;
 use std::cmp::Ordering;
 use std::default::Default;
@@ -15,8 +21,9 @@ use std::fmt::Debug;
 use theme::Theme;
 use ui::{UiId, Ui};
 use utils::{clamp, map_range, percentage, val_to_string};
-use vecmath::vec2_sub;
+use vecmath::vec2_add;
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// Used for editing a series of 2D Points on a cartesian (X, Y) plane within some given range.
@@ -28,12 +35,14 @@ pub struct EnvelopeEditor<'a, E:'a, F> where E: EnvelopePoint {
     min_x: E::X, max_x: E::X,
     min_y: E::Y, max_y: E::Y,
     pos: Position,
-    dim: Dimensions,
+    margin: Margin,
+    padding: Padding,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
     depth: Depth,
     maybe_react: Option<F>,
     maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
     style: Style,
     enabled: bool,
 }
@@ -42,13 +51,20 @@ pub struct EnvelopeEditor<'a, E:'a, F> where E: EnvelopePoint {
 #[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
     pub maybe_label_color: Option<Color>,
     pub maybe_label_font_size: Option<FontSize>,
     pub maybe_value_font_size: Option<FontSize>,
     pub maybe_point_radius: Option<f64>,
     pub maybe_line_width: Option<f64>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
 }
 
 /// Represents the state of the EnvelopeEditor widget.
@@ -62,6 +78,7 @@ pub struct State<E> where E: EnvelopePoint {
     max_y: E::Y,
     skew_y_range: f32,
     maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
     maybe_closest_point: Option<(usize, (f64, f64))>,
 }
 
@@ -83,7 +100,8 @@ pub enum Elem {
     /// as well as the last mouse pos for comparison
     /// in determining new value.
     EnvPoint(usize, (f64, f64)),
-    /// Represents an EnvelopePoint's `curve` value.
+    /// Represents the curve handle at the middle of the segment following the EnvelopePoint at
+    /// `usize` index as well as the last mouse pos.
     CurvePoint(usize, (f64, f64)),
 }
 
@@ -110,10 +128,14 @@ pub trait EnvelopePoint: Any + Clone + Debug + PartialEq {
     fn set_x(&mut self, _x: Self::X);
     /// Set the Y value.
     fn set_y(&mut self, _y: Self::Y);
-    /// Return the bezier curve depth (-1. to 1.) for the next interpolation.
+    /// Return the curve depth (-1. to 1.) for the segment between this point and the next, where
+    /// 0.0 is a linear interpolation. Only used if `has_curve` returns true.
     fn get_curve(&self) -> f32 { 1.0 }
-    /// Set the bezier curve depth (-1. to 1.) for the next interpolation.
+    /// Set the curve depth (-1. to 1.) for the segment between this point and the next.
     fn set_curve(&mut self, _curve: f32) {}
+    /// Whether or not the point stores the curve depth given to `set_curve`. The segments
+    /// following points that don't are always drawn as straight lines and offer no curve handle.
+    fn has_curve() -> bool { false }
     /// Create a new EnvPoint.
     fn new(_x: Self::X, _y: Self::Y) -> Self;
 }
@@ -133,6 +155,63 @@ impl EnvelopePoint for Point {
     fn new(x: Scalar, y: Scalar) -> Point { [x, y] }
 }
 
+/// An envelope point that also stores the curve depth of the segment that follows it.
+impl EnvelopePoint for (Scalar, Scalar, f32) {
+    type X = Scalar;
+    type Y = Scalar;
+    /// Return the X value.
+    fn get_x(&self) -> Scalar { self.0 }
+    /// Return the Y value.
+    fn get_y(&self) -> Scalar { self.1 }
+    /// Set the X value.
+    fn set_x(&mut self, x: Scalar) { self.0 = x }
+    /// Set the Y value.
+    fn set_y(&mut self, y: Scalar) { self.1 = y }
+    /// Return the curve depth.
+    fn get_curve(&self) -> f32 { self.2 }
+    /// Set the curve depth.
+    fn set_curve(&mut self, curve: f32) { self.2 = curve }
+    /// The curve depth is stored as the third element.
+    fn has_curve() -> bool { true }
+    /// Create a new, linear Envelope Point.
+    fn new(x: Scalar, y: Scalar) -> (Scalar, Scalar, f32) { (x, y, 0.0) }
+}
+
+
+/// The exponent steepness of a segment with a curve depth of 1.0 or -1.0.
+const CURVE_STEEPNESS: f32 = 4.0;
+
+/// The number of lines used to approximate a curved segment when drawing.
+const CURVE_RESOLUTION: usize = 16;
+
+/// Map a linear progression `t` (0.0 to 1.0) along a segment to its curved progression for the
+/// given curve depth (-1.0 to 1.0). Positive depths rise quickly before flattening out, while
+/// negative depths start slowly. A depth of 0.0 is linear.
+pub fn curve_perc(t: f32, curve: f32) -> f32 {
+    t.powf((-curve * CURVE_STEEPNESS).exp())
+}
+
+/// Determine the curve depth required for a segment to pass through `mid_perc` (0.0 to 1.0) of its
+/// height at the middle of its width. This is the inverse of `curve_perc` where `t` is 0.5.
+fn curve_from_mid_perc(mid_perc: f32) -> f32 {
+    let mid_perc = clamp(mid_perc, 0.001, 0.999);
+    let exponent = mid_perc.ln() / (0.5f32).ln();
+    clamp(-exponent.ln() / CURVE_STEEPNESS, -1.0, 1.0)
+}
+
+/// The curve depth of the segment following the given point, which is linear for point types that
+/// can't store a curve.
+fn curve_of<E: EnvelopePoint>(pt: &E) -> f32 {
+    if E::has_curve() { pt.get_curve() } else { 0.0 }
+}
+
+/// The position of the curve handle for the segment beginning at `idx` as percentages of the pad.
+fn curve_handle_perc(perc_env: &[(f32, f32, f32)], idx: usize) -> (f32, f32) {
+    let (x_a, y_a, curve) = perc_env[idx];
+    let (x_b, y_b, _) = perc_env[idx + 1];
+    ((x_a + x_b) / 2.0, y_a + (y_b - y_a) * curve_perc(0.5, curve))
+}
+
 
 impl Interaction {
     /// Alter the widget color depending on the state.
@@ -143,6 +222,14 @@ impl Interaction {
             Interaction::Clicked(_, _) => color.clicked(),
         }
     }
+
+    /// Whether or not some element is currently clicked (and so may be being dragged).
+    fn is_clicked(&self) -> bool {
+        match *self {
+            Interaction::Clicked(_, _) => true,
+            _ => false,
+        }
+    }
 }
 
 
@@ -152,7 +239,8 @@ fn is_over_elem(mouse_xy: Point,
                 dim: Dimensions,
                 pad_dim: Dimensions,
                 perc_env: &[(f32, f32, f32)],
-                point_radius: Scalar) -> Option<Elem> {
+                point_radius: Scalar,
+                has_curves: bool) -> Option<Elem> {
     use utils::is_over_rect;
     if is_over_rect([0.0, 0.0], mouse_xy, dim) {
         if is_over_rect([0.0, 0.0], mouse_xy, pad_dim) {
@@ -168,6 +256,19 @@ fn is_over_elem(mouse_xy: Point,
                     return Some(Elem::EnvPoint(i, (p_xy[0], p_xy[1])));
                 }
             }
+            let num_curve_handles = if has_curves { perc_env.len().saturating_sub(1) } else { 0 };
+            for i in 0..num_curve_handles {
+                let (x, y) = curve_handle_perc(perc_env, i);
+                let half_pad_w = pad_dim[0] / 2.0;
+                let half_pad_h = pad_dim[1] / 2.0;
+                let p_xy = [map_range(x, 0.0, 1.0, -half_pad_w, half_pad_w),
+                            map_range(y, 0.0, 1.0, -half_pad_h, half_pad_h)];
+                let distance = (mouse_xy[0] - p_xy[0]).powf(2.0)
+                             + (mouse_xy[1] - p_xy[1]).powf(2.0);
+                if distance <= point_radius.powf(2.0) {
+                    return Some(Elem::CurvePoint(i, (p_xy[0], p_xy[1])));
+                }
+            }
             Some(Elem::Pad)
         } else {
             Some(Elem::Rect)
@@ -205,7 +306,7 @@ fn get_new_interaction(is_over_elem: Option<Elem>,
     use self::Elem::{EnvPoint, CurvePoint};
     use self::MouseButton::{Left, Right};
     use self::Interaction::{Normal, Highlighted, Clicked};
-    match (is_over_elem, prev, mouse.left, mouse.right) {
+    match (is_over_elem, prev, mouse.left.state, mouse.right.state) {
         (Some(_), Normal, Down, Up) => Normal,
         (Some(elem), _, Up, Up) => Highlighted(elem),
         (Some(elem), Highlighted(_), Down, Up) => Clicked(elem, Left),
@@ -278,12 +379,14 @@ impl<'a, E, F> EnvelopeEditor<'a, E, F> where E: EnvelopePoint {
             min_x: min_x, max_x: max_x,
             min_y: min_y, max_y: max_y,
             pos: Position::default(),
-            dim: [256.0, 128.0],
+            margin: Margin::none(),
+            padding: Padding::none(),
             maybe_h_align: None,
             maybe_v_align: None,
             depth: 0.0,
             maybe_react: None,
             maybe_label: None,
+            maybe_label_spans: None,
             style: Style::new(),
             enabled: true,
         }
@@ -295,12 +398,6 @@ impl<'a, E, F> EnvelopeEditor<'a, E, F> where E: EnvelopePoint {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
-        self
-    }
-
 }
 
 
@@ -335,10 +432,12 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
             max_y: self.max_y,
             skew_y_range: self.skew_y_range,
             maybe_label: None,
+            maybe_label_spans: None,
             maybe_closest_point: None,
         }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the EnvelopeEditor's cached state.
     fn update<C>(mut self,
@@ -350,18 +449,16 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
             C: CharacterCache,
     {
         let widget::State { ref state, .. } = *prev_state;
-        let dim = self.dim;
+        let dim = style.dim(&ui.theme);
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
-        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
         let skew = self.skew_y_range;
         let (min_x, max_x, min_y, max_y) = (self.min_x, self.max_x, self.min_y, self.max_y);
 
         let pt_radius = style.point_radius(&ui.theme);
-        let frame = style.frame(&ui.theme);
-        let frame_2 = frame * 2.0;
-        let pad_dim = vec2_sub(dim, [frame_2; 2]);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let mouse = ui.get_mouse_state(ui_id).relative_to(vec2_add(xy, pad_offset));
         let half_pad_w = pad_dim[0] / 2.0;
         let half_pad_h = pad_dim[1] / 2.0;
 
@@ -370,11 +467,12 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
         let perc_env: Vec<(f32, f32, f32)> = self.env.iter().map(|pt| {
             (percentage(pt.get_x(), min_x, max_x),
              percentage(pt.get_y(), min_y, max_y).powf(1.0 / skew),
-             pt.get_curve())
+             curve_of(pt))
         }).collect();
 
         // Check for new state.
-        let is_over_elem = is_over_elem(mouse.xy, dim, pad_dim, &perc_env[..], pt_radius);
+        let is_over_elem =
+            is_over_elem(mouse.xy, dim, pad_dim, &perc_env[..], pt_radius, E::has_curve());
         let new_interaction = 
             if self.enabled {
                 get_new_interaction(is_over_elem, state.interaction, mouse)
@@ -382,6 +480,9 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
                 Interaction::Normal
             };
 
+        // Keep hold of the mouse while dragging so the drag may continue beyond the editor.
+        ui.mouse_dragged_by(ui_id, state.interaction.is_clicked(), new_interaction.is_clicked());
+
         // Draw the closest envelope point and it's label. Return the idx if it is currently clicked.
         let is_clicked_env_point = match new_interaction {
             Interaction::Clicked(elem, _) | Interaction::Highlighted(elem) => {
@@ -403,6 +504,38 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
              map_range(new_y_perc, 0.0, 1.0, min_y, max_y))
         };
 
+        // If a curve handle is being dragged with the left button, adjust the segment's curve so
+        // that its middle follows the mouse. A right click resets the segment to linear.
+        let is_clicked_curve_point = match (state.interaction, new_interaction) {
+            (_, Interaction::Clicked(Elem::CurvePoint(idx, _), MouseButton::Left)) =>
+                Some((idx, MouseButton::Left)),
+            (Interaction::Clicked(Elem::CurvePoint(idx, _), MouseButton::Right),
+             Interaction::Highlighted(_)) => Some((idx, MouseButton::Right)),
+            _ => None,
+        };
+        if let Some((idx, m_button)) = is_clicked_curve_point {
+            if idx + 1 < perc_env.len() {
+                let new_curve = match m_button {
+                    MouseButton::Left => {
+                        let (_, y_a, _) = perc_env[idx];
+                        let (_, y_b, _) = perc_env[idx + 1];
+                        if y_a != y_b {
+                            let mouse_y = clamp(mouse.xy[1], -half_pad_h, half_pad_h);
+                            let mouse_y_perc = percentage(mouse_y, -half_pad_h, half_pad_h);
+                            curve_from_mid_perc((mouse_y_perc - y_a) / (y_b - y_a))
+                        } else {
+                            self.env[idx].get_curve()
+                        }
+                    },
+                    MouseButton::Right => 0.0,
+                };
+                if new_curve != self.env[idx].get_curve() {
+                    self.env[idx].set_curve(new_curve);
+                    if let Some(ref mut react) = self.maybe_react { react(self.env, idx) }
+                }
+            }
+        }
+
         // If a point is currently clicked, check for react and value setting conditions.
         if let Some(idx) = is_clicked_env_point {
 
@@ -506,6 +639,7 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
                 skew_y_range: skew,
                 maybe_closest_point: maybe_closest_point,
                 maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
             }
         };
 
@@ -515,7 +649,8 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
             || state.min_x != min_x || state.max_x != max_x
             || state.min_y != min_y || state.max_y != max_y
             || state.skew_y_range != skew
-            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label;
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
 
         // If so, construct the new state.
         let maybe_new_state = if state_has_changed { Some(construct_new_state()) }
@@ -524,17 +659,17 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
         widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
     }
 
-    /// Construct an Element from the given EnvelopeEditor State.
-    fn draw<C>(new_state: &widget::State<State<E>>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given EnvelopeEditor State.
+    fn draw<C>(new_state: &widget::State<State<E>>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{circle, collage, Form, line, rect, solid, text};
+        use elmesque::form::{circle, line, rect, solid, text};
         use elmesque::text::Text;
 
         let widget::State { ref state, dim, xy, .. } = *new_state;
         let frame = style.frame(&ui.theme);
-        let pad_dim = vec2_sub(dim, [frame * 2.0; 2]);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
         let (half_pad_w, half_pad_h) = (pad_dim[0] / 2.0, pad_dim[1] / 2.0);
         let skew = state.skew_y_range;
         let (min_x, max_x, min_y, max_y) = (state.min_x, state.max_x, state.min_y, state.max_y);
@@ -542,36 +677,48 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
         // Construct the frame and inner rectangle Forms.
         let value_font_size = style.value_font_size(&ui.theme);
         let frame_color = style.frame_color(&ui.theme);
-        let frame_form = rect(dim[0], dim[1]).filled(frame_color);
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
         let color = state.interaction.color(style.color(&ui.theme));
         let pressable_form = rect(pad_dim[0], pad_dim[1]).filled(color);
 
-        // Construct the label Form.
-        let maybe_label_form = state.maybe_label.as_ref().map(|l_text| {
-            let l_color = style.label_color(&ui.theme);
-            let l_size = style.label_font_size(&ui.theme);
-            text(Text::from_string(l_text.clone()).color(l_color).height(l_size as f64))
-        });
+        // Construct the label Forms.
+        let l_color = style.label_color(&ui.theme);
+        let l_size = style.label_font_size(&ui.theme);
+        let label_forms = match (state.maybe_label_spans.as_ref(), state.maybe_label.as_ref()) {
+            (Some(spans), _) => spans.forms(ui, l_color, l_size).1,
+            (None, Some(l_text)) => {
+                let l_text = Text::from_string(l_text.clone()).color(l_color);
+                vec![text(l_text.height(l_size as f64))]
+            },
+            (None, None) => Vec::new(),
+        };
 
         // Create a vector with each EnvelopePoint value represented as a skewed weight
         // between 0.0 and 1.0.
         let perc_env: Vec<(f32, f32, f32)> = state.env.iter().map(|pt| {
             (percentage(pt.get_x(), min_x, max_x),
              percentage(pt.get_y(), min_y, max_y).powf(1.0 / skew),
-             pt.get_curve())
+             curve_of(pt))
         }).collect();
 
-        // Draw the envelope lines.
+        // Draw the envelope lines, approximating curved segments with a series of lines.
         let line_color = color.plain_contrast();
         let line_width = style.line_width(&ui.theme);
-        let envelope_line_forms = perc_env.windows(2).map(|window| {
-            let ((x_a, y_a, _), (x_b, y_b, _)) = (window[0], window[1]);
-            let p_a = [map_range(x_a, 0.0, 1.0, -half_pad_w, half_pad_w),
-                       map_range(y_a, 0.0, 1.0, -half_pad_h, half_pad_h)];
-            let p_b = [map_range(x_b, 0.0, 1.0, -half_pad_w, half_pad_w),
-                       map_range(y_b, 0.0, 1.0, -half_pad_h, half_pad_h)];
+        let envelope_line_forms = perc_env.windows(2).flat_map(|window| {
+            let ((x_a, y_a, curve), (x_b, y_b, _)) = (window[0], window[1]);
+            let num_lines = if curve == 0.0 { 1 } else { CURVE_RESOLUTION };
+            let points: Vec<Point> = (0..num_lines + 1).map(|i| {
+                let t = i as f32 / num_lines as f32;
+                let x = x_a + (x_b - x_a) * t;
+                let y = y_a + (y_b - y_a) * curve_perc(t, curve);
+                [map_range(x, 0.0, 1.0, -half_pad_w, half_pad_w),
+                 map_range(y, 0.0, 1.0, -half_pad_h, half_pad_h)]
+            }).collect();
             let style = solid(line_color).width(line_width);
-            line(style, p_a[0], p_a[1], p_b[0], p_b[1])
+            points.windows(2).map(|pair| {
+                line(style.clone(), pair[0][0], pair[0][1], pair[1][0], pair[1][1])
+            }).collect::<Vec<_>>().into_iter()
         });
 
         // Draw the closest envelope point and it's label. Return the idx if it is currently clicked.
@@ -624,6 +771,19 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
                         let point_form = env_pt_form(ui, &state.env[..], idx, p_pos_clamped);
                         Some(point_form)
                     },
+                    // If a curve handle is highlighted or clicked, draw the handle.
+                    Elem::CurvePoint(idx, _) => if idx + 1 < perc_env.len() {
+                        let (x, y) = curve_handle_perc(&perc_env[..], idx);
+                        let handle_xy = [map_range(x, 0.0, 1.0, -half_pad_w, half_pad_w),
+                                         map_range(y, 0.0, 1.0, -half_pad_h, half_pad_h)];
+                        let point_radius = style.point_radius(&ui.theme);
+                        let handle_form = circle(point_radius * 0.75)
+                            .filled(color.plain_contrast().alpha(0.5))
+                            .shift(handle_xy[0].floor(), handle_xy[1].floor());
+                        Some(Some(handle_form).into_iter().chain(None.into_iter()))
+                    } else {
+                        None
+                    },
                     // Otherwise, draw the closest point if there is one.
                     Elem::Pad => if let Some((closest_idx, (x, y))) = state.maybe_closest_point {
                         Some(env_pt_form(ui, &state.env[..], closest_idx, [x, y]))
@@ -639,25 +799,33 @@ impl<'a, E, F> Widget for EnvelopeEditor<'a, E, F>
         };
 
         // Group the different Forms into a single form.
-        let form_chain = Some(frame_form).into_iter()
-            .chain(Some(pressable_form).into_iter())
-            .chain(maybe_label_form.into_iter())
-            .chain(envelope_line_forms);
-        let forms = match maybe_closest_point_form {
-            Some(closest_point_form) => form_chain
-                .chain(closest_point_form)
-                .map(|form| form.shift(xy[0].floor(), xy[1].floor()))
-                .collect(),
-            None => form_chain
-                .map(|form| form.shift(xy[0].floor(), xy[1].floor()))
-                .collect(),
-        };
+        let mut pad_forms: Vec<Form> = Some(pressable_form).into_iter()
+            .chain(label_forms.into_iter())
+            .chain(envelope_line_forms)
+            .collect();
+        if let Some(closest_point_form) = maybe_closest_point_form {
+            pad_forms.extend(closest_point_form);
+        }
+        let forms = Some(frame_form).into_iter()
+            .chain(pad_forms.into_iter().map(|form| form.shift(pad_offset[0], pad_offset[1])))
+            .map(|form| form.shift(xy[0].floor(), xy[1].floor()))
+            .collect();
 
         // Turn the form into a renderable element.
-        collage(dim[0] as i32, dim[1] as i32, forms)
+        forms
 
     }
 
+    /// Describe the EnvelopeEditor for assistive technologies, with the number of points as its
+    /// value.
+    fn accessibility(state: &State<E>) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::EnvelopeEditor)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(state.env.len())
+    }
+
 }
 
 
@@ -667,21 +835,30 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
             maybe_label_color: None,
             maybe_label_font_size: None,
             maybe_value_font_size: None,
             maybe_point_radius: None,
             maybe_line_width: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_envelope_editor.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_envelope_editor.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -698,6 +875,27 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_envelope_editor.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_envelope_editor.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_envelope_editor.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
     /// Get the label Color for an Element.
     pub fn label_color(&self, theme: &Theme) -> Color {
         self.maybe_label_color.or(theme.maybe_envelope_editor.as_ref().map(|style| {
@@ -736,9 +934,38 @@ impl Style {
         })).unwrap_or(DEFAULT_LINE_WIDTH)
     }
 
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [256.0, 128.0];
+        let width = self.maybe_width.or(theme.maybe_envelope_editor.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_envelope_editor.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_envelope_editor.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
 }
 
 
+impl<'a, E, F> Enableable for EnvelopeEditor<'a, E, F>
+    where
+        E: EnvelopePoint
+{
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
 impl<'a, E, F> Colorable for EnvelopeEditor<'a, E, F>
     where
         E: EnvelopePoint
@@ -749,6 +976,26 @@ impl<'a, E, F> Colorable for EnvelopeEditor<'a, E, F>
     }
 }
 
+impl<'a, E, F> Paletted for EnvelopeEditor<'a, E, F>
+    where
+        E: EnvelopePoint
+{
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, E, F> Fontable for EnvelopeEditor<'a, E, F>
+    where
+        E: EnvelopePoint
+{
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, E, F> Frameable for EnvelopeEditor<'a, E, F>
     where
         E: EnvelopePoint
@@ -761,6 +1008,18 @@ impl<'a, E, F> Frameable for EnvelopeEditor<'a, E, F>
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
 }
 
 impl<'a, E, F> Labelable<'a> for EnvelopeEditor<'a, E, F>
@@ -772,6 +1031,11 @@ impl<'a, E, F> Labelable<'a> for EnvelopeEditor<'a, E, F>
         self
     }
 
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
     fn label_color(mut self, color: Color) -> Self {
         self.style.maybe_label_color = Some(color);
         self
@@ -799,6 +1063,11 @@ impl<'a, E, F> position::Positionable for EnvelopeEditor<'a, E, F>
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         EnvelopeEditor { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl<'a, E, F> position::Sizeable for EnvelopeEditor<'a, E, F>
@@ -806,14 +1075,72 @@ impl<'a, E, F> position::Sizeable for EnvelopeEditor<'a, E, F>
         E: EnvelopePoint
 {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        EnvelopeEditor { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        EnvelopeEditor { dim: [w, h], ..self }
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::{curve_from_mid_perc, curve_handle_perc, curve_perc};
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1.0e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn curves_always_meet_the_segment_endpoints() {
+        for &curve in &[-1.0, -0.5, 0.0, 0.5, 1.0] {
+            assert_close(curve_perc(0.0, curve), 0.0);
+            assert_close(curve_perc(1.0, curve), 1.0);
+        }
+    }
+
+    #[test]
+    fn flat_curves_are_linear() {
+        for &t in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+            assert_close(curve_perc(t, 0.0), t);
+        }
+        assert_close(curve_from_mid_perc(0.5), 0.0);
+    }
+
+    #[test]
+    fn positive_curves_rise_before_negative_curves() {
+        assert!(curve_perc(0.5, 0.5) > 0.5);
+        assert!(curve_perc(0.5, -0.5) < 0.5);
+    }
+
+    #[test]
+    fn curve_from_mid_perc_inverts_the_mid_point() {
+        for &curve in &[-0.5, -0.25, 0.0, 0.25, 0.5] {
+            assert_close(curve_from_mid_perc(curve_perc(0.5, curve)), curve);
+        }
+        // Mid-points beyond the reachable range clamp to the deepest curve.
+        assert_close(curve_from_mid_perc(1.0), 1.0);
+    }
+
+    #[test]
+    fn curve_handles_sit_on_the_middle_of_their_segment() {
+        let env = [(0.0, 0.0, 0.0), (0.5, 1.0, 0.5), (1.0, 0.0, 0.0)];
+        let (x, y) = curve_handle_perc(&env, 0);
+        assert_close(x, 0.25);
+        assert_close(y, 0.5);
+        // A falling segment is curved by the same depth from its starting height.
+        let (x, y) = curve_handle_perc(&env, 1);
+        assert_close(x, 0.75);
+        assert_close(y, 1.0 - curve_perc(0.5, 0.5));
+    }
+
+}
@@ -0,0 +1,742 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use id::IdGenerator;
+use label::{self, FontSize, Labelable};
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               Positionable, Sizeable, VerticalAlign};
+use std::fs;
+use std::path::{Path, PathBuf};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::is_over_rect;
+use widget::{self, Widget};
+use widget::button::Button;
+use widget::drop_down_list::DropDownList;
+use widget::text_box::TextBox;
+
+
+/// Index of a FileDialog's filter or listed entry.
+pub type Idx = usize;
+
+/// A filter for the files listed by a FileDialog, given as its name and the extensions of the
+/// files it shows (i.e. `("Images", &["png", "jpg"])`). A filter with no extensions shows all
+/// files.
+pub type Filter<'a> = (&'a str, &'a [&'a str]);
+
+/// The distance between the FileDialog's edge and its contents, along with that between its
+/// controls.
+const SPACING: f64 = 8.0;
+/// The width of each of the OK and Cancel buttons.
+const BUTTON_WIDTH: f64 = 72.0;
+/// The width of the filter drop down list.
+const FILTER_WIDTH: f64 = 128.0;
+
+/// A dialog for choosing a file, listing the contents of a directory via `std::fs`.
+///
+/// The path of the listed directory is shown as a row of breadcrumbs above the listing, any of
+/// which may be clicked to list that directory instead. Clicking an entry selects it and double
+/// clicking opens a directory or chooses a file. The name of the chosen file may also be typed
+/// into the text box beneath the listing, beside a drop down list of the dialog's filters.
+///
+/// The reaction is triggered with the path of the chosen file upon pressing OK (or Enter within
+/// the text box), or with `None` upon pressing Cancel. The listed directory, selection and
+/// filename persist within the FileDialog's `State`, so the application need only stop setting
+/// the dialog once it has reacted.
+pub struct FileDialog<'a, F> {
+    initial_dir: &'a Path,
+    filters: &'a [Filter<'a>],
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the FileDialog, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_row_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The state of the FileDialog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    /// The directory being listed.
+    dir: PathBuf,
+    /// The directory and filter for which `entries` were last read.
+    maybe_listed: Option<(PathBuf, Idx)>,
+    entries: Vec<Entry>,
+    /// The reason the directory could not be read, if it could not.
+    maybe_error: Option<String>,
+    maybe_selected: Option<Idx>,
+    filename: String,
+    filter: Idx,
+    /// The distance by which the listing has been scrolled down.
+    scroll: f64,
+    /// Whether or not the left mouse button was down as of the previous update.
+    was_down: bool,
+}
+
+/// A directory or file within the listed directory.
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+/// The rectangles of the FileDialog's areas and controls.
+struct Layout {
+    crumbs: (Point, Dimensions),
+    listing: (Point, Dimensions),
+    filename: (Point, Dimensions),
+    filter: (Point, Dimensions),
+    ok: (Point, Dimensions),
+    cancel: (Point, Dimensions),
+}
+
+
+/// Divide the FileDialog's area into rows for the breadcrumbs, the listing and the controls.
+fn layout(xy: Point, dim: Dimensions, frame: f64, row_h: f64, has_filters: bool) -> Layout {
+    let inner_w = dim[0] - frame * 2.0 - SPACING * 2.0;
+    let left = xy[0] - inner_w / 2.0;
+    let top = xy[1] + dim[1] / 2.0 - frame - SPACING;
+    let bottom = xy[1] - dim[1] / 2.0 + frame + SPACING;
+    let crumbs = ([xy[0], top - row_h / 2.0], [inner_w, row_h]);
+    let listing_top = top - row_h - SPACING;
+    let listing_bottom = bottom + row_h + SPACING;
+    let listing_h = (listing_top - listing_bottom).max(0.0);
+    let listing = ([xy[0], listing_bottom + listing_h / 2.0], [inner_w, listing_h]);
+
+    // The controls are laid out from the right, leaving the remaining width to the filename.
+    let y = bottom + row_h / 2.0;
+    let right = xy[0] + inner_w / 2.0;
+    let cancel = ([right - BUTTON_WIDTH / 2.0, y], [BUTTON_WIDTH, row_h]);
+    let ok_right = right - BUTTON_WIDTH - SPACING;
+    let ok = ([ok_right - BUTTON_WIDTH / 2.0, y], [BUTTON_WIDTH, row_h]);
+    let filter_w = if has_filters { FILTER_WIDTH } else { 0.0 };
+    let filter_right = ok_right - BUTTON_WIDTH - SPACING;
+    let filter = ([filter_right - filter_w / 2.0, y], [filter_w, row_h]);
+    let filename_right = if has_filters { filter_right - filter_w - SPACING } else { filter_right };
+    let filename_w = (filename_right - left).max(0.0);
+    let filename = ([left + filename_w / 2.0, y], [filename_w, row_h]);
+
+    Layout {
+        crumbs: crumbs,
+        listing: listing,
+        filename: filename,
+        filter: filter,
+        ok: ok,
+        cancel: cancel,
+    }
+}
+
+/// Each ancestor of the given directory (including itself) along with the name shown for it.
+fn crumbs(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut path = PathBuf::new();
+    dir.components().map(|component| {
+        path.push(component.as_os_str());
+        (component.as_os_str().to_string_lossy().into_owned(), path.clone())
+    }).collect()
+}
+
+/// The rectangle of each breadcrumb that fits within the given row, from left to right. Where the
+/// path is too long to fit, the crumbs nearest the root are left out.
+fn crumb_rects<C>(ui: &mut Ui<C>,
+                  names: &[String],
+                  row: (Point, Dimensions),
+                  font_size: FontSize) -> Vec<(Idx, Point, Dimensions)>
+    where
+        C: CharacterCache,
+{
+    let (row_xy, row_dim) = row;
+    let widths: Vec<f64> = names.iter()
+        .map(|name| label::width(ui, font_size, name) + SPACING * 2.0)
+        .collect();
+    let mut first = widths.len();
+    let mut total = 0.0;
+    while first > 0 && total + widths[first - 1] <= row_dim[0] {
+        first -= 1;
+        total += widths[first];
+    }
+    let mut left = row_xy[0] - row_dim[0] / 2.0;
+    (first..widths.len()).map(|i| {
+        let rect = (i, [left + widths[i] / 2.0, row_xy[1]], [widths[i], row_dim[1]]);
+        left += widths[i];
+        rect
+    }).collect()
+}
+
+/// The rectangles of the listed entries that are wholly visible given the listing's scroll.
+fn row_rects(listing: (Point, Dimensions),
+             row_h: f64,
+             scroll: f64,
+             num_entries: usize) -> Vec<(Idx, Point, Dimensions)> {
+    let (l_xy, l_dim) = listing;
+    let (top, bottom) = (l_xy[1] + l_dim[1] / 2.0, l_xy[1] - l_dim[1] / 2.0);
+    (0..num_entries).filter_map(|i| {
+        let row_top = top - i as f64 * row_h + scroll;
+        if row_top <= top + 0.5 && row_top - row_h >= bottom - 0.5 {
+            Some((i, [l_xy[0], row_top - row_h / 2.0], [l_dim[0], row_h]))
+        } else {
+            None
+        }
+    }).collect()
+}
+
+/// Read the directories and files within the given directory, directories first, leaving out
+/// hidden entries and any files without one of the given extensions (unless there are none).
+fn list(dir: &Path, extensions: &[&str]) -> Result<Vec<Entry>, String> {
+    let read_dir = try!(fs::read_dir(dir).map_err(|err| err.to_string()));
+    let mut entries = Vec::new();
+    for maybe_entry in read_dir {
+        let entry = try!(maybe_entry.map_err(|err| err.to_string()));
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let is_dir = fs::metadata(entry.path()).map(|data| data.is_dir()).unwrap_or(false);
+        let is_shown = is_dir || extensions.is_empty() || {
+            let path = entry.path();
+            let maybe_ext = path.extension().map(|ext| ext.to_string_lossy().to_lowercase());
+            maybe_ext.map_or(false, |ext| extensions.iter().any(|e| e.to_lowercase() == ext))
+        };
+        if is_shown {
+            entries.push(Entry { name: name, is_dir: is_dir });
+        }
+    }
+    entries.sort_by(|a, b| {
+        (!a.is_dir, a.name.to_lowercase()).cmp(&(!b.is_dir, b.name.to_lowercase()))
+    });
+    Ok(entries)
+}
+
+
+impl<'a, F> FileDialog<'a, F> {
+
+    /// Construct a new FileDialog, initially listing the given directory.
+    pub fn new(initial_dir: &'a Path) -> FileDialog<'a, F> {
+        FileDialog {
+            initial_dir: initial_dir,
+            filters: &[],
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the filters that may be chosen between to limit the files listed. The first filter
+    /// is chosen initially. By default, all files are listed.
+    pub fn filters(mut self, filters: &'a [Filter<'a>]) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Set the height of each row of the listing, along with that of the controls.
+    pub fn row_height(mut self, height: f64) -> Self {
+        self.style.maybe_row_height = Some(height);
+        self
+    }
+
+    /// Set the reaction for the FileDialog. It is triggered with the path of the chosen file, or
+    /// with `None` if the dialog was cancelled.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the depth of the FileDialog.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for FileDialog<'a, F>
+    where
+        F: FnMut(Option<PathBuf>),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "FileDialog" }
+    fn init_state(&self) -> State {
+        State {
+            dir: self.initial_dir.to_path_buf(),
+            maybe_listed: None,
+            entries: Vec::new(),
+            maybe_error: None,
+            maybe_selected: None,
+            filename: String::new(),
+            filter: 0,
+            scroll: 0.0,
+            was_down: false,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the FileDialog, setting the controls within it.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::Down;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let frame = style.frame(&ui.theme);
+        let row_h = style.row_height(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let font = style.font(&ui.theme);
+        let has_filters = !self.filters.is_empty();
+        let layout = layout(xy, dim, frame, row_h, has_filters);
+
+        let mut dir = state.dir.clone();
+        let mut maybe_selected = state.maybe_selected;
+        let mut filename = state.filename.clone();
+        let mut filter = if state.filter < self.filters.len() { state.filter } else { 0 };
+        let mut scroll = state.scroll;
+        let mut is_confirmed = false;
+        let mut is_cancelled = false;
+
+        // Scroll the listing with the mouse wheel and handle presses upon the breadcrumbs and
+        // the listed entries.
+        let mouse = ui.get_mouse_state(ui_id);
+        let is_down = mouse.left.state == Down;
+        let is_pressed = self.enabled && is_down && !state.was_down;
+        let (listing_xy, listing_dim) = layout.listing;
+        let is_over_listing = is_over_rect(listing_xy, mouse.xy, listing_dim);
+        let max_scroll = (state.entries.len() as f64 * row_h - listing_dim[1]).max(0.0);
+        if self.enabled && is_over_listing {
+            scroll = (scroll - ui.get_scroll(ui_id)[1]).max(0.0).min(max_scroll);
+        }
+        if is_pressed {
+            let crumbs = crumbs(&dir);
+            let names: Vec<String> = crumbs.iter().map(|&(ref name, _)| name.clone()).collect();
+            let crumb_rects = crumb_rects(ui, &names, layout.crumbs, font_size);
+            let rows = row_rects(layout.listing, row_h, scroll, state.entries.len());
+            let is_over = |&(_, r_xy, r_dim): &(Idx, Point, Dimensions)| {
+                is_over_rect(r_xy, mouse.xy, r_dim)
+            };
+            if let Some(&(idx, _, _)) = crumb_rects.iter().find(|rect| is_over(rect)) {
+                dir = crumbs[idx].1.clone();
+            } else if let Some(&(idx, _, _)) = rows.iter().find(|rect| is_over(rect)) {
+                let entry = &state.entries[idx];
+                maybe_selected = Some(idx);
+                if !entry.is_dir {
+                    filename = entry.name.clone();
+                }
+                if mouse.left.clicks >= 2 {
+                    if entry.is_dir { dir = dir.join(&entry.name) } else { is_confirmed = true }
+                }
+            }
+        }
+
+        // Set the controls, each with an id namespaced by the FileDialog's own.
+        let mut ids = IdGenerator::new(ui_id);
+        let (filename_id, filter_id) = (ids.next(ui), ids.next(ui));
+        let (ok_id, cancel_id) = (ids.next(ui), ids.next(ui));
+        let (filename_xy, filename_dim) = layout.filename;
+        TextBox::new(&mut filename)
+            .point(filename_xy)
+            .dim(filename_dim)
+            .font(font)
+            .font_size(font_size)
+            .enabled(self.enabled)
+            .react(|_: &mut String| is_confirmed = true)
+            .set(filename_id, ui);
+        if has_filters {
+            let (filter_xy, filter_dim) = layout.filter;
+            let mut names: Vec<String> =
+                self.filters.iter().map(|&(name, _)| name.to_string()).collect();
+            let mut maybe_filter = Some(filter);
+            DropDownList::new(&mut names, &mut maybe_filter)
+                .point(filter_xy)
+                .dim(filter_dim)
+                .font(font)
+                .label_font_size(font_size)
+                .enabled(self.enabled)
+                .react(|_: &mut Option<Idx>, _: Idx, _: String| ())
+                .set(filter_id, ui);
+            filter = maybe_filter.unwrap_or(0);
+        }
+        let (ok_xy, ok_dim) = layout.ok;
+        Button::new()
+            .point(ok_xy)
+            .dim(ok_dim)
+            .label("OK")
+            .font(font)
+            .label_font_size(font_size)
+            .enabled(self.enabled && !filename.is_empty())
+            .react(|| is_confirmed = true)
+            .set(ok_id, ui);
+        let (cancel_xy, cancel_dim) = layout.cancel;
+        Button::new()
+            .point(cancel_xy)
+            .dim(cancel_dim)
+            .label("Cancel")
+            .font(font)
+            .label_font_size(font_size)
+            .enabled(self.enabled)
+            .react(|| is_cancelled = true)
+            .set(cancel_id, ui);
+
+        // Setting the controls resets the font and spacing of the widget being set, so restore
+        // those of the FileDialog before it is cached.
+        ui.set_current_font(font);
+        ui.set_current_spacing(self.margin, self.padding);
+
+        // Confirming the name of a listed directory opens it rather than choosing it.
+        if is_confirmed && !filename.is_empty() {
+            let is_dir = state.entries.iter().any(|entry| entry.is_dir && entry.name == filename);
+            if is_dir {
+                dir = dir.join(&filename);
+                filename.clear();
+            } else if let Some(ref mut react) = self.maybe_react {
+                react(Some(dir.join(&filename)));
+            }
+        } else if is_cancelled {
+            if let Some(ref mut react) = self.maybe_react {
+                react(None);
+            }
+        }
+
+        // Read the directory anew whenever it or the filter changes.
+        let listing = (dir.clone(), filter);
+        let (entries, maybe_error) = if state.maybe_listed.as_ref() == Some(&listing) {
+            (state.entries.clone(), state.maybe_error.clone())
+        } else {
+            maybe_selected = None;
+            scroll = 0.0;
+            let extensions = self.filters.get(filter).map(|&(_, exts)| exts).unwrap_or(&[]);
+            match list(&dir, extensions) {
+                Ok(entries) => (entries, None),
+                Err(err) => (Vec::new(), Some(err)),
+            }
+        };
+
+        let new_state = State {
+            dir: dir,
+            maybe_listed: Some(listing),
+            entries: entries,
+            maybe_error: maybe_error,
+            maybe_selected: maybe_selected,
+            filename: filename,
+            filter: filter,
+            scroll: scroll,
+            was_down: is_down,
+        };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given FileDialog State. The controls draw themselves.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{rect, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let color = style.color(&ui.theme);
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let row_h = style.row_height(&ui.theme);
+        let layout = layout(xy, dim, frame, row_h, false);
+        let label_form = |string: &str, color: Color| {
+            text(Text::from_string(string.to_string()).color(color).height(font_size as f64))
+        };
+
+        // The dialog's frame and background.
+        let mut forms = Vec::new();
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        forms.push(frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                               style.frame_style(&ui.theme), frame_color)
+                       .shift(xy[0], xy[1]));
+        forms.push(rect(inner_dim[0], inner_dim[1]).filled(color)
+                       .shift(xy[0] + inner_offset[0], xy[1] + inner_offset[1]));
+
+        // The breadcrumbs, the last of which is the listed directory.
+        let crumbs = crumbs(&state.dir);
+        let names: Vec<String> = crumbs.into_iter().map(|(name, _)| name).collect();
+        let crumb_rects = crumb_rects(ui, &names, layout.crumbs, font_size);
+        for &(idx, c_xy, c_dim) in crumb_rects.iter() {
+            let crumb_color = if idx == names.len() - 1 { color.clicked() } else { color };
+            forms.push(rect(c_dim[0], c_dim[1]).filled(frame_color).shift(c_xy[0], c_xy[1]));
+            forms.push(rect(c_dim[0] - frame * 2.0, c_dim[1] - frame * 2.0).filled(crumb_color)
+                           .shift(c_xy[0], c_xy[1]));
+            forms.push(label_form(&names[idx], label_color)
+                           .shift(c_xy[0].floor(), c_xy[1].floor()));
+        }
+
+        // The listing, or the reason the directory could not be read.
+        let (l_xy, l_dim) = layout.listing;
+        forms.push(rect(l_dim[0], l_dim[1]).filled(frame_color).shift(l_xy[0], l_xy[1]));
+        forms.push(rect(l_dim[0] - frame * 2.0, l_dim[1] - frame * 2.0)
+                       .filled(color.with_luminance(0.1)).shift(l_xy[0], l_xy[1]));
+        if let Some(ref err) = state.maybe_error {
+            forms.push(label_form(err, label_color).shift(l_xy[0].floor(), l_xy[1].floor()));
+        }
+        let rows = row_rects(layout.listing, row_h, state.scroll, state.entries.len());
+        for &(idx, r_xy, r_dim) in rows.iter() {
+            let entry = &state.entries[idx];
+            if state.maybe_selected == Some(idx) {
+                forms.push(rect(r_dim[0] - frame * 2.0, r_dim[1]).filled(color.highlighted())
+                               .shift(r_xy[0], r_xy[1]));
+            }
+            let name = if entry.is_dir { format!("{}/", entry.name) } else { entry.name.clone() };
+            let text_w = label::width(ui, font_size, &name);
+            let text_x = r_xy[0] + position::align_left_of(r_dim[0], text_w) + SPACING;
+            forms.push(label_form(&name, label_color).shift(text_x.floor(), r_xy[1].floor()));
+        }
+
+        // Collect the Forms for drawing.
+        forms
+    }
+
+    /// Describe the FileDialog for assistive technologies, giving the listed directory.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::Dialog).value(state.dir.display())
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_row_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_file_dialog.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_file_dialog.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_medium)
+        })).unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [480.0, 360.0];
+        let width = self.maybe_width.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the height of each row of the listing.
+    pub fn row_height(&self, theme: &Theme) -> f64 {
+        const DEFAULT_ROW_HEIGHT: f64 = 28.0;
+        self.maybe_row_height.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_row_height.unwrap_or(DEFAULT_ROW_HEIGHT)
+        })).unwrap_or(DEFAULT_ROW_HEIGHT)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_file_dialog.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for FileDialog<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for FileDialog<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for FileDialog<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for FileDialog<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Frameable for FileDialog<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Positionable for FileDialog<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        FileDialog { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        FileDialog { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> Sizeable for FileDialog<'a, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
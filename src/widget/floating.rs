@@ -0,0 +1,640 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::{self, Enableable};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL, FRAME_BOTTOM,
+            FRAME_LEFT, FRAME_RIGHT, FRAME_TOP};
+use graphics::character::CharacterCache;
+use label::{self, FontSize, Labelable};
+use mouse::Cursor as MouseCursor;
+use palette::{ColorRole, Paletted};
+use position::{Dimensions, Point};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::is_over_rect;
+use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
+
+
+/// A window that floats above the other widgets, with a title bar by which it may be dragged, a
+/// button by which it may be closed and edges by which it may be resized.
+///
+/// As with the View of an EditorCanvas, the window's rectangle is owned by the application. The
+/// Floating is given its centre and dimensions each update, and its reaction is called with an
+/// `Event` whenever it is moved, resized or closed.
+///
+/// Clicking anywhere over the Floating brings it to the front of its layer (see
+/// `Ui::bring_to_front`). It is usually set along with its children within the `Floating` layer
+/// (see `Ui::with_layer`) so that it is drawn above the rest of the interface. Its child widgets
+/// should be set after it, placed within its `inner_rect` and given a depth of up to
+/// `RAISED_DEPTH_STEP` less than `ui.raised_depth(floating_id)`, so that they are brought to the
+/// front along with it.
+pub struct Floating<'a, F> {
+    xy: Point,
+    dim: Dimensions,
+    maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
+    resizable: bool,
+    closable: bool,
+    min_dim: Dimensions,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the Floating, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_title_bar_color: Option<Color>,
+    pub maybe_title_bar_height: Option<f64>,
+    pub maybe_resize_margin: Option<f64>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The changes to the Floating's rectangle requested by the user.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The window was dragged by its title bar to the given centre.
+    Move(Point),
+    /// The window was resized by its edges to the given centre and dimensions.
+    Resize(Point, Dimensions),
+    /// The window's close button was clicked.
+    Close,
+}
+
+/// The parts of the Floating with which the mouse may interact.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Elem {
+    TitleBar,
+    CloseButton,
+    /// The given edges, by which the Floating is resized.
+    Edges(FrameSides),
+    Body,
+}
+
+/// The current interaction with the Floating.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted(Elem),
+    /// The given Elem was pressed while the mouse and the Floating were at the given positions.
+    Clicked(Elem, Point, Point, Dimensions),
+    /// The left mouse button was pressed away from the Floating and is still held.
+    Blocked,
+}
+
+/// The state of the Floating.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    interaction: Interaction,
+    maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+    closable: bool,
+    enabled: bool,
+}
+
+
+impl Interaction {
+    /// Alter the color of the given Elem depending on the interaction.
+    fn color(&self, elem: Elem, color: Color) -> Color {
+        match *self {
+            Interaction::Highlighted(e) if e == elem => color.highlighted(),
+            Interaction::Clicked(e, _, _, _) if e == elem => color.clicked(),
+            _ => color,
+        }
+    }
+}
+
+
+/// The centre and dimensions of the close button within the title bar, relative to the centre of
+/// the Floating.
+fn close_button_rect(dim: Dimensions, frame: f64, title_bar_h: f64) -> (Point, Dimensions) {
+    let size = (title_bar_h - 8.0).max(0.0);
+    let y = dim[1] / 2.0 - frame - title_bar_h / 2.0;
+    let x = dim[0] / 2.0 - frame - title_bar_h / 2.0;
+    ([x, y], [size, size])
+}
+
+/// The part of the Floating over which the given point (relative to its centre) lies, if any.
+fn elem_at(rel_xy: Point,
+           dim: Dimensions,
+           frame: f64,
+           title_bar_h: f64,
+           resize_margin: Option<f64>,
+           closable: bool) -> Option<Elem> {
+    if !is_over_rect([0.0, 0.0], rel_xy, dim) {
+        return None;
+    }
+    let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+    if let Some(margin) = resize_margin {
+        let mut edges = FrameSides::empty();
+        if rel_xy[0] < -half_w + margin { edges = edges | FRAME_LEFT; }
+        if rel_xy[0] > half_w - margin { edges = edges | FRAME_RIGHT; }
+        if rel_xy[1] > half_h - margin { edges = edges | FRAME_TOP; }
+        if rel_xy[1] < -half_h + margin { edges = edges | FRAME_BOTTOM; }
+        if !edges.is_empty() {
+            return Some(Elem::Edges(edges));
+        }
+    }
+    let (close_xy, close_dim) = close_button_rect(dim, frame, title_bar_h);
+    if closable && is_over_rect(close_xy, rel_xy, close_dim) {
+        Some(Elem::CloseButton)
+    } else if rel_xy[1] > half_h - frame - title_bar_h {
+        Some(Elem::TitleBar)
+    } else {
+        Some(Elem::Body)
+    }
+}
+
+/// The rectangle resulting from dragging the given edges of the rectangle by `delta`, keeping the
+/// opposite edges in place and the dimensions no smaller than `min_dim`.
+fn resize(xy: Point, dim: Dimensions, edges: FrameSides, delta: Point, min_dim: Dimensions)
+    -> (Point, Dimensions)
+{
+    let (mut left, mut right) = (xy[0] - dim[0] / 2.0, xy[0] + dim[0] / 2.0);
+    let (mut bottom, mut top) = (xy[1] - dim[1] / 2.0, xy[1] + dim[1] / 2.0);
+    if edges.contains(FRAME_LEFT) { left = (left + delta[0]).min(right - min_dim[0]); }
+    if edges.contains(FRAME_RIGHT) { right = (right + delta[0]).max(left + min_dim[0]); }
+    if edges.contains(FRAME_BOTTOM) { bottom = (bottom + delta[1]).min(top - min_dim[1]); }
+    if edges.contains(FRAME_TOP) { top = (top + delta[1]).max(bottom + min_dim[1]); }
+    ([(left + right) / 2.0, (bottom + top) / 2.0], [right - left, top - bottom])
+}
+
+
+impl<'a, F> Floating<'a, F> {
+
+    /// Construct a Floating with the given centre and dimensions.
+    pub fn new(xy: Point, dim: Dimensions) -> Floating<'a, F> {
+        Floating {
+            xy: xy,
+            dim: dim,
+            maybe_label: None,
+            maybe_label_spans: None,
+            resizable: true,
+            closable: true,
+            min_dim: [64.0, 48.0],
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the reaction for the Floating. It is called with an `Event` whenever the Floating is
+    /// moved, resized or closed. Note that the Floating will not change its rectangle or close
+    /// itself, you should do this yourself within the react closure.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Whether or not the Floating may be resized by dragging its edges.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Whether or not the Floating displays a button by which it may be closed.
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Set the smallest dimensions to which the Floating may be resized.
+    pub fn min_dim(mut self, min_dim: Dimensions) -> Self {
+        self.min_dim = min_dim;
+        self
+    }
+
+    /// Set the height of the title bar.
+    #[inline]
+    pub fn title_bar_height(mut self, height: f64) -> Self {
+        self.style.maybe_title_bar_height = Some(height);
+        self
+    }
+
+    /// Set the color of the title bar.
+    #[inline]
+    pub fn title_bar_color(mut self, color: Color) -> Self {
+        self.style.maybe_title_bar_color = Some(color);
+        self
+    }
+
+    /// The centre and dimensions of the area beneath the title bar and within the frame, within
+    /// which the Floating's child widgets should be placed.
+    pub fn inner_rect(&self, theme: &Theme) -> (Point, Dimensions) {
+        let frame = self.style.frame(theme);
+        let title_bar_h = self.style.title_bar_height(theme);
+        let w = (self.dim[0] - frame * 2.0).max(0.0);
+        let h = (self.dim[1] - frame * 2.0 - title_bar_h).max(0.0);
+        let y = self.xy[1] - self.dim[1] / 2.0 + frame + h / 2.0;
+        ([self.xy[0], y], [w, h])
+    }
+
+}
+
+
+impl<'a, F> Widget for Floating<'a, F>
+    where
+        F: FnMut(Event),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Floating" }
+    fn init_state(&self) -> State {
+        State {
+            interaction: Interaction::Normal,
+            maybe_label: None,
+            maybe_label_spans: None,
+            closable: self.closable,
+            enabled: self.enabled,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the Floating.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::Down;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let (xy, dim) = (self.xy, self.dim);
+        let frame = style.frame(&ui.theme);
+        let title_bar_h = style.title_bar_height(&ui.theme);
+        let resize_margin = if self.resizable { Some(style.resize_margin(&ui.theme)) }
+                            else { None };
+        let mouse = ui.get_mouse_state(ui_id);
+        let rel_xy = [mouse.xy[0] - xy[0], mouse.xy[1] - xy[1]];
+        let maybe_elem = elem_at(rel_xy, dim, frame, title_bar_h, resize_margin, self.closable);
+
+        // Newly opened windows are brought to the front of those already open.
+        if !ui.has_widget_state(ui_id) {
+            ui.bring_to_front(ui_id);
+        }
+
+        let is_down = self.enabled && mouse.left.state == Down;
+        let new_interaction = match (is_down, state.interaction, maybe_elem) {
+            (false, _, Some(elem)) => Interaction::Highlighted(elem),
+            (false, _, None) => Interaction::Normal,
+            (true, Interaction::Clicked(elem, mouse_xy, start_xy, start_dim), _) =>
+                Interaction::Clicked(elem, mouse_xy, start_xy, start_dim),
+            (true, Interaction::Blocked, _) => Interaction::Blocked,
+            (true, _, Some(elem)) => {
+                ui.bring_to_front(ui_id);
+                Interaction::Clicked(elem, mouse.xy, xy, dim)
+            },
+            (true, _, None) => Interaction::Blocked,
+        };
+
+        // Keep hold of the mouse while dragging the title bar or the edges, but not the body so
+        // that the Floating's children still receive it.
+        let is_dragging = |interaction: Interaction| match interaction {
+            Interaction::Clicked(Elem::TitleBar, _, _, _) |
+            Interaction::Clicked(Elem::Edges(_), _, _, _) => true,
+            _ => false,
+        };
+        ui.mouse_dragged_by(ui_id, is_dragging(state.interaction), is_dragging(new_interaction));
+
+        // Show the direction in which the edges beneath the mouse (or being dragged) resize.
+        let maybe_edges = match (new_interaction, maybe_elem) {
+            (Interaction::Clicked(Elem::Edges(edges), _, _, _), _) |
+            (Interaction::Highlighted(_), Some(Elem::Edges(edges))) => Some(edges),
+            _ => None,
+        };
+        if let Some(edges) = maybe_edges {
+            let cursor = if edges == FRAME_TOP | FRAME_LEFT || edges == FRAME_BOTTOM | FRAME_RIGHT {
+                MouseCursor::ResizeTopLeftBottomRight
+            } else if edges == FRAME_TOP | FRAME_RIGHT || edges == FRAME_BOTTOM | FRAME_LEFT {
+                MouseCursor::ResizeTopRightBottomLeft
+            } else if edges.intersects(FRAME_LEFT | FRAME_RIGHT) {
+                MouseCursor::ResizeHorizontal
+            } else {
+                MouseCursor::ResizeVertical
+            };
+            ui.set_mouse_cursor(ui_id, cursor);
+        }
+
+        // React to dragging, resizing or closing.
+        let maybe_event = match (state.interaction, new_interaction) {
+            (_, Interaction::Clicked(Elem::TitleBar, mouse_xy, start_xy, _)) => {
+                let delta = [mouse.xy[0] - mouse_xy[0], mouse.xy[1] - mouse_xy[1]];
+                let new_xy = [start_xy[0] + delta[0], start_xy[1] + delta[1]];
+                if new_xy != xy { Some(Event::Move(new_xy)) } else { None }
+            },
+            (_, Interaction::Clicked(Elem::Edges(edges), mouse_xy, start_xy, start_dim)) => {
+                let delta = [mouse.xy[0] - mouse_xy[0], mouse.xy[1] - mouse_xy[1]];
+                let (new_xy, new_dim) = resize(start_xy, start_dim, edges, delta, self.min_dim);
+                if new_xy != xy || new_dim != dim { Some(Event::Resize(new_xy, new_dim)) }
+                else { None }
+            },
+            (Interaction::Clicked(Elem::CloseButton, _, _, _),
+             Interaction::Highlighted(Elem::CloseButton)) => Some(Event::Close),
+            _ => None,
+        };
+        if let (Some(event), Some(react)) = (maybe_event, self.maybe_react.as_mut()) {
+            react(event);
+        }
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.interaction != new_interaction
+            || state.closable != self.closable
+            || state.enabled != self.enabled
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed {
+            Some(State {
+                interaction: new_interaction,
+                maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+                closable: self.closable,
+                enabled: self.enabled,
+            })
+        } else {
+            None
+        };
+
+        // The `Ui` offsets the depth by the window's `raised_depth`.
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: 0.0 }
+    }
+
+    /// Construct the Forms that draw the given Floating State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{rect, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let frame = style.frame(&ui.theme);
+        let title_bar_h = style.title_bar_height(&ui.theme);
+        let color = enable::color(style.color(&ui.theme), state.enabled, &ui.theme);
+        let frame_color = enable::color(style.frame_color(&ui.theme), state.enabled, &ui.theme);
+        let title_bar_color = enable::color(style.title_bar_color(&ui.theme), state.enabled,
+                                            &ui.theme);
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+
+        // The frame, the body and the title bar along the top of the body.
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let body_form = rect(inner_dim[0], inner_dim[1]).filled(color)
+            .shift(inner_offset[0], inner_offset[1]);
+        let title_bar_y = dim[1] / 2.0 - frame - title_bar_h / 2.0;
+        let title_bar_color = state.interaction.color(Elem::TitleBar, title_bar_color);
+        let title_bar_form = rect(inner_dim[0], title_bar_h).filled(title_bar_color)
+            .shift(inner_offset[0], title_bar_y);
+        let mut forms = vec![frame_form, body_form, title_bar_form];
+
+        // The title, aligned to the left of the title bar.
+        let pad = (title_bar_h - font_size as f64) / 2.0;
+        let left = -dim[0] / 2.0 + frame + pad;
+        match (state.maybe_label_spans.as_ref(), state.maybe_label.as_ref()) {
+            (Some(spans), _) => {
+                let (label_dim, label_forms) = spans.forms(ui, label_color, font_size);
+                let x = left + label_dim[0] / 2.0;
+                forms.extend(label_forms.into_iter()
+                                 .map(|form| form.shift(x.floor(), title_bar_y.floor())));
+            },
+            (None, Some(title)) => {
+                let x = left + label::width(ui, font_size, title) / 2.0;
+                forms.push(text(Text::from_string(title.clone())
+                                    .color(label_color)
+                                    .height(font_size as f64))
+                               .shift(x.floor(), title_bar_y.floor()));
+            },
+            (None, None) => (),
+        }
+
+        // The close button at the right of the title bar.
+        if state.closable {
+            let (close_xy, close_dim) = close_button_rect(dim, frame, title_bar_h);
+            let close_color = state.interaction.color(Elem::CloseButton, title_bar_color);
+            forms.push(rect(close_dim[0], close_dim[1]).filled(close_color)
+                           .shift(close_xy[0], close_xy[1]));
+            forms.push(text(Text::from_string("×".to_string())
+                                .color(label_color)
+                                .height(font_size as f64))
+                           .shift(close_xy[0].floor(), close_xy[1].floor()));
+        }
+
+        // Shift the Forms into position and collect them for drawing.
+        let forms = forms.into_iter().map(|form| form.shift(xy[0], xy[1]));
+        forms.collect()
+    }
+
+    /// Describe the Floating for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::Window).label(maybe_label.map(|l| &l[..]))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_title_bar_color: None,
+            maybe_title_bar_height: None,
+            maybe_resize_margin: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_floating.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_floating.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the Color of the title bar.
+    pub fn title_bar_color(&self, theme: &Theme) -> Color {
+        self.maybe_title_bar_color.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_title_bar_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the height of the title bar.
+    pub fn title_bar_height(&self, theme: &Theme) -> f64 {
+        const DEFAULT_HEIGHT: f64 = 24.0;
+        self.maybe_title_bar_height.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_title_bar_height.unwrap_or(DEFAULT_HEIGHT)
+        })).unwrap_or(DEFAULT_HEIGHT)
+    }
+
+    /// Get the distance from each edge within which the mouse may grab the edge to resize.
+    pub fn resize_margin(&self, theme: &Theme) -> f64 {
+        const DEFAULT_MARGIN: f64 = 4.0;
+        self.maybe_resize_margin.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_resize_margin.unwrap_or(DEFAULT_MARGIN)
+        })).unwrap_or(DEFAULT_MARGIN)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.shape_color)
+        })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_floating.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for Floating<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for Floating<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for Floating<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for Floating<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Frameable for Floating<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Labelable<'a> for Floating<'a, F> {
+    fn label(mut self, text: &'a str) -> Self {
+        self.maybe_label = Some(text);
+        self
+    }
+
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
+    fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+}
@@ -0,0 +1,240 @@
+
+use position::{Dimensions, Direction, HorizontalAlign, Margin, Padding, Point, Position,
+               Positionable, Sizeable, VerticalAlign};
+use ui::Ui;
+
+/// Reaction params.
+pub type ChildNum = usize;
+
+/// Where children shorter than the Flow along its cross axis are placed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Align {
+    /// Against the top (for a horizontal Flow) or left (for a vertical Flow) edge.
+    Start,
+    /// In the middle of the Flow.
+    Middle,
+    /// Against the bottom (for a horizontal Flow) or right (for a vertical Flow) edge.
+    End,
+    /// Stretched to fill the Flow.
+    Stretch,
+}
+
+/// A child registered with a Flow, describing its preferred size and how it adapts to the space
+/// available.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Child {
+    /// The preferred length of the child along the Flow's direction.
+    pub length: f64,
+    /// The preferred length of the child across the Flow's direction. This is ignored when the
+    /// Flow's alignment is `Align::Stretch`.
+    pub cross_length: f64,
+    /// The share of any spare length given to the child.
+    pub grow: f64,
+    /// The share of any missing length taken from the child, relative to its length.
+    pub shrink: f64,
+}
+
+/// Lay out a row or column of any rectangular widget types, where the Flow will provide a
+/// function with the child number along with the location and dimensions at which the child
+/// should be drawn.
+///
+/// The children are laid out in the order in which they were registered, separated by the
+/// Flow's spacing. Any length left over along the Flow's direction is shared among the children
+/// by their `grow` weights, while any length missing is taken from them by their `shrink`
+/// weights. As the layout is computed each time `each_child` is called, a Flow sized relative
+/// to the window adapts to the window being resized.
+#[derive(Clone)]
+pub struct Flow {
+    direction: Direction,
+    children: Vec<Child>,
+    spacing: f64,
+    align: Align,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    dim: Dimensions,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+}
+
+
+impl Child {
+
+    /// A child with the given preferred length along and across the Flow's direction, that
+    /// neither grows nor shrinks.
+    pub fn new(length: f64, cross_length: f64) -> Child {
+        Child { length: length, cross_length: cross_length, grow: 0.0, shrink: 0.0 }
+    }
+
+    /// Set the share of any spare length given to the child.
+    pub fn grow(self, weight: f64) -> Child {
+        Child { grow: weight, ..self }
+    }
+
+    /// Set the share of any missing length taken from the child.
+    pub fn shrink(self, weight: f64) -> Child {
+        Child { shrink: weight, ..self }
+    }
+
+}
+
+
+/// The length of each child along the Flow once the `available` length has been shared among
+/// them.
+fn lengths(children: &[Child], spacing: f64, available: f64) -> Vec<f64> {
+    let gaps = if children.is_empty() { 0.0 } else { spacing * (children.len() - 1) as f64 };
+    let preferred = children.iter().fold(0.0, |total, child| total + child.length);
+    let spare = available - gaps - preferred;
+    if spare > 0.0 {
+        let total_grow = children.iter().fold(0.0, |total, child| total + child.grow.max(0.0));
+        children.iter().map(|child| if total_grow > 0.0 {
+            child.length + spare * child.grow.max(0.0) / total_grow
+        } else {
+            child.length
+        }).collect()
+    } else {
+        let total_shrink = children.iter()
+            .fold(0.0, |total, child| total + child.shrink.max(0.0) * child.length);
+        children.iter().map(|child| if total_shrink > 0.0 {
+            let share = child.shrink.max(0.0) * child.length / total_shrink;
+            (child.length + spare * share).max(0.0)
+        } else {
+            child.length
+        }).collect()
+    }
+}
+
+
+impl Flow {
+
+    /// Create a flow context, laying out children in the given direction (i.e. `Direction::Right`
+    /// lays out children from left to right, `Direction::Down` from top to bottom).
+    pub fn new(direction: Direction) -> Flow {
+        Flow {
+            direction: direction,
+            children: Vec::new(),
+            spacing: 0.0,
+            align: Align::Stretch,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            dim: [256.0, 256.0],
+            maybe_h_align: None,
+            maybe_v_align: None,
+        }
+    }
+
+    /// Register a child with the Flow. Children are laid out in the order in which they are
+    /// registered.
+    pub fn child(mut self, child: Child) -> Flow {
+        self.children.push(child);
+        self
+    }
+
+    /// Register each of the given children with the Flow.
+    pub fn children(mut self, children: &[Child]) -> Flow {
+        self.children.extend(children.iter().cloned());
+        self
+    }
+
+    /// Set the distance between neighbouring children.
+    pub fn spacing(self, spacing: f64) -> Flow {
+        Flow { spacing: spacing, ..self }
+    }
+
+    /// Set where children are placed across the Flow's direction. Children are stretched to fill
+    /// the Flow by default.
+    pub fn align(self, align: Align) -> Flow {
+        Flow { align: align, ..self }
+    }
+
+    /// The reaction called for each child of the Flow. This should be called following all
+    /// builder methods.
+    pub fn each_child<C, F>(&mut self, ui: &mut Ui<C>, mut react: F)
+        where
+            F: FnMut(&mut Ui<C>, ChildNum, Point, Dimensions)
+    {
+        let dim = self.dim;
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_margin(self.pos, dim, self.margin, h_align, v_align);
+        // The children are laid out within the padded area.
+        let xy = ::vecmath::vec2_add(xy, self.padding.inner_offset());
+        let dim = self.padding.inner_dim(dim);
+
+        // Lay the children out along the main axis from its start, working in lengths relative
+        // to the centre of the Flow. `sign` flips the main axis for leftward and upward flows.
+        let (main_axis, sign) = match self.direction {
+            Direction::Right => (0, 1.0),
+            Direction::Left => (0, -1.0),
+            Direction::Down => (1, -1.0),
+            Direction::Up => (1, 1.0),
+        };
+        let cross_axis = 1 - main_axis;
+        let lengths = lengths(&self.children, self.spacing, dim[main_axis]);
+        let cross = dim[cross_axis];
+        let mut start = -dim[main_axis] / 2.0;
+        for (i, (child, &length)) in self.children.iter().zip(lengths.iter()).enumerate() {
+            let cross_length = match self.align {
+                Align::Stretch => cross,
+                _ => child.cross_length.min(cross),
+            };
+            // The cross axis starts at the top of a horizontal Flow or the left of a vertical one.
+            let slack = (cross - cross_length) / 2.0;
+            let cross_sign = if main_axis == 0 { 1.0 } else { -1.0 };
+            let cross_offset = match self.align {
+                Align::Start => cross_sign * slack,
+                Align::End => -cross_sign * slack,
+                Align::Middle | Align::Stretch => 0.0,
+            };
+            let main_offset = sign * (start + length / 2.0);
+            let mut child_xy = xy;
+            child_xy[main_axis] += main_offset;
+            child_xy[cross_axis] += cross_offset;
+            let mut child_dim = [0.0, 0.0];
+            child_dim[main_axis] = length;
+            child_dim[cross_axis] = cross_length;
+            react(ui, i, child_xy, child_dim);
+            start += length + self.spacing;
+        }
+    }
+
+}
+
+impl Positionable for Flow {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        Flow { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        Flow { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl Sizeable for Flow {
+    #[inline]
+    fn width(self, w: f64) -> Self {
+        let h = self.dim[1];
+        Flow { dim: [w, h], ..self }
+    }
+    #[inline]
+    fn height(self, h: f64) -> Self {
+        let w = self.dim[0];
+        Flow { dim: [w, h], ..self }
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -1,19 +1,25 @@
 
+use accessibility::{Accessibility, Role};
 use color::{Color, Colorable};
-use elmesque::Element;
+use elmesque::form::Form;
+use font::{FontId, Fontable};
 use graphics::character::CharacterCache;
 use label::{self, FontSize};
-use position::{Depth, HorizontalAlign, Position, Positionable, VerticalAlign};
+use palette::{ColorRole, Paletted};
+use position::{Depth, HorizontalAlign, Margin, Padding, Position, Positionable, VerticalAlign};
 use theme::Theme;
 use ui::{Ui, UiId};
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// Displays some given text centred within a rectangle.
 #[derive(Clone, Debug)]
 pub struct Label<'a> {
     text: &'a str,
+    maybe_spans: Option<SpannedLabel>,
     pos: Position,
+    margin: Margin,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
     depth: Depth,
@@ -25,11 +31,13 @@ pub struct Label<'a> {
 pub struct Style {
     maybe_font_size: Option<FontSize>,
     maybe_color: Option<Color>,
+    maybe_color_role: Option<ColorRole>,
+    maybe_font: Option<FontId>,
 }
 
 /// The state to be stored between updates for the Label.
 #[derive(Clone, Debug, PartialEq)]
-pub struct State(String);
+pub struct State(String, Option<SpannedLabel>);
 
 
 impl<'a> Label<'a> {
@@ -38,7 +46,9 @@ impl<'a> Label<'a> {
     pub fn new(text: &'a str) -> Label<'a> {
         Label {
             text: text,
+            maybe_spans: None,
             pos: Position::default(),
+            margin: Margin::none(),
             maybe_h_align: None,
             maybe_v_align: None,
             depth: 0.0,
@@ -53,6 +63,14 @@ impl<'a> Label<'a> {
         self
     }
 
+    /// Display the given differently styled Spans in place of the Label's text. Any Span without
+    /// its own color or font size uses the Label's color and font size.
+    #[inline]
+    pub fn spans(mut self, spans: &[Span]) -> Label<'a> {
+        self.maybe_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
 }
 
 
@@ -60,8 +78,9 @@ impl<'a> Widget for Label<'a> {
     type State = State;
     type Style = Style;
     fn unique_kind(&self) -> &'static str { "Label" }
-    fn init_state(&self) -> State { State(String::new()) }
+    fn init_state(&self) -> State { State(String::new(), None) }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the Label.
     fn update<C>(self,
@@ -72,33 +91,49 @@ impl<'a> Widget for Label<'a> {
         where
             C: CharacterCache,
     {
-        let widget::State { state: State(ref string), .. } = *prev_state;
+        let widget::State { state: State(ref string, ref maybe_spans), .. } = *prev_state;
         let size = style.font_size(&ui.theme);
-        let dim = [label::width(ui, size, self.text), size as f64];
+        let color = style.color(&ui.theme);
+        let dim = match self.maybe_spans {
+            Some(ref spans) => spans.forms(ui, color, size).0,
+            None => [label::width(ui, size, self.text), size as f64],
+        };
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
-        let maybe_new_state = if &string[..] != self.text { Some(State(self.text.to_string())) }
-                              else { None };
+        let padding = Padding::none();
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, padding, h_align, v_align);
+        let maybe_new_state = if &string[..] != self.text || *maybe_spans != self.maybe_spans {
+            Some(State(self.text.to_string(), self.maybe_spans.clone()))
+        } else {
+            None
+        };
         widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
     }
 
-    /// Construct an Element for the Label.
-    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the Label.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{text, collage};
+        use elmesque::form::text;
         use elmesque::text::Text;
-        let widget::State { state: State(ref string), dim, xy, .. } = *new_state;
+        let widget::State { state: State(ref string, ref maybe_spans), xy, .. } = *new_state;
         let size = style.font_size(&ui.theme);
         let color = style.color(&ui.theme);
-        let form = text(Text::from_string(string.clone())
-                            .color(color)
-                            .height(size as f64)).shift(xy[0].floor(), xy[1].floor());
-        collage(dim[0] as i32, dim[1] as i32, vec![form])
+        let forms = match *maybe_spans {
+            Some(ref spans) => spans.forms(ui, color, size).1,
+            None => vec![text(Text::from_string(string.clone()).color(color).height(size as f64))],
+        };
+        let forms = forms.into_iter().map(|form| form.shift(xy[0].floor(), xy[1].floor()));
+        forms.collect()
     }
     
+    /// Describe the Label for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let text = state.1.as_ref().map(|spans| spans.text()).unwrap_or_else(|| state.0.clone());
+        Accessibility::new(Role::Text).label(Some(&text[..]))
+    }
+
 }
 
 
@@ -108,13 +143,16 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_font_size: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.unwrap_or(theme.label_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .unwrap_or(theme.label_color)
     }
 
     /// Get the label font size for an Element.
@@ -122,6 +160,11 @@ impl Style {
         self.maybe_font_size.unwrap_or(theme.font_size_medium)
     }
 
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.unwrap_or(theme.font)
+    }
+
 }
 
 
@@ -132,6 +175,20 @@ impl<'a> Colorable for Label<'a> {
     }
 }
 
+impl<'a> Paletted for Label<'a> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a> Fontable for Label<'a> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a> Positionable for Label<'a> {
     fn position(mut self, pos: Position) -> Self {
         self.pos = pos;
@@ -145,5 +202,10 @@ impl<'a> Positionable for Label<'a> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         Label { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
@@ -0,0 +1,606 @@
+
+use accessibility::{Accessibility, Role};
+use color::{self, Color, Colorable};
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use graphics::character::CharacterCache;
+use graphics::math::Scalar;
+use label::FontSize;
+use mouse::Mouse;
+use position::{Depth, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               VerticalAlign};
+use theme::Theme;
+use ui::{Ui, UiId};
+use widget::{self, Widget};
+use widget::rich_text::{self, Line, Piece, RunStyle};
+
+
+/// Renders a subset of Markdown as a wrapped paragraph of styled text. Supported syntax includes
+/// `#` headings, `**bold**` and `*italic*` emphasis, `-` / `*` / `1.` list items, `` `inline
+/// code` ``, fenced code blocks and `[links](url)`. Clicking a link triggers the reaction with
+/// the link's url.
+pub struct Markdown<'a, F> {
+    text: &'a str,
+    wrap_width: Scalar,
+    pos: Position,
+    margin: Margin,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the Markdown, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_font_size: Option<FontSize>,
+    pub maybe_line_spacing: Option<Scalar>,
+    pub maybe_link_color: Option<Color>,
+    pub maybe_code_color: Option<Color>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The state to be stored between updates for the Markdown.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    lines: Vec<Line>,
+    links: Vec<String>,
+    interaction: Interaction,
+}
+
+/// Represents an interaction with the Markdown's links. Each variant holds the index of the link.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted(usize),
+    Clicked(usize),
+}
+
+/// Inline styling flags gathered while parsing a line of Markdown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Inline {
+    bold: bool,
+    italic: bool,
+    code: bool,
+    maybe_link: Option<usize>,
+}
+
+/// The horizontal distance by which list items and code blocks are indented.
+const INDENT: Scalar = 20.0;
+/// The font size multiplier for each heading level.
+const HEADING_SCALES: [f32; 6] = [2.0, 1.5, 1.25, 1.1, 1.0, 0.9];
+
+
+impl Interaction {
+    /// The index of the link that is currently being interacted with, if any.
+    fn link(&self) -> Option<usize> {
+        match *self {
+            Interaction::Normal => None,
+            Interaction::Highlighted(idx) | Interaction::Clicked(idx) => Some(idx),
+        }
+    }
+}
+
+/// Check the current interaction with the links given the link beneath the mouse (if any).
+fn get_new_interaction(maybe_link: Option<usize>, prev: Interaction, mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (maybe_link, prev, mouse.left.state) {
+        (Some(_),   Normal,         Down) => Normal,
+        (Some(_),   Clicked(idx),   Down) => Clicked(idx),
+        (Some(idx), _,              Down) => Clicked(idx),
+        (Some(idx), _,              Up)   => Highlighted(idx),
+        (None,      Clicked(idx),   Down) => Clicked(idx),
+        _                                 => Normal,
+    }
+}
+
+/// Find the given link's label and url at the start of `text`, along with the length of the
+/// entire `[label](url)` syntax.
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    let close = match text.find("](") { Some(idx) => idx, None => return None };
+    let url_start = close + 2;
+    let url_end = match text[url_start..].find(')') {
+        Some(idx) => url_start + idx,
+        None => return None,
+    };
+    Some((&text[1..close], &text[url_start..url_end], url_end + 1))
+}
+
+/// Split a single line of Markdown into pieces of text sharing the same inline styling. The url
+/// of each link encountered is pushed onto `links`. Emphasis markers that are not closed later
+/// within the line are kept as literal text.
+fn parse_inline(text: &str, links: &mut Vec<String>) -> Vec<(String, Inline)> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut inline = Inline { bold: false, italic: false, code: false, maybe_link: None };
+    let mut rest = text;
+
+    fn flush(pieces: &mut Vec<(String, Inline)>, current: &mut String, inline: Inline) {
+        if !current.is_empty() {
+            pieces.push((::std::mem::replace(current, String::new()), inline));
+        }
+    }
+
+    while !rest.is_empty() {
+        if rest.starts_with("**") {
+            if inline.bold || rest[2..].contains("**") {
+                flush(&mut pieces, &mut current, inline);
+                inline.bold = !inline.bold;
+            } else {
+                current.push_str("**");
+            }
+            rest = &rest[2..];
+            continue;
+        }
+        if rest.starts_with("*") {
+            // A lone `*` closes italics, so those within `**` don't count.
+            if inline.italic || rest[1..].split("**").any(|part| part.contains('*')) {
+                flush(&mut pieces, &mut current, inline);
+                inline.italic = !inline.italic;
+            } else {
+                current.push('*');
+            }
+            rest = &rest[1..];
+            continue;
+        }
+        if rest.starts_with("`") {
+            if let Some(end) = rest[1..].find('`') {
+                flush(&mut pieces, &mut current, inline);
+                let code = Inline { code: true, ..inline };
+                pieces.push((rest[1..end + 1].to_string(), code));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if rest.starts_with("[") {
+            if let Some((label, url, len)) = parse_link(rest) {
+                flush(&mut pieces, &mut current, inline);
+                let link = Inline { maybe_link: Some(links.len()), ..inline };
+                links.push(url.to_string());
+                pieces.push((label.to_string(), link));
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        let ch = rest.chars().next().unwrap();
+        current.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    flush(&mut pieces, &mut current, inline);
+    pieces
+}
+
+/// If the given line is a heading, return its level along with the heading text.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&ch| ch == '#').count();
+    if level >= 1 && level <= 6 && line[level..].starts_with(" ") {
+        Some((level, line[level..].trim()))
+    } else {
+        None
+    }
+}
+
+/// If the given line is a list item, return the marker to display along with the item's text.
+fn list_item(line: &str) -> Option<(String, &str)> {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return Some(("\u{2022} ".to_string(), &line[2..]));
+    }
+    let num_digits = line.chars().take_while(|ch| ch.is_digit(10)).count();
+    if num_digits > 0 && line[num_digits..].starts_with(". ") {
+        return Some((line[..num_digits + 2].to_string(), &line[num_digits + 2..]));
+    }
+    None
+}
+
+/// Parse the Markdown source into pieces of text with resolved styles and indentation, along with
+/// the url of every link in the order in which they appear.
+fn parse(text: &str,
+         style: &Style,
+         theme: &Theme) -> (Vec<(String, RunStyle, Scalar)>, Vec<String>) {
+    let color = style.color(theme);
+    let font_size = style.font_size(theme);
+    let link_color = style.link_color(theme);
+    let code_color = style.code_color(theme);
+    let mut pieces = Vec::new();
+    let mut links = Vec::new();
+    let mut in_code_block = false;
+    // Whether or not we're in the middle of a paragraph that has yet to be terminated.
+    let mut in_paragraph = false;
+
+    let run_style = |inline: Inline, size: FontSize| RunStyle {
+        color: if inline.maybe_link.is_some() { link_color } else { color },
+        font_size: size,
+        bold: inline.bold,
+        italic: inline.italic,
+        underline: inline.maybe_link.is_some(),
+        monospace: inline.code,
+        maybe_background: if inline.code { Some(code_color) } else { None },
+        maybe_link: inline.maybe_link,
+    };
+    let plain = Inline { bold: false, italic: false, code: false, maybe_link: None };
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            if in_paragraph {
+                pieces.push(("\n".to_string(), run_style(plain, font_size), 0.0));
+                in_paragraph = false;
+            }
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            let code = Inline { code: true, ..plain };
+            pieces.push((format!("{}\n", line), run_style(code, font_size), INDENT));
+            continue;
+        }
+
+        // Terminate the current paragraph if this line starts a new block.
+        if in_paragraph && (trimmed.is_empty() || heading(trimmed).is_some()
+                            || list_item(trimmed).is_some()) {
+            pieces.push(("\n".to_string(), run_style(plain, font_size), 0.0));
+            in_paragraph = false;
+        }
+
+        if trimmed.is_empty() {
+            pieces.push(("\n".to_string(), run_style(plain, font_size), 0.0));
+        } else if let Some((level, text)) = heading(trimmed) {
+            let size = (font_size as f32 * HEADING_SCALES[level - 1]).round() as FontSize;
+            for (text, inline) in parse_inline(text, &mut links).into_iter() {
+                let inline = Inline { bold: true, ..inline };
+                pieces.push((text, run_style(inline, size), 0.0));
+            }
+            pieces.push(("\n".to_string(), run_style(plain, size), 0.0));
+        } else if let Some((marker, text)) = list_item(trimmed) {
+            pieces.push((marker, run_style(plain, font_size), INDENT));
+            for (text, inline) in parse_inline(text, &mut links).into_iter() {
+                pieces.push((text, run_style(inline, font_size), INDENT));
+            }
+            pieces.push(("\n".to_string(), run_style(plain, font_size), INDENT));
+        } else {
+            // Consecutive lines of a paragraph are joined by a single space.
+            for (text, inline) in parse_inline(trimmed, &mut links).into_iter() {
+                pieces.push((text, run_style(inline, font_size), 0.0));
+            }
+            pieces.push((" ".to_string(), run_style(plain, font_size), 0.0));
+            in_paragraph = true;
+        }
+    }
+
+    (pieces, links)
+}
+
+
+impl<'a, F> Markdown<'a, F> {
+
+    /// Construct a new Markdown widget from the given source text.
+    pub fn new(text: &'a str) -> Markdown<'a, F> {
+        Markdown {
+            text: text,
+            wrap_width: 256.0,
+            pos: Position::default(),
+            margin: Margin::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the width at which lines will be wrapped.
+    #[inline]
+    pub fn wrap_width(mut self, width: Scalar) -> Markdown<'a, F> {
+        self.wrap_width = width;
+        self
+    }
+
+    /// Set the font size for body text. Headings are scaled relative to this size.
+    #[inline]
+    pub fn font_size(mut self, size: FontSize) -> Markdown<'a, F> {
+        self.style.maybe_font_size = Some(size);
+        self
+    }
+
+    /// Set the spacing between each line in pixels.
+    #[inline]
+    pub fn line_spacing(mut self, spacing: Scalar) -> Markdown<'a, F> {
+        self.style.maybe_line_spacing = Some(spacing);
+        self
+    }
+
+    /// Set the color used for links.
+    #[inline]
+    pub fn link_color(mut self, color: Color) -> Markdown<'a, F> {
+        self.style.maybe_link_color = Some(color);
+        self
+    }
+
+    /// Set the background color used for inline code and code blocks.
+    #[inline]
+    pub fn code_color(mut self, color: Color) -> Markdown<'a, F> {
+        self.style.maybe_code_color = Some(color);
+        self
+    }
+
+    /// Set the reaction for the Markdown. It will be triggered with the link's url upon release
+    /// of the mouse over a link.
+    pub fn react(mut self, reaction: F) -> Markdown<'a, F> {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for Markdown<'a, F>
+    where
+        F: FnMut(&str),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Markdown" }
+    fn init_state(&self) -> State {
+        State { lines: Vec::new(), links: Vec::new(), interaction: Interaction::Normal }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the Markdown.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use utils::is_over_rect;
+        let widget::State { ref state, .. } = *prev_state;
+        let (owned_pieces, links) = parse(self.text, style, &ui.theme);
+        let pieces: Vec<Piece> = owned_pieces.iter().map(|&(ref text, ref style, indent)| {
+            Piece { text: text, style: style.clone(), indent: indent }
+        }).collect();
+        let default_font_size = style.font_size(&ui.theme);
+        let mut lines = rich_text::layout(ui, &pieces, default_font_size, self.wrap_width);
+
+        // Don't allow trailing blank lines to pad out the bottom of the widget.
+        while lines.len() > 1 && lines.last().map(|line| line.runs.is_empty()).unwrap_or(false) {
+            lines.pop();
+        }
+
+        let spacing = style.line_spacing(&ui.theme);
+        let dim = [self.wrap_width, rich_text::height(&lines, spacing)];
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let padding = Padding::none();
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+
+        // Find the link beneath the mouse, if there is one.
+        let maybe_link = if is_over_rect([0.0, 0.0], mouse.xy, dim) {
+            let top_left_xy: Point = [mouse.xy[0] + dim[0] / 2.0, mouse.xy[1] - dim[1] / 2.0];
+            rich_text::run_at(&lines, spacing, top_left_xy).and_then(|run| run.style.maybe_link)
+        } else {
+            None
+        };
+
+        let new_interaction = if self.enabled {
+            get_new_interaction(maybe_link, state.interaction, mouse)
+        } else {
+            Interaction::Normal
+        };
+
+        // If the mouse was released over the same link that was clicked, react.
+        if let (Interaction::Clicked(clicked), Interaction::Highlighted(released)) =
+            (state.interaction, new_interaction) {
+            if clicked == released {
+                if let Some(ref mut react) = self.maybe_react { react(&links[clicked][..]) }
+            }
+        }
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.interaction != new_interaction
+            || state.lines != lines
+            || state.links != links;
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed {
+            Some(State { lines: lines, links: links, interaction: new_interaction })
+        } else {
+            None
+        };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the Markdown.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let spacing = style.line_spacing(&ui.theme);
+        let (left, top) = (xy[0] - dim[0] / 2.0, xy[1] + dim[1] / 2.0);
+        let maybe_highlighted = state.interaction.link();
+        rich_text::forms(&state.lines, left, top, spacing, maybe_highlighted)
+    }
+
+    /// Describe the Markdown for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::Text).label(Some(&rich_text::plain_text(&state.lines)[..]))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_font_size: None,
+            maybe_line_spacing: None,
+            maybe_link_color: None,
+            maybe_code_color: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for body text.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.unwrap_or(theme.label_color)
+    }
+
+    /// Get the font size for body text.
+    pub fn font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_font_size.unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the spacing between each line.
+    pub fn line_spacing(&self, _theme: &Theme) -> Scalar {
+        const DEFAULT_LINE_SPACING: Scalar = 4.0;
+        self.maybe_line_spacing.unwrap_or(DEFAULT_LINE_SPACING)
+    }
+
+    /// Get the Color for links.
+    pub fn link_color(&self, _theme: &Theme) -> Color {
+        self.maybe_link_color.unwrap_or(color::blue())
+    }
+
+    /// Get the background Color for code.
+    pub fn code_color(&self, theme: &Theme) -> Color {
+        self.maybe_code_color.unwrap_or(theme.label_color.alpha(0.1))
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for Markdown<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for Markdown<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Fontable for Markdown<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Positionable for Markdown<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        Markdown { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        Markdown { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{heading, list_item, parse_inline, parse_link, Inline};
+
+    fn inline(bold: bool, italic: bool, code: bool, maybe_link: Option<usize>) -> Inline {
+        Inline { bold: bold, italic: italic, code: code, maybe_link: maybe_link }
+    }
+
+    fn pieces(text: &str) -> Vec<(String, Inline)> {
+        parse_inline(text, &mut Vec::new())
+    }
+
+    #[test]
+    fn emphasis_is_toggled_by_its_markers() {
+        let plain = inline(false, false, false, None);
+        let bold = inline(true, false, false, None);
+        let italic = inline(false, true, false, None);
+        let bold_italic = inline(true, true, false, None);
+        assert_eq!(pieces("a **b** *c*"), vec![("a ".to_string(), plain),
+                                               ("b".to_string(), bold),
+                                               (" ".to_string(), plain),
+                                               ("c".to_string(), italic)]);
+        assert_eq!(pieces("*a **b** c*"), vec![("a ".to_string(), italic),
+                                               ("b".to_string(), bold_italic),
+                                               (" c".to_string(), italic)]);
+    }
+
+    #[test]
+    fn unclosed_emphasis_markers_are_literal() {
+        let plain = inline(false, false, false, None);
+        assert_eq!(pieces("2 * 3 and **open"), vec![("2 * 3 and **open".to_string(), plain)]);
+        assert_eq!(pieces("a **b"), vec![("a **b".to_string(), plain)]);
+    }
+
+    #[test]
+    fn inline_code_and_links_are_parsed() {
+        let plain = inline(false, false, false, None);
+        assert_eq!(pieces("a `b*` c"), vec![("a ".to_string(), plain),
+                                            ("b*".to_string(), inline(false, false, true, None)),
+                                            (" c".to_string(), plain)]);
+        let mut links = Vec::new();
+        assert_eq!(parse_inline("see [docs](http://a.b)", &mut links),
+                   vec![("see ".to_string(), plain),
+                        ("docs".to_string(), inline(false, false, false, Some(0)))]);
+        assert_eq!(links, vec!["http://a.b".to_string()]);
+    }
+
+    #[test]
+    fn links_require_a_label_and_a_closed_url() {
+        assert_eq!(parse_link("[a](b) c"), Some(("a", "b", 6)));
+        assert_eq!(parse_link("[a] (b)"), None);
+        assert_eq!(parse_link("[a](b"), None);
+    }
+
+    #[test]
+    fn headings_have_one_to_six_hashes_and_a_space() {
+        assert_eq!(heading("# Title"), Some((1, "Title")));
+        assert_eq!(heading("###  Sub "), Some((3, "Sub")));
+        assert_eq!(heading("#Title"), None);
+        assert_eq!(heading("####### Title"), None);
+    }
+
+    #[test]
+    fn list_items_are_bulleted_or_numbered() {
+        assert_eq!(list_item("- a"), Some(("\u{2022} ".to_string(), "a")));
+        assert_eq!(list_item("* a"), Some(("\u{2022} ".to_string(), "a")));
+        assert_eq!(list_item("12. a"), Some(("12. ".to_string(), "a")));
+        assert_eq!(list_item("1.a"), None);
+        assert_eq!(list_item("-a"), None);
+    }
+
+}
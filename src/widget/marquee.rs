@@ -0,0 +1,449 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use elmesque::form::Form;
+use enable::Enableable;
+use frame::{Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use palette::{ColorRole, Paletted};
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::{is_over_rect, rects_overlap};
+use widget::{self, Widget};
+
+
+/// An area within which widgets are selected by dragging a rectangle (the "marquee" or
+/// "rubber-band") over them, as found in most editors.
+///
+/// A drag must begin on empty space within the area, that is, not over any of the selectable
+/// widgets. While dragging, the reaction is called every update with the widgets overlapped by
+/// the rectangle, the rectangle's centre and dimensions and `false`. Upon release it is called one
+/// last time with `true`. A click without dragging reports an empty selection, so that clicking on
+/// empty space deselects everything.
+///
+/// If Shift is held when the drag begins, the selection is additive: the `selection` given to
+/// `Marquee::new` is kept and the overlapped widgets are added to it.
+///
+/// Use `ui_selection` to select widgets via the `Ui`'s selection (see `Ui::selection`) instead,
+/// so that, for example, the selected Draggables may be dragged together.
+///
+/// By default any widget overlapping the area may be selected, excluding those at least as large
+/// as the area itself (i.e. an EditorCanvas beneath it). Use `candidates` to restrict the
+/// selectable widgets. The Marquee should be set before the widgets it selects so that it is
+/// drawn beneath them.
+pub struct Marquee<'a, F> {
+    selection: &'a [UiId],
+    maybe_candidates: Option<&'a [UiId]>,
+    ui_selection: bool,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the Marquee, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+}
+
+/// The state of the Marquee.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    drag: Drag,
+}
+
+/// Whether or not the marquee is being dragged.
+#[derive(Clone, Debug, PartialEq)]
+enum Drag {
+    /// The left mouse button is up.
+    Idle,
+    /// The left mouse button was pressed outside of the empty area and is still held.
+    Blocked,
+    /// A rectangle is being dragged from `origin` to `corner`. `base` is the selection that was
+    /// kept when the drag began.
+    Selecting { origin: Point, corner: Point, base: Vec<UiId> },
+}
+
+
+/// The centre and dimensions of the rectangle with the given opposite corners.
+fn rect_between(a: Point, b: Point) -> (Point, Dimensions) {
+    ([(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0], [(a[0] - b[0]).abs(), (a[1] - b[1]).abs()])
+}
+
+/// The given base selection followed by each of the overlapped widgets not already within it.
+fn combine(base: &[UiId], overlapped: &[UiId]) -> Vec<UiId> {
+    let mut selected = base.to_vec();
+    for &ui_id in overlapped.iter() {
+        if !selected.contains(&ui_id) { selected.push(ui_id); }
+    }
+    selected
+}
+
+
+impl<'a, F> Marquee<'a, F> {
+
+    /// Construct a new Marquee with the currently selected widgets.
+    pub fn new(selection: &'a [UiId]) -> Marquee<'a, F> {
+        Marquee {
+            selection: selection,
+            maybe_candidates: None,
+            ui_selection: false,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the reaction for the Marquee. It is called with the selected widgets, the centre and
+    /// dimensions of the marquee and whether or not the mouse has been released.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Restrict the widgets that may be selected to the given candidates.
+    pub fn candidates(mut self, candidates: &'a [UiId]) -> Self {
+        self.maybe_candidates = Some(candidates);
+        self
+    }
+
+    /// Use the `Ui`'s selection in place of the `selection` given to `Marquee::new`, replacing it
+    /// with the selected widgets as the marquee is dragged.
+    pub fn ui_selection(mut self) -> Self {
+        self.ui_selection = true;
+        self
+    }
+
+    /// Set the depth of the Marquee.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for Marquee<'a, F>
+    where
+        F: FnMut(&[UiId], Point, Dimensions, bool),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Marquee" }
+    fn init_state(&self) -> State {
+        State { drag: Drag::Idle }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+
+    /// Update the state of the Marquee.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::Down;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id);
+
+        // The widgets that may be selected.
+        let selectable: Vec<UiId> = match self.maybe_candidates {
+            Some(candidates) => candidates.to_vec(),
+            None => ui.widgets_overlapping(xy, dim).into_iter().filter(|&id| {
+                let w_dim = ui.widget_size(id);
+                id != ui_id && (w_dim[0] < dim[0] || w_dim[1] < dim[1])
+            }).collect(),
+        };
+        let overlapped = |ui: &Ui<C>, origin: Point, corner: Point| -> Vec<UiId> {
+            let (rect_xy, rect_dim) = rect_between(origin, corner);
+            selectable.iter().map(|&id| id).filter(|&id| {
+                rects_overlap(ui.widget_xy(id), ui.widget_size(id), rect_xy, rect_dim)
+            }).collect()
+        };
+
+        // Alt with the left button is reserved for panning an EditorCanvas.
+        let is_down = self.enabled && mouse.left.state == Down && !ui.is_alt_down();
+        let drag = match (is_down, &state.drag) {
+            (false, _) => Drag::Idle,
+            (true, &Drag::Selecting { origin, ref base, .. }) =>
+                Drag::Selecting { origin: origin, corner: mouse.xy, base: base.clone() },
+            (true, &Drag::Idle) => {
+                let is_over_empty = is_over_rect(xy, mouse.xy, dim)
+                    && !selectable.iter().any(|&id| {
+                        is_over_rect(ui.widget_xy(id), mouse.xy, ui.widget_size(id))
+                    });
+                if is_over_empty {
+                    let base = match (ui.is_shift_down(), self.ui_selection) {
+                        (true, true) => ui.selection().to_vec(),
+                        (true, false) => self.selection.to_vec(),
+                        (false, _) => Vec::new(),
+                    };
+                    Drag::Selecting { origin: mouse.xy, corner: mouse.xy, base: base }
+                } else {
+                    Drag::Blocked
+                }
+            },
+            (true, _) => Drag::Blocked,
+        };
+        let was_selecting = if let Drag::Selecting { .. } = state.drag { true } else { false };
+        let is_selecting = if let Drag::Selecting { .. } = drag { true } else { false };
+        ui.mouse_dragged_by(ui_id, was_selecting, is_selecting);
+
+        // React with the selection, using the previous rectangle upon release.
+        let mut maybe_ui_selection = None;
+        {
+            let maybe_selection = match (&state.drag, &drag) {
+                (_, &Drag::Selecting { origin, corner, ref base }) =>
+                    Some((origin, corner, base, false)),
+                (&Drag::Selecting { origin, corner, ref base }, _) =>
+                    Some((origin, corner, base, true)),
+                _ => None,
+            };
+            if let Some((origin, corner, base, released)) = maybe_selection {
+                let selected = combine(base, &overlapped(ui, origin, corner));
+                let (rect_xy, rect_dim) = rect_between(origin, corner);
+                if let Some(ref mut react) = self.maybe_react {
+                    react(&selected, rect_xy, rect_dim, released)
+                }
+                if self.ui_selection {
+                    maybe_ui_selection = Some(selected);
+                }
+            }
+        }
+        if let Some(selected) = maybe_ui_selection {
+            ui.set_selection(&selected);
+        }
+
+        let new_state = State { drag: drag };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given Marquee State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{line, rect, solid};
+        let widget::State { ref state, .. } = *new_state;
+        match state.drag {
+            Drag::Selecting { origin, corner, .. } => {
+                let (rect_xy, rect_dim) = rect_between(origin, corner);
+                let color = style.color(&ui.theme);
+                let frame_color = style.frame_color(&ui.theme);
+                let (left, right) = (origin[0].min(corner[0]), origin[0].max(corner[0]));
+                let (bottom, top) = (origin[1].min(corner[1]), origin[1].max(corner[1]));
+                let widths = style.frame_widths(&ui.theme);
+                let edges = [
+                    (widths.top, [left, top], [right, top]),
+                    (widths.right, [right, top], [right, bottom]),
+                    (widths.bottom, [right, bottom], [left, bottom]),
+                    (widths.left, [left, bottom], [left, top]),
+                ];
+                let mut forms = vec![
+                    rect(rect_dim[0], rect_dim[1]).filled(color.alpha(0.2))
+                        .shift(rect_xy[0], rect_xy[1]),
+                ];
+                for &(width, a, b) in edges.iter().filter(|&&(width, _, _)| width > 0.0) {
+                    forms.push(line(solid(frame_color).width(width), a[0], a[1], b[0], b[1]));
+                }
+                forms
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    /// Describe the Marquee for assistive technologies.
+    fn accessibility(_state: &State) -> Accessibility {
+        Accessibility::new(Role::Marquee)
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_width: None,
+            maybe_height: None,
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+        }
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [256.0, 256.0];
+        let width = self.maybe_width.or(theme.maybe_marquee.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_marquee.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the Color with which the marquee is filled (at a low alpha).
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_marquee.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the width of the marquee's outline.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_marquee.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the Color of the marquee's outline.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_marquee.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_marquee.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_marquee.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_marquee.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+}
+
+
+impl<'a, F> Enableable for Marquee<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for Marquee<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for Marquee<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Frameable for Marquee<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Positionable for Marquee<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        Marquee { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        Marquee { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> ::position::Sizeable for Marquee<'a, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
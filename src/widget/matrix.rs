@@ -1,5 +1,6 @@
 
-use position::{self, Dimensions, HorizontalAlign, Point, Position, VerticalAlign};
+use position::{self, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, VerticalAlign};
+use std::ops::Range;
 use ui::Ui;
 
 /// Reaction params.
@@ -14,11 +15,19 @@ pub type PosY = f64;
 /// Draw a matrix of any rectangular widget type, where the matrix will provide a function with
 /// the widget number, it's `rows` and `cols` position, the width and height for the widget and
 /// the location at which the widget should be drawn.
-#[derive(Copy, Clone)]
+///
+/// Columns and rows are of uniform size unless given relative weights, and individual cells may
+/// span multiple columns and rows.
+#[derive(Clone)]
 pub struct Matrix {
     cols: usize,
     rows: usize,
+    col_weights: Vec<f64>,
+    row_weights: Vec<f64>,
+    spans: Vec<Span>,
     pos: Position,
+    margin: Margin,
+    padding: Padding,
     dim: Dimensions,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
@@ -26,6 +35,40 @@ pub struct Matrix {
     cell_pad_h: f64,
 }
 
+/// A cell spanning multiple columns and rows, from its top left cell.
+#[derive(Copy, Clone, Debug)]
+struct Span {
+    col: ColNum,
+    row: RowNum,
+    cols: usize,
+    rows: usize,
+}
+
+/// The distance of each boundary between `n` columns or rows (including the outer edges) from the
+/// start of the given length, sharing the length by the given weights.
+fn boundaries(weights: &[f64], n: usize, length: f64) -> Vec<f64> {
+    let weight = |i: usize| weights.get(i).cloned().unwrap_or(1.0).max(0.0);
+    let total = (0..n).fold(0.0, |total, i| total + weight(i));
+    let mut boundaries = Vec::with_capacity(n + 1);
+    let mut distance = 0.0;
+    boundaries.push(distance);
+    for i in 0..n {
+        distance += if total > 0.0 { length * weight(i) / total } else { length / n as f64 };
+        boundaries.push(distance);
+    }
+    boundaries
+}
+
+/// The range of the `n` columns or rows for which the given function returns true, assuming
+/// that they are contiguous.
+fn contiguous_range<F>(n: usize, is_within: F) -> Range<usize>
+    where F: Fn(usize) -> bool,
+{
+    let start = (0..n).position(|i| is_within(i)).unwrap_or(n);
+    let end = start + (start..n).take_while(|&i| is_within(i)).count();
+    start..end
+}
+
 /*
 /// A cell to be returned via the cell reaction.
 pub struct MatrixCell<'a>(&'a mut UiContext, WidgetNum, ColNum, RowNum, PosX, PosY, Width, Height);
@@ -38,7 +81,12 @@ impl Matrix {
         Matrix {
             cols: cols,
             rows: rows,
+            col_weights: Vec::new(),
+            row_weights: Vec::new(),
+            spans: Vec::new(),
             pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
             dim: [256.0, 256.0],
             maybe_h_align: None,
             maybe_v_align: None,
@@ -49,35 +97,130 @@ impl Matrix {
 
     /// The reaction called for each widget in the matrix. This should be called following all
     /// builder methods.
-    pub fn each_widget<C, F>(&mut self, ui: &mut Ui<C>, mut react: F)
+    ///
+    /// The widget number of each cell is `col * rows + row`, regardless of any spanning cells, so
+    /// that it may be used to derive stable `UiId`s. Cells covered by a spanning cell are
+    /// skipped.
+    pub fn each_widget<C, F>(&mut self, ui: &mut Ui<C>, react: F)
         where
             F: FnMut(&mut Ui<C>, WidgetNum, ColNum, RowNum, Point, Dimensions)
     {
-        use utils::map_range;
+        let (cols, rows) = (self.cols, self.rows);
+        self.each_widget_within(ui, 0..cols, 0..rows, react);
+    }
+
+    /// Like `each_widget`, but the reaction is only called for the cells that overlap the visible
+    /// area of the `Ui` (see `Ui::visible_area`), i.e. the cells scrolled into view within a
+    /// clipping scope. This makes matrices with many thousands of cells feasible.
+    ///
+    /// A spanning cell is instantiated whenever any part of it is visible. The widgets of cells
+    /// scrolled out of view are no longer set but keep their state, unless widget removal has
+    /// been enabled via `Ui::enable_widget_removal`, in which case they are removed once its
+    /// grace has passed.
+    pub fn each_visible_widget<C, F>(&mut self, ui: &mut Ui<C>, react: F)
+        where
+            F: FnMut(&mut Ui<C>, WidgetNum, ColNum, RowNum, Point, Dimensions)
+    {
+        let (cols, rows) = self.visible_cells(ui);
+        self.each_widget_within(ui, cols, rows, react);
+    }
+
+    /// The ranges of the columns and rows that overlap the visible area of the `Ui` (see
+    /// `Ui::visible_area`).
+    pub fn visible_cells<C>(&self, ui: &Ui<C>) -> (Range<ColNum>, Range<RowNum>) {
+        let (left, top, xs, ys) = self.layout(ui);
+        let (vis_xy, vis_dim) = ui.visible_area();
+        let (vis_left, vis_right) = (vis_xy[0] - vis_dim[0] / 2.0, vis_xy[0] + vis_dim[0] / 2.0);
+        let (vis_bottom, vis_top) = (vis_xy[1] - vis_dim[1] / 2.0, vis_xy[1] + vis_dim[1] / 2.0);
+        let cols = contiguous_range(self.cols, |col| {
+            left + xs[col + 1] > vis_left && left + xs[col] < vis_right
+        });
+        let rows = contiguous_range(self.rows, |row| {
+            top - ys[row] > vis_bottom && top - ys[row + 1] < vis_top
+        });
+        (cols, rows)
+    }
+
+    /// The left and top edges of the area within which the cells are laid out, along with the
+    /// distance of each column and row boundary from them.
+    fn layout<C>(&self, ui: &Ui<C>) -> (f64, f64, Vec<f64>, Vec<f64>) {
         let dim = self.dim;
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
-        let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
-        let widget_w = dim[0] / self.cols as f64;
-        let widget_h = dim[1] / self.rows as f64;
-        let x_min = -half_w + widget_w / 2.0;
-        let x_max = half_w + widget_w / 2.0;
-        let y_min = -half_h - widget_h / 2.0;
-        let y_max = half_h - widget_h / 2.0;
-        let mut widget_num = 0;
-        for col in 0..self.cols {
-            for row in 0..self.rows {
-                let x = xy[0] + map_range(col as f64, 0.0, self.cols as f64, x_min, x_max);
-                let y = xy[1] + map_range(row as f64, 0.0, self.rows as f64, y_max, y_min);
-                let w = widget_w - self.cell_pad_w * 2.0;
-                let h = widget_h - self.cell_pad_h * 2.0;
+        let xy = ui.get_xy_with_margin(self.pos, dim, self.margin, h_align, v_align);
+        // The cells are laid out within the padded area.
+        let xy = ::vecmath::vec2_add(xy, self.padding.inner_offset());
+        let dim = self.padding.inner_dim(dim);
+        let (left, top) = (xy[0] - dim[0] / 2.0, xy[1] + dim[1] / 2.0);
+        let xs = boundaries(&self.col_weights, self.cols, dim[0]);
+        let ys = boundaries(&self.row_weights, self.rows, dim[1]);
+        (left, top, xs, ys)
+    }
+
+    /// Call the reaction for each cell within the given columns and rows, along with any spanning
+    /// cell that covers them.
+    fn each_widget_within<C, F>(&self,
+                                ui: &mut Ui<C>,
+                                cols: Range<ColNum>,
+                                rows: Range<RowNum>,
+                                mut react: F)
+        where
+            F: FnMut(&mut Ui<C>, WidgetNum, ColNum, RowNum, Point, Dimensions)
+    {
+        let (left, top, xs, ys) = self.layout(ui);
+        for visible_col in cols.clone() {
+            for visible_row in rows.clone() {
+                // A spanning cell is instantiated at the first of its cells within the range.
+                let maybe_span = self.span_at(visible_col, visible_row).cloned();
+                let (col, row, span_cols, span_rows) = match maybe_span {
+                    Some(span) => {
+                        let first = (span.col.max(cols.start), span.row.max(rows.start));
+                        if first != (visible_col, visible_row) { continue }
+                        (span.col, span.row, span.cols, span.rows)
+                    },
+                    None => (visible_col, visible_row, 1, 1),
+                };
+                let end_col = (col + span_cols).min(self.cols);
+                let end_row = (row + span_rows).min(self.rows);
+                let (x_start, x_end) = (left + xs[col], left + xs[end_col]);
+                let (y_start, y_end) = (top - ys[row], top - ys[end_row]);
+                let x = (x_start + x_end) / 2.0;
+                let y = (y_start + y_end) / 2.0;
+                let w = x_end - x_start - self.cell_pad_w * 2.0;
+                let h = y_start - y_end - self.cell_pad_h * 2.0;
+                let widget_num = col * self.rows + row;
                 react(ui, widget_num, col, row, [x, y], [w, h]);
-                widget_num += 1;
             }
         }
     }
 
+    /// Set the relative width of each column. Columns without a weight have a weight of `1.0`.
+    pub fn col_weights(mut self, weights: &[f64]) -> Matrix {
+        self.col_weights = weights.to_vec();
+        self
+    }
+
+    /// Set the relative height of each row. Rows without a weight have a weight of `1.0`.
+    pub fn row_weights(mut self, weights: &[f64]) -> Matrix {
+        self.row_weights = weights.to_vec();
+        self
+    }
+
+    /// Have the cell at the given column and row span the given number of columns and rows. The
+    /// cells it covers are skipped. Where spans overlap, the first given takes precedence.
+    pub fn span(mut self, col: ColNum, row: RowNum, cols: usize, rows: usize) -> Matrix {
+        self.spans.push(Span { col: col, row: row, cols: cols.max(1), rows: rows.max(1) });
+        self
+    }
+
+    /// The span covering the given cell, if there is one.
+    fn span_at(&self, col: ColNum, row: RowNum) -> Option<&Span> {
+        self.spans.iter().find(|span| {
+            col >= span.col && col < span.col + span.cols
+            && row >= span.row && row < span.row + span.rows
+        })
+    }
+
     /// A builder method for adding padding to the cell.
     pub fn cell_padding(self, w: f64, h: f64) -> Matrix {
         Matrix { cell_pad_w: w, cell_pad_h: h, ..self }
@@ -98,6 +241,11 @@ impl position::Positionable for Matrix {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         Matrix { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl position::Sizeable for Matrix {
@@ -111,5 +259,39 @@ impl position::Sizeable for Matrix {
         let w = self.dim[0];
         Matrix { dim: [w, h], ..self }
     }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::{boundaries, contiguous_range};
+
+    #[test]
+    fn boundaries_share_the_length_evenly_by_default() {
+        assert_eq!(boundaries(&[], 3, 90.0), vec![0.0, 30.0, 60.0, 90.0]);
+        assert_eq!(boundaries(&[], 0, 90.0), vec![0.0]);
+    }
+
+    #[test]
+    fn boundaries_share_the_length_by_weight() {
+        assert_eq!(boundaries(&[1.0, 2.0], 2, 90.0), vec![0.0, 30.0, 90.0]);
+        // Missing weights are 1.0 and negative weights are 0.0.
+        assert_eq!(boundaries(&[2.0], 2, 90.0), vec![0.0, 60.0, 90.0]);
+        assert_eq!(boundaries(&[-1.0], 2, 10.0), vec![0.0, 0.0, 10.0]);
+        // If every weight is 0.0, the length is shared evenly.
+        assert_eq!(boundaries(&[0.0, 0.0], 2, 10.0), vec![0.0, 5.0, 10.0]);
+    }
+
+    #[test]
+    fn contiguous_range_spans_the_cells_within() {
+        assert_eq!(contiguous_range(5, |i| i >= 1 && i < 3), 1..3);
+        assert_eq!(contiguous_range(5, |i| i >= 3), 3..5);
+        assert_eq!(contiguous_range(5, |_| false), 5..5);
+    }
+
+}
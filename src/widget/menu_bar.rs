@@ -0,0 +1,821 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize};
+use palette::{ColorRole, Paletted};
+use piston::input::keyboard::Key;
+use position::{self, Depth, Dimensions, HorizontalAlign, Layer, Margin, Padding, Point, Position,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::is_over_rect;
+use widget::{self, Widget};
+
+
+/// Index of a menu along the MenuBar or of an item within a menu.
+pub type Idx = usize;
+
+/// A menu along the MenuBar, given as its title and its items.
+pub type Menu<'a> = (&'a str, &'a [Item<'a>]);
+
+/// The height of a separator between groups of items.
+const SEPARATOR_HEIGHT: f64 = 9.0;
+
+/// An item within one of the MenuBar's menus.
+#[derive(Copy, Clone, Debug)]
+pub enum Item<'a> {
+    /// An entry that may be chosen.
+    Entry(&'a str),
+    /// An entry that is shown but may not be chosen.
+    Disabled(&'a str),
+    /// An entry that opens a nested menu of the given items.
+    Submenu(&'a str, &'a [Item<'a>]),
+    /// A line dividing groups of items.
+    Separator,
+}
+
+/// A horizontal strip of menus, each opening a panel of items when its title is clicked. Once a
+/// menu is open, hovering over another title opens that menu instead and hovering over a submenu
+/// opens its nested panel.
+///
+/// While a menu is open, the Up and Down keys move between its items, Right opens the highlighted
+/// submenu (or the next menu), Left closes the innermost submenu (or opens the previous menu) and
+/// Enter chooses the highlighted entry. The open menus are a transient, so pressing Escape or
+/// clicking anywhere outside of them closes them without reacting.
+///
+/// The reaction is triggered upon choosing an entry with the path of indices leading to it,
+/// starting with the index of the menu along the bar, along with the entry's label.
+pub struct MenuBar<'a, F> {
+    menus: &'a [Menu<'a>],
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// Styling for the MenuBar, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_item_width: Option<f64>,
+    pub maybe_item_padding: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// Represents the state of the MenuBar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    menus: Vec<Node>,
+    /// The index of the open menu followed by that of each open submenu, or empty while closed.
+    open_path: Vec<Idx>,
+    /// The highlighted item within the innermost open menu.
+    maybe_highlighted: Option<Idx>,
+    /// Whether or not the left mouse button was down as of the previous update.
+    was_down: bool,
+}
+
+/// An owned copy of a menu or item, stored within the State so that it may be drawn.
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    kind: Kind,
+    label: String,
+    children: Vec<Node>,
+}
+
+/// The kinds of item that may appear within a menu.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Kind {
+    Entry,
+    Disabled,
+    Submenu,
+    Separator,
+}
+
+/// An open menu's panel, along with the rectangle of each of its items.
+struct Panel {
+    xy: Point,
+    dim: Dimensions,
+    items: Vec<(Point, Dimensions)>,
+}
+
+
+impl Node {
+
+    /// Copy the given item along with any items nested within it.
+    fn from_item(item: &Item) -> Node {
+        let (kind, label, children) = match *item {
+            Item::Entry(label) => (Kind::Entry, label, Vec::new()),
+            Item::Disabled(label) => (Kind::Disabled, label, Vec::new()),
+            Item::Submenu(label, items) =>
+                (Kind::Submenu, label, items.iter().map(Node::from_item).collect()),
+            Item::Separator => (Kind::Separator, "", Vec::new()),
+        };
+        Node { kind: kind, label: label.to_string(), children: children }
+    }
+
+    /// Whether or not the item may be highlighted via the keyboard.
+    fn is_selectable(&self) -> bool {
+        self.kind == Kind::Entry || self.kind == Kind::Submenu
+    }
+
+    /// The height of the item within its menu's panel.
+    fn height(&self, item_h: f64) -> f64 {
+        if self.kind == Kind::Separator { SEPARATOR_HEIGHT } else { item_h }
+    }
+
+}
+
+
+/// Copy the given menus, each as a submenu of the bar.
+fn nodes(menus: &[Menu]) -> Vec<Node> {
+    menus.iter().map(|&(title, items)| Node::from_item(&Item::Submenu(title, items))).collect()
+}
+
+/// The menu at the end of the given path, which must be valid.
+fn node_at<'a>(menus: &'a [Node], path: &[Idx]) -> &'a Node {
+    path[1..].iter().fold(&menus[path[0]], |node, &idx| &node.children[idx])
+}
+
+/// Whether or not the given path leads through submenus that exist.
+fn is_valid_path(menus: &[Node], path: &[Idx]) -> bool {
+    match path.first() {
+        Some(&idx) if idx < menus.len() => (1..path.len()).all(|len| {
+            let items = &node_at(menus, &path[..len]).children;
+            path[len] < items.len() && items[path[len]].kind == Kind::Submenu
+        }),
+        Some(_) => false,
+        None => true,
+    }
+}
+
+/// The path of the given item within the innermost of the open menus.
+fn path_to(open_path: &[Idx], idx: Idx) -> Vec<Idx> {
+    open_path.iter().cloned().chain(Some(idx).into_iter()).collect()
+}
+
+/// The next selectable item after (or before) the given one, wrapping around the menu.
+fn next_selectable(items: &[Node], maybe_from: Option<Idx>, forward: bool) -> Option<Idx> {
+    let len = items.len();
+    let mut maybe_idx = maybe_from;
+    for _ in 0..len {
+        let next = match (maybe_idx, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(idx), true) => (idx + 1) % len,
+            (Some(idx), false) => (idx + len - 1) % len,
+        };
+        if items[next].is_selectable() {
+            return Some(next);
+        }
+        maybe_idx = Some(next);
+    }
+    None
+}
+
+/// The rectangle of each menu's title along the bar, from left to right.
+fn title_rects<C>(ui: &mut Ui<C>,
+                  menus: &[Node],
+                  xy: Point,
+                  dim: Dimensions,
+                  font_size: FontSize,
+                  item_pad: f64) -> Vec<(Point, Dimensions)>
+    where
+        C: CharacterCache,
+{
+    let mut left = xy[0] - dim[0] / 2.0;
+    menus.iter().map(|menu| {
+        let w = label::width(ui, font_size, &menu.label) + item_pad * 2.0;
+        let rect = ([left + w / 2.0, xy[1]], [w, dim[1]]);
+        left += w;
+        rect
+    }).collect()
+}
+
+/// The panel of the given items, hanging from the given top left corner.
+fn panel(top_left: Point, items: &[Node], item_dim: Dimensions, frame: f64) -> Panel {
+    let x = top_left[0] + item_dim[0] / 2.0;
+    let inner_w = item_dim[0] - frame * 2.0;
+    let mut y = top_left[1] - frame;
+    let rects = items.iter().map(|item| {
+        let h = item.height(item_dim[1]);
+        let rect = ([x, y - h / 2.0], [inner_w, h]);
+        y -= h;
+        rect
+    }).collect();
+    let h = top_left[1] - y + frame;
+    Panel { xy: [x, top_left[1] - h / 2.0], dim: [item_dim[0], h], items: rects }
+}
+
+/// The panels of the open menu and each of its open submenus. The menu hangs beneath its title,
+/// while each submenu extends to the right of the item that opened it.
+fn open_panels(menus: &[Node],
+               open_path: &[Idx],
+               titles: &[(Point, Dimensions)],
+               item_dim: Dimensions,
+               frame: f64) -> Vec<Panel> {
+    let mut panels: Vec<Panel> = Vec::new();
+    if open_path.is_empty() {
+        return panels;
+    }
+    let (title_xy, title_dim) = titles[open_path[0]];
+    let mut top_left = [title_xy[0] - title_dim[0] / 2.0, title_xy[1] - title_dim[1] / 2.0];
+    for len in 1..open_path.len() + 1 {
+        let items = &node_at(menus, &open_path[..len]).children;
+        let panel = panel(top_left, items, item_dim, frame);
+        if let Some(&idx) = open_path.get(len) {
+            let (item_xy, item_dim) = panel.items[idx];
+            top_left = [panel.xy[0] + panel.dim[0] / 2.0, item_xy[1] + item_dim[1] / 2.0 + frame];
+        }
+        panels.push(panel);
+    }
+    panels
+}
+
+/// Navigate the open menus via the given key, returning the path of the chosen entry if one was
+/// chosen.
+fn navigate(menus: &[Node],
+            key: Key,
+            open_path: &mut Vec<Idx>,
+            maybe_highlighted: &mut Option<Idx>) -> Option<Vec<Idx>> {
+    let num_menus = menus.len();
+    let items = &node_at(menus, open_path).children;
+    let maybe_kind = maybe_highlighted.map(|idx| items[idx].kind);
+    match (key, maybe_kind) {
+        (Key::Down, _) | (Key::Up, _) => {
+            *maybe_highlighted = next_selectable(items, *maybe_highlighted, key == Key::Down);
+        },
+        (Key::Right, Some(Kind::Submenu)) | (Key::Return, Some(Kind::Submenu)) => {
+            let idx = maybe_highlighted.unwrap();
+            *maybe_highlighted = next_selectable(&items[idx].children, None, true);
+            open_path.push(idx);
+        },
+        (Key::Return, Some(Kind::Entry)) => {
+            return Some(path_to(open_path, maybe_highlighted.unwrap()));
+        },
+        (Key::Right, _) => {
+            let idx = (open_path[0] + 1) % num_menus;
+            *open_path = vec![idx];
+            *maybe_highlighted = next_selectable(&menus[idx].children, None, true);
+        },
+        (Key::Left, _) => if open_path.len() > 1 {
+            *maybe_highlighted = open_path.pop();
+        } else {
+            let idx = (open_path[0] + num_menus - 1) % num_menus;
+            *open_path = vec![idx];
+            *maybe_highlighted = next_selectable(&menus[idx].children, None, true);
+        },
+        _ => (),
+    }
+    None
+}
+
+
+impl<'a, F> MenuBar<'a, F> {
+
+    /// Construct a new MenuBar with the given menus.
+    pub fn new(menus: &'a [Menu<'a>]) -> MenuBar<'a, F> {
+        MenuBar {
+            menus: menus,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: -1.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the width of the panel of each open menu.
+    #[inline]
+    pub fn item_width(mut self, width: f64) -> MenuBar<'a, F> {
+        self.style.maybe_item_width = Some(width);
+        self
+    }
+
+    /// Set the padding on either side of each title and item's text.
+    #[inline]
+    pub fn item_padding(mut self, padding: f64) -> MenuBar<'a, F> {
+        self.style.maybe_item_padding = Some(padding);
+        self
+    }
+
+    /// Set the rendering depth of the MenuBar and its open menus.
+    #[inline]
+    pub fn depth(mut self, depth: Depth) -> MenuBar<'a, F> {
+        self.depth = depth;
+        self
+    }
+
+    /// Set the color of the titles' and items' text.
+    #[inline]
+    pub fn label_color(mut self, color: Color) -> MenuBar<'a, F> {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    /// Set the font size of the titles' and items' text.
+    #[inline]
+    pub fn label_font_size(mut self, size: FontSize) -> MenuBar<'a, F> {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+
+    /// Set the reaction for the MenuBar. It will be triggered upon choosing an entry.
+    pub fn react(mut self, reaction: F) -> MenuBar<'a, F> {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for MenuBar<'a, F>
+    where
+        F: FnMut(&[Idx], &str),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "MenuBar" }
+    fn init_state(&self) -> State {
+        State { menus: Vec::new(), open_path: Vec::new(), maybe_highlighted: None, was_down: false }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the MenuBar.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::Down;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let frame = style.frame(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let item_pad = style.item_padding(&ui.theme);
+        let item_dim = [style.item_width(&ui.theme), dim[1]];
+        let menus = nodes(self.menus);
+        let titles = title_rects(ui, &menus, xy, dim, font_size, item_pad);
+
+        let mouse = ui.get_mouse_state(ui_id);
+        let is_down = mouse.left.state == Down;
+        let is_pressed = is_down && !state.was_down;
+        let is_released = !is_down && state.was_down;
+        let maybe_over_title =
+            titles.iter().position(|&(t_xy, t_dim)| is_over_rect(t_xy, mouse.xy, t_dim));
+
+        // Close the menus if those that were open have since been removed.
+        let was_open = !state.open_path.is_empty();
+        let (mut open_path, mut maybe_highlighted) = if is_valid_path(&menus, &state.open_path) {
+            let path = state.open_path.clone();
+            let num_items = if path.is_empty() { 0 } else { node_at(&menus, &path).children.len() };
+            let maybe_highlighted = state.maybe_highlighted.and_then(|idx| {
+                if idx < num_items { Some(idx) } else { None }
+            });
+            (path, maybe_highlighted)
+        } else {
+            (Vec::new(), None)
+        };
+        let mut maybe_chosen = None;
+
+        if !self.enabled || (was_open && ui.is_dismissed(ui_id)) {
+            open_path.clear();
+        } else if open_path.is_empty() {
+            // Pressing a title opens its menu.
+            if let (true, Some(idx)) = (is_pressed, maybe_over_title) {
+                open_path.push(idx);
+                maybe_highlighted = None;
+            }
+        } else {
+            let panels = open_panels(&menus, &open_path, &titles, item_dim, frame);
+            let maybe_over_item = panels.iter().enumerate().rev().filter_map(|(depth, panel)| {
+                panel.items.iter()
+                    .position(|&(i_xy, i_dim)| is_over_rect(i_xy, mouse.xy, i_dim))
+                    .map(|idx| (depth, idx))
+            }).next();
+            let is_over_panel =
+                panels.iter().any(|panel| is_over_rect(panel.xy, mouse.xy, panel.dim));
+            match (maybe_over_title, maybe_over_item) {
+                // Pressing the open menu's title closes it, while hovering over another title
+                // opens that menu instead.
+                (Some(idx), _) => if is_pressed && open_path[0] == idx {
+                    open_path.clear();
+                } else if open_path[0] != idx {
+                    open_path = vec![idx];
+                    maybe_highlighted = None;
+                },
+                // Hovering over a submenu opens it, while releasing over an entry chooses it.
+                (None, Some((depth, idx))) => {
+                    open_path.truncate(depth + 1);
+                    let kind = node_at(&menus, &open_path).children[idx].kind;
+                    maybe_highlighted = None;
+                    match kind {
+                        Kind::Submenu => open_path.push(idx),
+                        Kind::Entry if is_released =>
+                            maybe_chosen = Some(path_to(&open_path, idx)),
+                        Kind::Entry => maybe_highlighted = Some(idx),
+                        Kind::Disabled | Kind::Separator => (),
+                    }
+                },
+                (None, None) => if is_pressed && !is_over_panel {
+                    open_path.clear();
+                },
+            }
+        }
+
+        // Navigate the open menus via the keyboard.
+        for &key in ui.get_pressed_keys(ui_id).iter() {
+            if open_path.is_empty() || maybe_chosen.is_some() {
+                break;
+            }
+            maybe_chosen = navigate(&menus, key, &mut open_path, &mut maybe_highlighted);
+        }
+
+        if let Some(path) = maybe_chosen {
+            if let Some(ref mut react) = self.maybe_react {
+                let (parent, idx) = (&path[..path.len() - 1], path[path.len() - 1]);
+                react(&path[..], &node_at(&menus, parent).children[idx].label[..]);
+            }
+            open_path.clear();
+        }
+        if open_path.is_empty() {
+            maybe_highlighted = None;
+        }
+
+        // Capture the mouse and keyboard while the menus are open so that the widgets beneath
+        // don't react, registering the area covered by the bar and the open panels as a transient
+        // and drawing them within the popup layer.
+        let is_open = !open_path.is_empty();
+        match (was_open, is_open) {
+            (false, true) => {
+                ui.mouse_captured_by(ui_id);
+                ui.keyboard_captured_by(ui_id);
+                ui.set_layer(ui_id, Layer::Popup);
+            },
+            (true, false) => {
+                ui.mouse_uncaptured_by(ui_id);
+                ui.keyboard_uncaptured_by(ui_id);
+                ui.transient_closed_by(ui_id);
+                ui.reset_layer(ui_id);
+            },
+            _ => (),
+        }
+        if is_open {
+            let panels = open_panels(&menus, &open_path, &titles, item_dim, frame);
+            let (mut left, mut right) = (xy[0] - dim[0] / 2.0, xy[0] + dim[0] / 2.0);
+            let (mut bottom, top) = (xy[1] - dim[1] / 2.0, xy[1] + dim[1] / 2.0);
+            for panel in panels.iter() {
+                left = left.min(panel.xy[0] - panel.dim[0] / 2.0);
+                right = right.max(panel.xy[0] + panel.dim[0] / 2.0);
+                bottom = bottom.min(panel.xy[1] - panel.dim[1] / 2.0);
+            }
+            let area_xy = [(left + right) / 2.0, (bottom + top) / 2.0];
+            ui.transient_opened_by(ui_id, area_xy, [right - left, top - bottom]);
+        }
+
+        let new_state = State {
+            menus: menus,
+            open_path: open_path,
+            maybe_highlighted: maybe_highlighted,
+            was_down: is_down,
+        };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given MenuBar State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{polygon, rect, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+
+        // Retrieve the styling for the Element.
+        let color = style.color(&ui.theme);
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let item_pad = style.item_padding(&ui.theme);
+        let item_dim = [style.item_width(&ui.theme), dim[1]];
+        let label_form = |string: &str, color: Color| {
+            text(Text::from_string(string.to_string()).color(color).height(font_size as f64))
+        };
+
+        // The bar, with the title of the open menu shown as pressed.
+        let mut forms = Vec::new();
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        forms.push(frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                               style.frame_style(&ui.theme), frame_color)
+                       .shift(xy[0], xy[1]));
+        forms.push(rect(inner_dim[0], inner_dim[1]).filled(color)
+                       .shift(xy[0] + inner_offset[0], xy[1] + inner_offset[1]));
+        let titles = title_rects(ui, &state.menus, xy, dim, font_size, item_pad);
+        for (i, (menu, &(t_xy, t_dim))) in state.menus.iter().zip(titles.iter()).enumerate() {
+            if state.open_path.first() == Some(&i) {
+                forms.push(rect(t_dim[0], inner_dim[1]).filled(color.clicked())
+                               .shift(t_xy[0], t_xy[1]));
+            }
+            forms.push(label_form(&menu.label, label_color)
+                           .shift(t_xy[0].floor(), t_xy[1].floor()));
+        }
+
+        // The panel of each open menu, highlighting the item that opened the next submenu or, in
+        // the innermost menu, the highlighted item.
+        let panels = open_panels(&state.menus, &state.open_path, &titles, item_dim, frame);
+        for (depth, panel) in panels.iter().enumerate() {
+            let items = &node_at(&state.menus, &state.open_path[..depth + 1]).children;
+            let maybe_active = match state.open_path.get(depth + 1) {
+                Some(&idx) => Some(idx),
+                None => state.maybe_highlighted,
+            };
+            let (p_xy, p_dim) = (panel.xy, panel.dim);
+            forms.push(rect(p_dim[0], p_dim[1]).filled(frame_color).shift(p_xy[0], p_xy[1]));
+            forms.push(rect(p_dim[0] - frame * 2.0, p_dim[1] - frame * 2.0).filled(color)
+                           .shift(p_xy[0], p_xy[1]));
+            for (i, (item, &(i_xy, i_dim))) in items.iter().zip(panel.items.iter()).enumerate() {
+                if item.kind == Kind::Separator {
+                    forms.push(rect(i_dim[0] - item_pad * 2.0, frame.max(1.0)).filled(frame_color)
+                                   .shift(i_xy[0], i_xy[1]));
+                    continue;
+                }
+                if maybe_active == Some(i) {
+                    forms.push(rect(i_dim[0], i_dim[1]).filled(color.highlighted())
+                                   .shift(i_xy[0], i_xy[1]));
+                }
+                let item_label_color = match item.kind {
+                    Kind::Disabled => label_color.alpha(0.4),
+                    _ => label_color,
+                };
+                let text_w = label::width(ui, font_size, &item.label);
+                let text_x = i_xy[0] + position::align_left_of(i_dim[0], text_w) + item_pad;
+                forms.push(label_form(&item.label, item_label_color)
+                               .shift(text_x.floor(), i_xy[1].floor()));
+
+                // An arrow at the right of each submenu.
+                if item.kind == Kind::Submenu {
+                    let r = font_size as f64 / 4.0;
+                    let x = i_xy[0] + i_dim[0] / 2.0 - item_pad - r;
+                    let y = i_xy[1];
+                    forms.push(polygon(vec![(x - r, y + r), (x - r, y - r), (x + r, y)])
+                                   .filled(label_color));
+                }
+            }
+        }
+
+        // Collect the Forms for drawing.
+        forms
+    }
+
+    /// Describe the MenuBar for assistive technologies, giving the titles of the open menus.
+    fn accessibility(state: &State) -> Accessibility {
+        let open: Vec<&str> = (1..state.open_path.len() + 1)
+            .map(|len| &node_at(&state.menus, &state.open_path[..len]).label[..])
+            .collect();
+        Accessibility::new(Role::MenuBar).value(open.join(" > "))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_item_width: None,
+            maybe_item_padding: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_menu_bar.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_menu_bar.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_medium)
+        })).unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the Dimensions of the bar, the height of which is also that of each item.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [512.0, 28.0];
+        let width = self.maybe_width.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the width of the panel of each open menu.
+    pub fn item_width(&self, theme: &Theme) -> f64 {
+        const DEFAULT_ITEM_WIDTH: f64 = 160.0;
+        self.maybe_item_width.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_item_width.unwrap_or(DEFAULT_ITEM_WIDTH)
+        })).unwrap_or(DEFAULT_ITEM_WIDTH)
+    }
+
+    /// Get the padding on either side of each title and item's text.
+    pub fn item_padding(&self, theme: &Theme) -> f64 {
+        const DEFAULT_ITEM_PADDING: f64 = 8.0;
+        self.maybe_item_padding.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_item_padding.unwrap_or(DEFAULT_ITEM_PADDING)
+        })).unwrap_or(DEFAULT_ITEM_PADDING)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_menu_bar.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for MenuBar<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for MenuBar<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for MenuBar<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for MenuBar<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Frameable for MenuBar<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> position::Positionable for MenuBar<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        MenuBar { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        MenuBar { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> position::Sizeable for MenuBar<'a, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -1,18 +1,46 @@
 
-use elmesque::Element;
+use accessibility::{Accessibility, Role};
+use clock_ticks::precise_time_ns;
+use elmesque::form::Form;
+use font::{DEFAULT_FONT, FontId};
 use graphics::character::CharacterCache;
-use position::{Depth, Dimensions, Point};
-use std::any::Any;
+use position::{Depth, Dimensions, Layer, Margin, Padding, Point};
+use std::any::{Any, TypeId};
 use std::fmt::Debug;
+use theme::Theme;
 use ui::{UiId, Ui};
 
+pub mod ab_compare;
 pub mod button;
+pub mod collapsible_area;
+pub mod context_menu;
+pub mod date_picker;
+pub mod draggable;
+pub mod draw_area;
 pub mod drop_down_list;
+pub mod editor_canvas;
 pub mod envelope_editor;
+pub mod event_monitor;
+pub mod file_dialog;
+pub mod floating;
+pub mod flow;
 pub mod label;
+pub mod markdown;
+pub mod marquee;
 pub mod matrix;
+pub mod menu_bar;
+pub mod perf_hud;
+pub mod plot;
 pub mod number_dialer;
+pub mod number_stepper;
+pub mod popover;
+pub mod radio_group;
+pub mod rich_text;
+pub mod ruler;
+pub mod scope;
 pub mod slider;
+pub mod split_pane;
+pub mod switch;
 pub mod text_box;
 pub mod toggle;
 pub mod xy_pad;
@@ -25,7 +53,7 @@ pub trait Widget: Sized {
     /// that you'd like to avoid repeating between calls to `update` and `draw`. Conrod will never
     /// clone the state, it will only ever be moved.
     type State: Any + PartialEq + ::std::fmt::Debug;
-    /// Styling used by the widget to construct an Element. Styling is useful to have in its own
+    /// Styling used by the widget to construct its Forms. Styling is useful to have in its own
     /// abstraction in order to making Theme serializing easier. Conrod doesn't yet support
     /// serializing non-internal widget styling with the `Theme` type, but we hope to soon.
     type Style: Any + PartialEq + ::std::fmt::Debug;
@@ -37,8 +65,8 @@ pub trait Widget: Sized {
     /// - The widget's current `Style` will be retrieved (from the `Widget::style` method).
     /// - The widget's state will be updated (using the `Widget::udpate` method).
     /// - If the widget's state or style has changed, `Widget::draw` will be called to create the
-    /// new Element for rendering.
-    /// - The new State, Style and Element (if there is one) will be cached within the `Ui`.
+    /// new Forms for rendering.
+    /// - The new State, Style and Forms (if there are any) will be cached within the `Ui`.
     fn set<C>(self, ui_id: UiId, ui: &mut Ui<C>) where C: CharacterCache {
         let kind = self.unique_kind();
         let new_style = self.style();
@@ -50,8 +78,15 @@ pub trait Widget: Sized {
             (None, State { state: self.init_state(), dim: [0.0, 0.0], xy: [0.0, 0.0], depth: 0.0 })
         });
 
-        // Update the widget's state.
+        // Measure and draw the widget's text using its font.
+        let font = Self::style_font(&new_style, &ui.theme);
+        ui.set_current_font(font);
+
+        // Update the widget's state, timing the update if profiling.
+        let is_profiling = ui.is_profiling();
+        let update_start_ns = if is_profiling { precise_time_ns() } else { 0 };
         let maybe_new_state = self.update(&prev_state, &new_style, ui_id, ui);
+        let update_ns = if is_profiling { precise_time_ns() - update_start_ns } else { 0 };
 
         // Determine whether or not the `State` has changed.
         let (state_has_changed, new_state) = {
@@ -70,21 +105,69 @@ pub trait Widget: Sized {
 
         // Determine whether or not the widget's `Style` has changed.
         let style_has_changed = match maybe_prev_style {
-            Some(prev_style) => prev_style != new_style,
+            Some(ref prev_style) => *prev_style != new_style,
             None => false,
         };
 
-        // Construct the widget's element.
-        let maybe_new_element = if style_has_changed || state_has_changed {
+        // The theme's defaults may differ if the widget has moved in or out of a style scope.
+        let theme_has_changed = ui.has_theme_changed(ui_id);
+
+        // Construct the widget's forms.
+        let draw_start_ns = if is_profiling { precise_time_ns() } else { 0 };
+        let maybe_new_forms = if style_has_changed || state_has_changed || theme_has_changed {
             Some(Self::draw(&new_state, &new_style, ui))
         } else {
             None
         };
+        if is_profiling {
+            let draw_ns = precise_time_ns() - draw_start_ns;
+            ui.profile_widget(update_ns, maybe_new_forms.as_ref().map(|_| draw_ns));
+        }
+
+        // Describe the widget anew for assistive technologies if its `State` has changed.
+        let maybe_new_accessibility = if state_has_changed || maybe_prev_style.is_none() {
+            Some(Self::accessibility(&new_state.state))
+        } else {
+            None
+        };
 
         // Store the new `State` and `Style` within the cache.
         let State { state, dim, xy, depth, .. } = new_state;
         let store: Store<Self::State, Self::Style> = Store { state: state, style: new_style };
-        ui.update_widget(ui_id, kind, store, dim, xy, depth, maybe_new_element);
+        ui.update_widget(ui_id, kind, store, dim, xy, depth, maybe_new_forms);
+        ui.set_current_font(DEFAULT_FONT);
+        if let Some(accessibility) = maybe_new_accessibility {
+            ui.set_widget_accessibility(ui_id, accessibility);
+        }
+    }
+
+    /// Attach some arbitrary user data to the widget. The data will be stored alongside the
+    /// widget's state once it is set and may be retrieved using `Ui::widget_tag` (for example,
+    /// to map the widget back to some application entity within a reaction). Note that this
+    /// should be the last method called before `set`.
+    fn tag<T: Any>(self, tag: T) -> Tagged<Self, T> {
+        Tagged { widget: self, tag: tag }
+    }
+
+    /// Call the given function with the widget's UiId when its state is first created within the
+    /// `Ui`, i.e. to acquire some external resource (a texture, timer or thread) for the widget.
+    /// Note that this should be called after all other builder methods, just before `set`.
+    fn on_create<'a, F>(self, on_create: F) -> Lifecycle<'a, Self>
+        where F: FnMut(UiId) + 'a,
+    {
+        Lifecycle::new(self).on_create(on_create)
+    }
+
+    /// Call the given function with the widget's UiId once it has been removed from the `Ui`,
+    /// i.e. to release any resources acquired within `on_create`. Widgets are only removed once
+    /// removal is enabled via `Ui::enable_widget_removal`, after which a widget is removed (and
+    /// its state dropped) when it goes unset for more than the given number of consecutive calls
+    /// to `Ui::draw`. Note that this should be called after all other builder methods, just
+    /// before `set`.
+    fn on_remove<'a, F>(self, on_remove: F) -> Lifecycle<'a, Self>
+        where F: FnMut(UiId) + 'static,
+    {
+        Lifecycle::new(self).on_remove(on_remove)
     }
 
     /// Return the kind of the widget as a &'static str. Note that this must be unique from all
@@ -95,7 +178,7 @@ pub trait Widget: Sized {
     fn init_state(&self) -> Self::State;
 
     /// Return the styling of the widget. The `Ui` will call this once prior to each `update`. It
-    /// does this so that it can check for differences in `Style` in case a new `Element` needs to
+    /// does this so that it can check for differences in `Style` in case new Forms need to
     /// be constructed.
     fn style(&self) -> Self::Style;
 
@@ -108,14 +191,95 @@ pub trait Widget: Sized {
                  ui: &mut Ui<C>) -> State<Option<Self::State>>
         where C: CharacterCache;
 
-    /// Construct a renderable Element from the current styling and new state. This will *only* be
-    /// called on the occasion that the widget's `Style` or `State` has changed. Keep this in mind
-    /// when designing your widget's `Style` and `State` types.
+    /// Construct the Forms that draw the widget from the current styling and new state, positioned
+    /// relative to the centre of the window. This will *only* be called on the occasion that the
+    /// widget's `Style` or `State` has changed. Keep this in mind when designing your widget's
+    /// `Style` and `State` types.
+    ///
+    /// Rather than each widget rendering its own `collage`, the `Ui` appends the Forms of every
+    /// widget to a single display list each frame (see `Ui::draw`).
     fn draw<C>(new_state: &State<Self::State>,
                current_style: &Self::Style,
-               ui: &mut Ui<C>) -> Element
+               ui: &mut Ui<C>) -> Vec<Form>
         where C: CharacterCache;
 
+    /// The font with which the widget's text is measured and drawn, given its current styling.
+    /// Widgets that display text should return the font from their `Style`. By default, widgets
+    /// use the `Theme`'s font.
+    fn style_font(_style: &Self::Style, theme: &Theme) -> FontId {
+        theme.font
+    }
+
+    /// Describe the widget to assistive technologies (i.e. screen readers) given its newly updated
+    /// state. This is called whenever the state changes and the description may be retrieved via
+    /// `Ui::accessibility_nodes`. By default, widgets are described with the `Unknown` role.
+    fn accessibility(_state: &Self::State) -> Accessibility {
+        Accessibility::new(Role::Unknown)
+    }
+
+}
+
+/// A Widget along with some user data that is to be stored alongside it.
+pub struct Tagged<W, T> {
+    widget: W,
+    tag: T,
+}
+
+impl<W, T> Tagged<W, T> where W: Widget, T: Any {
+    /// Set the widget within the given `Ui` and store its tag.
+    pub fn set<C>(self, ui_id: UiId, ui: &mut Ui<C>) where C: CharacterCache {
+        let Tagged { widget, tag } = self;
+        widget.set(ui_id, ui);
+        ui.set_widget_tag(ui_id, tag);
+    }
+}
+
+/// A Widget along with the functions to be called when its state is created and removed.
+///
+/// A widget's state is created the first time it is set at its UiId, and again whenever it is
+/// set after having been removed. Widgets are never removed unless the application opts in via
+/// `Ui::enable_widget_removal(grace)`, in which case a widget is removed by the first call to
+/// `Ui::draw` after it has gone unset for more than `grace` consecutive draws. Until then,
+/// `on_remove` is never called and the widget's state is kept for as long as the `Ui`.
+pub struct Lifecycle<'a, W> {
+    widget: W,
+    maybe_on_create: Option<Box<FnMut(UiId) + 'a>>,
+    maybe_on_remove: Option<Box<FnMut(UiId)>>,
+}
+
+impl<'a, W> Lifecycle<'a, W> where W: Widget {
+
+    /// Wrap the given widget with no lifecycle functions.
+    fn new(widget: W) -> Lifecycle<'a, W> {
+        Lifecycle { widget: widget, maybe_on_create: None, maybe_on_remove: None }
+    }
+
+    /// Call the given function with the widget's UiId when its state is first created.
+    pub fn on_create<F>(mut self, on_create: F) -> Self where F: FnMut(UiId) + 'a {
+        self.maybe_on_create = Some(Box::new(on_create));
+        self
+    }
+
+    /// Call the given function with the widget's UiId once it has been removed from the `Ui`.
+    pub fn on_remove<F>(mut self, on_remove: F) -> Self where F: FnMut(UiId) + 'static {
+        self.maybe_on_remove = Some(Box::new(on_remove));
+        self
+    }
+
+    /// Set the widget within the given `Ui`, calling `on_create` if its state did not yet exist
+    /// and keeping `on_remove` until the widget is removed.
+    pub fn set<C>(self, ui_id: UiId, ui: &mut Ui<C>) where C: CharacterCache {
+        let Lifecycle { widget, maybe_on_create, maybe_on_remove } = self;
+        let is_new = !ui.has_widget_state(ui_id);
+        widget.set(ui_id, ui);
+        if let Some(on_remove) = maybe_on_remove {
+            ui.set_widget_removal_hook(ui_id, on_remove);
+        }
+        if let (true, Some(mut on_create)) = (is_new, maybe_on_create) {
+            on_create(ui_id);
+        }
+    }
+
 }
 
 /// Represents the unique cached state of a widget.
@@ -160,12 +324,38 @@ pub struct Store<Sta, Sty>
 #[derive(Debug)]
 pub struct Cached {
     pub maybe_state: Option<Box<Any>>,
+    /// The `TypeId` of the widget's `Store`, by which its kind is checked, or `None` if no widget
+    /// occupies this position.
+    pub maybe_type_id: Option<TypeId>,
+    /// The widget's `unique_kind`, used to describe it within error messages.
     pub kind: &'static str,
     pub dim: Dimensions,
     pub xy: Point,
     pub depth: Depth,
-    pub element: Element,
+    /// The layer in which the widget is drawn and hit-tested (see `Ui::set_layer`).
+    pub layer: Layer,
+    /// The order in which the widget was last set relative to all others. Of widgets within the
+    /// same layer and at the same depth, those set later are drawn above.
+    pub set_order: u64,
+    /// The Forms that draw the widget, as constructed by its most recent `Widget::draw`.
+    pub forms: Vec<Form>,
     pub has_updated: bool,
+    /// The font with which the widget's Forms are drawn.
+    pub font: FontId,
+    /// The Margin kept clear around the widget, respected when positioning relative to it.
+    pub margin: Margin,
+    /// The Padding within the widget, respected when placing widgets on it.
+    pub padding: Padding,
+    /// The style scope (see `Ui::with_style`) within which the widget's Forms were drawn, or
+    /// `0` if it was drawn with the `Ui`'s own theme.
+    pub theme_scope: u64,
+    /// The rectangle to which the widget's Forms are cropped, if it was set within a clipping
+    /// scope (see `Ui::with_clip`).
+    pub maybe_clip: Option<(Point, Dimensions)>,
+    /// User data attached to the widget via `Widget::tag`.
+    pub maybe_tag: Option<Box<Any>>,
+    /// The description of the widget for assistive technologies.
+    pub maybe_accessibility: Option<Accessibility>,
 }
 
 impl Cached {
@@ -174,14 +364,30 @@ impl Cached {
     pub fn empty() -> Cached {
         Cached {
             maybe_state: None,
+            maybe_type_id: None,
             kind: "EMPTY",
             dim: [0.0, 0.0],
             xy: [0.0, 0.0],
             depth: 0.0,
-            element: ::elmesque::element::empty(),
+            layer: Layer::Normal,
+            set_order: 0,
+            forms: Vec::new(),
             has_updated: false,
+            font: DEFAULT_FONT,
+            margin: Margin::none(),
+            padding: Padding::none(),
+            theme_scope: 0,
+            maybe_clip: None,
+            maybe_tag: None,
+            maybe_accessibility: None,
         }
     }
 
+    /// Whether or not this position within the Ui is vacant.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.maybe_type_id.is_none()
+    }
+
 }
 
@@ -1,47 +1,75 @@
 
+use accessibility::{Accessibility, Role};
 use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use corner::{self, CornerRadii, Roundable};
+use elmesque::form::Form;
+use enable::{self, Enableable, ReadOnly};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::character::CharacterCache;
 use label::{self, FontSize, Labelable};
 use mouse::Mouse;
 use num::{Float, NumCast};
-use position::{self, Depth, Dimensions, HorizontalAlign, Point, Position, VerticalAlign};
+use palette::{ColorRole, Paletted};
+use panel::{self, PanelId};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
 use std::any::Any;
 use std::cmp::Ordering;
 use std::iter::repeat;
 use theme::Theme;
 use utils::clamp;
 use ui::{UiId, Ui};
+use unit::{self, Conversion, Unit};
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// A widget for precision control over any digit within a value. The reaction is triggered when
 /// the value is updated or if the mouse button is released while the cursor is above the widget.
+///
+/// Once clicked, the NumberDialer has keyboard focus and may be stepped through a tenth of its
+/// range via PageUp and PageDown, or through a hundredth while Shift is held. Scrolling the mouse
+/// wheel over a digit steps that digit.
 pub struct NumberDialer<'a, T, F> {
     value: T,
     min: T,
     max: T,
     pos: Position,
+    margin: Margin,
+    padding: Padding,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
-    dim: Dimensions,
     depth: Depth,
     maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
     precision: u8,
+    maybe_unit: Option<Unit>,
+    maybe_conversion: Option<Conversion>,
+    maybe_quantity: Option<&'a str>,
+    maybe_panel: Option<PanelId>,
     maybe_react: Option<F>,
     style: Style,
     enabled: bool,
+    read_only: bool,
 }
 
 /// Styling for the NumberDialer, necessary for constructing its renderable Element.
 #[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_corner_radii: Option<CornerRadii>,
     pub maybe_label_color: Option<Color>,
     pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
 }
 
 /// Represents the specific elements that the NumberDialer is made up of. This is used to specify
@@ -71,7 +99,13 @@ pub struct State<T> {
     max: T,
     precision: u8,
     maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+    /// The unit whose symbol is displayed after the value, if any.
+    maybe_unit: Option<Unit>,
     interaction: Interaction,
+    is_focused: bool,
+    enabled: bool,
+    read_only: bool,
 }
 
 
@@ -107,6 +141,18 @@ fn create_val_string<T: ToString>(val: T, len: usize, precision: u8) -> String {
     }
 }
 
+/// The amount by which the value is stepped via the value glyph at the given index.
+fn glyph_step(val_string: &str, idx: usize) -> f64 {
+    let power = match val_string.chars().position(|ch| ch == '.') {
+        None => (val_string.len() - idx - 1) as isize,
+        Some(dec_idx) => {
+            let power = dec_idx as isize - idx as isize - 1;
+            if power < -1 { power + 1 } else { power }
+        },
+    };
+    (10.0).powf(power as f32) as f64
+}
+
 /// Return the dimensions of a value glyph slot.
 fn value_glyph_slot_width(size: FontSize) -> f64 {
     (size as f64 * 0.75).floor() as f64
@@ -119,6 +165,34 @@ fn val_string_width(font_size: FontSize, val_string: &String) -> f64 {
     val_string_w
 }
 
+/// Return the dimensions of the label followed by a colon, along with its Forms centred upon the
+/// origin. The spans take precedence over the plain label if both are given.
+fn create_label_forms<C>(ui: &mut Ui<C>,
+                         maybe_label: Option<&str>,
+                         maybe_spans: Option<&SpannedLabel>,
+                         color: Color,
+                         font_size: FontSize) -> (Dimensions, Vec<Form>)
+    where
+        C: CharacterCache,
+{
+    use elmesque::form::text;
+    use elmesque::text::Text;
+    match (maybe_spans, maybe_label) {
+        (Some(spans), _) => {
+            let mut spans = spans.clone();
+            spans.push(Span::new(": "));
+            spans.forms(ui, color, font_size)
+        },
+        (None, Some(label_text)) => {
+            let label_string = format!("{}: ", label_text);
+            let label_dim = [label::width(ui, font_size, &label_string), font_size as f64];
+            let label_text = Text::from_string(label_string).color(color).height(font_size as f64);
+            (label_dim, vec![text(label_text)])
+        },
+        (None, None) => ([0.0, font_size as f64], Vec::new()),
+    }
+}
+
 /// Determine if the cursor is over the number_dialer and if so, which element.
 #[inline]
 fn is_over(mouse_xy: Point,
@@ -160,7 +234,7 @@ fn get_new_interaction(is_over_elem: Option<Elem>, prev: Interaction, mouse: Mou
     use mouse::ButtonState::{Down, Up};
     use self::Elem::ValueGlyph;
     use self::Interaction::{Normal, Highlighted, Clicked};
-    match (is_over_elem, prev, mouse.left) {
+    match (is_over_elem, prev, mouse.left.state) {
         (Some(_),    Normal,          Down) => Normal,
         (Some(elem), _,               Up)   => Highlighted(elem),
         (Some(elem), Highlighted(_),  Down) => Clicked(elem),
@@ -190,15 +264,22 @@ impl<'a, T: Float, F> NumberDialer<'a, T, F> {
             min: min,
             max: max,
             pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
             maybe_h_align: None,
             maybe_v_align: None,
-            dim: [128.0, 48.0],
             depth: 0.0,
             precision: precision,
             maybe_label: None,
+            maybe_label_spans: None,
+            maybe_unit: None,
+            maybe_conversion: None,
+            maybe_quantity: None,
+            maybe_panel: None,
             maybe_react: None,
             style: Style::new(),
             enabled: true,
+            read_only: false,
         }
     }
 
@@ -209,9 +290,36 @@ impl<'a, T: Float, F> NumberDialer<'a, T, F> {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
+    /// Display the given unit's symbol after the value. The unit of any active conversion takes
+    /// precedence.
+    #[inline]
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.maybe_unit = Some(unit);
+        self
+    }
+
+    /// Display and edit the value in the conversion's unit, while still reporting the value in its
+    /// canonical unit via the reaction. This takes precedence over any conversion that the theme
+    /// holds for the NumberDialer's `quantity`.
+    #[inline]
+    pub fn conversion(mut self, conversion: Conversion) -> Self {
+        self.maybe_conversion = Some(conversion);
+        self
+    }
+
+    /// Set the quantity that the value measures (i.e. `"temperature"`), so that it is displayed
+    /// and edited via the conversion held for the quantity within the theme's `display_units`.
+    #[inline]
+    pub fn quantity(mut self, quantity: &'a str) -> Self {
+        self.maybe_quantity = Some(quantity);
+        self
+    }
+
+    /// Make the NumberDialer a member of the given keyboard panel, so that the arrow keys move the
+    /// focus between it and the panel's other members.
+    #[inline]
+    pub fn panel(mut self, panel_id: PanelId) -> Self {
+        self.maybe_panel = Some(panel_id);
         self
     }
 
@@ -232,10 +340,16 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
             max: self.max,
             precision: self.precision,
             maybe_label: None,
+            maybe_label_spans: None,
+            maybe_unit: None,
             interaction: Interaction::Normal,
+            is_focused: false,
+            enabled: self.enabled,
+            read_only: self.read_only,
         }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the Button.
     fn update<C>(mut self,
@@ -248,29 +362,55 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
     {
 
         let widget::State { ref state, .. } = *prev_state;
-        let dim = self.dim;
+
+        // Work in the display unit from here on, converting back only when reacting.
+        let maybe_conversion = unit::active_conversion(self.maybe_conversion.as_ref(),
+                                                       self.maybe_quantity,
+                                                       &ui.theme);
+        let conversion = maybe_conversion.as_ref();
+        self.value = unit::to_display(conversion, self.value);
+        self.min = unit::to_display(conversion, self.min);
+        self.max = unit::to_display(conversion, self.max);
+        if let Some(conversion) = conversion {
+            self.maybe_unit = Some(conversion.unit.clone());
+        }
+
+        let dim = style.dim(&ui.theme);
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
         let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
-        let frame = style.frame(&ui.theme);
-        let pad_dim = ::vecmath::vec2_sub(dim, [frame * 2.0; 2]);
+        let (_, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
         let font_size = style.label_font_size(&ui.theme);
-        let label_string = self.maybe_label.map_or_else(|| String::new(), |text| format!("{}: ", text));
-        let label_dim = [label::width(ui, font_size, &label_string), font_size as f64];
+        let label_color = style.label_color(&ui.theme);
+        let maybe_spans = self.maybe_label_spans.as_ref();
+        let (label_dim, _) =
+            create_label_forms(ui, self.maybe_label, maybe_spans, label_color, font_size);
         let val_string_len = self.max.to_string().len() + if self.precision == 0 { 0 }
                                                           else { 1 + self.precision as usize };
         let val_string = create_val_string(self.value, val_string_len, self.precision);
         let val_string_dim = [val_string_width(font_size, &val_string), font_size as f64];
         let label_x = -val_string_dim[0] / 2.0;
         let is_over_elem = is_over(mouse.xy, dim, pad_dim, label_x, label_dim, val_string_dim, val_string_len);
+        let is_interactive = self.enabled && !self.read_only;
         let new_interaction = 
-            if self.enabled {
+            if is_interactive {
                 get_new_interaction(is_over_elem, state.interaction, mouse)
             } else {
                 Interaction::Normal
             };
 
+        // Claim keyboard focus when clicked so that the value may also be adjusted via the keys,
+        // reporting the NumberDialer if an external control binding is being learned.
+        if let (true, Interaction::Clicked(_)) = (is_interactive, new_interaction) {
+            ui.focus(ui_id);
+            ui.binding_interacted(ui_id);
+        }
+        if let Some(panel_id) = self.maybe_panel {
+            ui.join_panel(panel_id, ui_id);
+        }
+        let is_focused = is_interactive && ui.is_focused(ui_id);
+
         // Determine new value from the initial state and the new state.
         let mut new_val = self.value;
         if let (Interaction::Clicked(elem), Interaction::Clicked(new_elem)) =
@@ -278,42 +418,44 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
             if let (Elem::ValueGlyph(idx, y), Elem::ValueGlyph(_, new_y)) = (elem, new_elem) {
                 let ord = new_y.partial_cmp(&y).unwrap_or(Ordering::Equal);
                 if ord != Ordering::Equal {
-                    let decimal_pos = val_string.chars().position(|ch| ch == '.');
+                    let step = glyph_step(&val_string, idx);
                     let val_f: f64 = NumCast::from(self.value).unwrap();
                     let min_f: f64 = NumCast::from(self.min).unwrap();
                     let max_f: f64 = NumCast::from(self.max).unwrap();
-                    let new_val_f = match decimal_pos {
-                        None => {
-                            let power = val_string.len() - idx - 1;
-                            match ord {
-                                Ordering::Greater => {
-                                    clamp(val_f + (10.0).powf(power as f32) as f64, min_f, max_f)
-                                },
-                                Ordering::Less => {
-                                    clamp(val_f - (10.0).powf(power as f32) as f64, min_f, max_f)
-                                },
-                                _ => val_f,
-                            }
-                        },
-                        Some(dec_idx) => {
-                            let mut power = dec_idx as isize - idx as isize - 1;
-                            if power < -1 { power += 1; }
-                            match ord {
-                                Ordering::Greater => {
-                                    clamp(val_f + (10.0).powf(power as f32) as f64, min_f, max_f)
-                                },
-                                Ordering::Less => {
-                                    clamp(val_f - (10.0).powf(power as f32) as f64, min_f, max_f)
-                                },
-                                _ => val_f,
-                            }
-                        },
+                    let new_val_f = match ord {
+                        Ordering::Greater => clamp(val_f + step, min_f, max_f),
+                        Ordering::Less => clamp(val_f - step, min_f, max_f),
+                        _ => val_f,
                     };
                     new_val = NumCast::from(new_val_f).unwrap()
                 };
             }
         };
 
+        // PageUp and PageDown adjust the value while focused.
+        if is_focused {
+            let keys = ui.get_focused_keys(ui_id);
+            new_val = panel::page_adjust(new_val, self.min, self.max, keys, ui.is_shift_down());
+        }
+
+        // The mouse wheel steps the digit beneath the cursor.
+        let scroll = ui.get_scroll(ui_id);
+        if let (true, Interaction::Highlighted(Elem::ValueGlyph(idx, _))) =
+            (is_interactive && scroll[1] != 0.0, new_interaction) {
+            let step = glyph_step(&val_string, idx) * scroll[1].signum();
+            let val_f: f64 = NumCast::from(new_val).unwrap();
+            let min_f: f64 = NumCast::from(self.min).unwrap();
+            let max_f: f64 = NumCast::from(self.max).unwrap();
+            new_val = NumCast::from(clamp(val_f + step, min_f, max_f)).unwrap();
+        }
+
+        // Adopt the value of a bound external control, normalised across the range.
+        if let (true, Some(control)) = (is_interactive, ui.take_control_value(ui_id)) {
+            let min_f: f64 = NumCast::from(self.min).unwrap();
+            let max_f: f64 = NumCast::from(self.max).unwrap();
+            new_val = NumCast::from(min_f + (max_f - min_f) * clamp(control, 0.0, 1.0)).unwrap();
+        }
+
         // Call the `react` with the new value if the mouse is pressed/released on the widget
         // or if the value has changed.
         if self.value != new_val || match (state.interaction, new_interaction) {
@@ -321,7 +463,9 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
             (Interaction::Clicked(_), Interaction::Highlighted(_)) => true,
             _ => false,
         } {
-            if let Some(ref mut react) = self.maybe_react { react(new_val) }
+            if let Some(ref mut react) = self.maybe_react {
+                react(unit::from_display(conversion, new_val))
+            }
         }
 
         // A function for constructing a new State.
@@ -332,16 +476,26 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
                 max: self.max,
                 precision: self.precision,
                 maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+                maybe_unit: self.maybe_unit.clone(),
                 interaction: new_interaction,
+                is_focused: is_focused,
+                enabled: self.enabled,
+                read_only: self.read_only,
             }
         };
 
         // Check whether or not the state has changed since the previous update.
         let state_has_changed = state.interaction != new_interaction
+            || state.is_focused != is_focused
+            || state.enabled != self.enabled
+            || state.read_only != self.read_only
             || state.value != new_val
             || state.min != self.min || state.max != self.max
             || state.precision != self.precision
-            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label;
+            || state.maybe_unit != self.maybe_unit
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
 
         // Construct the new state if there was a change.
         let maybe_new_state = if state_has_changed { Some(construct_new_state()) }
@@ -350,40 +504,45 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
         widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
     }
 
-    /// Construct an Element from the given Button State.
-    fn draw<C>(new_state: &widget::State<State<T>>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given Button State.
+    fn draw<C>(new_state: &widget::State<State<T>>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{collage, rect, text};
+        use elmesque::form::{rect, text};
         use elmesque::text::Text;
 
         let widget::State { ref state, dim, xy, .. } = *new_state;
 
         // Construct the frame and inner rectangle Forms.
         let frame = style.frame(&ui.theme);
-        let pad_dim = ::vecmath::vec2_sub(dim, [frame * 2.0; 2]);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
         let frame_color = style.frame_color(&ui.theme);
-        let color = style.color(&ui.theme);
-        let frame_form = rect(dim[0], dim[1]).filled(frame_color);
-        let inner_form = rect(pad_dim[0], pad_dim[1]).filled(color);
+        let frame_color = if state.is_focused { frame_color.highlighted() } else { frame_color };
+        let frame_color = enable::color(frame_color, state.enabled, &ui.theme);
+        let color = enable::color(style.color(&ui.theme), state.enabled, &ui.theme);
+        let radii = style.corner_radii(&ui.theme);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let inner_form = corner::rounded_rect(pad_dim, radii.inset(frame)).filled(color)
+            .shift(pad_offset[0], pad_offset[1]);
         let val_string_len = state.max.to_string().len() + if state.precision == 0 { 0 }
                                                           else { 1 + state.precision as usize };
-        let label_string = state.maybe_label.as_ref()
-            .map_or_else(|| String::new(), |text| format!("{}: ", text));
         let font_size = style.label_font_size(&ui.theme);
 
         // If the value has changed, create a new string for val_string.
         let val_string = create_val_string(state.value, val_string_len, state.precision);
         let val_string_dim = [val_string_width(font_size, &val_string), font_size as f64];
         let label_x = -val_string_dim[0] / 2.0;
-        let label_dim = [label::width(ui, font_size, &label_string), font_size as f64];
 
-        // Construct the label form.
+        // Construct the label's Forms.
         let val_string_color = style.label_color(&ui.theme);
-        let label_form = text(Text::from_string(label_string.clone())
-                                  .color(val_string_color)
-                                  .height(font_size as f64)).shift_x(label_x.floor());
+        let val_string_color = enable::color(val_string_color, state.enabled, &ui.theme);
+        let maybe_label = state.maybe_label.as_ref().map(|label| &label[..]);
+        let maybe_spans = state.maybe_label_spans.as_ref();
+        let (label_dim, label_forms) =
+            create_label_forms(ui, maybe_label, maybe_spans, val_string_color, font_size);
+        let label_forms = label_forms.into_iter().map(|form| form.shift_x(label_x.floor()));
 
         // Construct the value_string's Form.
         let val_string_pos = [label_x + label_dim[0] / 2.0, 0.0];
@@ -397,7 +556,7 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
                                          else { color };
                         Some(rect(slot_w, pad_dim[1]).filled(rect_color)
                              .shift(val_string_pos[0].floor(), val_string_pos[1].floor())
-                             .shift_x(x.floor()))
+                             .shift(x.floor(), pad_offset[1]))
                     } else {
                         None
                     },
@@ -406,7 +565,7 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
                                          else { color };
                         Some(rect(slot_w, pad_dim[1]).filled(rect_color)
                              .shift(val_string_pos[0].floor(), val_string_pos[1].floor())
-                             .shift_x(x.floor()))
+                             .shift(x.floor(), pad_offset[1]))
                     } else {
                         None
                     },
@@ -422,18 +581,44 @@ impl<'a, T, F> Widget for NumberDialer<'a, T, F>
             })
         };
 
+        // Construct the Form for the unit's symbol, which follows the value.
+        let maybe_unit_form = state.maybe_unit.as_ref().map(|unit| {
+            let unit_string = format!(" {}", unit.symbol());
+            let unit_w = label::width(ui, font_size, &unit_string);
+            let unit_x = val_string_pos[0] + val_string_dim[0] + unit_w / 2.0;
+            text(Text::from_string(unit_string)
+                     .color(val_string_color)
+                     .height(font_size as f64)).shift_x(unit_x.floor())
+        });
+
+        // Mark the NumberDialer with a lock if it is read-only.
+        let lock_forms = enable::lock_forms(state.read_only, dim, frame, val_string_color,
+                                            &ui.theme);
+
         // Chain the forms and shift them into position.
         let form_chain = Some(frame_form).into_iter()
             .chain(Some(inner_form).into_iter())
-            .chain(Some(label_form).into_iter())
+            .chain(label_forms)
             .chain(val_string_forms)
+            .chain(maybe_unit_form.into_iter())
+            .chain(lock_forms.into_iter())
             .map(|form| form.shift(xy[0].floor(), xy[1].floor()));
 
-        // Collect the Forms into a renderable Element.
-        collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+        // Collect the Forms for drawing.
+        form_chain.collect()
 
     }
 
+    /// Describe the NumberDialer for assistive technologies.
+    fn accessibility(state: &State<T>) -> Accessibility {
+        let value: f64 = NumCast::from(state.value).unwrap();
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::NumberDialer)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(format!("{:.*}", state.precision as usize, value))
+    }
+
 }
 
 
@@ -443,18 +628,28 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
             maybe_label_color: None,
             maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_number_dialer.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_number_dialer.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -471,6 +666,34 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_number_dialer.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_number_dialer.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_number_dialer.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of an Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_number_dialer.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
     /// Get the label Color for an Element.
     pub fn label_color(&self, theme: &Theme) -> Color {
         self.maybe_label_color.or(theme.maybe_number_dialer.as_ref().map(|style| {
@@ -485,9 +708,42 @@ impl Style {
         })).unwrap_or(theme.font_size_medium)
     }
 
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [128.0, 48.0];
+        let width = self.maybe_width.or(theme.maybe_number_dialer.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_number_dialer.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_number_dialer.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
 }
 
 
+impl<'a, T: Float, F> Enableable for NumberDialer<'a, T, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, T: Float, F> ReadOnly for NumberDialer<'a, T, F> {
+    fn read_only(mut self, flag: bool) -> Self {
+        self.read_only = flag;
+        self
+    }
+}
+
 impl<'a, T, F> Colorable for NumberDialer<'a, T, F> {
     fn color(mut self, color: Color) -> Self {
         self.style.maybe_color = Some(color);
@@ -495,6 +751,20 @@ impl<'a, T, F> Colorable for NumberDialer<'a, T, F> {
     }
 }
 
+impl<'a, T, F> Paletted for NumberDialer<'a, T, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, T, F> Fontable for NumberDialer<'a, T, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, T, F> Frameable for NumberDialer<'a, T, F> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -504,6 +774,25 @@ impl<'a, T, F> Frameable for NumberDialer<'a, T, F> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, T, F> Roundable for NumberDialer<'a, T, F> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
 }
 
 impl<'a, T, F> Labelable<'a> for NumberDialer<'a, T, F>
@@ -513,6 +802,11 @@ impl<'a, T, F> Labelable<'a> for NumberDialer<'a, T, F>
         self
     }
 
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
     fn label_color(mut self, color: Color) -> Self {
         self.style.maybe_label_color = Some(color);
         self
@@ -537,18 +831,28 @@ impl<'a, T, F> position::Positionable for NumberDialer<'a, T, F> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         NumberDialer { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl<'a, T, F> position::Sizeable for NumberDialer<'a, T, F> {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        NumberDialer { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        NumberDialer { dim: [w, h], ..self }
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
     }
 }
 
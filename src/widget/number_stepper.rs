@@ -0,0 +1,812 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::{self, CornerRadii, Roundable};
+use elmesque::form::Form;
+use enable::{self, Enableable, ReadOnly};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize, Labelable};
+use mouse::Mouse;
+use num::{Float, NumCast};
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
+use std::any::Any;
+use theme::Theme;
+use utils::{clamp, is_over_rect};
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
+
+
+/// The delay in nanoseconds between pressing a step button and the first repeated step.
+pub const REPEAT_DELAY_NS: u64 = 400_000_000;
+/// The interval in nanoseconds between the first repeated steps while a step button is held.
+pub const REPEAT_INTERVAL_NS: u64 = 100_000_000;
+/// The shortest interval in nanoseconds between repeated steps, reached after holding a step
+/// button for a while.
+pub const MIN_REPEAT_INTERVAL_NS: u64 = 10_000_000;
+/// The factor by which the interval shrinks with each repeated step.
+const REPEAT_ACCELERATION: f64 = 0.9;
+
+/// A value display flanked by buttons that decrement and increment the value by a fixed step.
+/// Holding a button repeats its step at an accelerating rate. The reaction is triggered with the
+/// new value whenever it changes.
+///
+/// Once clicked, the NumberStepper has keyboard focus and may be stepped via the Up and Down keys.
+/// Double clicking the value allows a new value to be typed, which is applied upon pressing
+/// Return or clicking elsewhere, or discarded upon pressing Escape.
+///
+/// Where the NumberDialer suits fine adjustments of any digit, the NumberStepper suits coarse
+/// adjustments in whole steps.
+pub struct NumberStepper<'a, T, F> {
+    value: T,
+    min: T,
+    max: T,
+    step: T,
+    precision: u8,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+    read_only: bool,
+}
+
+/// Styling for the NumberStepper, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_corner_radii: Option<CornerRadii>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The elements that the NumberStepper is made up of.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Elem {
+    Decrement,
+    Value,
+    Increment,
+}
+
+/// The current interaction with the NumberStepper.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted(Elem),
+    Clicked(Elem),
+}
+
+/// The auto-repeat of a held step button.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Repeat {
+    /// No step button is held.
+    Idle,
+    /// A step button is held. The next step is due at the given time, following the given number
+    /// of repeated steps.
+    Holding { next_ns: u64, count: u32 },
+}
+
+/// The state of the NumberStepper.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State<T> {
+    value: T,
+    min: T,
+    max: T,
+    precision: u8,
+    maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+    interaction: Interaction,
+    repeat: Repeat,
+    /// The text being typed in place of the value, if any.
+    maybe_edit: Option<String>,
+    is_focused: bool,
+    enabled: bool,
+    read_only: bool,
+}
+
+
+impl Interaction {
+    /// Alter the color of the given element depending on the interaction.
+    fn color(&self, elem: Elem, color: Color) -> Color {
+        match *self {
+            Interaction::Highlighted(e) if e == elem => color.highlighted(),
+            Interaction::Clicked(e) if e == elem => color.clicked(),
+            _ => color,
+        }
+    }
+}
+
+impl Elem {
+    /// The direction in which the element steps the value.
+    fn direction(&self) -> i32 {
+        match *self {
+            Elem::Decrement => -1,
+            Elem::Value => 0,
+            Elem::Increment => 1,
+        }
+    }
+}
+
+
+/// The width of each of the step buttons within the given inner dimensions.
+fn button_width(pad_dim: Dimensions) -> f64 {
+    pad_dim[1].min(pad_dim[0] / 3.0)
+}
+
+/// Determine if the cursor is over the NumberStepper and if so, which element.
+fn is_over(mouse_xy: Point, dim: Dimensions, pad_offset: Point, pad_dim: Dimensions)
+    -> Option<Elem>
+{
+    if !is_over_rect([0.0, 0.0], mouse_xy, dim) { return None }
+    let button_w = button_width(pad_dim);
+    let x = mouse_xy[0] - pad_offset[0];
+    if x < -pad_dim[0] / 2.0 + button_w { Some(Elem::Decrement) }
+    else if x > pad_dim[0] / 2.0 - button_w { Some(Elem::Increment) }
+    else { Some(Elem::Value) }
+}
+
+/// Check and return the current state of the NumberStepper.
+fn get_new_interaction(is_over_elem: Option<Elem>, prev: Interaction, mouse: Mouse)
+    -> Interaction
+{
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (is_over_elem, prev, mouse.left.state) {
+        (_,          Clicked(elem), Down) => Clicked(elem),
+        (Some(_),    Normal,        Down) => Normal,
+        (Some(elem), _,             Down) => Clicked(elem),
+        (Some(elem), _,             Up)   => Highlighted(elem),
+        _                                 => Normal,
+    }
+}
+
+/// The interval before the repeated step following the given number of repeated steps.
+fn repeat_interval_ns(count: u32) -> u64 {
+    let interval = REPEAT_INTERVAL_NS as f64 * REPEAT_ACCELERATION.powi(count as i32);
+    interval.max(MIN_REPEAT_INTERVAL_NS as f64) as u64
+}
+
+/// Step the value by the given number of steps, keeping it within the bounds.
+fn step_value<T: Float + NumCast>(value: T, step: T, min: T, max: T, steps: i32) -> T {
+    let value_f: f64 = NumCast::from(value).unwrap();
+    let step_f: f64 = NumCast::from(step).unwrap();
+    let (min_f, max_f) = bounds(min, max);
+    NumCast::from(clamp(value_f + step_f * steps as f64, min_f, max_f)).unwrap()
+}
+
+/// The lower and upper bounds of the value as `f64`s.
+fn bounds<T: Float + NumCast>(min: T, max: T) -> (f64, f64) {
+    let min_f: f64 = NumCast::from(min).unwrap();
+    let max_f: f64 = NumCast::from(max).unwrap();
+    (min_f.min(max_f), min_f.max(max_f))
+}
+
+/// Create the string displaying the given value at the given precision.
+fn create_val_string<T: Float + NumCast>(value: T, precision: u8) -> String {
+    let value_f: f64 = NumCast::from(value).unwrap();
+    format!("{:.*}", precision as usize, value_f)
+}
+
+/// Return the dimensions of the label followed by a colon, along with its Forms centred upon the
+/// origin. The spans take precedence over the plain label if both are given.
+fn create_label_forms<C>(ui: &mut Ui<C>,
+                         maybe_label: Option<&str>,
+                         maybe_spans: Option<&SpannedLabel>,
+                         color: Color,
+                         font_size: FontSize) -> (Dimensions, Vec<Form>)
+    where
+        C: CharacterCache,
+{
+    use elmesque::form::text;
+    use elmesque::text::Text;
+    match (maybe_spans, maybe_label) {
+        (Some(spans), _) => {
+            let mut spans = spans.clone();
+            spans.push(Span::new(": "));
+            spans.forms(ui, color, font_size)
+        },
+        (None, Some(label_text)) => {
+            let label_string = format!("{}: ", label_text);
+            let label_dim = [label::width(ui, font_size, &label_string), font_size as f64];
+            let label_text = Text::from_string(label_string).color(color).height(font_size as f64);
+            (label_dim, vec![text(label_text)])
+        },
+        (None, None) => ([0.0, font_size as f64], Vec::new()),
+    }
+}
+
+
+impl<'a, T: Float, F> NumberStepper<'a, T, F> {
+
+    /// Construct a new NumberStepper widget. The value is displayed with the given number of
+    /// decimal places and, by default, is stepped by the smallest displayed increment.
+    pub fn new(value: T, min: T, max: T, precision: u8) -> NumberStepper<'a, T, F> {
+        let step = NumCast::from(10.0f64.powi(-(precision as i32))).unwrap();
+        NumberStepper {
+            value: value,
+            min: min,
+            max: max,
+            step: step,
+            precision: precision,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_label: None,
+            maybe_label_spans: None,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+            read_only: false,
+        }
+    }
+
+    /// Set the reaction for the NumberStepper. It will be triggered with the new value whenever
+    /// the value changes.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the amount by which each press of a step button changes the value.
+    #[inline]
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
+        self
+    }
+
+}
+
+impl<'a, T, F> Widget for NumberStepper<'a, T, F>
+    where
+        F: FnMut(T),
+        T: Any + ::std::fmt::Debug + Float + NumCast + ToString,
+{
+    type State = State<T>;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "NumberStepper" }
+    fn init_state(&self) -> State<T> {
+        State {
+            value: self.value,
+            min: self.min,
+            max: self.max,
+            precision: self.precision,
+            maybe_label: None,
+            maybe_label_spans: None,
+            interaction: Interaction::Normal,
+            repeat: Repeat::Idle,
+            maybe_edit: None,
+            is_focused: false,
+            enabled: self.enabled,
+            read_only: self.read_only,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the NumberStepper.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State<T>>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State<T>>>
+        where
+            C: CharacterCache,
+    {
+        use piston::input::keyboard::Key::{Backspace, Down, Escape, Return, Up};
+
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let is_over_elem = is_over(mouse.xy, dim, pad_offset, pad_dim);
+        let is_interactive = self.enabled && !self.read_only;
+        let new_interaction =
+            if is_interactive {
+                get_new_interaction(is_over_elem, state.interaction, mouse)
+            } else {
+                Interaction::Normal
+            };
+
+        // Claim keyboard focus when clicked so that the value may also be stepped via the keys.
+        let is_newly_clicked = match (state.interaction, new_interaction) {
+            (Interaction::Clicked(_), _) => false,
+            (_, Interaction::Clicked(_)) => true,
+            _ => false,
+        };
+        if is_newly_clicked {
+            ui.focus(ui_id);
+        }
+        let is_focused = is_interactive && ui.is_focused(ui_id);
+
+        // Double clicking the value begins typing a new value in its place.
+        let maybe_edit = match (is_newly_clicked, new_interaction, &state.maybe_edit) {
+            _ if !is_interactive => None,
+            (true, Interaction::Clicked(Elem::Value), &None) if mouse.left.clicks >= 2 =>
+                Some(create_val_string(self.value, self.precision)),
+            (_, _, maybe_edit) => maybe_edit.clone(),
+        };
+
+        // Apply the typed text upon Return or upon clicking elsewhere, or discard it upon Escape.
+        let mut maybe_typed_val = None;
+        let maybe_edit = match maybe_edit {
+            Some(mut edit) => {
+                for text in ui.get_entered_text(ui_id).to_vec().iter() {
+                    edit.extend(text.chars().filter(|&ch| {
+                        ch.is_digit(10) || ch == '.' || ch == '-'
+                    }));
+                }
+                let is_pressed_elsewhere = match (mouse.left.state, is_over_elem) {
+                    (::mouse::ButtonState::Down, None) => true,
+                    _ => is_newly_clicked && is_over_elem != Some(Elem::Value),
+                };
+                let mut is_applied = is_pressed_elsewhere;
+                let mut is_discarded = false;
+                for key in ui.get_pressed_keys(ui_id).to_vec().iter() {
+                    match *key {
+                        Backspace => { edit.pop(); },
+                        Return => is_applied = true,
+                        Escape => is_discarded = true,
+                        _ => (),
+                    }
+                }
+                if is_discarded { None }
+                else if is_applied { maybe_typed_val = edit.parse::<f64>().ok(); None }
+                else { Some(edit) }
+            },
+            None => None,
+        };
+
+        // Capture the keyboard while a value is being typed.
+        match (state.maybe_edit.is_some(), maybe_edit.is_some()) {
+            (false, true) => ui.keyboard_captured_by(ui_id),
+            (true, false) => ui.keyboard_uncaptured_by(ui_id),
+            _ => (),
+        }
+
+        // Step once upon pressing a step button and then repeatedly, at an accelerating rate, for
+        // as long as it is held beneath the cursor.
+        let now_ns = ui.time_ns();
+        let (mut steps, repeat) = match new_interaction {
+            Interaction::Clicked(elem) if elem.direction() != 0 => match state.repeat {
+                _ if is_newly_clicked =>
+                    (elem.direction(), Repeat::Holding { next_ns: now_ns + REPEAT_DELAY_NS,
+                                                         count: 0 }),
+                Repeat::Holding { next_ns, count } if is_over_elem == Some(elem)
+                                                   && now_ns >= next_ns => {
+                    let count = count + 1;
+                    let next_ns = now_ns + repeat_interval_ns(count);
+                    (elem.direction(), Repeat::Holding { next_ns: next_ns, count: count })
+                },
+                repeat => (0, repeat),
+            },
+            _ => (0, Repeat::Idle),
+        };
+
+        // The Up and Down keys step the value while focused, as does the mouse wheel.
+        if is_focused {
+            for key in ui.get_focused_keys(ui_id).iter() {
+                match *key {
+                    Up => steps += 1,
+                    Down => steps -= 1,
+                    _ => (),
+                }
+            }
+        }
+        let scroll = ui.get_scroll(ui_id);
+        if is_interactive && scroll[1] != 0.0 && is_over_elem.is_some() {
+            steps += scroll[1].signum() as i32;
+        }
+
+        // Determine the new value from any typed value and steps.
+        let mut new_val = self.value;
+        if let Some(typed_val) = maybe_typed_val {
+            let (min_f, max_f) = bounds(self.min, self.max);
+            new_val = NumCast::from(clamp(typed_val, min_f, max_f)).unwrap();
+        }
+        if steps != 0 {
+            new_val = step_value(new_val, self.step, self.min, self.max, steps);
+        }
+        if new_val != self.value {
+            if let Some(ref mut react) = self.maybe_react { react(new_val) }
+        }
+
+        // A function for constructing a new State.
+        let construct_new_state = || {
+            State {
+                value: new_val,
+                min: self.min,
+                max: self.max,
+                precision: self.precision,
+                maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+                interaction: new_interaction,
+                repeat: repeat,
+                maybe_edit: maybe_edit.clone(),
+                is_focused: is_focused,
+                enabled: self.enabled,
+                read_only: self.read_only,
+            }
+        };
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.interaction != new_interaction
+            || state.repeat != repeat
+            || state.maybe_edit != maybe_edit
+            || state.is_focused != is_focused
+            || state.enabled != self.enabled
+            || state.read_only != self.read_only
+            || state.value != new_val
+            || state.min != self.min || state.max != self.max
+            || state.precision != self.precision
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed { Some(construct_new_state()) }
+                              else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given NumberStepper State.
+    fn draw<C>(new_state: &widget::State<State<T>>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{rect, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+
+        // Construct the frame and inner rectangle Forms.
+        let frame = style.frame(&ui.theme);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let frame_color = style.frame_color(&ui.theme);
+        let frame_color = if state.is_focused { frame_color.highlighted() } else { frame_color };
+        let frame_color = enable::color(frame_color, state.enabled, &ui.theme);
+        let color = enable::color(style.color(&ui.theme), state.enabled, &ui.theme);
+        let radii = style.corner_radii(&ui.theme);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let inner_form = corner::rounded_rect(pad_dim, radii.inset(frame)).filled(color)
+            .shift(pad_offset[0], pad_offset[1]);
+
+        // Construct the step buttons, each marked with a minus or plus that is dimmed once the
+        // value reaches the bound in its direction.
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let button_w = button_width(pad_dim);
+        let value_f: f64 = NumCast::from(state.value).unwrap();
+        let (min_f, max_f) = bounds(state.min, state.max);
+        let mut button_forms = Vec::with_capacity(6);
+        for &elem in [Elem::Decrement, Elem::Increment].iter() {
+            let direction = elem.direction() as f64;
+            let x = pad_offset[0] + direction * (pad_dim[0] - button_w) / 2.0;
+            let can_step = if direction < 0.0 { value_f > min_f } else { value_f < max_f };
+            let button_color = state.interaction.color(elem, color);
+            let glyph_color = enable::color(label_color, state.enabled && can_step, &ui.theme);
+            let glyph_len = (button_w * 0.4).floor();
+            let glyph_thickness = (font_size as f64 / 6.0).max(1.0).floor();
+            button_forms.push(rect(button_w, pad_dim[1]).filled(button_color)
+                .shift(x, pad_offset[1]));
+            button_forms.push(rect(glyph_len, glyph_thickness).filled(glyph_color)
+                .shift(x.floor(), pad_offset[1].floor()));
+            if let Elem::Increment = elem {
+                button_forms.push(rect(glyph_thickness, glyph_len).filled(glyph_color)
+                    .shift(x.floor(), pad_offset[1].floor()));
+            }
+        }
+
+        // Construct the label followed by either the value or the text being typed in its place.
+        let maybe_label = state.maybe_label.as_ref().map(|label| &label[..]);
+        let maybe_spans = state.maybe_label_spans.as_ref();
+        let (label_dim, label_forms) =
+            create_label_forms(ui, maybe_label, maybe_spans, label_color, font_size);
+        let val_string = match state.maybe_edit {
+            Some(ref edit) => edit.clone(),
+            None => create_val_string(state.value, state.precision),
+        };
+        let val_string_w = label::width(ui, font_size, &val_string);
+        let text_w = label_dim[0] + val_string_w;
+        let label_x = pad_offset[0] - text_w / 2.0 + label_dim[0] / 2.0;
+        let val_string_x = pad_offset[0] - text_w / 2.0 + label_dim[0] + val_string_w / 2.0;
+        let label_forms = label_forms.into_iter()
+            .map(|form| form.shift(label_x.floor(), pad_offset[1].floor()));
+        let val_string_form = text(Text::from_string(val_string)
+                                       .color(label_color)
+                                       .height(font_size as f64))
+            .shift(val_string_x.floor(), pad_offset[1].floor());
+
+        // Mark the end of the typed text with a cursor.
+        let maybe_cursor_form = state.maybe_edit.as_ref().map(|_| {
+            let cursor_x = val_string_x + val_string_w / 2.0 + 1.0;
+            rect(1.0, font_size as f64).filled(label_color)
+                .shift(cursor_x.floor(), pad_offset[1].floor())
+        });
+
+        // Mark the NumberStepper with a lock if it is read-only.
+        let lock_forms = enable::lock_forms(state.read_only, dim, frame, label_color, &ui.theme);
+
+        // Chain the forms and shift them into position.
+        let form_chain = Some(frame_form).into_iter()
+            .chain(Some(inner_form).into_iter())
+            .chain(button_forms.into_iter())
+            .chain(label_forms)
+            .chain(Some(val_string_form).into_iter())
+            .chain(maybe_cursor_form.into_iter())
+            .chain(lock_forms.into_iter())
+            .map(|form| form.shift(xy[0].floor(), xy[1].floor()));
+
+        // Collect the Forms for drawing.
+        form_chain.collect()
+    }
+
+    /// Describe the NumberStepper for assistive technologies.
+    fn accessibility(state: &State<T>) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::NumberStepper)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(create_val_string(state.value, state.precision))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_number_stepper.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_number_stepper.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of an Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_medium)
+        })).unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [128.0, 32.0];
+        let width = self.maybe_width.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_number_stepper.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, T: Float, F> Enableable for NumberStepper<'a, T, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, T: Float, F> ReadOnly for NumberStepper<'a, T, F> {
+    fn read_only(mut self, flag: bool) -> Self {
+        self.read_only = flag;
+        self
+    }
+}
+
+impl<'a, T, F> Colorable for NumberStepper<'a, T, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, T, F> Paletted for NumberStepper<'a, T, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, T, F> Fontable for NumberStepper<'a, T, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, T, F> Frameable for NumberStepper<'a, T, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, T, F> Roundable for NumberStepper<'a, T, F> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
+}
+
+impl<'a, T, F> Labelable<'a> for NumberStepper<'a, T, F> {
+    fn label(mut self, text: &'a str) -> Self {
+        self.maybe_label = Some(text);
+        self
+    }
+
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
+    fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+}
+
+impl<'a, T, F> position::Positionable for NumberStepper<'a, T, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        NumberStepper { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        NumberStepper { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, T, F> position::Sizeable for NumberStepper<'a, T, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
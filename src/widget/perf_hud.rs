@@ -0,0 +1,420 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize};
+use palette::{ColorRole, Paletted};
+use piston::input::keyboard::Key;
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Place, Position, Positionable,
+               VerticalAlign};
+use profile::{DEFAULT_HISTORY, Profiler};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+
+
+/// The distance between the inner edge of the frame and the text.
+const PADDING: f64 = 4.0;
+
+/// The number of lines of text displayed by the PerfHud.
+const LINES: usize = 6;
+
+/// A toggleable overlay displaying the frame rate along with where the time within each frame
+/// goes: the time spent updating widgets, drawing their Elements and rendering, the number of
+/// widgets updated versus those that reused their cached Element, and the number of allocations
+/// made (if the application reports them via `Ui::record_allocations`).
+///
+/// Setting a PerfHud enables the `Ui`'s profiler (see `Ui::enable_profiler`) if it isn't already.
+/// The HUD is shown and hidden by pressing its toggle key (`F12` by default). Timings are those
+/// of the most recently completed frame, and the PerfHud is itself counted among the widgets.
+pub struct PerfHud {
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    toggle_key: Key,
+    is_initially_shown: bool,
+    style: Style,
+}
+
+/// Styling for the PerfHud, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_width: Option<f64>,
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<FontSize>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The state of the PerfHud.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    is_shown: bool,
+    lines: Vec<String>,
+}
+
+
+/// Format a duration in nanoseconds as milliseconds.
+fn ms(ns: u64) -> String {
+    format!("{:.2} ms", ns as f64 / 1_000_000.0)
+}
+
+/// The lines of text describing the profiler's most recent frame.
+fn describe(profiler: &Profiler) -> Vec<String> {
+    let mut lines = vec![format!("FPS: {:.1}", profiler.fps())];
+    match profiler.latest() {
+        Some(frame) => {
+            lines.push(format!("Update: {}", ms(frame.update_ns)));
+            lines.push(format!("Draw: {}", ms(frame.draw_ns)));
+            lines.push(format!("Render: {}", ms(frame.render_ns)));
+            lines.push(format!("Widgets: {} updated, {} cached",
+                               frame.widgets_updated, frame.widgets_cached()));
+            lines.push(match frame.maybe_allocations {
+                Some(count) => format!("Allocations: {}", count),
+                None => "Allocations: n/a".to_string(),
+            });
+        },
+        None => lines.push("Waiting for the first frame...".to_string()),
+    }
+    lines
+}
+
+
+impl PerfHud {
+
+    /// Construct a new PerfHud, initially placed at the top right of the window (or current
+    /// Canvas) above all other widgets.
+    pub fn new() -> PerfHud {
+        PerfHud {
+            pos: Position::Place(Place::TopRight, None),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: -2.0,
+            toggle_key: Key::F12,
+            is_initially_shown: true,
+            style: Style::new(),
+        }
+    }
+
+    /// Set the key that shows and hides the PerfHud.
+    pub fn toggle_key(mut self, key: Key) -> Self {
+        self.toggle_key = key;
+        self
+    }
+
+    /// Whether or not the PerfHud is shown when it is first set. It is shown by default.
+    pub fn initially_shown(mut self, is_shown: bool) -> Self {
+        self.is_initially_shown = is_shown;
+        self
+    }
+
+    /// Set the color of the text.
+    pub fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    /// Set the font size of the text.
+    pub fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+
+    /// Set the depth of the PerfHud.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl Widget for PerfHud {
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "PerfHud" }
+    fn init_state(&self) -> State {
+        State { is_shown: self.is_initially_shown, lines: Vec::new() }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the PerfHud.
+    fn update<C>(self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 _ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let toggle_key = self.toggle_key;
+        let is_toggled = ui.keys_just_pressed.iter().any(|&key| key == toggle_key);
+        let is_shown = state.is_shown != is_toggled;
+
+        // A hidden HUD takes up no space, so that it never obstructs the widgets beneath it.
+        let dim = if is_shown { style.dim(&ui.theme) } else { [0.0, 0.0] };
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+
+        if !ui.is_profiling() {
+            ui.enable_profiler(DEFAULT_HISTORY);
+        }
+
+        // Only describe the latest frame while shown, so that a hidden HUD isn't redrawn.
+        let lines = match (is_shown, ui.profiler()) {
+            (true, Some(profiler)) => describe(profiler),
+            _ => Vec::new(),
+        };
+
+        let new_state = State { is_shown: is_shown, lines: lines };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given PerfHud State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{rect, text};
+        use elmesque::text::Text;
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+
+        if !state.is_shown {
+            return Vec::new();
+        }
+
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let color = style.color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color)
+            .shift(xy[0], xy[1]);
+        let inner_form = rect(inner_dim[0], inner_dim[1]).filled(color)
+            .shift(xy[0] + inner_offset[0], xy[1] + inner_offset[1]);
+        let mut forms = vec![frame_form, inner_form];
+
+        // Lay out the lines from the top, left aligned.
+        let line_h = font_size as f64;
+        let left = xy[0] - dim[0] / 2.0 + frame + PADDING;
+        let top = xy[1] + dim[1] / 2.0 - frame - PADDING;
+        for (i, line) in state.lines.iter().enumerate() {
+            let w = label::width(ui, font_size, line);
+            let y = top - line_h * i as f64 - line_h / 2.0;
+            let label = Text::from_string(line.clone()).color(label_color).height(line_h);
+            forms.push(text(label).shift((left + w / 2.0).floor(), y.floor()));
+        }
+
+        forms
+    }
+
+    /// Describe the PerfHud for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let value = state.lines.first().map(|line| &line[..]).unwrap_or("");
+        Accessibility::new(Role::Status).value(value)
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_width: None,
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Dimensions for an Element. The height always fits the lines of text.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_WIDTH: f64 = 220.0;
+        let width = self.maybe_width.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_WIDTH)
+        })).unwrap_or(DEFAULT_WIDTH);
+        let line_h = self.label_font_size(theme) as f64;
+        let height = line_h * LINES as f64 + (self.frame(theme) + PADDING) * 2.0;
+        [width, height]
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_perf_hud.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_perf_hud.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_perf_hud.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl Colorable for PerfHud {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl Paletted for PerfHud {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl Fontable for PerfHud {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl Frameable for PerfHud {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl Positionable for PerfHud {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        PerfHud { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        PerfHud { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl ::position::Sizeable for PerfHud {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    /// The height of the PerfHud always fits its lines of text, so this has no effect.
+    #[inline]
+    fn height(self, _h: f64) -> Self {
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
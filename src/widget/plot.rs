@@ -0,0 +1,820 @@
+use accessibility::{Accessibility, Role};
+use color::{self, Color, Colorable};
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use frame::{Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize, Labelable};
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::{clamp, is_over_rect};
+use vecmath::vec2_add;
+use widget::{self, Widget};
+use widget::editor_canvas::{nice_step, ruler_label};
+use widget::rich_text::{Span, SpannedLabel};
+
+
+/// The length of the ticks drawn outside of the axes.
+const TICK_LEN: f64 = 4.0;
+/// The gap between the end of a tick and its label.
+const TICK_LABEL_GAP: f64 = 2.0;
+/// The minimum spacing between ticks along the x axis in pixels.
+const X_TICK_SPACING: f64 = 64.0;
+/// The minimum spacing between ticks along the y axis in pixels.
+const Y_TICK_SPACING: f64 = 32.0;
+/// The portion of the space between consecutive bars that is filled by bars.
+const BAR_FILL: f64 = 0.8;
+
+/// A chart plotting one or more series of data as lines or bars against a pair of axes with
+/// labelled ticks and an optional grid.
+///
+/// The range of each axis fits the data unless given via `x_range` and `y_range`. Functions are
+/// sampled once per pixel across the x range, so a range must be given via `x_range` if there are
+/// no other series from which it may be found.
+///
+/// The reaction is triggered with the position of the mouse in the data's coordinates as it moves
+/// over the plotted area, and with `None` as it leaves.
+///
+/// ```ignore
+/// Plot::new()
+///     .series(Series::points(&readings))
+///     .series(Series::function(&|x| x.sin()).color(color::red()))
+///     .react(|maybe_xy| app.hover = maybe_xy)
+///     .set(PLOT, ui);
+/// ```
+pub struct Plot<'a, F> {
+    series: Vec<Series<'a>>,
+    maybe_x_range: Option<(f64, f64)>,
+    maybe_y_range: Option<(f64, f64)>,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// The data for a single series.
+#[derive(Copy, Clone)]
+pub enum Data<'a> {
+    /// Values along the y axis, plotted at the x positions `0`, `1`, `2` and so on.
+    Values(&'a [f64]),
+    /// Points in the data's coordinates.
+    Points(&'a [Point]),
+    /// A function of x, sampled once per pixel across the x range.
+    Function(&'a Fn(f64) -> f64),
+}
+
+/// The way in which a series is drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Chart {
+    /// A line joining each consecutive point.
+    Line,
+    /// A bar from zero to each point. The bars of all bar series are grouped side by side.
+    Bars,
+}
+
+/// A series of data to be plotted, along with the way in which it is drawn.
+#[derive(Copy, Clone)]
+pub struct Series<'a> {
+    data: Data<'a>,
+    chart: Chart,
+    maybe_color: Option<Color>,
+}
+
+/// Styling for the Plot, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<FontSize>,
+    pub maybe_tick_font_size: Option<FontSize>,
+    pub maybe_line_width: Option<f64>,
+    pub maybe_grid_lines: Option<bool>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The state of the Plot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    series: Vec<SeriesState>,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    x_step: f64,
+    y_step: f64,
+    /// The distance along the x axis between consecutive bars.
+    bar_spacing: f64,
+    /// The offset of the plotted area from the centre of the Plot, along with its dimensions.
+    area: (Point, Dimensions),
+    /// The position of the mouse over the plotted area in the data's coordinates.
+    maybe_hover: Option<Point>,
+    maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+}
+
+/// The points of a single series, with any function sampled across the x range.
+#[derive(Clone, Debug, PartialEq)]
+struct SeriesState {
+    chart: Chart,
+    color: Color,
+    points: Vec<Point>,
+}
+
+
+impl<'a> Series<'a> {
+
+    /// A series of values along the y axis, plotted at the x positions `0`, `1`, `2` and so on.
+    pub fn values(values: &'a [f64]) -> Series<'a> {
+        Series { data: Data::Values(values), chart: Chart::Line, maybe_color: None }
+    }
+
+    /// A series of points in the data's coordinates.
+    pub fn points(points: &'a [Point]) -> Series<'a> {
+        Series { data: Data::Points(points), chart: Chart::Line, maybe_color: None }
+    }
+
+    /// A function of x, sampled once per pixel across the x range.
+    pub fn function(function: &'a Fn(f64) -> f64) -> Series<'a> {
+        Series { data: Data::Function(function), chart: Chart::Line, maybe_color: None }
+    }
+
+    /// Draw the series as bars rather than a line.
+    pub fn bars(self) -> Series<'a> {
+        Series { chart: Chart::Bars, ..self }
+    }
+
+    /// Set the color of the series. By default, each series takes the next color of a palette.
+    pub fn color(self, color: Color) -> Series<'a> {
+        Series { maybe_color: Some(color), ..self }
+    }
+
+    /// The points of the series, or none if it is a function.
+    fn fixed_points(&self) -> Vec<Point> {
+        match self.data {
+            Data::Values(values) => {
+                values.iter().enumerate().map(|(i, &y)| [i as f64, y]).collect()
+            },
+            Data::Points(points) => points.to_vec(),
+            Data::Function(_) => Vec::new(),
+        }
+    }
+
+}
+
+
+/// The default color of the series at the given index.
+fn palette(idx: usize) -> Color {
+    match idx % 5 {
+        0 => color::blue(),
+        1 => color::orange(),
+        2 => color::green(),
+        3 => color::red(),
+        _ => color::purple(),
+    }
+}
+
+/// The lowest and highest of the given values, if there are any.
+fn bounds<I: Iterator<Item=f64>>(values: I) -> Option<(f64, f64)> {
+    values.filter(|v| v.is_finite()).fold(None, |maybe_bounds, v| match maybe_bounds {
+        Some((lo, hi)) => Some((if v < lo { v } else { lo }, if v > hi { v } else { hi })),
+        None => Some((v, v)),
+    })
+}
+
+/// Widen a range over which nothing varies so that it may still be plotted.
+fn non_empty((lo, hi): (f64, f64)) -> (f64, f64) {
+    if hi - lo > 0.0 { (lo, hi) } else { (lo - 1.0, hi + 1.0) }
+}
+
+/// Map a value within the given range to a pixel offset from the centre of an axis.
+fn to_px(value: f64, (lo, hi): (f64, f64), len: f64) -> f64 {
+    (value - lo) / (hi - lo) * len - len / 2.0
+}
+
+/// Map a pixel offset from the centre of an axis to a value within the given range.
+fn from_px(px: f64, (lo, hi): (f64, f64), len: f64) -> f64 {
+    (px + len / 2.0) / len * (hi - lo) + lo
+}
+
+/// The step between ticks along an axis of the given length so that they are at least `spacing`
+/// pixels apart.
+fn tick_step((lo, hi): (f64, f64), len: f64, spacing: f64) -> f64 {
+    nice_step((hi - lo) * spacing / len.max(1.0))
+}
+
+/// The values of the ticks within the given range.
+fn ticks((lo, hi): (f64, f64), step: f64) -> Vec<f64> {
+    let first = (lo / step).ceil() as i64;
+    let last = (hi / step).floor() as i64;
+    (first..last + 1).map(|i| i as f64 * step).collect()
+}
+
+
+impl<'a, F> Plot<'a, F> {
+
+    /// Construct a new Plot with no series.
+    pub fn new() -> Plot<'a, F> {
+        Plot {
+            series: Vec::new(),
+            maybe_x_range: None,
+            maybe_y_range: None,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_label: None,
+            maybe_label_spans: None,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Add a series of data to the Plot. Series are drawn in the order in which they are added.
+    pub fn series(mut self, series: Series<'a>) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Use the given range for the x axis rather than fitting it to the data.
+    pub fn x_range(mut self, min: f64, max: f64) -> Self {
+        self.maybe_x_range = Some((min, max));
+        self
+    }
+
+    /// Use the given range for the y axis rather than fitting it to the data.
+    pub fn y_range(mut self, min: f64, max: f64) -> Self {
+        self.maybe_y_range = Some((min, max));
+        self
+    }
+
+    /// Set the width of the lines of each line series.
+    pub fn line_width(mut self, width: f64) -> Self {
+        self.style.maybe_line_width = Some(width);
+        self
+    }
+
+    /// Set the font size of the tick labels.
+    pub fn tick_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_tick_font_size = Some(size);
+        self
+    }
+
+    /// Whether or not faint grid lines should be drawn at each tick.
+    pub fn grid_lines(mut self, show: bool) -> Self {
+        self.style.maybe_grid_lines = Some(show);
+        self
+    }
+
+    /// Set the reaction for the Plot. It will be triggered with the position of the mouse in the
+    /// data's coordinates as it moves over the plotted area, and with `None` as it leaves.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the depth of the Plot.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+impl<'a, F> Widget for Plot<'a, F>
+    where
+        F: FnMut(Option<Point>),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Plot" }
+    fn init_state(&self) -> State {
+        State {
+            series: Vec::new(),
+            x_range: (0.0, 1.0),
+            y_range: (0.0, 1.0),
+            x_step: 1.0,
+            y_step: 1.0,
+            bar_spacing: 1.0,
+            area: ([0.0, 0.0], [0.0, 0.0]),
+            maybe_hover: None,
+            maybe_label: None,
+            maybe_label_spans: None,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the Plot's cached state.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+
+        // Fit the x axis to the series' points, leaving room for half of a bar at either end.
+        let mut series: Vec<SeriesState> = self.series.iter().enumerate().map(|(i, series)| {
+            SeriesState {
+                chart: series.chart,
+                color: series.maybe_color.unwrap_or_else(|| palette(i)),
+                points: series.fixed_points(),
+            }
+        }).collect();
+        let bar_spacing = series.iter().filter(|s| s.chart == Chart::Bars).flat_map(|s| {
+            s.points.windows(2).map(|pair| (pair[1][0] - pair[0][0]).abs()).collect::<Vec<_>>()
+        }).filter(|&d| d > 0.0).fold(None, |min: Option<f64>, d| {
+            Some(min.map_or(d, |min| min.min(d)))
+        }).unwrap_or(1.0);
+        let x_range = non_empty(self.maybe_x_range.unwrap_or_else(|| {
+            let xs = series.iter().flat_map(|s| s.points.iter().map(|p| p[0]));
+            let (lo, hi) = bounds(xs).unwrap_or((0.0, 1.0));
+            match series.iter().any(|s| s.chart == Chart::Bars) {
+                true => (lo - bar_spacing / 2.0, hi + bar_spacing / 2.0),
+                false => (lo, hi),
+            }
+        }));
+
+        // Sample each function once per pixel across the x range.
+        let samples = (pad_dim[0].max(0.0) as usize).max(2);
+        for (series, state) in self.series.iter().zip(series.iter_mut()) {
+            if let Data::Function(function) = series.data {
+                state.points = (0..samples).map(|i| {
+                    let x = x_range.0 + (x_range.1 - x_range.0) * i as f64 / (samples - 1) as f64;
+                    [x, function(x)]
+                }).filter(|p| p[1].is_finite()).collect();
+            }
+        }
+
+        // Fit the y axis to all points, including zero if there are any bars.
+        let y_range = non_empty(self.maybe_y_range.unwrap_or_else(|| {
+            let ys = series.iter().flat_map(|s| s.points.iter().map(|p| p[1]));
+            let has_bars = series.iter().any(|s| s.chart == Chart::Bars);
+            let zero = if has_bars { Some(0.0) } else { None };
+            bounds(ys.chain(zero.into_iter())).unwrap_or((0.0, 1.0))
+        }));
+
+        // Lay out the plotted area, leaving room for the title and the tick labels.
+        let tick_size = style.tick_font_size(&ui.theme);
+        let has_label = self.maybe_label.is_some() || self.maybe_label_spans.is_some();
+        let top = if has_label {
+            style.label_font_size(&ui.theme) as f64 + TICK_LEN
+        } else {
+            tick_size as f64 / 2.0 + TICK_LEN
+        };
+        let bottom = tick_size as f64 + TICK_LEN + TICK_LABEL_GAP * 2.0;
+        let area_h = (pad_dim[1] - top - bottom).max(0.0);
+        let y_step = tick_step(y_range, area_h, Y_TICK_SPACING);
+        let label_w = ticks(y_range, y_step).iter().fold(0.0, |max, &y| {
+            label::width(ui, tick_size, &ruler_label(y, y_step)).max(max)
+        });
+        let left = label_w + TICK_LEN + TICK_LABEL_GAP * 2.0;
+        let right = X_TICK_SPACING / 4.0;
+        let area_w = (pad_dim[0] - left - right).max(0.0);
+        let x_step = tick_step(x_range, area_w, X_TICK_SPACING);
+        let area_offset = vec2_add(pad_offset, [(left - right) / 2.0, (bottom - top) / 2.0]);
+        let area = (area_offset, [area_w, area_h]);
+
+        // Find the position of the mouse over the plotted area in the data's coordinates.
+        let mouse = ui.get_mouse_state(ui_id).relative_to(vec2_add(xy, area_offset));
+        let maybe_hover = match self.enabled && is_over_rect([0.0, 0.0], mouse.xy, area.1) {
+            true => Some([from_px(mouse.xy[0], x_range, area_w),
+                          from_px(mouse.xy[1], y_range, area_h)]),
+            false => None,
+        };
+        if maybe_hover != state.maybe_hover {
+            if let Some(ref mut react) = self.maybe_react { react(maybe_hover) }
+        }
+
+        // Construct the new state if there was a change.
+        let state_has_changed = state.series != series
+            || state.x_range != x_range || state.y_range != y_range
+            || state.x_step != x_step || state.y_step != y_step
+            || state.bar_spacing != bar_spacing
+            || state.area != area
+            || state.maybe_hover != maybe_hover
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
+        let maybe_new_state = if state_has_changed {
+            Some(State {
+                series: series,
+                x_range: x_range,
+                y_range: y_range,
+                x_step: x_step,
+                y_step: y_step,
+                bar_spacing: bar_spacing,
+                area: area,
+                maybe_hover: maybe_hover,
+                maybe_label: self.maybe_label.map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+            })
+        } else {
+            None
+        };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given Plot State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{line, rect, solid, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let frame = style.frame(&ui.theme);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let color = style.color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let tick_size = style.tick_font_size(&ui.theme);
+        let (area_offset, area_dim) = state.area;
+        let (half_w, half_h) = (area_dim[0] / 2.0, area_dim[1] / 2.0);
+        let x_px = |x: f64| to_px(x, state.x_range, area_dim[0]);
+        let y_px = |y: f64| clamp(to_px(y, state.y_range, area_dim[1]), -half_h, half_h);
+
+        // Construct the frame and inner rectangle Forms.
+        let frame_form = rect(dim[0], dim[1]).filled(style.frame_color(&ui.theme));
+        let inner_form = rect(pad_dim[0], pad_dim[1]).filled(color)
+            .shift(pad_offset[0], pad_offset[1]);
+
+        // Construct the title's Forms, centred above the plotted area.
+        let l_size = style.label_font_size(&ui.theme);
+        let title_y = pad_offset[1] + pad_dim[1] / 2.0 - (l_size as f64 + TICK_LEN) / 2.0;
+        let title_forms = match (state.maybe_label_spans.as_ref(), state.maybe_label.as_ref()) {
+            (Some(spans), _) => spans.forms(ui, label_color, l_size).1,
+            (None, Some(l_text)) => {
+                let l_text = Text::from_string(l_text.clone()).color(label_color);
+                vec![text(l_text.height(l_size as f64))]
+            },
+            (None, None) => Vec::new(),
+        }.into_iter().map(|form| form.shift(area_offset[0].floor(), title_y.floor()));
+
+        // Construct the grid, tick and tick label Forms.
+        let mut area_forms: Vec<Form> = Vec::new();
+        let grid_style = solid(label_color.alpha(0.15));
+        let tick_style = solid(label_color);
+        let show_grid = style.grid_lines(&ui.theme);
+        let tick_h = tick_size as f64;
+        for x in ticks(state.x_range, state.x_step) {
+            let px = x_px(x).floor();
+            if show_grid {
+                area_forms.push(line(grid_style.clone(), px, -half_h, px, half_h));
+            }
+            area_forms.push(line(tick_style.clone(), px, -half_h, px, -half_h - TICK_LEN));
+            let string = ruler_label(x, state.x_step);
+            let label_y = -half_h - TICK_LEN - TICK_LABEL_GAP - tick_h / 2.0;
+            let label = Text::from_string(string).color(label_color).height(tick_h);
+            area_forms.push(text(label).shift(px, label_y.floor()));
+        }
+        for y in ticks(state.y_range, state.y_step) {
+            let py = y_px(y).floor();
+            if show_grid {
+                area_forms.push(line(grid_style.clone(), -half_w, py, half_w, py));
+            }
+            area_forms.push(line(tick_style.clone(), -half_w, py, -half_w - TICK_LEN, py));
+            let string = ruler_label(y, state.y_step);
+            let w = label::width(ui, tick_size, &string);
+            let label_x = -half_w - TICK_LEN - TICK_LABEL_GAP - w / 2.0;
+            let label = Text::from_string(string).color(label_color).height(tick_h);
+            area_forms.push(text(label).shift(label_x.floor(), py));
+        }
+
+        // Construct the axis Forms along the left and bottom edges of the plotted area.
+        area_forms.push(line(tick_style.clone(), -half_w, -half_h, -half_w, half_h));
+        area_forms.push(line(tick_style.clone(), -half_w, -half_h, half_w, -half_h));
+
+        // Construct the Forms for each series, grouping the bars of all bar series side by side.
+        let num_bar_series = state.series.iter().filter(|s| s.chart == Chart::Bars).count();
+        let bar_spacing_px = state.bar_spacing / (state.x_range.1 - state.x_range.0) * area_dim[0];
+        let bar_w = bar_spacing_px * BAR_FILL / num_bar_series.max(1) as f64;
+        let baseline = y_px(0.0);
+        let line_width = style.line_width(&ui.theme);
+        let is_visible = |x: f64| x_px(x).abs() <= half_w + 0.5;
+        let mut bar_idx = 0;
+        for series in state.series.iter() {
+            match series.chart {
+                Chart::Line => {
+                    let line_style = solid(series.color).width(line_width);
+                    for pair in series.points.windows(2) {
+                        if !is_visible(pair[0][0]) || !is_visible(pair[1][0]) { continue }
+                        area_forms.push(line(line_style.clone(),
+                                             x_px(pair[0][0]), y_px(pair[0][1]),
+                                             x_px(pair[1][0]), y_px(pair[1][1])));
+                    }
+                },
+                Chart::Bars => {
+                    let offset = (bar_idx as f64 - (num_bar_series - 1) as f64 / 2.0) * bar_w;
+                    for point in series.points.iter().filter(|p| is_visible(p[0])) {
+                        let top = y_px(point[1]);
+                        let bar_x = clamp(x_px(point[0]) + offset, -half_w, half_w);
+                        area_forms.push(rect(bar_w, (top - baseline).abs())
+                            .filled(series.color)
+                            .shift(bar_x, (top + baseline) / 2.0));
+                    }
+                    bar_idx += 1;
+                },
+            }
+        }
+
+        // Indicate the position of the mouse, with its coordinates in the top right corner.
+        if let Some(hover) = state.maybe_hover {
+            let px = x_px(hover[0]).floor();
+            area_forms.push(line(solid(label_color.alpha(0.5)), px, -half_h, px, half_h));
+            let string = format!("{}, {}", ruler_label(hover[0], state.x_step / 10.0),
+                                           ruler_label(hover[1], state.y_step / 10.0));
+            let w = label::width(ui, tick_size, &string);
+            let x = half_w - TICK_LABEL_GAP - w / 2.0;
+            let y = half_h - TICK_LABEL_GAP - tick_h / 2.0;
+            let label = Text::from_string(string).color(label_color).height(tick_h);
+            area_forms.push(text(label).shift(x.floor(), y.floor()));
+        }
+
+        // Chain the Forms and shift them into position.
+        let form_chain = Some(frame_form).into_iter()
+            .chain(Some(inner_form).into_iter())
+            .chain(title_forms)
+            .chain(area_forms.into_iter().map(|form| form.shift(area_offset[0], area_offset[1])))
+            .map(|form| form.shift(xy[0].round(), xy[1].round()));
+
+        // Turn the form for drawing.
+        form_chain.collect()
+    }
+
+    /// Describe the Plot for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        let accessibility = Accessibility::new(Role::Chart).label(maybe_label.map(|l| &l[..]));
+        match state.maybe_hover {
+            Some(hover) => accessibility.value(format!("{}, {}", hover[0], hover[1])),
+            None => accessibility,
+        }
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_tick_font_size: None,
+            maybe_line_width: None,
+            maybe_grid_lines: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_plot.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_plot.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_medium)
+        })).unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the tick label font size for an Element.
+    pub fn tick_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_tick_font_size.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_tick_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the width of the lines of each line series.
+    pub fn line_width(&self, theme: &Theme) -> f64 {
+        const DEFAULT_LINE_WIDTH: f64 = 1.5;
+        self.maybe_line_width.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_line_width.unwrap_or(DEFAULT_LINE_WIDTH)
+        })).unwrap_or(DEFAULT_LINE_WIDTH)
+    }
+
+    /// Get whether or not grid lines should be drawn for an Element.
+    pub fn grid_lines(&self, theme: &Theme) -> bool {
+        const DEFAULT_GRID_LINES: bool = true;
+        self.maybe_grid_lines.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_grid_lines.unwrap_or(DEFAULT_GRID_LINES)
+        })).unwrap_or(DEFAULT_GRID_LINES)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [256.0, 160.0];
+        let width = self.maybe_width.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_plot.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+impl<'a, F> Enableable for Plot<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for Plot<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for Plot<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for Plot<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Frameable for Plot<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Labelable<'a> for Plot<'a, F> {
+    fn label(mut self, text: &'a str) -> Self {
+        self.maybe_label = Some(text);
+        self
+    }
+
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
+    fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+}
+
+impl<'a, F> position::Positionable for Plot<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        Plot { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        Plot { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> position::Sizeable for Plot<'a, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
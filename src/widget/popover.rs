@@ -0,0 +1,391 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use corner::CornerRadii;
+use elmesque::form::Form;
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, Direction, Margin, Padding, Point};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+
+
+/// A floating panel anchored to the edge of some trigger widget, with an arrow pointing at the
+/// trigger. If the panel doesn't fit within the window on the preferred side of the trigger it
+/// flips to the opposite side, and it slides along the trigger's edge to remain within the window.
+///
+/// The Popover hosts arbitrary child widgets, which should be set after the Popover (so that they
+/// are drawn above it) and positioned relative to it (i.e. `.relative_to(popover_id, [0.0, 0.0])`
+/// or `.down_from(popover_id, 10.0)`) while it is open. Set the Popover along with its children
+/// within the `Popup` layer (see `Ui::with_layer`) so that they are drawn above, and receive the
+/// mouse before, the rest of the interface.
+///
+/// The open Popover is a transient, so pressing Escape or clicking outside of it dismisses it, at
+/// which point its reaction is triggered. Note that the Popover will not close itself, you should
+/// do this yourself within the react closure.
+pub struct Popover<F> {
+    trigger: UiId,
+    is_open: bool,
+    depth: Depth,
+    padding: Padding,
+    maybe_react: Option<F>,
+    style: Style,
+}
+
+/// Styling for the Popover, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_side: Option<Direction>,
+    pub maybe_arrow_size: Option<f64>,
+}
+
+/// The state of the Popover.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    is_open: bool,
+    /// The side of the trigger on which the Popover is placed.
+    side: Direction,
+    /// The offset of the arrow from the centre of the edge facing the trigger.
+    arrow_offset: f64,
+}
+
+
+/// Find the side of the trigger on which the Popover fits (preferring the given side), along with
+/// the Popover's centre and the offset of its arrow from the centre of the edge facing the trigger.
+fn placement(side: Direction,
+             trigger_xy: Point,
+             trigger_dim: Dimensions,
+             dim: Dimensions,
+             arrow: f64,
+             win_dim: Dimensions) -> (Direction, Point, f64) {
+    let (side, xy) = position::place_beside(side, trigger_xy, trigger_dim, dim, arrow, win_dim);
+
+    // Keep the arrow pointed at the trigger, even where the Popover has slid along its edge.
+    let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+    let arrow_offset = match side {
+        Direction::Up | Direction::Down =>
+            (trigger_xy[0] - xy[0]).max(-half_w + arrow).min(half_w - arrow),
+        Direction::Left | Direction::Right =>
+            (trigger_xy[1] - xy[1]).max(-half_h + arrow).min(half_h - arrow),
+    };
+    (side, xy, arrow_offset)
+}
+
+
+impl<F> Popover<F> {
+
+    /// Construct a Popover anchored to the widget with the given UiId. The Popover is only
+    /// displayed while `is_open` is true.
+    pub fn new(trigger: UiId, is_open: bool) -> Popover<F> {
+        Popover {
+            trigger: trigger,
+            is_open: is_open,
+            depth: 0.0,
+            padding: Padding::none(),
+            maybe_react: None,
+            style: Style::new(),
+        }
+    }
+
+    /// Set the reaction for the Popover. It will be triggered when the open Popover is dismissed.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the side of the trigger on which the Popover should preferably be placed.
+    #[inline]
+    pub fn side(mut self, side: Direction) -> Self {
+        self.style.maybe_side = Some(side);
+        self
+    }
+
+    /// Set the length of the arrow pointing at the trigger.
+    #[inline]
+    pub fn arrow_size(mut self, size: f64) -> Self {
+        self.style.maybe_arrow_size = Some(size);
+        self
+    }
+
+    /// Set the rendering depth of the Popover.
+    #[inline]
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl<F> Widget for Popover<F>
+    where
+        F: FnMut(),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Popover" }
+    fn init_state(&self) -> State {
+        State { is_open: false, side: Direction::Down, arrow_offset: 0.0 }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+
+    /// Update the state of the Popover.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let arrow = style.arrow_size(&ui.theme);
+        let preferred_side = style.side(&ui.theme);
+        let trigger_xy = ui.widget_xy(self.trigger);
+        let trigger_dim = ui.widget_size(self.trigger);
+        let win_dim = [ui.win_w, ui.win_h];
+        ui.set_current_spacing(Margin::none(), self.padding);
+        let (side, xy, arrow_offset) =
+            placement(preferred_side, trigger_xy, trigger_dim, dim, arrow, win_dim);
+
+        // React if the `Ui` has dismissed the open Popover.
+        if self.is_open && ui.is_dismissed(ui_id) {
+            if let Some(ref mut react) = self.maybe_react { react() }
+        }
+
+        // Register the open Popover as a transient so that the `Ui` may dismiss it.
+        if self.is_open {
+            ui.transient_opened_by(ui_id, xy, dim);
+        } else if state.is_open {
+            ui.transient_closed_by(ui_id);
+        }
+
+        let new_state = State { is_open: self.is_open, side: side, arrow_offset: arrow_offset };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given Popover State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{polygon, rect};
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        if !state.is_open {
+            return Vec::new();
+        }
+
+        // Retrieve the styling for the Element.
+        let color = style.color(&ui.theme);
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let arrow = style.arrow_size(&ui.theme);
+
+        // Convert a point given as a distance along the edge facing the trigger and a distance out
+        // from that edge into a point relative to the centre of the Popover.
+        let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
+        let side = state.side;
+        let edge_point = |along: f64, out: f64| -> (f64, f64) {
+            match side {
+                Direction::Down => (along, half_h + out),
+                Direction::Up => (along, -(half_h + out)),
+                Direction::Right => (-(half_w + out), along),
+                Direction::Left => (half_w + out, along),
+            }
+        };
+        let offset = state.arrow_offset;
+        let arrow_form = polygon(vec![edge_point(offset - arrow, -frame),
+                                      edge_point(offset + arrow, -frame),
+                                      edge_point(offset, arrow)]).filled(frame_color);
+        let inner_arrow = arrow - frame * 2.0;
+        let inner_arrow_form = polygon(vec![edge_point(offset - inner_arrow, -frame * 2.0),
+                                            edge_point(offset + inner_arrow, -frame * 2.0),
+                                            edge_point(offset, inner_arrow)]).filled(color);
+
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let inner_form = rect(inner_dim[0], inner_dim[1]).filled(color)
+            .shift(inner_offset[0], inner_offset[1]);
+
+        // Chain and shift the Forms into position.
+        let form_chain = Some(frame_form).into_iter()
+            .chain(Some(arrow_form).into_iter())
+            .chain(Some(inner_form).into_iter())
+            .chain(Some(inner_arrow_form).into_iter())
+            .map(|form| form.shift(xy[0], xy[1]));
+
+        // Collect the Forms for drawing.
+        form_chain.collect()
+    }
+
+    /// Describe the Popover for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::Popover)
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_side: None,
+            maybe_arrow_size: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_popover.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_popover.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [192.0, 128.0];
+        let width = self.maybe_width.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the preferred side of the trigger on which to place the Element.
+    pub fn side(&self, theme: &Theme) -> Direction {
+        self.maybe_side.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_side.unwrap_or(Direction::Down)
+        })).unwrap_or(Direction::Down)
+    }
+
+    /// Get the length of the arrow pointing at the trigger.
+    pub fn arrow_size(&self, theme: &Theme) -> f64 {
+        const DEFAULT_ARROW_SIZE: f64 = 10.0;
+        self.maybe_arrow_size.or(theme.maybe_popover.as_ref().map(|style| {
+            style.maybe_arrow_size.unwrap_or(DEFAULT_ARROW_SIZE)
+        })).unwrap_or(DEFAULT_ARROW_SIZE)
+    }
+
+}
+
+
+impl<F> Colorable for Popover<F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<F> Paletted for Popover<F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<F> Frameable for Popover<F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<F> ::position::Sizeable for Popover<F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -0,0 +1,531 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use elmesque::form::Form;
+use enable::{self, Enableable};
+use font::{FontId, Fontable};
+use graphics::character::CharacterCache;
+use label::{self, FontSize};
+use mouse::Mouse;
+use palette::{ColorRole, Paletted};
+use piston::input::keyboard::Key;
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+
+pub type Idx = usize;
+
+
+/// A group of options drawn as circular buttons with labels, of which at most one may be
+/// selected. Its reaction is triggered upon release of an option and will return the index of the
+/// newly selected option. Note that the RadioGroup will not mutate the selection for you, you
+/// should do this yourself within the react closure.
+///
+/// Clicking the RadioGroup gives it keyboard focus, after which the arrow keys select the previous
+/// or next option.
+pub struct RadioGroup<'a, F> {
+    options: &'a [&'a str],
+    maybe_selected: Option<Idx>,
+    orientation: Orientation,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// The direction in which the options of a RadioGroup are laid out.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    /// The options sit side by side, with the first option on the left.
+    Horizontal,
+    /// The options are stacked, with the first option at the top.
+    Vertical,
+}
+
+/// Styling for the RadioGroup, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_ring_width: Option<f64>,
+    pub maybe_ring_color: Option<Color>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The way in which the RadioGroup's options are being interacted with.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted(Idx),
+    Clicked(Idx),
+}
+
+/// The state of the RadioGroup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    options: Vec<String>,
+    maybe_selected: Option<Idx>,
+    orientation: Orientation,
+    interaction: Interaction,
+    /// Whether or not the RadioGroup has keyboard focus.
+    is_focused: bool,
+    /// Whether or not the RadioGroup accepts user input.
+    enabled: bool,
+}
+
+
+impl Interaction {
+    /// Alter the color of the given option depending on the interaction.
+    fn color(&self, idx: Idx, color: Color) -> Color {
+        match *self {
+            Interaction::Highlighted(i) if i == idx => color.highlighted(),
+            Interaction::Clicked(i) if i == idx => color.clicked(),
+            _ => color,
+        }
+    }
+}
+
+
+/// The offset from the centre of the group and the dimensions of the option at the given index.
+fn option_rect(orientation: Orientation, dim: Dimensions, len: usize, idx: Idx)
+    -> (Point, Dimensions)
+{
+    let len = len as f64;
+    let idx = idx as f64;
+    match orientation {
+        Orientation::Horizontal => {
+            let w = dim[0] / len;
+            ([-dim[0] / 2.0 + w * idx + w / 2.0, 0.0], [w, dim[1]])
+        },
+        Orientation::Vertical => {
+            let h = dim[1] / len;
+            ([0.0, dim[1] / 2.0 - h * idx - h / 2.0], [dim[0], h])
+        },
+    }
+}
+
+/// The index of the option under the given point (relative to the centre of the group), if any.
+fn over_option(orientation: Orientation, dim: Dimensions, len: usize, xy: Point) -> Option<Idx> {
+    use utils::is_over_rect;
+    (0..len).find(|&idx| {
+        let (offset, option_dim) = option_rect(orientation, dim, len, idx);
+        is_over_rect(offset, xy, option_dim)
+    })
+}
+
+/// Determine the new interaction from the option under the mouse.
+fn get_new_interaction(maybe_over: Option<Idx>, prev: Interaction, mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (maybe_over, prev, mouse.left.state) {
+        (Some(_),   Normal,     Down) => Normal,
+        (Some(idx), _,          Down) => Clicked(idx),
+        (Some(idx), _,          Up)   => Highlighted(idx),
+        (None,      Clicked(p), Down) => Clicked(p),
+        _                             => Normal,
+    }
+}
+
+/// The option selected via the keys pressed while the RadioGroup has keyboard focus, if any.
+fn get_key_selection(keys: &[Key], len: usize, selected: Option<Idx>) -> Option<Idx> {
+    use piston::input::keyboard::Key::{Down, Left, Return, Right, Space, Up};
+    keys.iter().fold(selected, |maybe_idx, &key| match (key, maybe_idx) {
+        (Up, Some(idx)) | (Left, Some(idx)) => Some(if idx > 0 { idx - 1 } else { idx }),
+        (Down, Some(idx)) | (Right, Some(idx)) => Some(if idx + 1 < len { idx + 1 } else { idx }),
+        (Up, None) | (Left, None) | (Down, None) | (Right, None) |
+        (Return, None) | (Space, None) => Some(0),
+        _ => maybe_idx,
+    })
+}
+
+
+impl<'a, F> RadioGroup<'a, F> {
+
+    /// Construct a new RadioGroup with the given options, of which the option at the given index
+    /// is selected.
+    pub fn new(options: &'a [&'a str], maybe_selected: Option<Idx>) -> RadioGroup<'a, F> {
+        RadioGroup {
+            options: options,
+            maybe_selected: maybe_selected,
+            orientation: Orientation::Vertical,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the reaction for the RadioGroup. It will be triggered upon the selection of an option.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Lay the options out side by side or stacked. By default, the options are stacked.
+    #[inline]
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the width of the ring around each option.
+    #[inline]
+    pub fn ring_width(mut self, width: f64) -> Self {
+        self.style.maybe_ring_width = Some(width);
+        self
+    }
+
+    /// Set the color of the ring around each option.
+    #[inline]
+    pub fn ring_color(mut self, color: Color) -> Self {
+        self.style.maybe_ring_color = Some(color);
+        self
+    }
+
+    /// Set the color of the options' labels.
+    #[inline]
+    pub fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    /// Set the font size of the options' labels.
+    #[inline]
+    pub fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+
+}
+
+impl<'a, F> Widget for RadioGroup<'a, F>
+    where
+        F: FnMut(Idx),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "RadioGroup" }
+    fn init_state(&self) -> State {
+        State {
+            options: Vec::new(),
+            maybe_selected: None,
+            orientation: self.orientation,
+            interaction: Interaction::Normal,
+            is_focused: false,
+            enabled: self.enabled,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the RadioGroup.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let len = self.options.len();
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let dim = style.dim(&ui.theme, self.orientation, len);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
+        let selected = self.maybe_selected.and_then(|idx| if idx < len { Some(idx) }
+                                                          else { None });
+        let new_interaction =
+            if self.enabled && len > 0 {
+                let maybe_over = over_option(self.orientation, dim, len, mouse.xy);
+                get_new_interaction(maybe_over, state.interaction, mouse)
+            } else {
+                // This RadioGroup is disabled, pretend the interaction was normal.
+                Interaction::Normal
+            };
+
+        // Claim keyboard focus when clicked so that the arrow keys may also change the selection.
+        if let Interaction::Clicked(_) = new_interaction {
+            ui.focus(ui_id);
+        }
+        let is_focused = self.enabled && ui.is_focused(ui_id);
+        let maybe_key_selection = if is_focused && len > 0 {
+            get_key_selection(ui.get_focused_keys(ui_id), len, selected)
+        } else {
+            selected
+        };
+
+        // A selection handed back by an undo or redo.
+        let maybe_undone = ui.take_undone_state::<Option<Idx>>(ui_id, "RadioGroup")
+            .and_then(|undone| undone)
+            .and_then(|idx| if idx < len && Some(idx) != selected { Some(idx) } else { None });
+
+        // Select the option upon which the mouse was released, or the option chosen via the
+        // keyboard or an undo or redo.
+        let maybe_chosen = match (state.interaction, new_interaction) {
+            _ if maybe_undone.is_some() => maybe_undone,
+            (Interaction::Clicked(idx), Interaction::Highlighted(over)) if idx == over => Some(idx),
+            _ if maybe_key_selection != selected => maybe_key_selection,
+            _ => None,
+        };
+        let new_selected = match maybe_chosen {
+            Some(idx) if Some(idx) != selected => {
+                if maybe_undone.is_none() {
+                    ui.push_change(ui_id, "RadioGroup", &selected, &Some(idx));
+                }
+                if let Some(ref mut react) = self.maybe_react { react(idx) }
+                Some(idx)
+            },
+            _ => selected,
+        };
+
+        // A function for constructing a new RadioGroup State.
+        let new_state = || {
+            State {
+                options: self.options.iter().map(|option| option.to_string()).collect(),
+                maybe_selected: new_selected,
+                orientation: self.orientation,
+                interaction: new_interaction,
+                is_focused: is_focused,
+                enabled: self.enabled,
+            }
+        };
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.interaction != new_interaction
+            || state.maybe_selected != new_selected
+            || state.orientation != self.orientation
+            || state.is_focused != is_focused
+            || state.enabled != self.enabled
+            || state.options.len() != len
+            || state.options.iter().zip(self.options.iter()).any(|(a, b)| &a[..] != *b);
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed { Some(new_state()) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given RadioGroup State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{circle, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let len = state.options.len();
+        let color = enable::color(style.color(&ui.theme), state.enabled, &ui.theme);
+        let ring_width = style.ring_width(&ui.theme);
+        let ring_color = style.ring_color(&ui.theme);
+        let ring_color = if state.is_focused { ring_color.highlighted() } else { ring_color };
+        let ring_color = enable::color(ring_color, state.enabled, &ui.theme);
+        let fill_color = enable::color(ui.theme.shape_color, state.enabled, &ui.theme);
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+
+        // Each option is a ringed circle, filled with a dot when selected, followed by its label.
+        let mut forms = Vec::with_capacity(len * 4);
+        for (idx, option) in state.options.iter().enumerate() {
+            let (offset, option_dim) = option_rect(state.orientation, dim, len, idx);
+            let radius = (option_dim[0].min(option_dim[1]) * 0.3).min(font_size as f64 * 0.6);
+            let spacing = radius * 0.75;
+            let circle_x = offset[0] - option_dim[0] / 2.0 + spacing + radius;
+            let circle_xy = [circle_x, offset[1]];
+            let fill_color = state.interaction.color(idx, fill_color);
+            forms.push(circle(radius).filled(ring_color).shift(circle_xy[0], circle_xy[1]));
+            forms.push(circle((radius - ring_width).max(0.0)).filled(fill_color)
+                .shift(circle_xy[0], circle_xy[1]));
+            if state.maybe_selected == Some(idx) {
+                forms.push(circle(radius * 0.5).filled(color).shift(circle_xy[0], circle_xy[1]));
+            }
+            let label_w = label::width(ui, font_size, option);
+            let label_x = circle_x + radius + spacing + label_w / 2.0;
+            forms.push(text(Text::from_string(option.clone())
+                                .color(label_color)
+                                .height(font_size as f64))
+                .shift(label_x.floor(), offset[1].floor()));
+        }
+
+        // Shift the Forms into position and collect them for drawing.
+        let forms = forms.into_iter().map(|form| form.shift(xy[0].floor(), xy[1].floor()));
+        forms.collect()
+    }
+
+    /// Describe the RadioGroup for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let accessibility = Accessibility::new(Role::RadioGroup);
+        match state.maybe_selected.and_then(|idx| state.options.get(idx)) {
+            Some(option) => accessibility.value(option),
+            None => accessibility,
+        }
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_ring_width: None,
+            maybe_ring_color: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color of the dot marking the selected option.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_radio_group.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.palette.primary)
+            })).unwrap_or(theme.palette.primary)
+    }
+
+    /// Get the width of the ring around each option.
+    pub fn ring_width(&self, theme: &Theme) -> f64 {
+        self.maybe_ring_width.or(theme.maybe_radio_group.as_ref().map(|style| {
+            style.maybe_ring_width.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the Color of the ring around each option.
+    pub fn ring_color(&self, theme: &Theme) -> Color {
+        self.maybe_ring_color.or(theme.maybe_radio_group.as_ref().map(|style| {
+            style.maybe_ring_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_radio_group.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_radio_group.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_medium)
+        })).unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the Dimensions for an Element with the given number of options. By default, each
+    /// option is given a fixed size along the direction in which the options are laid out.
+    pub fn dim(&self, theme: &Theme, orientation: Orientation, len: usize) -> Dimensions {
+        const DEFAULT_OPTION_DIM: Dimensions = [120.0, 28.0];
+        let default_dim = match orientation {
+            Orientation::Horizontal =>
+                [DEFAULT_OPTION_DIM[0] * len as f64, DEFAULT_OPTION_DIM[1]],
+            Orientation::Vertical =>
+                [DEFAULT_OPTION_DIM[0], DEFAULT_OPTION_DIM[1] * len as f64],
+        };
+        let width = self.maybe_width.or(theme.maybe_radio_group.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(default_dim[0])
+        })).unwrap_or(default_dim[0]);
+        let height = self.maybe_height.or(theme.maybe_radio_group.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(default_dim[1])
+        })).unwrap_or(default_dim[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_radio_group.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for RadioGroup<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for RadioGroup<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for RadioGroup<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for RadioGroup<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> position::Positionable for RadioGroup<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        RadioGroup { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        RadioGroup { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> position::Sizeable for RadioGroup<'a, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
@@ -0,0 +1,537 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use elmesque::form::Form;
+use font::{FontId, Fontable};
+use graphics::character::CharacterCache;
+use graphics::math::Scalar;
+use label::{self, FontSize};
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               VerticalAlign};
+use theme::Theme;
+use ui::{Ui, UiId};
+use widget::{self, Widget};
+
+
+/// Displays a sequence of differently styled `Span`s as a single flowing paragraph. Text is
+/// wrapped onto a new line whenever a word would exceed the RichText's wrap width.
+#[derive(Clone, Debug)]
+pub struct RichText<'a> {
+    spans: Vec<Span<'a>>,
+    wrap_width: Scalar,
+    pos: Position,
+    margin: Margin,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    style: Style,
+}
+
+/// A run of text sharing a single style within a RichText paragraph.
+#[derive(Clone, Debug)]
+pub struct Span<'a> {
+    text: &'a str,
+    style: SpanStyle,
+}
+
+/// Styling unique to a single Span. Any field left as `None` falls back to the RichText's `Style`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanStyle {
+    pub maybe_color: Option<Color>,
+    pub maybe_font_size: Option<FontSize>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub monospace: bool,
+}
+
+/// The styling for a RichText's renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_font_size: Option<FontSize>,
+    pub maybe_line_spacing: Option<Scalar>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The state to be stored between updates for the RichText.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    lines: Vec<Line>,
+}
+
+/// A single laid out line of the paragraph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub height: Scalar,
+    pub runs: Vec<Run>,
+}
+
+/// A piece of text on a line that shares the same resolved style.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub text: String,
+    /// The distance from the left edge of the paragraph to the start of the run.
+    pub x: Scalar,
+    pub w: Scalar,
+    pub style: RunStyle,
+}
+
+/// A fully resolved style for a Run of text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunStyle {
+    pub color: Color,
+    pub font_size: FontSize,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub monospace: bool,
+    /// A color to fill the Run's bounding rectangle with before drawing the text.
+    pub maybe_background: Option<Color>,
+    /// The index of the link that the Run belongs to (if any).
+    pub maybe_link: Option<usize>,
+}
+
+/// A widget's label made up of differently styled Spans, as set via `Labelable::label_spans`.
+/// Unlike a Span, the SpannedLabel owns its text so that it may be stored within widget state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpannedLabel {
+    spans: Vec<(String, SpanStyle)>,
+}
+
+/// Some text with a fully resolved style, ready to be laid out into Lines.
+pub struct Piece<'a> {
+    pub text: &'a str,
+    pub style: RunStyle,
+    /// The distance from the left edge at which lines starting within this piece should begin.
+    pub indent: Scalar,
+}
+
+
+impl<'a> Span<'a> {
+
+    /// Construct a new Span of text.
+    pub fn new(text: &'a str) -> Span<'a> {
+        Span {
+            text: text,
+            style: SpanStyle {
+                maybe_color: None,
+                maybe_font_size: None,
+                bold: false,
+                italic: false,
+                underline: false,
+                monospace: false,
+            },
+        }
+    }
+
+    /// Set the font size for the span.
+    #[inline]
+    pub fn font_size(mut self, size: FontSize) -> Span<'a> {
+        self.style.maybe_font_size = Some(size);
+        self
+    }
+
+    /// Render the span in bold.
+    #[inline]
+    pub fn bold(mut self) -> Span<'a> {
+        self.style.bold = true;
+        self
+    }
+
+    /// Render the span in italics.
+    #[inline]
+    pub fn italic(mut self) -> Span<'a> {
+        self.style.italic = true;
+        self
+    }
+
+    /// Draw a line beneath the span.
+    #[inline]
+    pub fn underline(mut self) -> Span<'a> {
+        self.style.underline = true;
+        self
+    }
+
+    /// Render the span using a monospace typeface.
+    #[inline]
+    pub fn monospace(mut self) -> Span<'a> {
+        self.style.monospace = true;
+        self
+    }
+
+}
+
+impl SpanStyle {
+    /// Resolve the style of the span using the paragraph's `Style` and the `Theme`.
+    fn resolve(&self, style: &Style, theme: &Theme) -> RunStyle {
+        self.resolve_with(style.color(theme), style.font_size(theme))
+    }
+    /// Resolve the style of the span using the given default color and font size.
+    fn resolve_with(&self, color: Color, font_size: FontSize) -> RunStyle {
+        RunStyle {
+            color: self.maybe_color.unwrap_or(color),
+            font_size: self.maybe_font_size.unwrap_or(font_size),
+            bold: self.bold,
+            italic: self.italic,
+            underline: self.underline,
+            monospace: self.monospace,
+            maybe_background: None,
+            maybe_link: None,
+        }
+    }
+}
+
+impl SpannedLabel {
+
+    /// Construct a SpannedLabel from the given Spans.
+    pub fn new(spans: &[Span]) -> SpannedLabel {
+        SpannedLabel {
+            spans: spans.iter().map(|span| (span.text.to_string(), span.style.clone())).collect(),
+        }
+    }
+
+    /// Append a Span to the end of the label.
+    pub fn push(&mut self, span: Span) {
+        self.spans.push((span.text.to_string(), span.style));
+    }
+
+    /// The text of the label without any styling.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|&(ref text, _)| &text[..]).collect::<Vec<_>>().concat()
+    }
+
+    /// Lay out the label, using the given color and font size for any Span that doesn't specify
+    /// its own. Returns the dimensions of the label along with its Forms centred upon the origin.
+    pub fn forms<C: CharacterCache>(&self,
+                                    ui: &mut Ui<C>,
+                                    color: Color,
+                                    font_size: FontSize) -> (Dimensions, Vec<Form>) {
+        let pieces: Vec<Piece> = self.spans.iter().map(|&(ref text, ref style)| {
+            Piece { text: &text[..], style: style.resolve_with(color, font_size), indent: 0.0 }
+        }).collect();
+        let lines = layout(ui, &pieces, font_size, ::std::f64::MAX);
+        let w = lines.iter().fold(0.0, |max_w, line| {
+            let line_w = line.runs.last().map(|run| run.x + run.w).unwrap_or(0.0);
+            if line_w > max_w { line_w } else { max_w }
+        });
+        let h = height(&lines, 0.0);
+        ([w, h], forms(&lines, -w / 2.0, h / 2.0, 0.0, None))
+    }
+
+}
+
+impl Line {
+
+    /// Construct an empty Line.
+    pub fn new() -> Line {
+        Line { height: 0.0, runs: Vec::new() }
+    }
+
+    /// Push a word onto the end of the line, merging it with the last Run if the styles match.
+    fn push(&mut self, text: &str, x: Scalar, w: Scalar, style: &RunStyle) {
+        let font_h = style.font_size as Scalar;
+        if font_h > self.height { self.height = font_h; }
+        let matches_last = self.runs.last().map(|run| run.style == *style).unwrap_or(false);
+        if matches_last {
+            let run = self.runs.last_mut().unwrap();
+            run.text.push_str(text);
+            run.w += w;
+        } else {
+            self.runs.push(Run { text: text.to_string(), x: x, w: w, style: style.clone() });
+        }
+    }
+
+}
+
+
+/// Break the given Pieces into Lines of styled Runs that fit within `max_w`. Lines without any
+/// text will take up the height of the given default font size.
+pub fn layout<C: CharacterCache>(ui: &mut Ui<C>,
+                                 pieces: &[Piece],
+                                 default_font_size: FontSize,
+                                 max_w: Scalar) -> Vec<Line> {
+    let mut lines = vec![Line::new()];
+    let mut x = pieces.first().map(|piece| piece.indent).unwrap_or(0.0);
+    for piece in pieces.iter() {
+        for (i, paragraph) in piece.text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Line::new());
+                x = piece.indent;
+            }
+            for word in label::words(paragraph).into_iter() {
+                let w = label::width(ui, piece.style.font_size, word);
+                if x > piece.indent && x + w > max_w {
+                    lines.push(Line::new());
+                    x = piece.indent;
+                }
+                lines.last_mut().unwrap().push(word, x, w, &piece.style);
+                x += w;
+            }
+        }
+    }
+
+    let default_h = default_font_size as Scalar;
+    for line in lines.iter_mut() {
+        if line.height == 0.0 { line.height = default_h; }
+    }
+    lines
+}
+
+/// The total height of the given Lines with `spacing` between each.
+pub fn height(lines: &[Line], spacing: Scalar) -> Scalar {
+    let num_gaps = if lines.len() > 0 { lines.len() - 1 } else { 0 };
+    lines.iter().fold(0.0, |h, line| h + line.height) + spacing * num_gaps as Scalar
+}
+
+/// The text of the given lines without any styling, with each line separated by a newline.
+pub fn plain_text(lines: &[Line]) -> String {
+    let lines: Vec<String> = lines.iter().map(|line| {
+        line.runs.iter().map(|run| &run.text[..]).collect::<Vec<_>>().concat()
+    }).collect();
+    lines.join("\n")
+}
+
+/// Find the Run beneath the given point, where the point is relative to the top left corner of
+/// the laid out Lines.
+pub fn run_at(lines: &[Line], spacing: Scalar, xy: Point) -> Option<&Run> {
+    let mut top = 0.0;
+    for line in lines.iter() {
+        let bottom = top - line.height;
+        if xy[1] <= top && xy[1] > bottom {
+            return line.runs.iter().find(|run| xy[0] >= run.x && xy[0] < run.x + run.w);
+        }
+        top = bottom - spacing;
+    }
+    None
+}
+
+/// Construct the Forms for the given Lines, where `left` and `top` describe the position of the
+/// top left corner of the paragraph. Runs belonging to the `maybe_highlighted_link` are drawn
+/// with a highlighted color.
+pub fn forms(lines: &[Line],
+             left: Scalar,
+             top: Scalar,
+             spacing: Scalar,
+             maybe_highlighted_link: Option<usize>) -> Vec<Form> {
+    use elmesque::form::{line, rect, solid, text};
+    use elmesque::text::Text;
+    let mut top = top;
+    let mut forms = Vec::new();
+    for text_line in lines.iter() {
+        // Runs of differing font sizes share a common baseline at the bottom of the line.
+        let bottom = top - text_line.height;
+        for run in text_line.runs.iter() {
+            let size = run.style.font_size as f64;
+            let x = left + run.x + run.w / 2.0;
+            let y = bottom + size / 2.0;
+            let color = match (run.style.maybe_link, maybe_highlighted_link) {
+                (Some(link), Some(highlighted)) if link == highlighted => {
+                    run.style.color.highlighted()
+                },
+                _ => run.style.color,
+            };
+            if let Some(bg_color) = run.style.maybe_background {
+                forms.push(rect(run.w, size).filled(bg_color).shift(x.floor(), y.floor()));
+            }
+            let mut run_text = Text::from_string(run.text.clone()).color(color).height(size);
+            if run.style.bold { run_text = run_text.bold(); }
+            if run.style.italic { run_text = run_text.italic(); }
+            if run.style.monospace { run_text = run_text.monospace(); }
+            forms.push(text(run_text).shift(x.floor(), y.floor()));
+            if run.style.underline {
+                let (start_x, end_x) = (left + run.x, left + run.x + run.w);
+                forms.push(line(solid(color), start_x, bottom, end_x, bottom));
+            }
+        }
+        top = bottom - spacing;
+    }
+    forms
+}
+
+
+impl<'a> RichText<'a> {
+
+    /// Construct a new, empty RichText widget.
+    pub fn new() -> RichText<'a> {
+        RichText {
+            spans: Vec::new(),
+            wrap_width: 256.0,
+            pos: Position::default(),
+            margin: Margin::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            style: Style::new(),
+        }
+    }
+
+    /// Append a Span to the end of the paragraph.
+    #[inline]
+    pub fn span(mut self, span: Span<'a>) -> RichText<'a> {
+        self.spans.push(span);
+        self
+    }
+
+    /// Append a plain Span of text using the paragraph's default style.
+    #[inline]
+    pub fn text(self, text: &'a str) -> RichText<'a> {
+        self.span(Span::new(text))
+    }
+
+    /// Set the width at which lines will be wrapped.
+    #[inline]
+    pub fn wrap_width(mut self, width: Scalar) -> RichText<'a> {
+        self.wrap_width = width;
+        self
+    }
+
+    /// Set the default font size for spans that don't specify their own.
+    #[inline]
+    pub fn font_size(mut self, size: FontSize) -> RichText<'a> {
+        self.style.maybe_font_size = Some(size);
+        self
+    }
+
+    /// Set the spacing between each line in pixels.
+    #[inline]
+    pub fn line_spacing(mut self, spacing: Scalar) -> RichText<'a> {
+        self.style.maybe_line_spacing = Some(spacing);
+        self
+    }
+
+}
+
+
+impl<'a> Widget for RichText<'a> {
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "RichText" }
+    fn init_state(&self) -> State { State { lines: Vec::new() } }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the RichText.
+    fn update<C>(self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 _ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let pieces: Vec<Piece> = {
+            let theme = &ui.theme;
+            self.spans.iter().map(|span| {
+                Piece { text: span.text, style: span.style.resolve(style, theme), indent: 0.0 }
+            }).collect()
+        };
+        let default_font_size = style.font_size(&ui.theme);
+        let lines = layout(ui, &pieces, default_font_size, self.wrap_width);
+        let spacing = style.line_spacing(&ui.theme);
+        let dim = [self.wrap_width, height(&lines, spacing)];
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let padding = Padding::none();
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, padding, h_align, v_align);
+        let maybe_new_state = if state.lines != lines { Some(State { lines: lines }) }
+                              else { None };
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the RichText.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let spacing = style.line_spacing(&ui.theme);
+        let (left, top) = (xy[0] - dim[0] / 2.0, xy[1] + dim[1] / 2.0);
+        forms(&state.lines, left, top, spacing, None)
+    }
+
+    /// Describe the RichText for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::Text).label(Some(&plain_text(&state.lines)[..]))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_font_size: None,
+            maybe_line_spacing: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the default text Color for each Span.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.unwrap_or(theme.label_color)
+    }
+
+    /// Get the default font size for each Span.
+    pub fn font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_font_size.unwrap_or(theme.font_size_medium)
+    }
+
+    /// Get the spacing between each line.
+    pub fn line_spacing(&self, _theme: &Theme) -> Scalar {
+        const DEFAULT_LINE_SPACING: Scalar = 4.0;
+        self.maybe_line_spacing.unwrap_or(DEFAULT_LINE_SPACING)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a> Colorable for Span<'a> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a> Colorable for RichText<'a> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a> Fontable for RichText<'a> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a> Positionable for RichText<'a> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        RichText { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        RichText { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
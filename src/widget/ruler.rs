@@ -0,0 +1,485 @@
+
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable, rgb};
+use elmesque::form::Form;
+use enable::Enableable;
+use font::{FontId, Fontable};
+use graphics::character::CharacterCache;
+use label::{self, FontSize};
+use palette::{ColorRole, Paletted};
+use position::{Depth, Dimensions, Point};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::is_over_rect;
+use widget::{self, Widget};
+use widget::editor_canvas::{self, Transform};
+
+
+/// The distance in pixels from a guide within which it may be grabbed by the mouse.
+const GUIDE_GRAB_DISTANCE: f64 = 4.0;
+
+/// A ruler along the top (`Horizontal`) or left (`Vertical`) edge of an EditorCanvas, tied to the
+/// canvas' `Transform` so that its ticks and labels follow the canvas as it is panned and zoomed.
+/// The position of the mouse over the canvas is indicated upon the ruler.
+///
+/// Dragging from the ruler onto the canvas creates a guide line, guides may be dragged to move
+/// them, and dragging a guide back onto the ruler (or off the canvas) removes it. The guides are
+/// given in world coordinates and are owned by the application, which may snap content to them
+/// via `snap`.
+///
+/// The Ruler should be set after the EditorCanvas and its content so that it is drawn above them.
+/// The EditorCanvas' own rulers may be hidden via `EditorCanvas::ruler_width(0.0)`.
+pub struct Ruler<'a, F> {
+    orientation: Orientation,
+    transform: Transform,
+    guides: &'a mut Vec<f64>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// The edge of the EditorCanvas along which the Ruler lies.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    /// Along the top edge, measuring the world's x axis. Its guides are vertical lines.
+    Horizontal,
+    /// Along the left edge, measuring the world's y axis. Its guides are horizontal lines.
+    Vertical,
+}
+
+/// Styling for the Ruler, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_thickness: Option<f64>,
+    pub maybe_grid_spacing: Option<f64>,
+    pub maybe_guide_color: Option<Color>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<FontSize>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The state of the Ruler.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    orientation: Orientation,
+    transform: Transform,
+    guides: Vec<f64>,
+    /// The position of the mouse along the Ruler's axis in the world, while it is over the canvas.
+    maybe_cursor: Option<f64>,
+    drag: Drag,
+}
+
+/// The dragging of guides by the mouse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Drag {
+    /// The left mouse button is up.
+    Idle,
+    /// The left mouse button was pressed away from the Ruler and its guides and is still held.
+    Blocked,
+    /// The guide at the given index is being dragged.
+    Guide(usize),
+}
+
+
+/// The guide nearest to the given value if it lies within the given distance, otherwise the value
+/// itself. Distances are given in world units, so a distance in pixels should first be converted
+/// via `Transform::len_to_world`.
+pub fn snap(value: f64, guides: &[f64], distance: f64) -> f64 {
+    let mut snapped = value;
+    let mut nearest = distance;
+    for &guide in guides.iter() {
+        let d = (guide - value).abs();
+        if d <= nearest {
+            snapped = guide;
+            nearest = d;
+        }
+    }
+    snapped
+}
+
+impl Orientation {
+
+    /// The component of the given point along the Ruler's axis.
+    fn along(&self, xy: Point) -> f64 {
+        match *self {
+            Orientation::Horizontal => xy[0],
+            Orientation::Vertical => xy[1],
+        }
+    }
+
+}
+
+/// The position and dimensions of the Ruler along the edge of the canvas.
+fn rect(orientation: Orientation, transform: &Transform, thickness: f64) -> (Point, Dimensions) {
+    let Transform { xy, dim, .. } = *transform;
+    match orientation {
+        Orientation::Horizontal =>
+            ([xy[0], xy[1] + dim[1] / 2.0 - thickness / 2.0], [dim[0], thickness]),
+        Orientation::Vertical =>
+            ([xy[0] - dim[0] / 2.0 + thickness / 2.0, xy[1]], [thickness, dim[1]]),
+    }
+}
+
+
+impl<'a, F> Ruler<'a, F> {
+
+    /// Construct a Ruler along the given edge of the EditorCanvas with the given Transform,
+    /// editing the given guides.
+    pub fn new(orientation: Orientation,
+               transform: Transform,
+               guides: &'a mut Vec<f64>) -> Ruler<'a, F> {
+        Ruler {
+            orientation: orientation,
+            transform: transform,
+            guides: guides,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the reaction for the Ruler. It will be triggered with the guides whenever a guide is
+    /// created, moved or removed.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the thickness of the Ruler.
+    pub fn thickness(mut self, thickness: f64) -> Self {
+        self.style.maybe_thickness = Some(thickness);
+        self
+    }
+
+    /// Set the minimum spacing between ticks in pixels.
+    pub fn grid_spacing(mut self, spacing: f64) -> Self {
+        self.style.maybe_grid_spacing = Some(spacing);
+        self
+    }
+
+    /// Set the color of the guide lines.
+    pub fn guide_color(mut self, color: Color) -> Self {
+        self.style.maybe_guide_color = Some(color);
+        self
+    }
+
+    /// Set the color of the ticks and labels.
+    pub fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    /// Set the font size of the labels.
+    pub fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+
+    /// Set the rendering depth of the Ruler.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl<'a, F> Widget for Ruler<'a, F>
+    where
+        F: FnMut(&mut Vec<f64>),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Ruler" }
+    fn init_state(&self) -> State {
+        State {
+            orientation: self.orientation,
+            transform: self.transform,
+            guides: Vec::new(),
+            maybe_cursor: None,
+            drag: Drag::Idle,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the Ruler.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::{Down, Up};
+
+        let widget::State { ref state, .. } = *prev_state;
+        let orientation = self.orientation;
+        let transform = self.transform;
+        let (xy, dim) = rect(orientation, &transform, style.thickness(&ui.theme));
+        let mouse = ui.get_mouse_state(ui_id);
+        let is_over_ruler = is_over_rect(xy, mouse.xy, dim);
+        let is_over_canvas = transform.contains(mouse.xy);
+        let mouse_along = orientation.along(transform.to_world(mouse.xy));
+        let guides_before = self.guides.clone();
+
+        // Find the guide beneath the mouse, if there is one.
+        let maybe_hovered = if is_over_canvas && !is_over_ruler {
+            let mouse_ui = orientation.along(mouse.xy);
+            self.guides.iter().position(|&guide| {
+                let guide_ui = orientation.along(transform.to_ui([guide, guide]));
+                (guide_ui - mouse_ui).abs() <= GUIDE_GRAB_DISTANCE
+            })
+        } else {
+            None
+        };
+
+        let drag = match (self.enabled, state.drag, mouse.left.state) {
+            (false, _, _) => Drag::Idle,
+            (true, Drag::Idle, Down) => if is_over_ruler {
+                self.guides.push(mouse_along);
+                Drag::Guide(self.guides.len() - 1)
+            } else {
+                maybe_hovered.map(|idx| Drag::Guide(idx)).unwrap_or(Drag::Blocked)
+            },
+            (true, Drag::Guide(idx), Down) => {
+                if idx < self.guides.len() { self.guides[idx] = mouse_along }
+                Drag::Guide(idx)
+            },
+            (true, Drag::Guide(idx), Up) => {
+                // Guides dropped back onto the ruler or off the canvas are removed.
+                if (is_over_ruler || !is_over_canvas) && idx < self.guides.len() {
+                    self.guides.remove(idx);
+                }
+                Drag::Idle
+            },
+            (true, Drag::Blocked, Down) => Drag::Blocked,
+            (true, _, Up) => Drag::Idle,
+        };
+        let was_dragging = if let Drag::Guide(_) = state.drag { true } else { false };
+        let is_dragging = if let Drag::Guide(_) = drag { true } else { false };
+        ui.mouse_dragged_by(ui_id, was_dragging, is_dragging);
+
+        // React with the new guides.
+        if guides_before != *self.guides {
+            let Ruler { ref mut maybe_react, ref mut guides, .. } = self;
+            if let Some(ref mut react) = *maybe_react {
+                react(*guides);
+            }
+        }
+
+        let new_state = State {
+            orientation: orientation,
+            transform: transform,
+            guides: self.guides.clone(),
+            maybe_cursor: if is_over_canvas { Some(mouse_along) } else { None },
+            drag: drag,
+        };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given Ruler State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{line, rect, solid, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let orientation = state.orientation;
+        let transform = state.transform;
+
+        // Retrieve the styling for the Element.
+        let color = style.color(&ui.theme);
+        let thickness = style.thickness(&ui.theme);
+        let spacing = style.grid_spacing(&ui.theme);
+        let guide_color = style.guide_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+
+        // Convert a distance along the Ruler's axis from its centre and a distance across it from
+        // its inner edge (the edge facing the canvas' content) into a point relative to its centre.
+        let inner_edge = -thickness / 2.0;
+        let point = |along: f64, across: f64| -> (f64, f64) {
+            match orientation {
+                Orientation::Horizontal => (along, inner_edge + across),
+                Orientation::Vertical => (-(inner_edge + across), along),
+            }
+        };
+        let segment = |along: f64, from: f64, to: f64| -> Form {
+            let (x1, y1) = point(along, from);
+            let (x2, y2) = point(along, to);
+            line(solid(label_color), x1, y1, x2, y2)
+        };
+
+        let mut forms = vec![rect(dim[0], dim[1]).filled(color)];
+
+        // Construct the tick and label Forms.
+        let zoom = transform.view.zoom;
+        let centre = orientation.along(transform.view.centre);
+        let half_len = orientation.along(dim) / 2.0;
+        let step = editor_canvas::nice_step(spacing.max(1.0) / zoom);
+        let major = editor_canvas::nice_step(step * 4.5);
+        let label_h = font_size as f64;
+        let ticks = editor_canvas::grid_lines(centre, zoom, half_len, step, major);
+        for (along, value, is_major) in ticks {
+            let along = along.floor();
+            let tick_len = if is_major { thickness / 2.0 } else { thickness / 4.0 };
+            forms.push(segment(along, 0.0, tick_len));
+            if is_major {
+                let string = editor_canvas::ruler_label(value, step);
+                let w = label::width(ui, font_size, &string);
+                if along + 2.0 + w > half_len { continue }
+                let label = Text::from_string(string).color(label_color).height(label_h);
+                let (x, y) = point(along + 2.0 + w / 2.0, thickness * 3.0 / 4.0);
+                let form = match orientation {
+                    Orientation::Horizontal => text(label),
+                    Orientation::Vertical => text(label).rotate(::std::f64::consts::PI / 2.0),
+                };
+                forms.push(form.shift(x, y));
+            }
+        }
+
+        // Indicate the position of the mouse upon the Ruler.
+        if let Some(cursor) = state.maybe_cursor {
+            let along = ((cursor - centre) * zoom).floor();
+            forms.push(segment(along, 0.0, thickness));
+        }
+
+        // Construct the guide line Forms across the canvas.
+        let canvas_across = match orientation {
+            Orientation::Horizontal => transform.dim[1],
+            Orientation::Vertical => transform.dim[0],
+        };
+        for (idx, &guide) in state.guides.iter().enumerate() {
+            let along = ((guide - centre) * zoom).floor();
+            if along.abs() > half_len { continue }
+            let width = if state.drag == Drag::Guide(idx) { 2.0 } else { 1.0 };
+            let (x1, y1) = point(along, thickness);
+            let (x2, y2) = point(along, thickness - canvas_across);
+            forms.push(line(solid(guide_color).width(width), x1, y1, x2, y2));
+        }
+
+        // Shift the Forms into position and collect them for drawing.
+        forms.into_iter().map(|form| form.shift(xy[0], xy[1])).collect()
+    }
+
+    /// Describe the Ruler for assistive technologies, with the guides as its value.
+    fn accessibility(state: &State) -> Accessibility {
+        let guides: Vec<String> = state.guides.iter().map(|guide| guide.to_string()).collect();
+        Accessibility::new(Role::Ruler).value(guides.join(", "))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_thickness: None,
+            maybe_grid_spacing: None,
+            maybe_guide_color: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_ruler.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the thickness of the Ruler.
+    pub fn thickness(&self, theme: &Theme) -> f64 {
+        const DEFAULT_THICKNESS: f64 = 20.0;
+        self.maybe_thickness.or(theme.maybe_ruler.as_ref().map(|style| {
+            style.maybe_thickness.unwrap_or(DEFAULT_THICKNESS)
+        })).unwrap_or(DEFAULT_THICKNESS)
+    }
+
+    /// Get the minimum spacing between ticks in pixels.
+    pub fn grid_spacing(&self, theme: &Theme) -> f64 {
+        const DEFAULT_SPACING: f64 = 16.0;
+        self.maybe_grid_spacing.or(theme.maybe_ruler.as_ref().map(|style| {
+            style.maybe_grid_spacing.unwrap_or(DEFAULT_SPACING)
+        })).unwrap_or(DEFAULT_SPACING)
+    }
+
+    /// Get the Color of the guide lines.
+    pub fn guide_color(&self, theme: &Theme) -> Color {
+        let default = rgb(0.2, 0.6, 1.0);
+        self.maybe_guide_color.or(theme.maybe_ruler.as_ref().map(|style| {
+            style.maybe_guide_color.unwrap_or(default)
+        })).unwrap_or(default)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_ruler.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_ruler.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_ruler.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for Ruler<'a, F> {
+    /// If true, guides may be created, moved and removed with the mouse. If false, the guides are
+    /// only displayed.
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for Ruler<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for Ruler<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for Ruler<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
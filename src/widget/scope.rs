@@ -0,0 +1,538 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use elmesque::form::Form;
+use font::{FontId, Fontable};
+use frame::{Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{self, FontSize, Labelable};
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Position,
+               VerticalAlign};
+use std::cell::RefCell;
+use std::mem;
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
+
+
+/// Displays the most recent samples of a value over time as a rolling waveform, i.e. for
+/// monitoring the frame rate or an audio level.
+///
+/// The Scope owns a ring buffer of the given capacity within its state, to which the samples
+/// given via `push` (or `extend`) are appended each time it is set. Once the buffer is full, each
+/// new sample replaces the oldest, so the buffer is never reallocated unless its capacity changes.
+///
+/// ```ignore
+/// Scope::new(120)
+///     .push(frames_per_second)
+///     .label("FPS")
+///     .set(FPS_SCOPE, ui);
+/// ```
+pub struct Scope<'a> {
+    capacity: usize,
+    maybe_sample: Option<f64>,
+    samples: &'a [f64],
+    maybe_range: Option<(f64, f64)>,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
+    style: Style,
+}
+
+/// Styling for the Scope, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<FontSize>,
+    pub maybe_line_width: Option<f64>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The state of the Scope.
+#[derive(Debug, PartialEq)]
+pub struct State {
+    /// The samples, which are moved from the previous state into the next rather than copied.
+    buffer: RefCell<RingBuffer>,
+    maybe_range: Option<(f64, f64)>,
+    maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+}
+
+/// A fixed-capacity buffer of samples in which each new sample replaces the oldest once full.
+#[derive(Debug, PartialEq)]
+struct RingBuffer {
+    samples: Vec<f64>,
+    capacity: usize,
+    /// The index of the oldest sample once the buffer is full.
+    start: usize,
+}
+
+
+impl RingBuffer {
+
+    /// An empty buffer with room for the given number of samples.
+    fn new(capacity: usize) -> RingBuffer {
+        RingBuffer { samples: Vec::with_capacity(capacity), capacity: capacity, start: 0 }
+    }
+
+    /// Append a sample, replacing the oldest if the buffer is full.
+    fn push(&mut self, sample: f64) {
+        if self.samples.len() < self.capacity {
+            self.samples.push(sample);
+        } else if self.capacity > 0 {
+            self.samples[self.start] = sample;
+            self.start = (self.start + 1) % self.capacity;
+        }
+    }
+
+    /// The samples from the oldest to the most recent.
+    fn iter<'a>(&'a self) -> ::std::iter::Chain<::std::slice::Iter<'a, f64>,
+                                                ::std::slice::Iter<'a, f64>> {
+        self.samples[self.start..].iter().chain(self.samples[..self.start].iter())
+    }
+
+    /// The most recent sample, if there is one.
+    fn latest(&self) -> Option<f64> {
+        match self.start {
+            0 => self.samples.last().cloned(),
+            start => Some(self.samples[start - 1]),
+        }
+    }
+
+    /// Change the capacity of the buffer, keeping as many of the most recent samples as fit.
+    fn set_capacity(&mut self, capacity: usize) {
+        if capacity == self.capacity { return }
+        let skip = self.samples.len().saturating_sub(capacity);
+        let mut buffer = RingBuffer::new(capacity);
+        buffer.samples.extend(self.iter().skip(skip).cloned());
+        *self = buffer;
+    }
+
+}
+
+
+impl<'a> Scope<'a> {
+
+    /// Construct a new Scope displaying up to the given number of the most recent samples.
+    pub fn new(capacity: usize) -> Scope<'a> {
+        Scope {
+            capacity: capacity,
+            maybe_sample: None,
+            samples: &[],
+            maybe_range: None,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_label: None,
+            maybe_label_spans: None,
+            style: Style::new(),
+        }
+    }
+
+    /// Append a new sample, replacing the oldest once the Scope is full.
+    pub fn push(mut self, sample: f64) -> Self {
+        self.maybe_sample = Some(sample);
+        self
+    }
+
+    /// Append each of the given samples in order, i.e. a block of audio levels. These are
+    /// appended after any sample given via `push`.
+    pub fn extend(mut self, samples: &'a [f64]) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Use the given range for the values rather than fitting it to the buffered samples.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.maybe_range = Some((min, max));
+        self
+    }
+
+    /// Set the width of the waveform's line.
+    pub fn line_width(mut self, width: f64) -> Self {
+        self.style.maybe_line_width = Some(width);
+        self
+    }
+
+    /// Set the depth of the Scope.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+impl<'a> Widget for Scope<'a> {
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Scope" }
+    fn init_state(&self) -> State {
+        State {
+            buffer: RefCell::new(RingBuffer::new(self.capacity)),
+            maybe_range: None,
+            maybe_label: None,
+            maybe_label_spans: None,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the Scope's cached state.
+    fn update<C>(self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 _ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+
+        // Check whether or not the state has changed since the previous update.
+        let has_new_samples = self.maybe_sample.is_some() || !self.samples.is_empty();
+        let state_has_changed = has_new_samples
+            || state.buffer.borrow().capacity != self.capacity
+            || state.maybe_range != self.maybe_range
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
+
+        // Move the buffer into the new state, appending the new samples.
+        let maybe_new_state = if state_has_changed {
+            let mut buffer = mem::replace(&mut *state.buffer.borrow_mut(), RingBuffer::new(0));
+            buffer.set_capacity(self.capacity);
+            for &sample in self.maybe_sample.iter().chain(self.samples.iter()) {
+                buffer.push(sample);
+            }
+            Some(State {
+                buffer: RefCell::new(buffer),
+                maybe_range: self.maybe_range,
+                maybe_label: self.maybe_label.map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+            })
+        } else {
+            None
+        };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given Scope State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{line, rect, solid, text};
+        use elmesque::text::Text;
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+        let frame = style.frame(&ui.theme);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let (half_pad_w, half_pad_h) = (pad_dim[0] / 2.0, pad_dim[1] / 2.0);
+
+        // Construct the frame and inner rectangle Forms.
+        let color = style.color(&ui.theme);
+        let frame_form = rect(dim[0], dim[1]).filled(style.frame_color(&ui.theme));
+        let inner_form = rect(pad_dim[0], pad_dim[1]).filled(color);
+
+        // Construct the waveform's line Forms, with the most recent sample at the right edge.
+        let buffer = state.buffer.borrow();
+        let (min, max) = state.maybe_range.unwrap_or_else(|| {
+            buffer.iter().fold((::std::f64::MAX, ::std::f64::MIN), |(min, max), &sample| {
+                (min.min(sample), max.max(sample))
+            })
+        });
+        let (min, max) = if max - min > 0.0 { (min, max) } else { (min - 1.0, min + 1.0) };
+        let step = pad_dim[0] / (buffer.capacity.max(2) - 1) as f64;
+        let first_x = half_pad_w - (buffer.samples.len() as f64 - 1.0) * step;
+        let line_style = solid(color.plain_contrast()).width(style.line_width(&ui.theme));
+        let to_y = |sample: f64| {
+            let y = (sample - min) / (max - min) * pad_dim[1] - half_pad_h;
+            y.max(-half_pad_h).min(half_pad_h)
+        };
+        let mut maybe_prev = None;
+        let mut line_forms = Vec::with_capacity(buffer.samples.len());
+        for (i, &sample) in buffer.iter().enumerate() {
+            let point = (first_x + i as f64 * step, to_y(sample));
+            if let Some((x, y)) = maybe_prev {
+                line_forms.push(line(line_style.clone(), x, y, point.0, point.1));
+            }
+            maybe_prev = Some(point);
+        }
+
+        // Construct the label Forms in the top left corner.
+        let l_color = style.label_color(&ui.theme);
+        let l_size = style.label_font_size(&ui.theme);
+        let (label_dim, label_forms) = match (state.maybe_label_spans.as_ref(),
+                                              state.maybe_label.as_ref()) {
+            (Some(spans), _) => spans.forms(ui, l_color, l_size),
+            (None, Some(l_text)) => {
+                let w = label::width(ui, l_size, l_text);
+                let l_text = Text::from_string(l_text.clone()).color(l_color);
+                ([w, l_size as f64], vec![text(l_text.height(l_size as f64))])
+            },
+            (None, None) => ([0.0, 0.0], Vec::new()),
+        };
+        const LABEL_PAD: f64 = 4.0;
+        let label_x = (-half_pad_w + LABEL_PAD + label_dim[0] / 2.0).floor();
+        let label_y = (half_pad_h - LABEL_PAD - label_dim[1] / 2.0).floor();
+
+        // Chain the Forms and shift them into position.
+        let pad_forms = Some(inner_form).into_iter()
+            .chain(line_forms.into_iter())
+            .chain(label_forms.into_iter().map(|form| form.shift(label_x, label_y)))
+            .map(|form| form.shift(pad_offset[0], pad_offset[1]));
+        let form_chain = Some(frame_form).into_iter()
+            .chain(pad_forms)
+            .map(|form| form.shift(xy[0].round(), xy[1].round()));
+
+        // Turn the form for drawing.
+        form_chain.collect()
+    }
+
+    /// Describe the Scope for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        let accessibility = Accessibility::new(Role::Chart).label(maybe_label.map(|l| &l[..]));
+        match state.buffer.borrow().latest() {
+            Some(sample) => accessibility.value(sample),
+            None => accessibility,
+        }
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_line_width: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_scope.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_scope.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the width of the waveform's line.
+    pub fn line_width(&self, theme: &Theme) -> f64 {
+        const DEFAULT_LINE_WIDTH: f64 = 1.0;
+        self.maybe_line_width.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_line_width.unwrap_or(DEFAULT_LINE_WIDTH)
+        })).unwrap_or(DEFAULT_LINE_WIDTH)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [128.0, 48.0];
+        let width = self.maybe_width.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_scope.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+impl<'a> Colorable for Scope<'a> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a> Paletted for Scope<'a> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a> Fontable for Scope<'a> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a> Frameable for Scope<'a> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a> Labelable<'a> for Scope<'a> {
+    fn label(mut self, text: &'a str) -> Self {
+        self.maybe_label = Some(text);
+        self
+    }
+
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
+    fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+}
+
+impl<'a> position::Positionable for Scope<'a> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        Scope { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        Scope { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a> position::Sizeable for Scope<'a> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
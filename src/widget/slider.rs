@@ -1,55 +1,151 @@
 
-use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use accessibility::{Accessibility, Role};
+use color::{blue, orange, red, Color, Colorable};
+use corner::{CornerRadii, Roundable};
+use elmesque::form::Form;
+use enable::{self, Enableable, ReadOnly};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::character::CharacterCache;
+use gradient::{self, Gradient, Gradientable};
 use label::{FontSize, Labelable};
 use mouse::Mouse;
-use num::{Float, NumCast, ToPrimitive};
-use position::{self, Depth, Dimensions, HorizontalAlign, Position, VerticalAlign};
+use palette::{ColorRole, Paletted};
+use panel::{self, PanelId};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Place, Position,
+               VerticalAlign};
+use rate_limit::RateLimit;
+use std::any::Any;
+use std::fmt::Debug;
 use theme::Theme;
 use ui::{UiId, Ui};
-use utils::{clamp, percentage, value_from_perc};
+use unit::{self, Conversion, Unit};
+use utils::clamp;
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// Linear value selection. If the slider's width is greater than it's height, it will
 /// automatically become a horizontal slider, otherwise it will be a vertical slider. Its reaction
 /// is triggered if the value is updated or if the mouse button is released while the cursor is
 /// above the rectangle.
+///
+/// A secondary value, i.e. the target of some automation, may be displayed as a thin track along
+/// the Slider via `automation`.
+///
+/// Clicking the Slider gives it keyboard focus, after which PageUp and PageDown adjust its value
+/// by a tenth of its range (or a hundredth while Shift is held). Scrolling the mouse wheel over
+/// the Slider adjusts its value by a hundredth of its range per step.
 pub struct Slider<'a, T, F> {
     value: T,
     min: T,
     max: T,
     pos: Position,
+    margin: Margin,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
-    dim: Dimensions,
     depth: Depth,
     maybe_react: Option<F>,
+    maybe_rate_limit: Option<RateLimit>,
     maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
+    maybe_unit: Option<Unit>,
+    maybe_value_label: Option<Box<Fn(T) -> String + 'a>>,
+    maybe_conversion: Option<Conversion>,
+    maybe_quantity: Option<&'a str>,
+    maybe_panel: Option<PanelId>,
+    maybe_automation: Option<(T, Automation)>,
     style: Style,
     enabled: bool,
+    read_only: bool,
+    persistent: bool,
 }
 
 /// Styling for the Slider, necessary for constructing its renderable Element.
 #[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_corner_radii: Option<CornerRadii>,
+    pub maybe_gradient: Option<Gradient>,
     pub maybe_label_color: Option<Color>,
     pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_padding: Option<Padding>,
+    pub maybe_label_position: Option<LabelPosition>,
+    pub maybe_font: Option<FontId>,
+    pub maybe_automation_width: Option<f64>,
+    pub maybe_modulated_color: Option<Color>,
+    pub maybe_overridden_color: Option<Color>,
+    pub maybe_locked_color: Option<Color>,
 }
 
-/// Represents the state of the Slider widget.
+/// Represents the state of the Slider widget. The value and range are given in the display unit.
 #[derive(Clone, Debug, PartialEq)]
-pub struct State<T> {
-    value: T,
-    min: T,
-    max: T,
+pub struct State {
+    value: f64,
+    min: f64,
+    max: f64,
     maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+    /// The value as formatted for display, via either the value label or the unit.
+    maybe_value_text: Option<String>,
+    /// The secondary value in the display unit, along with the state of its automation.
+    maybe_automation: Option<(f64, Automation)>,
+    /// The value (in the canonical unit) at which the current drag began, so that the whole drag
+    /// may be undone as a single change.
+    maybe_drag_start: Option<f64>,
+    /// The value (in the canonical unit) produced by the user that is yet to be delivered to a
+    /// rate limited reaction, which the Slider displays in place of its given value meanwhile.
+    maybe_pending: Option<f64>,
     interaction: Interaction,
+    is_focused: bool,
+    enabled: bool,
+    read_only: bool,
+}
+
+/// The types of value that may be selected via a Slider.
+///
+/// The Slider interpolates between its `min` and `max` as `f64`s, so a type need only describe how
+/// it maps to and from an `f64`. This is implemented for all primitive integer and floating point
+/// types, where integers are rounded to the nearest value.
+pub trait SliderValue: Any + Copy + Debug + PartialEq {
+    /// The value as an `f64`.
+    fn to_f64(self) -> f64;
+    /// The value nearest to the given `f64`.
+    fn from_f64(f: f64) -> Self;
+}
+
+/// The state of the automation of a Slider's parameter, which determines the color of the track
+/// displaying the automation's value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Automation {
+    /// The value is being modulated, i.e. by an LFO or an envelope.
+    Modulated,
+    /// The automation has been overridden by the user, so the value no longer follows it.
+    Overridden,
+    /// The value is locked to the automation's value, i.e. a parameter lock within a sequencer.
+    Locked,
+}
+
+/// Where the Slider's label (along with its value, if displayed) is placed.
+#[derive(Copy, Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum LabelPosition {
+    /// Within the Slider, at the end from which its value grows (the left if horizontal or the
+    /// bottom if vertical).
+    Start,
+    /// Within the Slider, at its centre.
+    Center,
+    /// Within the Slider, at the end towards which its value grows.
+    End,
+    /// Outside of the Slider, beyond the end towards which its value grows.
+    Outside,
 }
 
 /// The ways in which the Slider can be interacted with.
@@ -61,7 +157,29 @@ pub enum Interaction {
 }
 
 
-impl<T> State<T> {
+macro_rules! impl_slider_value_for_floats {
+    ($($t:ty)*) => {$(
+        impl SliderValue for $t {
+            fn to_f64(self) -> f64 { self as f64 }
+            fn from_f64(f: f64) -> $t { f as $t }
+        }
+    )*};
+}
+
+macro_rules! impl_slider_value_for_integers {
+    ($($t:ty)*) => {$(
+        impl SliderValue for $t {
+            fn to_f64(self) -> f64 { self as f64 }
+            fn from_f64(f: f64) -> $t { f.round() as $t }
+        }
+    )*};
+}
+
+impl_slider_value_for_floats!(f32 f64);
+impl_slider_value_for_integers!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+
+impl State {
     /// Return the color associated with the state.
     fn color(&self, color: Color) -> Color {
         match self.interaction {
@@ -76,7 +194,7 @@ impl<T> State<T> {
 fn get_new_interaction(is_over: bool, prev: Interaction, mouse: Mouse) -> Interaction {
     use mouse::ButtonState::{Down, Up};
     use self::Interaction::{Normal, Highlighted, Clicked};
-    match (is_over, prev, mouse.left) {
+    match (is_over, prev, mouse.left.state) {
         (true,  Normal,  Down) => Normal,
         (true,  _,       Down) => Clicked,
         (true,  _,       Up)   => Highlighted,
@@ -87,21 +205,32 @@ fn get_new_interaction(is_over: bool, prev: Interaction, mouse: Mouse) -> Intera
 
 impl<'a, T, F> Slider<'a, T, F> {
 
-    /// Construct a new Slider widget.
+    /// Construct a new Slider widget. The value may be of any `SliderValue` type, including the
+    /// primitive integers, i.e. `Slider::new(3u32, 0, 10)`.
     pub fn new(value: T, min: T, max: T) -> Slider<'a, T, F> {
         Slider {
             value: value,
             min: min,
             max: max,
             pos: Position::default(),
+            margin: Margin::none(),
             maybe_h_align: None,
             maybe_v_align: None,
-            dim: [192.0, 48.0],
             depth: 0.0,
             maybe_react: None,
+            maybe_rate_limit: None,
             maybe_label: None,
+            maybe_label_spans: None,
+            maybe_unit: None,
+            maybe_value_label: None,
+            maybe_conversion: None,
+            maybe_quantity: None,
+            maybe_panel: None,
+            maybe_automation: None,
             style: Style::new(),
             enabled: true,
+            read_only: false,
+            persistent: false,
         }
     }
 
@@ -112,9 +241,102 @@ impl<'a, T, F> Slider<'a, T, F> {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
+    /// Set a reaction that is only triggered once the value has stopped changing for the given
+    /// number of seconds. This is useful for expensive reactions that needn't follow every step
+    /// of a drag.
+    pub fn react_debounced(mut self, secs: f64, reaction: F) -> Slider<'a, T, F> {
+        self.maybe_rate_limit = Some(RateLimit::Debounce(secs));
+        self.react(reaction)
+    }
+
+    /// Set a reaction that is triggered at most once per the given number of seconds. The latest
+    /// value is always delivered once the interval has passed.
+    pub fn react_throttled(mut self, secs: f64, reaction: F) -> Slider<'a, T, F> {
+        self.maybe_rate_limit = Some(RateLimit::Throttle(secs));
+        self.react(reaction)
+    }
+
+    /// Display the value alongside the label, formatted with the given unit. The unit of any
+    /// active conversion takes precedence.
+    #[inline]
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.maybe_unit = Some(unit);
+        self
+    }
+
+    /// Display the value alongside the label, formatted by the given function. This takes
+    /// precedence over any `unit`. The function is given the value in its display unit.
+    #[inline]
+    pub fn value_label<V>(mut self, format: V) -> Self
+        where
+            V: Fn(T) -> String + 'a,
+    {
+        self.maybe_value_label = Some(Box::new(format));
+        self
+    }
+
+    /// Set the position of the label (along with its value, if displayed).
+    #[inline]
+    pub fn label_position(mut self, position: LabelPosition) -> Self {
+        self.style.maybe_label_position = Some(position);
+        self
+    }
+
+    /// Display and edit the value in the conversion's unit, while still reporting the value in its
+    /// canonical unit via the reaction. This takes precedence over any conversion that the theme
+    /// holds for the Slider's `quantity`.
+    #[inline]
+    pub fn conversion(mut self, conversion: Conversion) -> Self {
+        self.maybe_conversion = Some(conversion);
+        self
+    }
+
+    /// Set the quantity that the value measures (i.e. `"temperature"`), so that it is displayed
+    /// and edited via the conversion held for the quantity within the theme's `display_units`.
+    #[inline]
+    pub fn quantity(mut self, quantity: &'a str) -> Self {
+        self.maybe_quantity = Some(quantity);
+        self
+    }
+
+    /// Display the given secondary value (i.e. the target of some automation) as a thin track
+    /// along the Slider, colored according to the state of the automation. This is only displayed,
+    /// and should be given by the application each update for as long as it applies.
+    #[inline]
+    pub fn automation(mut self, value: T, automation: Automation) -> Self {
+        self.maybe_automation = Some((value, automation));
+        self
+    }
+
+    /// Set the thickness of the automation track.
+    #[inline]
+    pub fn automation_width(mut self, width: f64) -> Self {
+        self.style.maybe_automation_width = Some(width);
+        self
+    }
+
+    /// Set the color of the automation track for each state of the automation.
+    #[inline]
+    pub fn automation_colors(mut self, modulated: Color, overridden: Color, locked: Color) -> Self {
+        self.style.maybe_modulated_color = Some(modulated);
+        self.style.maybe_overridden_color = Some(overridden);
+        self.style.maybe_locked_color = Some(locked);
+        self
+    }
+
+    /// Make the Slider a member of the given keyboard panel, so that the arrow keys move the focus
+    /// between it and the panel's other members.
+    #[inline]
+    pub fn panel(mut self, panel_id: PanelId) -> Self {
+        self.maybe_panel = Some(panel_id);
+        self
+    }
+
+    /// Record the Slider's value within the `Ui`'s widget states so that it may be saved and
+    /// restored in a later session, at which point the Slider reacts with the restored value.
+    #[inline]
+    pub fn persistent(mut self) -> Self {
+        self.persistent = true;
         self
     }
 
@@ -123,112 +345,256 @@ impl<'a, T, F> Slider<'a, T, F> {
 impl<'a, T, F> Widget for Slider<'a, T, F>
     where
         F: FnMut(T),
-        T: ::std::any::Any + ::std::fmt::Debug + Float + NumCast + ToPrimitive,
+        T: SliderValue,
 {
-    type State = State<T>;
+    type State = State;
     type Style = Style;
     fn unique_kind(&self) -> &'static str { "Slider" }
-    fn init_state(&self) -> State<T> {
+    fn init_state(&self) -> State {
         State {
-            value: self.value,
-            min: self.min,
-            max: self.max,
+            value: self.value.to_f64(),
+            min: self.min.to_f64(),
+            max: self.max.to_f64(),
             maybe_label: None,
+            maybe_label_spans: None,
+            maybe_value_text: None,
+            maybe_automation: None,
+            maybe_drag_start: None,
+            maybe_pending: None,
             interaction: Interaction::Normal,
+            is_focused: false,
+            enabled: self.enabled,
+            read_only: self.read_only,
         }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the Button.
     fn update<C>(mut self,
-                 prev_state: &widget::State<State<T>>,
+                 prev_state: &widget::State<State>,
                  style: &Style,
                  ui_id: UiId,
-                 ui: &mut Ui<C>) -> widget::State<Option<State<T>>>
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
         where
             C: CharacterCache,
     {
         use utils::{is_over_rect, map_range};
 
         let widget::State { ref state, .. } = *prev_state;
-        let dim = self.dim;
+
+        // While a rate limited reaction has yet to receive the user's latest value, the Slider
+        // follows that value rather than the one it was given.
+        if let (Some(pending), Some(_)) = (state.maybe_pending, self.maybe_rate_limit) {
+            self.value = T::from_f64(pending);
+        }
+
+        // Work in the display unit from here on, converting back only when reacting.
+        let maybe_conversion = unit::active_conversion(self.maybe_conversion.as_ref(),
+                                                       self.maybe_quantity,
+                                                       &ui.theme);
+        let conversion = maybe_conversion.as_ref();
+        let value = unit::to_display(conversion, self.value.to_f64());
+        let min = unit::to_display(conversion, self.min.to_f64());
+        let max = unit::to_display(conversion, self.max.to_f64());
+        let maybe_automation = self.maybe_automation.map(|(value, automation)| {
+            (unit::to_display(conversion, value.to_f64()), automation)
+        });
+        if let Some(conversion) = conversion {
+            self.maybe_unit = Some(conversion.unit.clone());
+        }
+
+        let dim = style.dim(&ui.theme);
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
+        let padding = style.padding(&ui.theme);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, padding, h_align, v_align);
         let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
         let is_over = is_over_rect([0.0, 0.0], mouse.xy, dim);
+        let is_interactive = self.enabled && !self.read_only;
         let new_interaction = 
-            if self.enabled {
+            if is_interactive {
                 get_new_interaction(is_over, state.interaction, mouse)
             } else {
-                //Slider is disabled, so pretend the interaction is normal
+                //Slider is disabled or read-only, so pretend the interaction is normal
                 Interaction::Normal
             };
 
-        let frame = style.frame(&ui.theme);
-        let frame_2 = frame * 2.0;
-        let (inner_w, inner_h) = (dim[0] - frame_2, dim[1] - frame_2);
+        // Keep hold of the mouse while dragging so the drag may continue beyond the Slider.
+        ui.mouse_dragged_by(ui_id,
+                            state.interaction == Interaction::Clicked,
+                            new_interaction == Interaction::Clicked);
+
+        // Claim keyboard focus when clicked so that the value may also be adjusted via the keys,
+        // reporting the Slider if an external control binding is being learned.
+        if is_interactive && new_interaction == Interaction::Clicked {
+            ui.focus(ui_id);
+            ui.binding_interacted(ui_id);
+        }
+        if let Some(panel_id) = self.maybe_panel {
+            ui.join_panel(panel_id, ui_id);
+        }
+        let is_focused = is_interactive && ui.is_focused(ui_id);
+
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let (inner_w, inner_h) = (inner_dim[0], inner_dim[1]);
         let (half_inner_w, half_inner_h) = (inner_w / 2.0, inner_h / 2.0);
+        let inner_mouse_xy = ::vecmath::vec2_sub(mouse.xy, inner_offset);
 
         let is_horizontal = dim[0] > dim[1];
 
+        let value_percentage = percentage(value, min, max);
         let new_value = if is_horizontal {
             // Horizontal.
             let w = match (is_over, state.interaction, new_interaction) {
                 (true, Interaction::Highlighted, Interaction::Clicked) |
                 (_, Interaction::Clicked, Interaction::Clicked) => {
-                    let w = map_range(mouse.xy[0], -half_inner_w, half_inner_w, 0.0, inner_w);
+                    let w = map_range(inner_mouse_xy[0], -half_inner_w, half_inner_w, 0.0, inner_w);
                     clamp(w, 0.0, inner_w)
                 },
-                _ => {
-                    let value_percentage = percentage(self.value, self.min, self.max);
-                    clamp(value_percentage as f64 * inner_w, 0.0, inner_w)
-                },
+                _ => clamp(value_percentage * inner_w, 0.0, inner_w),
             };
-            value_from_perc((w / inner_w) as f32, self.min, self.max)
+            min + (max - min) * w / inner_w
         } else {
             // Vertical.
             let h = match (is_over, state.interaction, new_interaction) {
                 (true, Interaction::Highlighted, Interaction::Clicked) |
                 (_, Interaction::Clicked, Interaction::Clicked) => {
-                    let h = map_range(mouse.xy[1], -half_inner_h, half_inner_h, 0.0, inner_h);
+                    let h = map_range(inner_mouse_xy[1], -half_inner_h, half_inner_h, 0.0, inner_h);
                     clamp(h, 0.0, inner_h)
                 },
-                _ => {
-                    let value_percentage = percentage(self.value, self.min, self.max);
-                    clamp(value_percentage as f64 * inner_h, 0.0, inner_h)
-                },
+                _ => clamp(value_percentage * inner_h, 0.0, inner_h),
             };
-            value_from_perc((h / inner_h) as f32, self.min, self.max)
+            min + (max - min) * h / inner_h
+        };
+
+        // PageUp and PageDown adjust the value while focused.
+        let new_value = match is_focused {
+            true => panel::page_adjust(new_value, min, max,
+                                       ui.get_focused_keys(ui_id), ui.is_shift_down()),
+            false => new_value,
         };
 
-        // React.
-        match self.maybe_react {
-            Some(ref mut react) => {
-                if self.value != new_value || match (state.interaction, new_interaction) {
-                    (Interaction::Highlighted, Interaction::Clicked) |
-                    (Interaction::Clicked, Interaction::Highlighted) => true,
-                    _ => false,
-                } { react(new_value) }
-            }, None => (),
+        // The mouse wheel adjusts the value by a hundredth of the range per step.
+        let scroll = ui.get_scroll(ui_id);
+        let new_value = match is_interactive && scroll[1] != 0.0 {
+            true => clamp(new_value + (max - min) / 100.0 * scroll[1], min.min(max), min.max(max)),
+            false => new_value,
+        };
+
+        // Adopt the value of a bound external control, normalised across the range.
+        let new_value = match ui.take_control_value(ui_id) {
+            Some(control) if is_interactive => min + (max - min) * clamp(control, 0.0, 1.0),
+            _ => new_value,
+        };
+
+        // Return to the canonical unit, rounding to the nearest value of the Slider's type.
+        let new_value = T::from_f64(unit::from_display(conversion, new_value));
+
+        // Record the user's change so that it may be undone, coalescing each drag into a single
+        // change from the value at which it began.
+        let maybe_drag_start = match (state.interaction, new_interaction) {
+            (Interaction::Clicked, Interaction::Clicked) => state.maybe_drag_start,
+            (_, Interaction::Clicked) => Some(self.value.to_f64()),
+            _ => None,
+        };
+        match (state.interaction, new_interaction) {
+            (_, Interaction::Clicked) => (),
+            (Interaction::Clicked, _) => if let Some(start) = state.maybe_drag_start {
+                ui.push_change(ui_id, "Slider", &start, &new_value.to_f64());
+            },
+            _ => if new_value != self.value {
+                ui.push_change(ui_id, "Slider", &self.value.to_f64(), &new_value.to_f64());
+            },
         }
 
+        // Adopt the value handed back by an undo or redo.
+        let new_value = match ui.take_undone_state::<f64>(ui_id, "Slider") {
+            Some(undone) => T::from_f64(undone),
+            None => new_value,
+        };
+
+        // Adopt the value restored from a previous session (which is kept in the canonical unit)
+        // and record the value to be saved for the next.
+        let new_value = match self.persistent {
+            true => match ui.take_restored_widget_state::<f64>(ui_id, "Slider") {
+                Some(restored) => {
+                    let (lo, hi) = (self.min.to_f64(), self.max.to_f64());
+                    T::from_f64(clamp(restored, lo.min(hi), lo.max(hi)))
+                },
+                None => new_value,
+            },
+            false => new_value,
+        };
+        if self.persistent {
+            ui.persist_widget_state(ui_id, "Slider", &new_value.to_f64());
+        }
+
+        // React. A rate limited reaction is only given changes to the value, as pressing and
+        // releasing the Slider produce no new value.
+        let maybe_value = match self.maybe_rate_limit {
+            Some(limit) => {
+                let maybe_change = if self.value != new_value { Some(new_value) } else { None };
+                ui.rate_limit(ui_id, limit, maybe_change)
+            },
+            None => {
+                let should_react = self.value != new_value
+                    || match (state.interaction, new_interaction) {
+                        (Interaction::Highlighted, Interaction::Clicked) |
+                        (Interaction::Clicked, Interaction::Highlighted) => true,
+                        _ => false,
+                    };
+                if should_react { Some(new_value) } else { None }
+            },
+        };
+        if let Some(value) = maybe_value {
+            if let Some(ref mut react) = self.maybe_react { react(value) }
+        }
+        let maybe_pending = match self.maybe_rate_limit.is_some()
+            && ui.is_rate_limit_pending(ui_id) {
+            true => Some(new_value.to_f64()),
+            false => None,
+        };
+
+        // Format the value for display, preferring the value label over the unit.
+        let maybe_value_text = match (self.maybe_value_label.as_ref(), self.maybe_unit.as_ref()) {
+            (Some(value_label), _) => Some(value_label(T::from_f64(value))),
+            (None, Some(unit)) => Some(unit.format(value, min)),
+            (None, None) => None,
+        };
+
         // A function for constructing a new state.
         let new_state = || {
             State {
                 interaction: new_interaction,
-                value: self.value,
-                min: self.min,
-                max: self.max,
+                value: value,
+                min: min,
+                max: max,
                 maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+                maybe_value_text: maybe_value_text.clone(),
+                maybe_automation: maybe_automation,
+                maybe_drag_start: maybe_drag_start,
+                enabled: self.enabled,
+                read_only: self.read_only,
+                maybe_pending: maybe_pending,
+                is_focused: is_focused,
             }
         };
 
         // Check whether or not the state has changed since the previous update.
         let state_has_changed = state.interaction != new_interaction
-            || state.value != self.value
-            || state.min != self.min || state.max != self.max
-            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label;
+            || state.is_focused != is_focused
+            || state.enabled != self.enabled
+            || state.read_only != self.read_only
+            || state.value != value
+            || state.min != min || state.max != max
+            || state.maybe_value_text != maybe_value_text
+            || state.maybe_automation != maybe_automation
+            || state.maybe_drag_start != maybe_drag_start
+            || state.maybe_pending != maybe_pending
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
 
         // Construct the new state if there was a change.
         let maybe_new_state = if state_has_changed { Some(new_state()) } else { None };
@@ -236,95 +602,193 @@ impl<'a, T, F> Widget for Slider<'a, T, F>
         widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
     }
 
-    /// Construct an Element from the given Button State.
-    fn draw<C>(new_state: &widget::State<State<T>>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given Button State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{collage, rect, text};
+        use elmesque::form::{rect, text};
 
         let widget::State { ref state, dim, xy, .. } = *new_state;
         let frame = style.frame(&ui.theme);
-        let (inner_w, inner_h) = (dim[0] - frame * 2.0, dim[1] - frame * 2.0);
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let (inner_w, inner_h) = (inner_dim[0], inner_dim[1]);
         let frame_color = state.color(style.frame_color(&ui.theme));
-        let color = state.color(style.color(&ui.theme));
+        let frame_color = if state.is_focused { frame_color.highlighted() } else { frame_color };
+        let frame_color = enable::color(frame_color, state.enabled, &ui.theme);
+        let color = enable::color(state.color(style.color(&ui.theme)), state.enabled, &ui.theme);
 
-        let new_value = NumCast::from(state.value).unwrap();
+        let value_percentage = percentage(state.value, state.min, state.max);
         let is_horizontal = dim[0] > dim[1];
         let (pad_rel_xy, pad_dim) = if is_horizontal {
             // Horizontal.
-            let value_percentage = percentage(new_value, state.min, state.max);
-            let w = clamp(value_percentage as f64 * inner_w, 0.0, inner_w);
-            let rel_xy = [-(inner_w - w) / 2.0, 0.0];
+            let w = clamp(value_percentage * inner_w, 0.0, inner_w);
+            let rel_xy = [inner_offset[0] - (inner_w - w) / 2.0, inner_offset[1]];
             (rel_xy, [w, inner_h])
         } else {
             // Vertical.
-            let value_percentage = percentage(new_value, state.min, state.max);
-            let h = clamp(value_percentage as f64 * inner_h, 0.0, inner_h);
-            let rel_xy = [0.0, -(inner_h - h) / 2.0];
+            let h = clamp(value_percentage * inner_h, 0.0, inner_h);
+            let rel_xy = [inner_offset[0], inner_offset[1] - (inner_h - h) / 2.0];
             (rel_xy, [inner_w, h])
         };
 
         // Rectangle frame / backdrop Form.
-        let frame_form = rect(dim[0], dim[1])
-            .filled(frame_color);
+        let radii = style.corner_radii(&ui.theme);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
         // Slider rectangle Form.
-        let pad_form = rect(pad_dim[0], pad_dim[1])
-            .filled(color)
+        let pad_form = gradient::filled_rect(pad_dim, radii.inset(frame), color,
+                                             style.gradient(&ui.theme))
             .shift(pad_rel_xy[0], pad_rel_xy[1]);
 
-        // Label Form.
-        let maybe_label_form = state.maybe_label.as_ref().map(|label_text| {
-            use elmesque::text::Text;
-            use label;
-            const TEXT_PADDING: f64 = 10.0;
-            let label_color = style.label_color(&ui.theme);
-            let size = style.label_font_size(&ui.theme);
-            let label_w = label::width(ui, size, &label_text);
-            let is_horizontal = dim[0] > dim[1];
-            let l_pos = if is_horizontal {
-                let x = position::align_left_of(dim[0], label_w) + TEXT_PADDING;
-                [x, 0.0]
+        // The automation's track runs along the inner edge of the Slider (the bottom if horizontal
+        // or the left if vertical) from the start to the automation's value.
+        let maybe_automation_form = state.maybe_automation.map(|(value, automation)| {
+            let width = style.automation_width(&ui.theme);
+            let color = enable::color(style.automation_color(automation, &ui.theme),
+                                      state.enabled, &ui.theme);
+            let perc = clamp(percentage(value, state.min, state.max), 0.0, 1.0);
+            let (track_rel_xy, track_dim) = if is_horizontal {
+                let w = perc * inner_w;
+                let x = inner_offset[0] - (inner_w - w) / 2.0;
+                let y = inner_offset[1] - (inner_h - width) / 2.0;
+                ([x, y], [w, width.min(inner_h)])
             } else {
-                let y = position::align_bottom_of(dim[1], size as f64) + TEXT_PADDING;
-                [0.0, y]
+                let h = perc * inner_h;
+                let x = inner_offset[0] - (inner_w - width) / 2.0;
+                let y = inner_offset[1] - (inner_h - h) / 2.0;
+                ([x, y], [width.min(inner_w), h])
             };
-            text(Text::from_string(label_text.clone()).color(label_color).height(size as f64))
-                .shift(l_pos[0].floor(), l_pos[1].floor())
-                .shift(xy[0].floor(), xy[1].floor())
+            rect(track_dim[0], track_dim[1]).filled(color).shift(track_rel_xy[0], track_rel_xy[1])
         });
 
+        // The label's text, followed by the value if it is displayed.
+        let maybe_value_text = state.maybe_value_text.as_ref();
+        let maybe_label_text = match (state.maybe_label.as_ref(), maybe_value_text) {
+            (Some(label), Some(value_text)) => Some(format!("{}: {}", label, value_text)),
+            (None, Some(value_text)) => Some(value_text.clone()),
+            (maybe_label, None) => maybe_label.map(|label| label.clone()),
+        };
+
+        // The label's dimensions and Forms, preferring the spans if there are any.
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let size = style.label_font_size(&ui.theme);
+        let maybe_label = match state.maybe_label_spans {
+            Some(ref spans) => {
+                let mut spans = spans.clone();
+                if let Some(ref value_text) = maybe_value_text {
+                    spans.push(Span::new(&format!(": {}", value_text)));
+                }
+                Some(spans.forms(ui, label_color, size))
+            },
+            None => maybe_label_text.map(|label_text| {
+                use elmesque::text::Text;
+                use label;
+                let label_w = label::width(ui, size, &label_text);
+                let label_text = Text::from_string(label_text).color(label_color);
+                let form = text(label_text.height(size as f64));
+                ([label_w, size as f64], vec![form])
+            }),
+        };
+
+        // Shift the label's Forms into their place within the padded area, or beyond the end of
+        // the Slider if it is placed outside.
+        let label_forms = maybe_label.map(|(label_dim, forms)| {
+            let padding = style.padding(&ui.theme);
+            let label_position = style.label_position(&ui.theme);
+            let place = match (label_position, is_horizontal) {
+                (LabelPosition::Start, true) => Place::MidLeft,
+                (LabelPosition::Start, false) => Place::MidBottom,
+                (LabelPosition::Center, _) => Place::Middle,
+                (LabelPosition::End, true) | (LabelPosition::Outside, true) => Place::MidRight,
+                (LabelPosition::End, false) | (LabelPosition::Outside, false) => Place::MidTop,
+            };
+            let padded_dim = padding.inner_dim(dim);
+            let l_pos = ::vecmath::vec2_add(position::place_within(place, padded_dim, label_dim),
+                                            padding.inner_offset());
+            let l_pos = match (label_position, is_horizontal) {
+                (LabelPosition::Outside, true) => {
+                    [dim[0] / 2.0 + padding.right + label_dim[0] / 2.0, l_pos[1]]
+                },
+                (LabelPosition::Outside, false) => {
+                    [l_pos[0], dim[1] / 2.0 + padding.top + label_dim[1] / 2.0]
+                },
+                _ => l_pos,
+            };
+            forms.into_iter()
+                .map(|form| form.shift(l_pos[0].floor(), l_pos[1].floor()))
+                .map(|form| form.shift(xy[0].floor(), xy[1].floor()))
+                .collect()
+        }).unwrap_or_else(Vec::new);
+
+        // Mark the Slider with a lock if it is read-only.
+        let lock_forms = enable::lock_forms(state.read_only, dim, frame, label_color, &ui.theme);
+
         // Chain the Forms and shift them into position.
         let form_chain = Some(frame_form).into_iter()
             .chain(Some(pad_form).into_iter())
+            .chain(maybe_automation_form.into_iter())
+            .chain(lock_forms.into_iter())
             .map(|form| form.shift(xy[0], xy[1]))
-            .chain(maybe_label_form.into_iter());
+            .chain(label_forms.into_iter());
+
+        // Collect the Forms for drawing.
+        form_chain.collect()
+    }
 
-        // Collect the Forms into a renderable Element.
-        collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+    /// Describe the Slider for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::Slider)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(state.value)
     }
 
 }
 
 
+/// The position of the value within the range, where `0.0` is `min` and `1.0` is `max`.
+fn percentage(value: f64, min: f64, max: f64) -> f64 {
+    (value - min) / (max - min)
+}
+
+
 impl Style {
 
     /// Construct the default Style.
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
+            maybe_gradient: None,
             maybe_label_color: None,
             maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_padding: None,
+            maybe_label_position: None,
+            maybe_font: None,
+            maybe_automation_width: None,
+            maybe_modulated_color: None,
+            maybe_overridden_color: None,
+            maybe_locked_color: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_slider.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_slider.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -341,6 +805,41 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_slider.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of an Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
+    /// Get the Gradient with which the Element's value is filled, if there is one.
+    pub fn gradient(&self, theme: &Theme) -> Option<Gradient> {
+        self.maybe_gradient
+            .or(theme.maybe_slider.as_ref().and_then(|style| style.maybe_gradient))
+            .or(theme.maybe_gradient)
+    }
+
     /// Get the label Color for an Element.
     pub fn label_color(&self, theme: &Theme) -> Color {
         self.maybe_label_color.or(theme.maybe_slider.as_ref().map(|style| {
@@ -355,9 +854,82 @@ impl Style {
         })).unwrap_or(theme.font_size_medium)
     }
 
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [192.0, 48.0];
+        let width = self.maybe_width.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the padding between the edge of the Element and its text.
+    pub fn padding(&self, theme: &Theme) -> Padding {
+        const DEFAULT_PADDING: f64 = 10.0;
+        self.maybe_padding.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_padding.unwrap_or(Padding::uniform(DEFAULT_PADDING))
+        })).unwrap_or(Padding::uniform(DEFAULT_PADDING))
+    }
+
+    /// Get the position of the label. By default, the label sits at the end of the Slider from
+    /// which its value grows.
+    pub fn label_position(&self, theme: &Theme) -> LabelPosition {
+        self.maybe_label_position.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_label_position.unwrap_or(LabelPosition::Start)
+        })).unwrap_or(LabelPosition::Start)
+    }
+
+    /// Get the thickness of the automation track.
+    pub fn automation_width(&self, theme: &Theme) -> f64 {
+        const DEFAULT_WIDTH: f64 = 3.0;
+        self.maybe_automation_width.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_automation_width.unwrap_or(DEFAULT_WIDTH)
+        })).unwrap_or(DEFAULT_WIDTH)
+    }
+
+    /// Get the color of the automation track for the given state of the automation.
+    pub fn automation_color(&self, automation: Automation, theme: &Theme) -> Color {
+        let maybe_theme_style = theme.maybe_slider.as_ref();
+        match automation {
+            Automation::Modulated => self.maybe_modulated_color.or(maybe_theme_style.map(|style| {
+                style.maybe_modulated_color.unwrap_or(blue())
+            })).unwrap_or(blue()),
+            Automation::Overridden => self.maybe_overridden_color.or(maybe_theme_style.map(|style| {
+                style.maybe_overridden_color.unwrap_or(orange())
+            })).unwrap_or(orange()),
+            Automation::Locked => self.maybe_locked_color.or(maybe_theme_style.map(|style| {
+                style.maybe_locked_color.unwrap_or(red())
+            })).unwrap_or(red()),
+        }
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_slider.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
 }
 
 
+impl<'a, T, F> Enableable for Slider<'a, T, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, T, F> ReadOnly for Slider<'a, T, F> {
+    fn read_only(mut self, flag: bool) -> Self {
+        self.read_only = flag;
+        self
+    }
+}
+
 impl<'a, T, F> Colorable for Slider<'a, T, F> {
     fn color(mut self, color: Color) -> Self {
         self.style.maybe_color = Some(color);
@@ -365,6 +937,20 @@ impl<'a, T, F> Colorable for Slider<'a, T, F> {
     }
 }
 
+impl<'a, T, F> Paletted for Slider<'a, T, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, T, F> Fontable for Slider<'a, T, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, T, F> Frameable for Slider<'a, T, F> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -374,6 +960,32 @@ impl<'a, T, F> Frameable for Slider<'a, T, F> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, T, F> Roundable for Slider<'a, T, F> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
+}
+
+impl<'a, T, F> Gradientable for Slider<'a, T, F> {
+    fn gradient(mut self, gradient: Gradient) -> Self {
+        self.style.maybe_gradient = Some(gradient);
+        self
+    }
 }
 
 impl<'a, T, F> Labelable<'a> for Slider<'a, T, F> {
@@ -382,6 +994,11 @@ impl<'a, T, F> Labelable<'a> for Slider<'a, T, F> {
         self
     }
 
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
     fn label_color(mut self, color: Color) -> Self {
         self.style.maybe_label_color = Some(color);
         self
@@ -402,6 +1019,11 @@ impl<'a, T, F> position::Positionable for Slider<'a, T, F> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         Slider { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
     fn position(mut self, pos: Position) -> Self {
         self.pos = pos;
         self
@@ -410,14 +1032,65 @@ impl<'a, T, F> position::Positionable for Slider<'a, T, F> {
 
 impl<'a, T, F> position::Sizeable for Slider<'a, T, F> {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        Slider { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        Slider { dim: [w, h], ..self }
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.style.maybe_padding = Some(padding.into());
+        self
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use frame::Frameable;
+    use harness::GlyphCache;
+    use position::{Positionable, Sizeable};
+    use super::{Slider, SliderValue};
+    use ui::{Ui, UiId};
+    use widget::Widget;
+
+    const WIDGET: UiId = 0;
+
+    #[test]
+    fn integers_round_to_the_nearest_value() {
+        assert_eq!(i32::from_f64(2.5), 3);
+        assert_eq!(i32::from_f64(-1.4), -1);
+        assert_eq!(u8::from_f64(199.6), 200);
+        assert_eq!(7u64.to_f64(), 7.0);
+    }
+
+    #[test]
+    fn floats_convert_without_rounding() {
+        assert_eq!(f64::from_f64(2.5), 2.5);
+        assert_eq!(f32::from_f64(0.25), 0.25);
+        assert_eq!(0.5f32.to_f64(), 0.5);
+    }
+
+    #[test]
+    fn integer_sliders_react_with_the_nearest_integer() {
+        let mut harness = Ui::test_harness([400.0, 400.0]);
+        let mut value = 0i32;
+        {
+            let mut set_widgets = |ui: &mut Ui<GlyphCache>| {
+                Slider::new(value, 0, 10)
+                    .dimensions(200.0, 30.0)
+                    .xy(0.0, 0.0)
+                    .frame(0.0)
+                    .react(|new_value| value = new_value)
+                    .set(WIDGET, ui);
+            };
+            harness.click([-34.0, 0.0], &mut set_widgets);
+        }
+        assert_eq!(value, 3);
+    }
+
+}
@@ -0,0 +1,426 @@
+use accessibility::{Accessibility, Role};
+use color::{Color, Colorable};
+use elmesque::form::Form;
+use enable::Enableable;
+use graphics::character::CharacterCache;
+use mouse::Cursor as MouseCursor;
+use palette::{ColorRole, Paletted};
+use position::{Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position, Positionable,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use utils::{clamp, is_over_rect};
+use widget::{self, Widget};
+
+
+/// The centre and dimensions of one of the panes of a SplitPane.
+pub type Pane = (Point, Dimensions);
+
+/// An area split into two panes by a divider that may be dragged by the mouse, i.e. the sidebar
+/// and document of an editor.
+///
+/// Unlike the value of a Slider, the ratio at which the area is split is owned by the SplitPane
+/// and persists within its `State`. The reaction is called every update with the centre and
+/// dimensions of each pane, so that the application may position the widgets within them.
+pub struct SplitPane<F> {
+    orientation: Orientation,
+    initial_ratio: f64,
+    min_pane_length: f64,
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    maybe_react: Option<F>,
+    style: Style,
+    enabled: bool,
+}
+
+/// The direction in which the SplitPane's area is split.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    /// The panes sit side by side, separated by a vertical divider. The first pane is the left.
+    Horizontal,
+    /// The panes are stacked, separated by a horizontal divider. The first pane is the top.
+    Vertical,
+}
+
+/// Styling for the SplitPane, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_divider_width: Option<f64>,
+}
+
+/// The state of the SplitPane.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    orientation: Orientation,
+    /// The share of the area (excluding the divider) given to the first pane.
+    ratio: f64,
+    drag: Drag,
+    interaction: Interaction,
+}
+
+/// Whether or not the divider is being dragged by the mouse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Drag {
+    /// The left mouse button is up.
+    Idle,
+    /// The left mouse button was pressed away from the divider and is still held.
+    Blocked,
+    /// The divider is being dragged, with the given offset of its centre from the mouse.
+    Dragging(f64),
+}
+
+/// Represents an interaction with the SplitPane's divider.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Interaction {
+    Normal,
+    Highlighted,
+    Clicked,
+}
+
+
+impl Interaction {
+    /// Alter the widget color depending on the state.
+    fn color(&self, color: Color) -> Color {
+        match *self {
+            Interaction::Normal => color,
+            Interaction::Highlighted => color.highlighted(),
+            Interaction::Clicked => color.clicked(),
+        }
+    }
+}
+
+
+impl Orientation {
+    /// The index of the axis along which the area is split.
+    fn axis(&self) -> usize {
+        match *self {
+            Orientation::Horizontal => 0,
+            Orientation::Vertical => 1,
+        }
+    }
+}
+
+
+/// The centre and dimensions of each pane along with those of the divider between them.
+fn layout(orientation: Orientation,
+          xy: Point,
+          dim: Dimensions,
+          divider_w: f64,
+          ratio: f64) -> (Pane, Pane, Pane) {
+    let axis = orientation.axis();
+    let available = (dim[axis] - divider_w).max(0.0);
+    let first_len = available * ratio;
+    let second_len = available - first_len;
+    // The first pane is at the left of a horizontal split and at the top of a vertical one.
+    let (start, sign) = match orientation {
+        Orientation::Horizontal => (xy[0] - dim[0] / 2.0, 1.0),
+        Orientation::Vertical => (xy[1] + dim[1] / 2.0, -1.0),
+    };
+    let pane = |offset: f64, length: f64| {
+        let mut pane_xy = xy;
+        let mut pane_dim = dim;
+        pane_xy[axis] = start + sign * (offset + length / 2.0);
+        pane_dim[axis] = length;
+        (pane_xy, pane_dim)
+    };
+    let first = pane(0.0, first_len);
+    let divider = pane(first_len, divider_w);
+    let second = pane(first_len + divider_w, second_len);
+    (first, divider, second)
+}
+
+
+impl<F> SplitPane<F> {
+
+    /// Construct a new SplitPane, split in the given direction.
+    pub fn new(orientation: Orientation) -> SplitPane<F> {
+        SplitPane {
+            orientation: orientation,
+            initial_ratio: 0.5,
+            min_pane_length: 24.0,
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// Set the share of the area given to the first pane when the SplitPane is first set.
+    pub fn initial_ratio(mut self, ratio: f64) -> Self {
+        self.initial_ratio = clamp(ratio, 0.0, 1.0);
+        self
+    }
+
+    /// Set the minimum length of each pane along the direction of the split.
+    pub fn min_pane_length(mut self, length: f64) -> Self {
+        self.min_pane_length = length;
+        self
+    }
+
+    /// Set the width of the divider between the panes.
+    pub fn divider_width(mut self, width: f64) -> Self {
+        self.style.maybe_divider_width = Some(width);
+        self
+    }
+
+    /// Set the reaction for the SplitPane. It is called every update with the first and second
+    /// panes.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the depth of the SplitPane.
+    pub fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
+    }
+
+}
+
+
+impl<F> Widget for SplitPane<F>
+    where
+        F: FnMut(Pane, Pane),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "SplitPane" }
+    fn init_state(&self) -> State {
+        State {
+            orientation: self.orientation,
+            ratio: self.initial_ratio,
+            drag: Drag::Idle,
+            interaction: Interaction::Normal,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+
+    /// Update the state of the SplitPane.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use mouse::ButtonState::Down;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let dim = style.dim(&ui.theme);
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id);
+        let divider_w = style.divider_width(&ui.theme);
+        let axis = self.orientation.axis();
+        let (_, (divider_xy, divider_dim), _) =
+            layout(self.orientation, xy, dim, divider_w, state.ratio);
+        let is_over_divider = is_over_rect(divider_xy, mouse.xy, divider_dim);
+
+        // Alt with the left button is reserved for panning an EditorCanvas.
+        let is_down = self.enabled && mouse.left.state == Down && !ui.is_alt_down();
+        let drag = match (is_down, state.drag) {
+            (false, _) => Drag::Idle,
+            (true, Drag::Dragging(offset)) => Drag::Dragging(offset),
+            (true, Drag::Idle) if is_over_divider =>
+                Drag::Dragging(divider_xy[axis] - mouse.xy[axis]),
+            (true, _) => Drag::Blocked,
+        };
+        let was_dragging = if let Drag::Dragging(_) = state.drag { true } else { false };
+        let is_dragging = if let Drag::Dragging(_) = drag { true } else { false };
+        ui.mouse_dragged_by(ui_id, was_dragging, is_dragging);
+        if self.enabled && (is_over_divider || is_dragging) {
+            let cursor = match self.orientation {
+                Orientation::Horizontal => MouseCursor::ResizeHorizontal,
+                Orientation::Vertical => MouseCursor::ResizeVertical,
+            };
+            ui.set_mouse_cursor(ui_id, cursor);
+        }
+
+        // Determine the new ratio from the position of the divider, keeping each pane at least
+        // its minimum length where there is room.
+        let available = (dim[axis] - divider_w).max(0.0);
+        let ratio = match drag {
+            Drag::Dragging(offset) if available > 0.0 => {
+                let divider_centre = mouse.xy[axis] + offset;
+                let first_len = match self.orientation {
+                    Orientation::Horizontal => divider_centre - (xy[0] - dim[0] / 2.0),
+                    Orientation::Vertical => (xy[1] + dim[1] / 2.0) - divider_centre,
+                } - divider_w / 2.0;
+                clamp(first_len / available, 0.0, 1.0)
+            },
+            _ => state.ratio,
+        };
+        let ratio = if available > self.min_pane_length * 2.0 {
+            let min = self.min_pane_length / available;
+            clamp(ratio, min, 1.0 - min)
+        } else {
+            ratio
+        };
+
+        if let Some(ref mut react) = self.maybe_react {
+            let (first, _, second) = layout(self.orientation, xy, dim, divider_w, ratio);
+            react(first, second);
+        }
+
+        let interaction = match drag {
+            Drag::Dragging(_) => Interaction::Clicked,
+            Drag::Idle if is_over_divider => Interaction::Highlighted,
+            _ => Interaction::Normal,
+        };
+
+        let new_state = State {
+            orientation: self.orientation,
+            ratio: ratio,
+            drag: drag,
+            interaction: interaction,
+        };
+        let maybe_new_state = if *state != new_state { Some(new_state) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given SplitPane State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::rect;
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+
+        // Only the divider is drawn, as the panes are filled by the application's widgets.
+        let color = state.interaction.color(style.color(&ui.theme));
+        let divider_w = style.divider_width(&ui.theme);
+        let (_, (divider_xy, divider_dim), _) =
+            layout(state.orientation, xy, dim, divider_w, state.ratio);
+        let divider_form = rect(divider_dim[0], divider_dim[1]).filled(color)
+            .shift(divider_xy[0], divider_xy[1]);
+        vec![divider_form]
+    }
+
+    /// Describe the SplitPane for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::Splitter).value(format!("{:.0}%", state.ratio * 100.0))
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_width: None,
+            maybe_height: None,
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_divider_width: None,
+        }
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [512.0, 256.0];
+        let width = self.maybe_width.or(theme.maybe_split_pane.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_split_pane.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the Color of the divider.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_split_pane.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.frame_color)
+            })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the width of the divider.
+    pub fn divider_width(&self, theme: &Theme) -> f64 {
+        const DEFAULT_DIVIDER_WIDTH: f64 = 6.0;
+        self.maybe_divider_width.or(theme.maybe_split_pane.as_ref().map(|style| {
+            style.maybe_divider_width.unwrap_or(DEFAULT_DIVIDER_WIDTH)
+        })).unwrap_or(DEFAULT_DIVIDER_WIDTH)
+    }
+
+}
+
+
+impl<F> Enableable for SplitPane<F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<F> Colorable for SplitPane<F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<F> Paletted for SplitPane<F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<F> Positionable for SplitPane<F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        SplitPane { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        SplitPane { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<F> ::position::Sizeable for SplitPane<F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
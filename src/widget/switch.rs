@@ -0,0 +1,588 @@
+use accessibility::{Accessibility, Role};
+use color::{black, Color, Colorable};
+use corner::{self, CornerRadii};
+use elmesque::form::Form;
+use enable::{self, Enableable, ReadOnly};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
+use graphics::character::CharacterCache;
+use label::{FontSize, Labelable};
+use mouse::Mouse;
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Position,
+               VerticalAlign};
+use theme::Theme;
+use ui::{UiId, Ui};
+use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
+
+
+/// A switch for toggling the state of a bool, drawn as a pill-shaped track with a round thumb
+/// that slides to the right when the switch is on and to the left when it is off. The thumb
+/// slides between positions over the `Theme`'s animation duration.
+///
+/// Like the Toggle, its reaction is triggered upon release and will return the new bool state.
+/// The switch will not mutate the bool for you, you should do this yourself within the react
+/// closure.
+///
+/// Clicking the Switch gives it keyboard focus, after which pressing Space will also toggle the
+/// value.
+pub struct Switch<'a, F> {
+    pos: Position,
+    margin: Margin,
+    padding: Padding,
+    maybe_h_align: Option<HorizontalAlign>,
+    maybe_v_align: Option<VerticalAlign>,
+    depth: Depth,
+    value: bool,
+    maybe_react: Option<F>,
+    maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
+    style: Style,
+    enabled: bool,
+    read_only: bool,
+}
+
+/// Styling for the Switch, necessary for constructing its renderable Element.
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct Style {
+    pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
+    pub maybe_thumb_color: Option<Color>,
+    pub maybe_frame: Option<f64>,
+    pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_label_color: Option<Color>,
+    pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
+}
+
+/// The way in which the Switch is being interacted with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interaction {
+    Normal,
+    Highlighted,
+    Clicked,
+}
+
+/// The state of the Switch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    value: bool,
+    interaction: Interaction,
+    maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+    /// The current color of the track, animated between values and interactions.
+    color: Color,
+    /// The position of the thumb along the track, from `0.0` (off) to `1.0` (on).
+    thumb_perc: f64,
+    /// Whether or not the Switch has keyboard focus.
+    is_focused: bool,
+    /// Whether or not the Switch accepts user input.
+    enabled: bool,
+    read_only: bool,
+}
+
+
+impl Interaction {
+    /// Alter the widget color depending on the interaction.
+    fn color(&self, color: Color) -> Color {
+        match *self {
+            Interaction::Normal => color,
+            Interaction::Highlighted => color.highlighted(),
+            Interaction::Clicked => color.clicked(),
+        }
+    }
+}
+
+
+/// Check the current state of the switch.
+fn get_new_interaction(is_over: bool,
+                       prev: Interaction,
+                       mouse: Mouse) -> Interaction {
+    use mouse::ButtonState::{Down, Up};
+    use self::Interaction::{Normal, Highlighted, Clicked};
+    match (is_over, prev, mouse.left.state) {
+        (true,  Normal,  Down) => Normal,
+        (true,  _,       Down) => Clicked,
+        (true,  _,       Up)   => Highlighted,
+        (false, Clicked, Down) => Clicked,
+        _                      => Normal,
+    }
+}
+
+
+impl<'a, F> Switch<'a, F> {
+
+    /// Construct a new Switch widget.
+    pub fn new(value: bool) -> Switch<'a, F> {
+        Switch {
+            pos: Position::default(),
+            margin: Margin::none(),
+            padding: Padding::none(),
+            maybe_h_align: None,
+            maybe_v_align: None,
+            depth: 0.0,
+            maybe_react: None,
+            maybe_label: None,
+            maybe_label_spans: None,
+            value: value,
+            style: Style::new(),
+            enabled: true,
+            read_only: false,
+        }
+    }
+
+    /// Set the reaction for the Switch. It will be triggered upon release of the button.
+    pub fn react(mut self, reaction: F) -> Self {
+        self.maybe_react = Some(reaction);
+        self
+    }
+
+    /// Set the color of the Switch's thumb.
+    #[inline]
+    pub fn thumb_color(mut self, color: Color) -> Self {
+        self.style.maybe_thumb_color = Some(color);
+        self
+    }
+
+}
+
+impl<'a, F> Widget for Switch<'a, F>
+    where
+        F: FnMut(bool),
+{
+    type State = State;
+    type Style = Style;
+    fn unique_kind(&self) -> &'static str { "Switch" }
+    fn init_state(&self) -> State {
+        State {
+            value: self.value,
+            interaction: Interaction::Normal,
+            maybe_label: None,
+            maybe_label_spans: None,
+            color: black(),
+            thumb_perc: if self.value { 1.0 } else { 0.0 },
+            is_focused: false,
+            enabled: self.enabled,
+            read_only: self.read_only,
+        }
+    }
+    fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
+
+    /// Update the state of the Switch.
+    fn update<C>(mut self,
+                 prev_state: &widget::State<State>,
+                 style: &Style,
+                 ui_id: UiId,
+                 ui: &mut Ui<C>) -> widget::State<Option<State>>
+        where
+            C: CharacterCache,
+    {
+        use utils::is_over_rect;
+
+        let widget::State { ref state, .. } = *prev_state;
+        let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
+        let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
+        let dim = style.dim(&ui.theme);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let mouse = ui.get_mouse_state(ui_id);
+        let is_over = is_over_rect(xy, mouse.xy, dim);
+        let is_interactive = self.enabled && !self.read_only;
+        let new_interaction =
+            if is_interactive {
+                get_new_interaction(is_over, state.interaction, mouse)
+            } else {
+                // This Switch is disabled or read-only, pretend the interaction was normal.
+                Interaction::Normal
+            };
+
+        // Claim keyboard focus when clicked so that Space may also toggle the value.
+        if is_interactive && new_interaction == Interaction::Clicked {
+            ui.focus(ui_id);
+        }
+        let is_focused = is_interactive && ui.is_focused(ui_id);
+        let is_space_pressed = is_focused && {
+            use piston::input::keyboard::Key::Space;
+            ui.get_focused_keys(ui_id).contains(&Space)
+        };
+
+        // A value handed back by an undo or redo toggles the value if it differs.
+        let is_undo_toggled = ui.take_undone_state::<bool>(ui_id, "Switch")
+            .map_or(false, |undone| undone != self.value);
+
+        // React to a click, to Space while focused or to an undo or redo.
+        let is_clicked = match (is_over, state.interaction, new_interaction) {
+            (true, Interaction::Clicked, Interaction::Highlighted) => true,
+            _ => false,
+        };
+        let new_value = if is_clicked || is_space_pressed || is_undo_toggled {
+            let new_value = !self.value;
+            if let Some(ref mut react) = self.maybe_react { react(new_value) }
+            new_value
+        } else {
+            self.value
+        };
+
+        // Record the user's change so that it may be undone.
+        if new_value != self.value && !is_undo_toggled {
+            ui.push_change(ui_id, "Switch", &self.value, &new_value);
+        }
+
+        // Slide the thumb and fade the track color towards those of the new value.
+        let thumb_perc = ui.animate_with_theme(ui_id, "thumb_perc",
+                                               if new_value { 1.0 } else { 0.0 });
+        let target_color = {
+            let color = style.color(&ui.theme);
+            let color = new_interaction.color(if new_value { color }
+                                              else { color.with_luminance(0.1) });
+            enable::color(color, self.enabled, &ui.theme)
+        };
+        let color = ui.animate_with_theme(ui_id, "color", target_color);
+
+        // A function for constructing a new Switch State.
+        let new_state = || {
+            State {
+                maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+                value: new_value,
+                interaction: new_interaction,
+                color: color,
+                thumb_perc: thumb_perc,
+                is_focused: is_focused,
+                enabled: self.enabled,
+                read_only: self.read_only,
+            }
+        };
+
+        // Check whether or not the state has changed since the previous update.
+        let state_has_changed = state.interaction != new_interaction
+            || state.color != color
+            || state.thumb_perc != thumb_perc
+            || state.is_focused != is_focused
+            || state.enabled != self.enabled
+            || state.read_only != self.read_only
+            || state.value != self.value
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
+
+        // Construct the new state if there was a change.
+        let maybe_new_state = if state_has_changed { Some(new_state()) } else { None };
+
+        widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
+    }
+
+    /// Construct the Forms that draw the given Switch State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
+        where
+            C: CharacterCache,
+    {
+        use elmesque::form::{circle, text};
+
+        let widget::State { ref state, dim, xy, .. } = *new_state;
+
+        // Construct the frame and track forms, rounding the ends of both into semicircles.
+        let frame = style.frame(&ui.theme);
+        let frame_color = style.frame_color(&ui.theme);
+        let frame_color = if state.is_focused { frame_color.highlighted() } else { frame_color };
+        let frame_color = enable::color(frame_color, state.enabled, &ui.theme);
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let radii = CornerRadii::all(dim[0].min(dim[1]) / 2.0);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let track_form = corner::rounded_rect(inner_dim, radii.inset(frame))
+            .filled(state.color)
+            .shift(inner_offset[0], inner_offset[1]);
+
+        // Construct the thumb, inset slightly from the ends of the track.
+        const THUMB_INSET: f64 = 2.0;
+        let thumb_radius = (inner_dim[0].min(inner_dim[1]) / 2.0 - THUMB_INSET).max(0.0);
+        let thumb_travel = (inner_dim[0] / 2.0 - THUMB_INSET - thumb_radius).max(0.0);
+        let thumb_x = inner_offset[0] - thumb_travel + 2.0 * thumb_travel * state.thumb_perc;
+        let thumb_color = enable::color(style.thumb_color(&ui.theme), state.enabled, &ui.theme);
+        let thumb_form = circle(thumb_radius)
+            .filled(thumb_color)
+            .shift(thumb_x, inner_offset[1]);
+
+        // Construct the label's Forms.
+        let mut label_forms = Vec::new();
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        if let Some(ref spans) = state.maybe_label_spans {
+            label_forms = spans.forms(ui, label_color, font_size).1;
+        } else if let Some(ref label_text) = state.maybe_label {
+            use elmesque::text::Text;
+            label_forms.push(text(Text::from_string(label_text.clone())
+                                      .color(label_color)
+                                      .height(font_size as f64)));
+        }
+
+        // Centre the label within the half of the track that the thumb is sliding away from.
+        if !label_forms.is_empty() {
+            let half_w = inner_dim[0] / 2.0;
+            let side = if state.thumb_perc >= 0.5 { -1.0 } else { 1.0 };
+            let l_x = inner_offset[0] + side * half_w / 2.0;
+            let l_y = inner_offset[1];
+            label_forms = label_forms.into_iter()
+                .map(|form| form.shift(l_x.floor(), l_y.floor()))
+                .collect();
+        }
+
+        // Mark the Switch with a lock if it is read-only.
+        let lock_forms = enable::lock_forms(state.read_only, dim, frame, label_color, &ui.theme);
+
+        // Chain the Forms and shift them into position.
+        let form_chain = Some(frame_form).into_iter()
+            .chain(Some(track_form).into_iter())
+            .chain(Some(thumb_form).into_iter())
+            .chain(lock_forms.into_iter())
+            .map(|form| form.shift(xy[0], xy[1]))
+            .chain(label_forms.into_iter().map(|form| form.shift(xy[0].floor(), xy[1].floor())));
+
+        // Collect the Forms for drawing.
+        form_chain.collect()
+    }
+
+    /// Describe the Switch for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::Toggle)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(if state.value { "on" } else { "off" })
+    }
+
+}
+
+
+impl Style {
+
+    /// Construct the default Style.
+    pub fn new() -> Style {
+        Style {
+            maybe_color: None,
+            maybe_color_role: None,
+            maybe_thumb_color: None,
+            maybe_frame: None,
+            maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_label_color: None,
+            maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
+        }
+    }
+
+    /// Get the Color of the track for an Element.
+    pub fn color(&self, theme: &Theme) -> Color {
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_switch.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.palette.primary)
+            })).unwrap_or(theme.palette.primary)
+    }
+
+    /// Get the Color of the thumb for an Element.
+    pub fn thumb_color(&self, theme: &Theme) -> Color {
+        self.maybe_thumb_color.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_thumb_color.unwrap_or(theme.palette.surface)
+        })).unwrap_or(theme.palette.surface)
+    }
+
+    /// Get the frame for an Element.
+    pub fn frame(&self, theme: &Theme) -> f64 {
+        self.maybe_frame.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_frame.unwrap_or(theme.frame_width)
+        })).unwrap_or(theme.frame_width)
+    }
+
+    /// Get the frame Color for an Element.
+    pub fn frame_color(&self, theme: &Theme) -> Color {
+        self.maybe_frame_color.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_frame_color.unwrap_or(theme.frame_color)
+        })).unwrap_or(theme.frame_color)
+    }
+
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_switch.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the label Color for an Element.
+    pub fn label_color(&self, theme: &Theme) -> Color {
+        self.maybe_label_color.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_label_color.unwrap_or(theme.label_color)
+        })).unwrap_or(theme.label_color)
+    }
+
+    /// Get the label font size for an Element.
+    pub fn label_font_size(&self, theme: &Theme) -> FontSize {
+        self.maybe_label_font_size.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_label_font_size.unwrap_or(theme.font_size_small)
+        })).unwrap_or(theme.font_size_small)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [56.0, 28.0];
+        let width = self.maybe_width.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_switch.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for Switch<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, F> ReadOnly for Switch<'a, F> {
+    fn read_only(mut self, flag: bool) -> Self {
+        self.read_only = flag;
+        self
+    }
+}
+
+impl<'a, F> Colorable for Switch<'a, F> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(color);
+        self
+    }
+}
+
+impl<'a, F> Paletted for Switch<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for Switch<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
+impl<'a, F> Frameable for Switch<'a, F> {
+    fn frame(mut self, width: f64) -> Self {
+        self.style.maybe_frame = Some(width);
+        self
+    }
+    fn frame_color(mut self, color: Color) -> Self {
+        self.style.maybe_frame_color = Some(color);
+        self
+    }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Labelable<'a> for Switch<'a, F> {
+    fn label(mut self, text: &'a str) -> Self {
+        self.maybe_label = Some(text);
+        self
+    }
+
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
+    fn label_color(mut self, color: Color) -> Self {
+        self.style.maybe_label_color = Some(color);
+        self
+    }
+
+    fn label_font_size(mut self, size: FontSize) -> Self {
+        self.style.maybe_label_font_size = Some(size);
+        self
+    }
+}
+
+impl<'a, F> position::Positionable for Switch<'a, F> {
+    fn position(mut self, pos: Position) -> Self {
+        self.pos = pos;
+        self
+    }
+    #[inline]
+    fn horizontal_align(self, h_align: HorizontalAlign) -> Self {
+        Switch { maybe_h_align: Some(h_align), ..self }
+    }
+    #[inline]
+    fn vertical_align(self, v_align: VerticalAlign) -> Self {
+        Switch { maybe_v_align: Some(v_align), ..self }
+    }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
+}
+
+impl<'a, F> position::Sizeable for Switch<'a, F> {
+    #[inline]
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
+
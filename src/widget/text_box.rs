@@ -1,46 +1,67 @@
 
+use accessibility::{Accessibility, Role};
 use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use corner::{self, CornerRadii, Roundable};
+use elmesque::form::Form;
+use enable::{self, Enableable};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::character::CharacterCache;
 use label::{self, FontSize};
-use mouse::Mouse;
+use mouse::{Cursor as MouseCursor, Mouse};
 use num::Float;
+use palette::{ColorRole, Paletted};
+use piston::input::keyboard::Key;
 use piston::input::keyboard::Key::{Backspace, Left, Right, Return};
-use position::{self, Depth, Dimensions, HorizontalAlign, Point, Position, VerticalAlign};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
+use rate_limit::RateLimit;
+use text;
 use theme::Theme;
 use ui::{UiId, Ui};
-use vecmath::vec2_sub;
 use widget::{self, Widget};
 
 
 pub type Idx = usize;
 pub type CursorX = f64;
 
-const TEXT_PADDING: f64 = 5.0;
-
 
 /// A widget for displaying and mutating a given one-line text `String`. It's reaction is
 /// triggered upon pressing of the `Enter`/`Return` key.
 pub struct TextBox<'a, F> {
     text: &'a mut String,
     pos: Position,
+    margin: Margin,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
-    dim: Dimensions,
     depth: Depth,
     maybe_react: Option<F>,
+    maybe_rate_limit: Option<RateLimit>,
     style: Style,
     enabled: bool,
+    persistent: bool,
+    maybe_validator: Option<Box<Fn(&str) -> bool + 'a>>,
+    maybe_max_len: Option<usize>,
+    numeric: bool,
+    maybe_mask: Option<char>,
 }
 
 /// Styling for the TextBox, necessary for constructing its renderable Element.
 #[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_corner_radii: Option<CornerRadii>,
     pub maybe_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_padding: Option<Padding>,
+    pub maybe_font: Option<FontId>,
 }
 
 /// The State of the TextBox widget that will be cached within the Ui.
@@ -48,6 +69,17 @@ pub struct Style {
 pub struct State {
     interaction: Interaction,
     text: String,
+    /// Text being composed via an input method, displayed at the cursor until it is committed.
+    maybe_composition: Option<String>,
+    enabled: bool,
+    /// The character with which the text is displayed, if it is masked.
+    maybe_mask: Option<char>,
+    /// Whether or not the text fails the TextBox's validation, i.e. if it was set elsewhere.
+    is_invalid: bool,
+    /// The time (in nanoseconds) at which the caret last moved, from which its blinking is timed.
+    caret_moved_ns: u64,
+    /// Whether or not the blinking caret is currently shown.
+    is_caret_shown: bool,
 }
 
 /// Represents the state of the text_box widget.
@@ -159,22 +191,38 @@ impl Cursor {
 
 // widget_fns!(TextBox, State, Kind::TextBox(State::Uncaptured(Uncaptured::Normal)));
 
-/// Find the position of a character in a text box.
-fn cursor_position<C: CharacterCache>(ui: &mut Ui<C>,
-                                      idx: usize,
-                                      mut text_start_x: f64,
-                                      font_size: FontSize,
-                                      text: &str) -> CursorX {
-    assert!(idx <= text.len());
-    if idx == 0 {
-         return text_start_x;
-    }
+/// The given text with the characters from `start` to `end` replaced by `insert`.
+fn spliced(text: &str, start: Idx, end: Idx, insert: &str) -> String {
+    let start: String = text.chars().take(start).collect();
+    let end: String = text.chars().skip(end).collect();
+    start + insert + &end
+}
 
-    for (i, ch) in text.chars().enumerate() {
-        if i >= idx { break; }
-        text_start_x += ui.get_character(font_size, ch).width();
+/// The text as it is displayed, with every character replaced by the mask if there is one.
+fn displayed(text: &str, maybe_mask: Option<char>) -> String {
+    match maybe_mask {
+        Some(mask) => text.chars().map(|_| mask).collect(),
+        None => text.to_string(),
     }
-    text_start_x
+}
+
+/// Whether or not the given text is a decimal number or the beginning of one (i.e. `"-"` or
+/// `"1."`).
+fn is_numeric(text: &str) -> bool {
+    let digits = if text.starts_with('-') { &text[1..] } else { text };
+    digits.chars().all(|ch| ch.is_digit(10) || ch == '.')
+        && digits.chars().filter(|&ch| ch == '.').count() <= 1
+}
+
+/// Find the range of characters making up the word that touches the caret at the given index.
+fn word_range(text: &str, idx: Idx) -> (Idx, Idx) {
+    let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let chars: Vec<char> = text.chars().collect();
+    let mut start = ::std::cmp::min(idx, chars.len());
+    let mut end = start;
+    while start > 0 && is_word_char(chars[start - 1]) { start -= 1; }
+    while end < chars.len() && is_word_char(chars[end]) { end += 1; }
+    (start, end)
 }
 
 /// Check if cursor is over the pad and if so, which
@@ -183,13 +231,12 @@ fn over_elem<C: CharacterCache>(ui: &mut Ui<C>,
                                 dim: Dimensions,
                                 pad_dim: Dimensions,
                                 text_start_x: f64,
-                                text_w: f64,
                                 font_size: FontSize,
                                 text: &str) -> Elem {
     use utils::is_over_rect;
     if is_over_rect([0.0, 0.0], mouse_xy, dim) {
         if is_over_rect([0.0, 0.0], mouse_xy, pad_dim) {
-            let (idx, _) = closest_idx(ui, mouse_xy, text_start_x, text_w, font_size, text);
+            let (idx, _) = text::closest_idx(ui, mouse_xy[0], text_start_x, font_size, text);
             Elem::Char(idx)
         } else {
             Elem::Rect
@@ -199,29 +246,6 @@ fn over_elem<C: CharacterCache>(ui: &mut Ui<C>,
     }
 }
 
-/// Check which character is closest to the mouse cursor.
-fn closest_idx<C: CharacterCache>(ui: &mut Ui<C>,
-                                  mouse_xy: Point,
-                                  text_start_x: f64,
-                                  text_w: f64,
-                                  font_size: FontSize,
-                                  text: &str) -> (Idx, f64) {
-    if mouse_xy[0] <= text_start_x { return (0, text_start_x) }
-    let mut left_x = text_start_x;
-    let mut x = left_x;
-    let mut prev_x = x;
-    for (i, ch) in text.chars().enumerate() {
-        let character = ui.get_character(font_size, ch);
-        let char_w = character.width();
-        x += char_w;
-        let right_x = prev_x + char_w / 2.0;
-        if mouse_xy[0] > left_x && mouse_xy[0] <= right_x { return (i, prev_x) }
-        prev_x = x;
-        left_x = right_x;
-    }
-    (text.len(), text_start_x + text_w)
-}
-
 /// Check and return the current state of the TextBox.
 fn get_new_interaction(over_elem: Elem, prev_interaction: Interaction, mouse: Mouse) -> Interaction {
     use mouse::ButtonState::{Down, Up};
@@ -229,7 +253,7 @@ fn get_new_interaction(over_elem: Elem, prev_interaction: Interaction, mouse: Mo
     use self::Uncaptured::{Normal, Highlighted};
 
     match prev_interaction {
-        Interaction::Captured(mut prev) => match mouse.left {
+        Interaction::Captured(mut prev) => match mouse.left.state {
             Down => match over_elem {
                 Elem::Nill => if prev.cursor.anchor == Anchor::None {
                     Uncaptured(Normal)
@@ -257,7 +281,7 @@ fn get_new_interaction(over_elem: Elem, prev_interaction: Interaction, mouse: Mo
             },
         },
 
-        Interaction::Uncaptured(prev) => match mouse.left {
+        Interaction::Uncaptured(prev) => match mouse.left.state {
             Down => match over_elem {
                 Elem::Nill => Uncaptured(Normal),
                 Elem::Rect => match prev {
@@ -293,13 +317,19 @@ impl<'a, F> TextBox<'a, F> {
         TextBox {
             text: text,
             pos: Position::default(),
+            margin: Margin::none(),
             maybe_h_align: None,
             maybe_v_align: None,
-            dim: [192.0, 48.0],
             depth: 0.0,
             maybe_react: None,
+            maybe_rate_limit: None,
             style: Style::new(),
             enabled: true,
+            persistent: false,
+            maybe_validator: None,
+            maybe_max_len: None,
+            numeric: false,
+            maybe_mask: None,
         }
     }
 
@@ -316,12 +346,66 @@ impl<'a, F> TextBox<'a, F> {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
+    /// Set a reaction that is triggered upon pressing `Enter`/`Return` and also once the text has
+    /// stopped being edited for the given number of seconds. Useful for search-as-you-type.
+    pub fn react_debounced(mut self, secs: f64, reaction: F) -> TextBox<'a, F> {
+        self.maybe_rate_limit = Some(RateLimit::Debounce(secs));
+        self.react(reaction)
+    }
+
+    /// Set a reaction that is triggered upon pressing `Enter`/`Return` and also at most once per
+    /// the given number of seconds while the text is being edited.
+    pub fn react_throttled(mut self, secs: f64, reaction: F) -> TextBox<'a, F> {
+        self.maybe_rate_limit = Some(RateLimit::Throttle(secs));
+        self.react(reaction)
+    }
+
+    /// Record the TextBox's text within the `Ui`'s widget states so that it may be saved and
+    /// restored in a later session, at which point the restored text is written to the given
+    /// `String` and the TextBox reacts with it.
+    pub fn persistent(mut self) -> TextBox<'a, F> {
+        self.persistent = true;
         self
     }
 
+    /// Reject any edit that would leave the text failing the given function, i.e. to only accept
+    /// hexadecimal digits.
+    pub fn validator<V>(mut self, validator: V) -> TextBox<'a, F>
+        where
+            V: Fn(&str) -> bool + 'a,
+    {
+        self.maybe_validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Only accept text that forms a decimal number (or the beginning of one, so that it may be
+    /// typed).
+    pub fn numeric(mut self) -> TextBox<'a, F> {
+        self.numeric = true;
+        self
+    }
+
+    /// Reject any edit that would leave the text longer than the given number of characters.
+    pub fn max_len(mut self, max_len: usize) -> TextBox<'a, F> {
+        self.maybe_max_len = Some(max_len);
+        self
+    }
+
+    /// Display every character of the text as the given character, i.e. `'*'` for password
+    /// fields. Masked text may not be copied or cut to the clipboard.
+    pub fn mask(mut self, mask: char) -> TextBox<'a, F> {
+        self.maybe_mask = Some(mask);
+        self
+    }
+
+    /// Whether or not the given text satisfies the TextBox's maximum length, numeric mode and
+    /// validator.
+    fn accepts(&self, text: &str) -> bool {
+        self.maybe_max_len.map_or(true, |max_len| text.chars().count() <= max_len)
+            && (!self.numeric || is_numeric(text))
+            && self.maybe_validator.as_ref().map_or(true, |validator| validator(text))
+    }
+
 }
 
 impl<'a, F> Widget for TextBox<'a, F>
@@ -335,9 +419,16 @@ impl<'a, F> Widget for TextBox<'a, F>
         State {
             interaction: Interaction::Uncaptured(Uncaptured::Normal),
             text: String::new(),
+            maybe_composition: None,
+            enabled: self.enabled,
+            maybe_mask: None,
+            is_invalid: false,
+            caret_moved_ns: 0,
+            is_caret_shown: true,
         }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the TextBox.
     fn update<C>(mut self,
@@ -350,18 +441,24 @@ impl<'a, F> Widget for TextBox<'a, F>
     {
 
         let widget::State { ref state, .. } = *prev_state;
-        let dim = self.dim;
+        let dim = style.dim(&ui.theme);
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
+        let padding = style.padding(&ui.theme);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, padding, h_align, v_align);
         let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
         let frame = style.frame(&ui.theme);
+        let text_padding = style.padding(&ui.theme);
         let font_size = style.font_size(&ui.theme);
-        let pad_dim = vec2_sub(dim, [frame * 2.0; 2]);
-        let text_w = label::width(ui, font_size, &self.text);
-        let text_x = position::align_left_of(pad_dim[0], text_w) + TEXT_PADDING;
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let mask = self.maybe_mask;
+        let display_text = displayed(&self.text, mask);
+        let text_w = label::width(ui, font_size, &display_text);
+        let text_x = pad_offset[0] + position::align_left_of(pad_dim[0], text_w)
+            + text_padding.left;
         let text_start_x = text_x - text_w / 2.0;
-        let over_elem = over_elem(ui, mouse.xy, dim, pad_dim, text_start_x, text_w, font_size, &self.text);
+        let over_elem =
+            over_elem(ui, mouse.xy, dim, pad_dim, text_start_x, font_size, &display_text);
         let mut new_interaction = 
             if self.enabled {
                 get_new_interaction(over_elem, state.interaction, mouse)
@@ -369,6 +466,25 @@ impl<'a, F> Widget for TextBox<'a, F>
                 //TextBox is disabled, so pretend the interaction is normal
                 Interaction::Uncaptured(Uncaptured::Normal)
             };
+        if let (true, Elem::Char(_)) = (self.enabled, over_elem) {
+            ui.set_mouse_cursor(ui_id, MouseCursor::IBeam);
+        }
+
+        // A double click selects the word beneath the mouse and a triple click selects all text.
+        if let (Interaction::Captured(mut view), Elem::Char(idx)) = (new_interaction, over_elem) {
+            if let ::mouse::ButtonState::Down = mouse.left.state {
+                let maybe_range = match mouse.left.clicks {
+                    2 => Some(word_range(&self.text, idx)),
+                    3 => Some((0, self.text.chars().count())),
+                    _ => None,
+                };
+                if let Some((start, end)) = maybe_range {
+                    view.cursor = Cursor::from_range(start, end);
+                    view.cursor.anchor = Anchor::None;
+                    new_interaction = Interaction::Captured(view);
+                }
+            }
+        }
 
         // Check cursor validity (and update new_interaction if necessary).
         if let Interaction::Captured(view) = new_interaction {
@@ -376,18 +492,18 @@ impl<'a, F> Widget for TextBox<'a, F>
             let mut v_offset = view.offset;
 
             // Ensure the cursor is still valid.
-            cursor.limit_end_to(self.text.len());
+            cursor.limit_end_to(self.text.chars().count());
 
             // This matters if the text is scrolled with the mouse.
             let cursor_idx = match cursor.anchor {
                 Anchor::End => cursor.start,
                 Anchor::Start | Anchor::None => cursor.end,
             };
-            let cursor_x = cursor_position(ui, cursor_idx, text_start_x, font_size, &self.text);
+            let cursor_x = text::caret_x(ui, cursor_idx, text_start_x, font_size, &display_text);
 
             if cursor.is_cursor() || cursor.anchor != Anchor::None {
                 let cursor_x_view = cursor_x - v_offset;
-                let text_right = self.dim[0] - TEXT_PADDING - frame;
+                let text_right = dim[0] - text_padding.right - frame;
 
                 if cursor_x_view < text_x {
                     v_offset += cursor_x_view - text_x;
@@ -400,45 +516,83 @@ impl<'a, F> Widget for TextBox<'a, F>
             new_interaction = Interaction::Captured(View { cursor: cursor, offset: v_offset });
         }
 
+        // Keep track of any edits so that rate limited reactions may be triggered by them.
+        let text_before_input = self.text.clone();
+
         // If TextBox is captured, check for recent input and update the text accordingly.
         if let Interaction::Captured(captured) = new_interaction {
             let mut cursor = captured.cursor;
+            let is_ctrl_down = ui.is_ctrl_down();
 
-            // Check for entered text.
+            // Check for entered text. Text entered while control is held belongs to a shortcut.
             for text in ui.get_entered_text(ui_id).to_vec().iter() {
-                if text.len() == 0 { continue; }
-
-                let max_w = pad_dim[0] - TEXT_PADDING * 2.0;
-                if text_w + label::width(ui, font_size, &text) > max_w { continue; }
-
-                let end: String = self.text.chars().skip(cursor.end).collect();
-                self.text.truncate(cursor.start);
-                self.text.push_str(&text);
-                self.text.push_str(&end);
-                cursor.shift(text.len() as i32);
+                if text.len() == 0 || is_ctrl_down { continue; }
+                // Some backends emit control characters (i.e. backspace) as text.
+                if text.chars().any(|ch| ch.is_control()) { continue; }
+
+                let max_w = pad_dim[0] - text_padding.left - text_padding.right;
+                let new_text = spliced(&self.text, cursor.start, cursor.end, text);
+                if label::width(ui, font_size, &displayed(&new_text, mask)) > max_w { continue; }
+                if !self.accepts(&new_text) { continue; }
+
+                *self.text = new_text;
+                cursor.end = cursor.start;
+                cursor.shift(text.chars().count() as i32);
             }
 
             // Check for control keys.
-            let pressed_keys = ui.get_pressed_keys(ui_id);
+            let pressed_keys = ui.get_pressed_keys(ui_id).to_vec();
             for key in pressed_keys.iter() {
                 match *key {
+                    Key::A if is_ctrl_down => {
+                        cursor = Cursor::from_range(0, self.text.chars().count());
+                        cursor.anchor = Anchor::None;
+                    },
+                    Key::C | Key::X if is_ctrl_down => if !cursor.is_cursor() && mask.is_none() {
+                        let selected: String = self.text.chars()
+                            .skip(cursor.start)
+                            .take(cursor.end - cursor.start)
+                            .collect();
+                        ui.clipboard().set(selected);
+                        if *key == Key::X {
+                            let new_text = spliced(&self.text, cursor.start, cursor.end, "");
+                            if self.accepts(&new_text) {
+                                *self.text = new_text;
+                                cursor.end = cursor.start;
+                            }
+                        }
+                    },
+                    Key::V if is_ctrl_down => if let Some(pasted) = ui.clipboard().get() {
+                        // The TextBox only displays a single line, so strip any line breaks.
+                        let pasted: String = pasted.chars().filter(|ch| !ch.is_control()).collect();
+                        let new_text = spliced(&self.text, cursor.start, cursor.end, &pasted);
+                        let max_w = pad_dim[0] - text_padding.left - text_padding.right;
+                        if label::width(ui, font_size, &displayed(&new_text, mask)) <= max_w
+                        && self.accepts(&new_text) {
+                            *self.text = new_text;
+                            cursor.end = cursor.start;
+                            cursor.shift(pasted.chars().count() as i32);
+                        }
+                    },
                     Backspace => if cursor.is_cursor() {
                         if cursor.start > 0 {
-                            let end: String = self.text.chars().skip(cursor.end).collect();
-                            self.text.truncate(cursor.start - 1);
-                            self.text.push_str(&end);
-                            cursor.shift(-1);
+                            let new_text = spliced(&self.text, cursor.start - 1, cursor.end, "");
+                            if self.accepts(&new_text) {
+                                *self.text = new_text;
+                                cursor.shift(-1);
+                            }
                         }
                     } else {
-                        let end: String = self.text.chars().skip(cursor.end).collect();
-                        self.text.truncate(cursor.start);
-                        self.text.push_str(&end);
-                        cursor.end = cursor.start;
+                        let new_text = spliced(&self.text, cursor.start, cursor.end, "");
+                        if self.accepts(&new_text) {
+                            *self.text = new_text;
+                            cursor.end = cursor.start;
+                        }
                     },
                     Left => if cursor.is_cursor() {
                         cursor.shift(-1);
                     },
-                    Right => if cursor.is_cursor() && self.text.len() > cursor.end {
+                    Right => if cursor.is_cursor() && self.text.chars().count() > cursor.end {
                         cursor.shift(1);
                     },
                     Return => if self.text.len() > 0 {
@@ -454,6 +608,45 @@ impl<'a, F> Widget for TextBox<'a, F>
             new_interaction = Interaction::Captured(View { cursor: cursor, .. captured });
         }
 
+        // Record the user's edits so that they may be undone, then adopt any text handed back by an
+        // undo or redo.
+        if text_before_input != *self.text {
+            ui.push_change(ui_id, "TextBox", &text_before_input, &*self.text);
+        }
+        if let Some(undone) = ui.take_undone_state::<String>(ui_id, "TextBox") {
+            let TextBox { ref mut maybe_react, ref mut text, .. } = self;
+            **text = undone;
+            if let Some(ref mut react) = *maybe_react {
+                react(*text);
+            }
+        }
+
+        // Adopt the text restored from a previous session and record the text to be saved for the
+        // next.
+        if self.persistent {
+            if let Some(restored) = ui.take_restored_widget_state::<String>(ui_id, "TextBox") {
+                let TextBox { ref mut maybe_react, ref mut text, .. } = self;
+                **text = restored;
+                if let Some(ref mut react) = *maybe_react {
+                    react(*text);
+                }
+            }
+            ui.persist_widget_state(ui_id, "TextBox", &*self.text);
+        }
+
+        // If the reaction is rate limited, it is also triggered by edits to the text. The edited
+        // text is submitted so that each edit counts as a change.
+        if let Some(limit) = self.maybe_rate_limit {
+            let maybe_edit = if text_before_input != *self.text { Some(self.text.clone()) }
+                             else { None };
+            if ui.rate_limit(ui_id, limit, maybe_edit).is_some() {
+                let TextBox { ref mut maybe_react, ref mut text, .. } = self;
+                if let Some(ref mut react) = *maybe_react {
+                    react(*text);
+                }
+            }
+        }
+
         // Check whether or not we need to capture or uncapture the keyboard.
         match (state.interaction, new_interaction) {
             (Interaction::Uncaptured(_), Interaction::Captured(_)) =>
@@ -463,17 +656,45 @@ impl<'a, F> Widget for TextBox<'a, F>
             _ => (),
         }
 
+        // The caret blinks on the `Ui`'s clock, restarting whenever it moves or the text changes.
+        let now_ns = ui.time_ns();
+        let caret_moved_ns = match (state.interaction, new_interaction) {
+            (Interaction::Captured(prev), Interaction::Captured(view))
+                if prev.cursor == view.cursor && &state.text[..] == &self.text[..] =>
+                    state.caret_moved_ns,
+            (_, Interaction::Captured(_)) => now_ns,
+            (_, Interaction::Uncaptured(_)) => state.caret_moved_ns,
+        };
+        let is_caret_shown = match new_interaction {
+            Interaction::Captured(_) => text::is_caret_shown(caret_moved_ns, now_ns),
+            Interaction::Uncaptured(_) => true,
+        };
+
         // Function for constructing a new state.
+        let maybe_composition = ui.get_composition(ui_id).map(|comp| comp.to_string());
+        let is_invalid = !self.accepts(&self.text);
         let new_state = || {
             State {
                 interaction: new_interaction,
                 text: self.text.clone(),
+                maybe_composition: maybe_composition.clone(),
+                enabled: self.enabled,
+                maybe_mask: mask,
+                is_invalid: is_invalid,
+                caret_moved_ns: caret_moved_ns,
+                is_caret_shown: is_caret_shown,
             }
         };
 
         // Check whether or not the state has changed since the previous update.
         let state_has_changed = state.interaction != new_interaction
-            || &state.text[..] != &self.text[..];
+            || &state.text[..] != &self.text[..]
+            || state.maybe_composition != maybe_composition
+            || state.enabled != self.enabled
+            || state.maybe_mask != mask
+            || state.is_invalid != is_invalid
+            || state.caret_moved_ns != caret_moved_ns
+            || state.is_caret_shown != is_caret_shown;
 
         // Construct the new state if there was a change.
         let maybe_new_state = if state_has_changed { Some(new_state()) } else { None };
@@ -482,26 +703,47 @@ impl<'a, F> Widget for TextBox<'a, F>
     }
 
 
-    /// Construct an Element from the given TextBox State.
-    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given TextBox State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{collage, line, rect, solid, text};
+        use elmesque::form::text;
         use elmesque::text::Text;
 
         let widget::State { ref state, dim, xy, .. } = *new_state;
 
         // Construct the frame and inner rectangle Forms.
         let frame = style.frame(&ui.theme);
-        let pad_dim = vec2_sub(dim, [frame * 2.0; 2]);
+        let text_padding = style.padding(&ui.theme);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
         let color = state.interaction.color(style.color(&ui.theme));
-        let frame_color = style.frame_color(&ui.theme);
-        let frame_form = rect(dim[0], dim[1]).filled(frame_color);
-        let inner_form = rect(pad_dim[0], pad_dim[1]).filled(color);
+        let color = enable::color(color, state.enabled, &ui.theme);
+        let frame_color = if state.is_invalid { ui.theme.palette.error }
+                          else { style.frame_color(&ui.theme) };
+        let frame_color = enable::color(frame_color, state.enabled, &ui.theme);
+        let radii = style.corner_radii(&ui.theme);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let inner_form = corner::rounded_rect(pad_dim, radii.inset(frame)).filled(color)
+            .shift(pad_offset[0], pad_offset[1]);
         let font_size = style.font_size(&ui.theme);
-        let text_w = label::width(ui, font_size, &state.text[..]);
-        let text_x = position::align_left_of(pad_dim[0], text_w) + TEXT_PADDING;
+
+        // Any text being composed via an input method is displayed at the cursor.
+        let maybe_composition = match state.interaction {
+            Interaction::Captured(view) if view.cursor.is_cursor() =>
+                state.maybe_composition.as_ref().map(|comp| (view.cursor.start, comp)),
+            _ => None,
+        };
+        let display_text = match maybe_composition {
+            Some((idx, composition)) => spliced(&state.text, idx, idx, composition),
+            None => state.text.clone(),
+        };
+        let display_text = displayed(&display_text, state.maybe_mask);
+
+        let text_w = label::width(ui, font_size, &display_text[..]);
+        let text_x = pad_offset[0] + position::align_left_of(pad_dim[0], text_w)
+            + text_padding.left;
         let text_start_x = text_x - text_w / 2.0;
 
         let (maybe_cursor_form, text_form) = if let Interaction::Captured(view) = state.interaction {
@@ -513,49 +755,64 @@ impl<'a, F> Widget for TextBox<'a, F>
                 Anchor::End => cursor.start,
                 Anchor::Start | Anchor::None => cursor.end,
             };
-            let cursor_x = cursor_position(ui, cursor_idx, text_start_x, font_size, &state.text);
-
-            let cursor_form = if cursor.is_cursor() {
-                let half_pad_h = pad_dim[1] / 2.0;
-                line(solid(color.plain_contrast()), 0.0, half_pad_h, 0.0, -half_pad_h)
-                    .alpha(0.75)
-                    .shift_x(cursor_x)
+            let composition_len = maybe_composition.map(|(_, comp)| comp.chars().count());
+            let cursor_idx = cursor_idx + composition_len.unwrap_or(0);
+            let cursor_x = text::caret_x(ui, cursor_idx, text_start_x, font_size, &display_text);
+
+            let maybe_cursor_form = if cursor.is_cursor() {
+                if state.is_caret_shown {
+                    let caret = text::caret_form(cursor_x, pad_dim[1], color.plain_contrast());
+                    Some(caret.alpha(0.75))
+                } else {
+                    None
+                }
             } else {
-                let (block_xy, dim) = {
-                    let (start, end) = (cursor.start, cursor.end);
-                    let cursor_x = cursor_position(ui, start, text_start_x, font_size, &state.text);
-                    let htext: String = state.text.chars().skip(start).take(end - start).collect();
-                    let htext_w = label::width(ui, font_size, &htext);
-                    ([cursor_x + htext_w / 2.0, 0.0], [htext_w, dim[1]])
-                };
-                rect(dim[0], dim[1] - frame * 2.0).filled(color.highlighted())
-                    .shift(block_xy[0], block_xy[1])
+                let (start, end) = (cursor.start, cursor.end);
+                let (left, right) =
+                    text::selection_x(ui, start, end, text_start_x, font_size, &display_text);
+                Some(text::selection_form(left, right, pad_dim[1], color.highlighted()))
             };
 
             // Construct the text's Form.
-            let text_form = text(Text::from_string(state.text.clone())
+            let text_form = text(Text::from_string(display_text.clone())
                                      .color(color.plain_contrast())
                                      .height(font_size as f64)).shift_x(text_x.floor());
 
-            (Some(cursor_form), text_form)
+            (maybe_cursor_form, text_form)
         } else {
 
             // Construct the text's Form.
-            let text_form = text(Text::from_string(state.text.clone())
+            let text_form = text(Text::from_string(display_text.clone())
                                      .color(color.plain_contrast())
                                      .height(font_size as f64)).shift_x(text_x.floor());
             (None, text_form)
         };
- 
+
+        // Underline the composed text to distinguish it from the committed text.
+        let maybe_composition_form = maybe_composition.map(|(idx, composition)| {
+            use elmesque::form::{line, solid};
+            let end_idx = idx + composition.chars().count();
+            let (left, right) =
+                text::selection_x(ui, idx, end_idx, text_start_x, font_size, &display_text);
+            let y = -(font_size as f64) / 2.0;
+            line(solid(color.plain_contrast()), left, y, right, y)
+        });
+
         // Chain the Forms and shift them into position.
         let form_chain = Some(frame_form).into_iter()
             .chain(Some(inner_form).into_iter())
             .chain(maybe_cursor_form.into_iter())
             .chain(Some(text_form).into_iter())
+            .chain(maybe_composition_form.into_iter())
             .map(|form| form.shift(xy[0], xy[1]));
 
         // Collect the Forms into a renderable `Element`.
-        collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+        form_chain.collect()
+    }
+
+    /// Describe the TextBox for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        Accessibility::new(Role::TextBox).value(&displayed(&state.text, state.maybe_mask)[..])
     }
 
 }
@@ -566,17 +823,28 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
             maybe_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_padding: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_text_box.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_text_box.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -593,6 +861,34 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_text_box.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_text_box.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_text_box.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of an Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_text_box.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
     /// Get the label font size for an Element.
     pub fn font_size(&self, theme: &Theme) -> FontSize {
         const DEFAULT_FONT_SIZE: u32 = 24;
@@ -601,6 +897,40 @@ impl Style {
         })).unwrap_or(DEFAULT_FONT_SIZE)
     }
 
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [192.0, 48.0];
+        let width = self.maybe_width.or(theme.maybe_text_box.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_text_box.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the padding between the edge of the Element and its text.
+    pub fn padding(&self, theme: &Theme) -> Padding {
+        const DEFAULT_PADDING: f64 = 5.0;
+        self.maybe_padding.or(theme.maybe_text_box.as_ref().map(|style| {
+            style.maybe_padding.unwrap_or(Padding::uniform(DEFAULT_PADDING))
+        })).unwrap_or(Padding::uniform(DEFAULT_PADDING))
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_text_box.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+impl<'a, F> Enableable for TextBox<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
 }
 
 impl<'a, F> Colorable for TextBox<'a, F> {
@@ -610,6 +940,20 @@ impl<'a, F> Colorable for TextBox<'a, F> {
     }
 }
 
+impl<'a, F> Paletted for TextBox<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for TextBox<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, F> Frameable for TextBox<'a, F> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -619,6 +963,25 @@ impl<'a, F> Frameable for TextBox<'a, F> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Roundable for TextBox<'a, F> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
 }
 
 impl<'a, F> position::Positionable for TextBox<'a, F> {
@@ -634,17 +997,72 @@ impl<'a, F> position::Positionable for TextBox<'a, F> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         TextBox { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl<'a, F> position::Sizeable for TextBox<'a, F> {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        TextBox { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        TextBox { dim: [w, h], ..self }
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
     }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.style.maybe_padding = Some(padding.into());
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{TextBox, displayed, is_numeric, spliced};
+
+    #[test]
+    fn splicing_replaces_the_range_of_characters() {
+        assert_eq!(spliced("hello", 1, 4, "ipp"), "hippo");
+        assert_eq!(spliced("hello", 5, 5, "!"), "hello!");
+        assert_eq!(spliced("h\u{e9}llo", 1, 2, "a"), "hallo");
+    }
+
+    #[test]
+    fn masked_text_hides_every_character() {
+        assert_eq!(displayed("s\u{e9}cret", Some('*')), "******");
+        assert_eq!(displayed("secret", None), "secret");
+    }
+
+    #[test]
+    fn partial_numbers_are_numeric() {
+        for text in &["", "-", "1.", "-0.5", "42"] {
+            assert!(is_numeric(text), "{:?} should be numeric", text);
+        }
+        for text in &["1.2.3", "--1", "1-", "e", "1,5"] {
+            assert!(!is_numeric(text), "{:?} should not be numeric", text);
+        }
+    }
+
+    #[test]
+    fn edits_must_satisfy_every_constraint() {
+        let mut text = String::new();
+        let text_box: TextBox<fn(&mut String)> = TextBox::new(&mut text).numeric().max_len(3);
+        assert!(text_box.accepts("1.5"));
+        assert!(!text_box.accepts("1.25"));
+        assert!(!text_box.accepts("abc"));
+
+        let mut text = String::new();
+        let text_box: TextBox<fn(&mut String)> = TextBox::new(&mut text)
+            .validator(|text: &str| text.chars().all(|ch| ch.is_digit(16)));
+        assert!(text_box.accepts("c0ffee"));
+        assert!(!text_box.accepts("coffee"));
+    }
+
 }
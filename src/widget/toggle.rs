@@ -1,40 +1,69 @@
 
-use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use accessibility::{Accessibility, Role};
+use color::{black, Color, Colorable};
+use corner::{CornerRadii, Roundable};
+use gradient::{self, Gradient, Gradientable};
+use elmesque::form::Form;
+use enable::{self, Enableable, ReadOnly};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::character::CharacterCache;
-use label::{FontSize, Labelable};
+use label::{self, FontSize, Labelable};
 use mouse::Mouse;
-use position::{self, Depth, Dimensions, HorizontalAlign, Position, VerticalAlign};
+use palette::{ColorRole, Paletted};
+use position::{self, Depth, Dimensions, HorizontalAlign, Margin, Padding, Place, Position,
+               VerticalAlign};
 use theme::Theme;
 use ui::{UiId, Ui};
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// A pressable widget for toggling the state of a bool. Like the button widget, it's reaction is
 /// triggered upon release and will return the new bool state. Note that the toggle will not
 /// mutate the bool for you, you should do this yourself within the react closure.
+///
+/// If the label marks an accelerator with an `&` (i.e. `"&Mute"`), pressing Alt and the marked
+/// letter will also toggle the value.
+///
+/// Clicking the Toggle gives it keyboard focus, after which pressing Space will also toggle the
+/// value.
 pub struct Toggle<'a, F> {
     pos: Position,
-    dim: Dimensions,
+    margin: Margin,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
     depth: Depth,
     value: bool,
     maybe_react: Option<F>,
     maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
     style: Style,
     enabled: bool,
+    read_only: bool,
+    persistent: bool,
 }
 
 /// Styling for the Toggle, necessary for constructing its renderable Element.
 #[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
+    pub maybe_corner_radii: Option<CornerRadii>,
+    pub maybe_gradient: Option<Gradient>,
     pub maybe_label_color: Option<Color>,
     pub maybe_label_font_size: Option<u32>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_padding: Option<Padding>,
+    pub maybe_label_position: Option<Place>,
+    pub maybe_auto_size: Option<bool>,
+    pub maybe_font: Option<FontId>,
 }
 
 /// The way in which the Toggle is being interacted with.
@@ -51,13 +80,23 @@ pub struct State {
     value: bool,
     interaction: Interaction,
     maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
+    /// The current color of the pressable area, animated between values and interactions.
+    color: Color,
+    /// Whether or not the label's accelerator should be underlined.
+    show_mnemonic: bool,
+    /// Whether or not the Toggle has keyboard focus.
+    is_focused: bool,
+    /// Whether or not the Toggle accepts user input.
+    enabled: bool,
+    read_only: bool,
 }
 
 
-impl State {
-    /// Alter the widget color depending on the state.
+impl Interaction {
+    /// Alter the widget color depending on the interaction.
     fn color(&self, color: Color) -> Color {
-        match self.interaction {
+        match *self {
             Interaction::Normal => color,
             Interaction::Highlighted => color.highlighted(),
             Interaction::Clicked => color.clicked(),
@@ -72,7 +111,7 @@ fn get_new_interaction(is_over: bool,
                        mouse: Mouse) -> Interaction {
     use mouse::ButtonState::{Down, Up};
     use self::Interaction::{Normal, Highlighted, Clicked};
-    match (is_over, prev, mouse.left) {
+    match (is_over, prev, mouse.left.state) {
         (true,  Normal,  Down) => Normal,
         (true,  _,       Down) => Clicked,
         (true,  _,       Up)   => Highlighted,
@@ -88,15 +127,18 @@ impl<'a, F> Toggle<'a, F> {
     pub fn new(value: bool) -> Toggle<'a, F> {
         Toggle {
             pos: Position::default(),
-            dim: [64.0, 64.0],
+            margin: Margin::none(),
             maybe_h_align: None,
             maybe_v_align: None,
             depth: 0.0,
             maybe_react: None,
             maybe_label: None,
+            maybe_label_spans: None,
             value: value,
             style: Style::new(),
             enabled: true,
+            read_only: false,
+            persistent: false,
         }
     }
 
@@ -106,9 +148,27 @@ impl<'a, F> Toggle<'a, F> {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
+    /// Set the Place of the label within the widget.
+    #[inline]
+    pub fn label_position(mut self, place: Place) -> Self {
+        self.style.maybe_label_position = Some(place);
+        self
+    }
+
+    /// Size the Toggle to fit its label (plus its padding) rather than using its default
+    /// dimensions. A width or height that is set explicitly is still used.
+    #[inline]
+    pub fn auto_size(mut self) -> Self {
+        self.style.maybe_auto_size = Some(true);
+        self
+    }
+
+    /// Record the Toggle's value within the `Ui`'s widget states so that it may be saved and
+    /// restored in a later session, at which point the Toggle reacts if the restored value
+    /// differs from its own.
+    #[inline]
+    pub fn persistent(mut self) -> Self {
+        self.persistent = true;
         self
     }
 
@@ -126,14 +186,21 @@ impl<'a, F> Widget for Toggle<'a, F>
             value: self.value,
             interaction: Interaction::Normal,
             maybe_label: None,
+            maybe_label_spans: None,
+            color: black(),
+            show_mnemonic: false,
+            is_focused: false,
+            enabled: self.enabled,
+            read_only: self.read_only,
         }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the state of the Toggle.
     fn update<C>(mut self,
                  prev_state: &widget::State<State>,
-                 _style: &Style,
+                 style: &Style,
                  ui_id: UiId,
                  ui: &mut Ui<C>) -> widget::State<Option<State>>
         where
@@ -144,20 +211,69 @@ impl<'a, F> Widget for Toggle<'a, F>
         let widget::State { ref state, .. } = *prev_state;
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let dim = self.dim;
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
+        let dim = if style.auto_size(&ui.theme) {
+            let size = style.label_font_size(&ui.theme);
+            let padding = style.padding(&ui.theme);
+            let maybe_spans = self.maybe_label_spans.as_ref();
+            let fit = label::fit_dim(ui, self.maybe_label, maybe_spans, size, padding);
+            [style.maybe_width.unwrap_or(fit[0]), style.maybe_height.unwrap_or(fit[1])]
+        } else {
+            style.dim(&ui.theme)
+        };
+        let padding = style.padding(&ui.theme);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, padding, h_align, v_align);
         let mouse = ui.get_mouse_state(ui_id);
         let is_over = is_over_rect(xy, mouse.xy, dim);
+        let is_interactive = self.enabled && !self.read_only;
         let new_interaction = 
-            if self.enabled {
+            if is_interactive {
                 get_new_interaction(is_over, state.interaction, mouse)
             } else {
-                //This Toggle is disabled, pretend the interaction was normal.
+                //This Toggle is disabled or read-only, pretend the interaction was normal.
                 Interaction::Normal
             };
 
-        // React.
+        // Claim keyboard focus when clicked so that Space may also toggle the value, reporting the
+        // Toggle if an external control binding is being learned.
+        if is_interactive && new_interaction == Interaction::Clicked {
+            ui.focus(ui_id);
+            ui.binding_interacted(ui_id);
+        }
+        let is_focused = is_interactive && ui.is_focused(ui_id);
+        let is_space_pressed = is_focused && {
+            use piston::input::keyboard::Key::Space;
+            ui.get_focused_keys(ui_id).contains(&Space)
+        };
+
+        // A bound external control turns the Toggle on from halfway through its range.
+        let is_control_toggled = match ui.take_control_value(ui_id) {
+            Some(control) if is_interactive => (control >= 0.5) != self.value,
+            _ => false,
+        };
+
+        // A value restored from a previous session toggles the value if it differs.
+        let is_restore_toggled = self.persistent && {
+            ui.take_restored_widget_state::<bool>(ui_id, "Toggle")
+                .map_or(false, |restored| restored != self.value)
+        };
+
+        // A value handed back by an undo or redo toggles the value if it differs.
+        let is_undo_toggled = ui.take_undone_state::<bool>(ui_id, "Toggle")
+            .map_or(false, |undone| undone != self.value);
+
+        // React to a click, to Space while focused, to the label's accelerator, to a bound
+        // external control, to a restored value or to an undo or redo.
+        let maybe_mnemonic = self.maybe_label.and_then(label::mnemonic_char);
+        let show_mnemonic = is_interactive && maybe_mnemonic.is_some() && ui.is_alt_down();
+        let is_mnemonic_pressed =
+            is_interactive && maybe_mnemonic.map_or(false, |ch| ui.is_mnemonic_pressed(ui_id, ch));
         let new_value = match (is_over, state.interaction, new_interaction) {
+            _ if is_mnemonic_pressed || is_space_pressed || is_control_toggled
+                || is_restore_toggled || is_undo_toggled => {
+                let new_value = !self.value;
+                if let Some(ref mut react) = self.maybe_react { react(new_value) }
+                new_value
+            },
             (true, Interaction::Clicked, Interaction::Highlighted) => {
                 let new_value = !self.value;
                 if let Some(ref mut react) = self.maybe_react { react(!self.value) }
@@ -166,19 +282,48 @@ impl<'a, F> Widget for Toggle<'a, F>
             _ => self.value,
         };
 
+        // Record the user's change so that it may be undone.
+        if new_value != self.value && !is_restore_toggled && !is_undo_toggled {
+            ui.push_change(ui_id, "Toggle", &self.value, &new_value);
+        }
+        if self.persistent {
+            ui.persist_widget_state(ui_id, "Toggle", &new_value);
+        }
+
+        // Fade the color towards that of the new value and interaction.
+        let target_color = {
+            let color = style.color(&ui.theme);
+            let color = new_interaction.color(if new_value { color }
+                                              else { color.with_luminance(0.1) });
+            enable::color(color, self.enabled, &ui.theme)
+        };
+        let color = ui.animate_with_theme(ui_id, "color", target_color);
+
         // A function for constructing a new Toggle State.
         let new_state = || {
             State {
                 maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
                 value: new_value,
                 interaction: new_interaction,
+                color: color,
+                show_mnemonic: show_mnemonic,
+                is_focused: is_focused,
+                enabled: self.enabled,
+                read_only: self.read_only,
             }
         };
 
         // Check whether or not the state has changed since the previous update.
         let state_has_changed = state.interaction != new_interaction
+            || state.color != color
+            || state.show_mnemonic != show_mnemonic
+            || state.is_focused != is_focused
+            || state.enabled != self.enabled
+            || state.read_only != self.read_only
             || state.value != self.value
-            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label;
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
 
         // Construct the new state if there was a change.
         let maybe_new_state = if state_has_changed { Some(new_state()) } else { None };
@@ -186,42 +331,85 @@ impl<'a, F> Widget for Toggle<'a, F>
         widget::State { state: maybe_new_state, dim: dim, xy: xy, depth: self.depth }
     }
 
-    /// Construct an Element from the given Toggle State.
-    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given Toggle State.
+    fn draw<C>(new_state: &widget::State<State>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{collage, rect, text};
+        use elmesque::form::text;
 
         let widget::State { ref state, dim, xy, .. } = *new_state;
 
         // Construct the frame and pressable forms.
         let frame = style.frame(&ui.theme);
         let frame_color = style.frame_color(&ui.theme);
-        let (inner_w, inner_h) = (dim[0] - frame * 2.0, dim[1] - frame * 2.0);
-        let frame_form = rect(dim[0], dim[1]).filled(frame_color);
-        let color = style.color(&ui.theme);
-        let color = state.color(if state.value { color }
-                                    else { color.with_luminance(0.1) });
-        let pressable_form = rect(inner_w, inner_h).filled(color);
-
-        // Construct the label's Form.
-        let maybe_label_form = state.maybe_label.as_ref().map(|label_text| {
+        let frame_color = if state.is_focused { frame_color.highlighted() } else { frame_color };
+        let frame_color = enable::color(frame_color, state.enabled, &ui.theme);
+        let (inner_offset, inner_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let radii = style.corner_radii(&ui.theme);
+        let frame_form = frame::form(dim, radii, style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
+        let maybe_gradient = style.gradient(&ui.theme);
+        let pressable_form =
+            gradient::filled_rect(inner_dim, radii.inset(frame), state.color, maybe_gradient)
+                .shift(inner_offset[0], inner_offset[1]);
+
+        // Construct the label's Forms.
+        let mut label_forms = Vec::new();
+        let label_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let mut label_dim = [0.0, font_size as f64];
+        if let Some(ref spans) = state.maybe_label_spans {
+            let (spans_dim, spans_forms) = spans.forms(ui, label_color, font_size);
+            label_dim = spans_dim;
+            label_forms = spans_forms;
+        } else if let Some(ref label_text) = state.maybe_label {
             use elmesque::text::Text;
-            let label_color = style.label_color(&ui.theme);
-            let font_size = style.label_font_size(&ui.theme) as f64;
-            text(Text::from_string(label_text.clone()).color(label_color).height(font_size))
-                .shift(xy[0].floor(), xy[1].floor())
-        });
+            let (label_text, maybe_mnemonic_idx) = label::strip_mnemonic(label_text);
+            if let (true, Some(idx)) = (state.show_mnemonic, maybe_mnemonic_idx) {
+                let underline = label::mnemonic_underline(ui, &label_text, idx, font_size,
+                                                          label_color);
+                label_forms.push(underline);
+            }
+            label_dim[0] = label::width(ui, font_size, &label_text);
+            label_forms.push(text(Text::from_string(label_text)
+                                      .color(label_color)
+                                      .height(font_size as f64)));
+        }
+
+        // Shift the label into its place within the padded area of the toggle.
+        if !label_forms.is_empty() {
+            let padding = style.padding(&ui.theme);
+            let place = style.label_position(&ui.theme);
+            let padded_dim = padding.inner_dim(dim);
+            let l_pos = ::vecmath::vec2_add(position::place_within(place, padded_dim, label_dim),
+                                            padding.inner_offset());
+            label_forms = label_forms.into_iter()
+                .map(|form| form.shift(l_pos[0].floor(), l_pos[1].floor()))
+                .collect();
+        }
+
+        // Mark the Toggle with a lock if it is read-only.
+        let lock_forms = enable::lock_forms(state.read_only, dim, frame, label_color, &ui.theme);
 
         // Chain the Forms and shift them into position.
         let form_chain = Some(frame_form).into_iter()
             .chain(Some(pressable_form).into_iter())
+            .chain(lock_forms.into_iter())
             .map(|form| form.shift(xy[0], xy[1]))
-            .chain(maybe_label_form.into_iter());
+            .chain(label_forms.into_iter().map(|form| form.shift(xy[0].floor(), xy[1].floor())));
+
+        // Collect the Forms for drawing.
+        form_chain.collect()
+    }
 
-        // Collect the Forms into a renderable Element.
-        collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+    /// Describe the Toggle for assistive technologies.
+    fn accessibility(state: &State) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::Toggle)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(if state.value { "on" } else { "off" })
     }
 
 }
@@ -233,18 +421,32 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
+            maybe_corner_radii: None,
+            maybe_gradient: None,
             maybe_label_color: None,
             maybe_label_font_size: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_padding: None,
+            maybe_label_position: None,
+            maybe_auto_size: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_toggle.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_toggle.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -261,6 +463,41 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_toggle.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
+    /// Get the radii of the corners of an Element.
+    pub fn corner_radii(&self, theme: &Theme) -> CornerRadii {
+        self.maybe_corner_radii.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_corner_radii.unwrap_or(theme.corner_radii)
+        })).unwrap_or(theme.corner_radii)
+    }
+
+    /// Get the Gradient with which the Element is filled, if there is one.
+    pub fn gradient(&self, theme: &Theme) -> Option<Gradient> {
+        self.maybe_gradient
+            .or(theme.maybe_toggle.as_ref().and_then(|style| style.maybe_gradient))
+            .or(theme.maybe_gradient)
+    }
+
     /// Get the label Color for an Element.
     pub fn label_color(&self, theme: &Theme) -> Color {
         self.maybe_label_color.or(theme.maybe_toggle.as_ref().map(|style| {
@@ -275,8 +512,63 @@ impl Style {
         })).unwrap_or(theme.font_size_medium)
     }
 
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [64.0, 64.0];
+        let width = self.maybe_width.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the padding between the edge of the Element and its text.
+    pub fn padding(&self, theme: &Theme) -> Padding {
+        const DEFAULT_PADDING: f64 = 10.0;
+        self.maybe_padding.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_padding.unwrap_or(Padding::uniform(DEFAULT_PADDING))
+        })).unwrap_or(Padding::uniform(DEFAULT_PADDING))
+    }
+
+    /// Whether or not the Element is sized to fit its label.
+    pub fn auto_size(&self, theme: &Theme) -> bool {
+        self.maybe_auto_size.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_auto_size.unwrap_or(false)
+        })).unwrap_or(false)
+    }
+
+    /// Get the Place of the label within the Element.
+    pub fn label_position(&self, theme: &Theme) -> Place {
+        self.maybe_label_position.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_label_position.unwrap_or(Place::Middle)
+        })).unwrap_or(Place::Middle)
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_toggle.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+
+impl<'a, F> Enableable for Toggle<'a, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
 }
 
+impl<'a, F> ReadOnly for Toggle<'a, F> {
+    fn read_only(mut self, flag: bool) -> Self {
+        self.read_only = flag;
+        self
+    }
+}
 
 impl<'a, F> Colorable for Toggle<'a, F> {
     fn color(mut self, color: Color) -> Self {
@@ -285,6 +577,20 @@ impl<'a, F> Colorable for Toggle<'a, F> {
     }
 }
 
+impl<'a, F> Paletted for Toggle<'a, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, F> Fontable for Toggle<'a, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, F> Frameable for Toggle<'a, F> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -294,6 +600,32 @@ impl<'a, F> Frameable for Toggle<'a, F> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
+}
+
+impl<'a, F> Roundable for Toggle<'a, F> {
+    fn corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.style.maybe_corner_radii = Some(radii);
+        self
+    }
+}
+
+impl<'a, F> Gradientable for Toggle<'a, F> {
+    fn gradient(mut self, gradient: Gradient) -> Self {
+        self.style.maybe_gradient = Some(gradient);
+        self
+    }
 }
 
 impl<'a, F> Labelable<'a> for Toggle<'a, F> {
@@ -302,6 +634,11 @@ impl<'a, F> Labelable<'a> for Toggle<'a, F> {
         self
     }
 
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
     fn label_color(mut self, color: Color) -> Self {
         self.style.maybe_label_color = Some(color);
         self
@@ -326,18 +663,28 @@ impl<'a, F> position::Positionable for Toggle<'a, F> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         Toggle { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl<'a, F> position::Sizeable for Toggle<'a, F> {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        Toggle { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
+    }
+    #[inline]
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        Toggle { dim: [w, h], ..self }
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.style.maybe_padding = Some(padding.into());
+        self
     }
 }
 
@@ -1,47 +1,71 @@
 
+use accessibility::{Accessibility, Role};
 use color::{Color, Colorable};
-use elmesque::Element;
-use frame::Frameable;
+use corner::CornerRadii;
+use elmesque::form::Form;
+use enable::{self, Enableable, ReadOnly};
+use font::{FontId, Fontable};
+use frame::{self, Frameable, FrameSides, FrameStyle, FrameWidths, FRAME_ALL};
 use graphics::character::CharacterCache;
 use label::{self, FontSize, Labelable};
 use mouse::Mouse;
-use num::Float;
-use position::{self, Corner, Depth, Dimensions, HorizontalAlign, Position, VerticalAlign};
+use num::{Float, NumCast};
+use palette::{ColorRole, Paletted};
+use position::{self, Corner, Depth, Dimensions, HorizontalAlign, Margin, Padding, Point, Position,
+               VerticalAlign};
+use rate_limit::RateLimit;
 use std::default::Default;
 use theme::Theme;
 use ui::{UiId, Ui};
 use utils::{clamp, map_range, val_to_string};
-use vecmath::vec2_sub;
+use vecmath::vec2_add;
 use widget::{self, Widget};
+use widget::rich_text::{Span, SpannedLabel};
 
 
 /// Used for displaying and controlling a 2D point on a cartesian plane within a given range.
 /// Its reaction is triggered when the value is updated or if the mouse button is released while
 /// the cursor is above the rectangle.
+///
+/// Holding Shift while dragging locks movement to the dominant axis of the drag, and a `grid` may
+/// be given in order to snap the emitted values to regular steps.
 pub struct XYPad<'a, X, Y, F> {
     x: X, min_x: X, max_x: X,
     y: Y, min_y: Y, max_y: Y,
+    maybe_grid: Option<(X, Y)>,
     pos: Position,
-    dim: Dimensions,
+    margin: Margin,
+    padding: Padding,
     maybe_h_align: Option<HorizontalAlign>,
     maybe_v_align: Option<VerticalAlign>,
     depth: Depth,
     maybe_label: Option<&'a str>,
+    maybe_label_spans: Option<SpannedLabel>,
     maybe_react: Option<F>,
+    maybe_rate_limit: Option<RateLimit>,
     style: Style,
     enabled: bool,
+    read_only: bool,
 }
 
 /// Styling for the XYPad, necessary for constructing its renderable Element.
 #[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Style {
     pub maybe_color: Option<Color>,
+    pub maybe_color_role: Option<ColorRole>,
     pub maybe_frame: Option<f64>,
     pub maybe_frame_color: Option<Color>,
+    pub maybe_frame_sides: Option<FrameSides>,
+    pub maybe_frame_widths: Option<FrameWidths>,
+    pub maybe_frame_style: Option<FrameStyle>,
     pub maybe_label_color: Option<Color>,
     pub maybe_label_font_size: Option<FontSize>,
     pub maybe_value_font_size: Option<FontSize>,
     pub maybe_line_width: Option<f64>,
+    pub maybe_grid_lines: Option<bool>,
+    pub maybe_width: Option<f64>,
+    pub maybe_height: Option<f64>,
+    pub maybe_font: Option<FontId>,
 }
 
 /// The state of the XYPad.
@@ -49,8 +73,17 @@ pub struct Style {
 pub struct State<X, Y> {
     x: X, min_x: X, max_x: X,
     y: Y, min_y: Y, max_y: Y,
+    maybe_grid: Option<(X, Y)>,
     maybe_label: Option<String>,
+    maybe_label_spans: Option<SpannedLabel>,
     interaction: Interaction,
+    /// The position over the pad at which the current drag began.
+    maybe_drag_origin: Option<Point>,
+    enabled: bool,
+    read_only: bool,
+    /// The values produced by the user that are yet to be delivered to a rate limited reaction,
+    /// which the pad displays in place of its given values meanwhile.
+    maybe_pending: Option<(X, Y)>,
 }
 
 /// The interaction state of the XYPad.
@@ -80,7 +113,7 @@ fn get_new_interaction(is_over: bool,
                        mouse: Mouse) -> Interaction {
     use mouse::ButtonState::{Down, Up};
     use self::Interaction::{Normal, Highlighted, Clicked};
-    match (is_over, prev, mouse.left) {
+    match (is_over, prev, mouse.left.state) {
         (true,  Normal,  Down) => Normal,
         (true,  _,       Down) => Clicked,
         (true,  _,       Up)   => Highlighted,
@@ -89,6 +122,29 @@ fn get_new_interaction(is_over: bool,
     }
 }
 
+/// Snap the given value to the nearest step from `min`, keeping it within the given range.
+fn snap<T: Float>(value: T, min: T, max: T, step: T) -> T {
+    if step == T::zero() { return value }
+    let snapped = ((value - min) / step).round() * step + min;
+    let (lo, hi) = if min < max { (min, max) } else { (max, min) };
+    clamp(snapped, lo, hi)
+}
+
+/// The pixel offsets from the centre of the pad for each grid line along a single axis.
+fn grid_line_offsets<T: Float>(min: T, max: T, step: T, pad_len: f64) -> Vec<f64> {
+    const MIN_SPACING: f64 = 4.0; // Skip lines that would be too dense to be useful.
+    let min_f: f64 = NumCast::from(min).unwrap();
+    let max_f: f64 = NumCast::from(max).unwrap();
+    let step_f: f64 = NumCast::from(step).unwrap();
+    let range = (max_f - min_f).abs();
+    let step_f = step_f.abs();
+    if step_f == 0.0 || range == 0.0 { return Vec::new() }
+    let px_step = pad_len * step_f / range;
+    if px_step < MIN_SPACING { return Vec::new() }
+    let num_lines = (range / step_f).floor() as usize + 1;
+    (0..num_lines).map(|i| -pad_len / 2.0 + i as f64 * px_step).collect()
+}
+
 
 impl<'a, X, Y, F> XYPad<'a, X, Y, F> {
 
@@ -97,15 +153,20 @@ impl<'a, X, Y, F> XYPad<'a, X, Y, F> {
         XYPad {
             x: x_val, min_x: min_x, max_x: max_x,
             y: y_val, min_y: min_y, max_y: max_y,
+            maybe_grid: None,
             pos: Position::default(),
-            dim: [128.0, 128.0],
+            margin: Margin::none(),
+            padding: Padding::none(),
             maybe_h_align: None,
             maybe_v_align: None,
             depth: 0.0,
             maybe_react: None,
+            maybe_rate_limit: None,
             maybe_label: None,
+            maybe_label_spans: None,
             style: Style::new(),
             enabled: true,
+            read_only: false,
         }
     }
 
@@ -123,6 +184,20 @@ impl<'a, X, Y, F> XYPad<'a, X, Y, F> {
         self
     }
 
+    /// Snap the values emitted by the XYPad to the given steps along each axis.
+    #[inline]
+    pub fn grid(mut self, x_step: X, y_step: Y) -> XYPad<'a, X, Y, F> {
+        self.maybe_grid = Some((x_step, y_step));
+        self
+    }
+
+    /// Whether or not the grid (if there is one) should be drawn as faint lines within the pad.
+    #[inline]
+    pub fn grid_lines(mut self, show: bool) -> XYPad<'a, X, Y, F> {
+        self.style.maybe_grid_lines = Some(show);
+        self
+    }
+
     /// Set the reaction for the XYPad. It will be triggered when the value is updated or if the
     /// mouse button is released while the cursor is above the rectangle.
     pub fn react(mut self, reaction: F) -> Self {
@@ -130,10 +205,19 @@ impl<'a, X, Y, F> XYPad<'a, X, Y, F> {
         self
     }
 
-    /// If true, will allow user inputs.  If false, will disallow user inputs.
-    pub fn enabled(mut self, flag: bool) -> Self {
-        self.enabled = flag;
-        self
+    /// Set a reaction that is only triggered once the value has stopped changing for the given
+    /// number of seconds. This is useful for expensive reactions that needn't follow every step
+    /// of a drag.
+    pub fn react_debounced(mut self, secs: f64, reaction: F) -> Self {
+        self.maybe_rate_limit = Some(RateLimit::Debounce(secs));
+        self.react(reaction)
+    }
+
+    /// Set a reaction that is triggered at most once per the given number of seconds. The latest
+    /// value is always delivered once the interval has passed.
+    pub fn react_throttled(mut self, secs: f64, reaction: F) -> Self {
+        self.maybe_rate_limit = Some(RateLimit::Throttle(secs));
+        self.react(reaction)
     }
 
 }
@@ -152,10 +236,17 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
             interaction: Interaction::Normal,
             x: self.x, min_x: self.min_x, max_x: self.max_x,
             y: self.y, min_y: self.min_y, max_y: self.max_y,
+            maybe_grid: None,
             maybe_label: None,
+            maybe_label_spans: None,
+            maybe_drag_origin: None,
+            enabled: self.enabled,
+            read_only: self.read_only,
+            maybe_pending: None,
         }
     }
     fn style(&self) -> Style { self.style.clone() }
+    fn style_font(style: &Style, theme: &Theme) -> FontId { style.font(theme) }
 
     /// Update the XYPad's cached state.
     fn update<C>(mut self,
@@ -169,16 +260,23 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
         use utils::is_over_rect;
 
         let widget::State { ref state, .. } = *prev_state;
-        let dim = self.dim;
+
+        // While a rate limited reaction has yet to receive the user's latest values, the pad
+        // follows those values rather than the ones it was given.
+        if let (Some((x, y)), Some(_)) = (state.maybe_pending, self.maybe_rate_limit) {
+            self.x = x;
+            self.y = y;
+        }
+
+        let dim = style.dim(&ui.theme);
         let h_align = self.maybe_h_align.unwrap_or(ui.theme.align.horizontal);
         let v_align = self.maybe_v_align.unwrap_or(ui.theme.align.vertical);
-        let xy = ui.get_xy(self.pos, dim, h_align, v_align);
-        let mouse = ui.get_mouse_state(ui_id).relative_to(xy);
-        let frame = style.frame(&ui.theme);
-        let pad_dim = vec2_sub(dim, [frame * 2.0; 2]);
+        let xy = ui.get_xy_with_spacing(self.pos, dim, self.margin, self.padding, h_align, v_align);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
+        let mouse = ui.get_mouse_state(ui_id).relative_to(vec2_add(xy, pad_offset));
         let is_over_pad = is_over_rect([0.0, 0.0], mouse.xy, pad_dim);
         let new_interaction = 
-            if self.enabled {
+            if self.enabled && !self.read_only {
                 get_new_interaction(is_over_pad, state.interaction, mouse)
             } else {
                 Interaction::Normal
@@ -186,28 +284,70 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
         let half_pad_w = pad_dim[0] / 2.0;
         let half_pad_h = pad_dim[1] / 2.0;
 
+        // Keep hold of the mouse while dragging so the drag may continue beyond the pad.
+        ui.mouse_dragged_by(ui_id,
+                            state.interaction == Interaction::Clicked,
+                            new_interaction == Interaction::Clicked);
+
+        // Keep track of where the drag began so that we may lock movement to a single axis.
+        let maybe_drag_origin = match (state.interaction, new_interaction) {
+            (Interaction::Clicked, Interaction::Clicked) => state.maybe_drag_origin,
+            (_, Interaction::Clicked) => Some(mouse.xy),
+            _ => None,
+        };
+
         // Determine new values from the mouse position over the pad.
         let (new_x, new_y) = match new_interaction {
             Interaction::Normal | Interaction::Highlighted => (self.x, self.y),
             Interaction::Clicked => {
-                let temp_x = clamp(mouse.xy[0], -half_pad_w, half_pad_w);
-                let temp_y = clamp(mouse.xy[1], -half_pad_h, half_pad_h);
-                (map_range(temp_x, -half_pad_w, half_pad_w, self.min_x, self.max_x),
-                 map_range(temp_y, -half_pad_h, half_pad_h, self.min_y, self.max_y))
+                let mut temp_x = clamp(mouse.xy[0], -half_pad_w, half_pad_w);
+                let mut temp_y = clamp(mouse.xy[1], -half_pad_h, half_pad_h);
+
+                // If shift is held, lock movement to the dominant axis of the drag.
+                if let (true, Some(origin)) = (ui.is_shift_down(), maybe_drag_origin) {
+                    let (dx, dy) = (mouse.xy[0] - origin[0], mouse.xy[1] - origin[1]);
+                    if dx.abs() > dy.abs() {
+                        temp_y = clamp(origin[1], -half_pad_h, half_pad_h);
+                    } else {
+                        temp_x = clamp(origin[0], -half_pad_w, half_pad_w);
+                    }
+                }
+
+                let x = map_range(temp_x, -half_pad_w, half_pad_w, self.min_x, self.max_x);
+                let y = map_range(temp_y, -half_pad_h, half_pad_h, self.min_y, self.max_y);
+                match self.maybe_grid {
+                    Some((step_x, step_y)) => (snap(x, self.min_x, self.max_x, step_x),
+                                               snap(y, self.min_y, self.max_y, step_y)),
+                    None => (x, y),
+                }
             }
         };
 
-        // React if value is changed or the pad is clicked/released.
-        if let Some(ref mut react) = self.maybe_react {
-            if self.x != new_x || self.y != new_y { react(new_x, new_y) }
-            else {
-                match (state.interaction, new_interaction) {
+        // React if value is changed or the pad is clicked/released. A rate limited reaction is
+        // only given changes to the values.
+        let is_changed = self.x != new_x || self.y != new_y;
+        let maybe_value = match self.maybe_rate_limit {
+            Some(limit) => {
+                let maybe_change = if is_changed { Some((new_x, new_y)) } else { None };
+                ui.rate_limit(ui_id, limit, maybe_change)
+            },
+            None => {
+                let should_react = is_changed || match (state.interaction, new_interaction) {
                     (Interaction::Highlighted, Interaction::Clicked) |
-                    (Interaction::Clicked, Interaction::Highlighted) => react(new_x, new_y),
-                    _ => (),
-                }
-            }
+                    (Interaction::Clicked, Interaction::Highlighted) => true,
+                    _ => false,
+                };
+                if should_react { Some((new_x, new_y)) } else { None }
+            },
+        };
+        if let Some((x, y)) = maybe_value {
+            if let Some(ref mut react) = self.maybe_react { react(x, y) }
         }
+        let maybe_pending = match self.maybe_rate_limit.is_some()
+            && ui.is_rate_limit_pending(ui_id) {
+            true => Some((new_x, new_y)),
+            false => None,
+        };
 
         // Function for constructing a new State.
         let new_state = || {
@@ -215,7 +355,13 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
                 interaction: new_interaction,
                 x: self.x, min_x: self.min_x, max_x: self.max_x,
                 y: self.y, min_y: self.min_y, max_y: self.max_y,
+                maybe_grid: self.maybe_grid,
                 maybe_label: self.maybe_label.as_ref().map(|label| label.to_string()),
+                maybe_label_spans: self.maybe_label_spans.clone(),
+                maybe_drag_origin: maybe_drag_origin,
+                enabled: self.enabled,
+                read_only: self.read_only,
+                maybe_pending: maybe_pending,
             }
         };
 
@@ -223,7 +369,13 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
         let state_has_changed = state.interaction != new_interaction
             || state.x != self.x || state.min_x != self.min_x || state.max_x != self.max_x
             || state.y != self.y || state.min_y != self.min_y || state.max_y != self.max_y
-            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label;
+            || state.maybe_grid != self.maybe_grid
+            || state.maybe_drag_origin != maybe_drag_origin
+            || state.enabled != self.enabled
+            || state.read_only != self.read_only
+            || state.maybe_pending != maybe_pending
+            || state.maybe_label.as_ref().map(|string| &string[..]) != self.maybe_label
+            || state.maybe_label_spans != self.maybe_label_spans;
 
         // Construct the new state if there was a change.
         let maybe_new_state = if state_has_changed { Some(new_state()) } else { None };
@@ -232,31 +384,54 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
 
     }
 
-    /// Construct an Element from the given XYPad State.
-    fn draw<C>(new_state: &widget::State<State<X, Y>>, style: &Style, ui: &mut Ui<C>) -> Element
+    /// Construct the Forms that draw the given XYPad State.
+    fn draw<C>(new_state: &widget::State<State<X, Y>>, style: &Style, ui: &mut Ui<C>) -> Vec<Form>
         where
             C: CharacterCache,
     {
-        use elmesque::form::{collage, line, rect, solid, text};
+        use elmesque::form::{line, rect, solid, text};
         use elmesque::text::Text;
 
         let widget::State { ref state, dim, xy, .. } = *new_state;
         let frame = style.frame(&ui.theme);
-        let pad_dim = vec2_sub(dim, [frame * 2.0; 2]);
+        let (pad_offset, pad_dim) = style.frame_widths(&ui.theme).inner_rect(dim);
         let (half_pad_w, half_pad_h) = (pad_dim[0] / 2.0, pad_dim[1] / 2.0);
 
         // Construct the frame and inner rectangle Forms.
-        let color = state.color(style.color(&ui.theme));
-        let frame_color = style.frame_color(&ui.theme);
-        let frame_form = rect(dim[0], dim[1]).filled(frame_color);
+        let color = enable::color(state.color(style.color(&ui.theme)), state.enabled, &ui.theme);
+        let frame_color = enable::color(style.frame_color(&ui.theme), state.enabled, &ui.theme);
+        let frame_form = frame::form(dim, CornerRadii::none(), style.frame_widths(&ui.theme),
+                                     style.frame_style(&ui.theme), frame_color);
         let pressable_form = rect(pad_dim[0], pad_dim[1]).filled(color);
 
-        // Construct the label Form.
-        let maybe_label_form = state.maybe_label.as_ref().map(|l_text| {
-            let l_color = style.label_color(&ui.theme);
-            let l_size = style.label_font_size(&ui.theme) as f64;
-            text(Text::from_string(l_text.clone()).color(l_color).height(l_size))
-        });
+        // Construct the label Forms.
+        let l_color = enable::color(style.label_color(&ui.theme), state.enabled, &ui.theme);
+        let l_size = style.label_font_size(&ui.theme);
+        let label_forms = match (state.maybe_label_spans.as_ref(), state.maybe_label.as_ref()) {
+            (Some(spans), _) => spans.forms(ui, l_color, l_size).1,
+            (None, Some(l_text)) => {
+                let l_text = Text::from_string(l_text.clone()).color(l_color);
+                vec![text(l_text.height(l_size as f64))]
+            },
+            (None, None) => Vec::new(),
+        };
+
+        // Construct the faint grid line Forms.
+        let grid_forms = match (state.maybe_grid, style.grid_lines(&ui.theme)) {
+            (Some((step_x, step_y)), true) => {
+                let grid_style = solid(color.plain_contrast().alpha(0.15));
+                let xs = grid_line_offsets(state.min_x, state.max_x, step_x, pad_dim[0]);
+                let ys = grid_line_offsets(state.min_y, state.max_y, step_y, pad_dim[1]);
+                let vert_forms = xs.into_iter().map(|x| {
+                    line(grid_style.clone(), 0.0, -half_pad_h, 0.0, half_pad_h).shift_x(x.floor())
+                });
+                let hori_forms = ys.into_iter().map(|y| {
+                    line(grid_style.clone(), -half_pad_w, 0.0, half_pad_w, 0.0).shift_y(y.floor())
+                });
+                vert_forms.chain(hori_forms).collect()
+            },
+            _ => Vec::new(),
+        };
 
         // Construct the crosshair line Forms.
         let ch_x = map_range(state.x, state.min_x, state.max_x, -half_pad_w, half_pad_w).floor();
@@ -289,16 +464,30 @@ impl<'a, X, Y, F> Widget for XYPad<'a, X, Y, F>
         };
 
         // Chain the Forms and shift them into position.
-        let form_chain = Some(frame_form).into_iter()
-            .chain(Some(pressable_form).into_iter())
-            .chain(maybe_label_form.into_iter())
+        let pad_forms = Some(pressable_form).into_iter()
+            .chain(grid_forms.into_iter())
+            .chain(label_forms.into_iter())
             .chain(Some(vert_form).into_iter())
             .chain(Some(hori_form).into_iter())
             .chain(Some(value_text_form).into_iter())
+            .map(|form| form.shift(pad_offset[0], pad_offset[1]));
+        let lock_forms = enable::lock_forms(state.read_only, dim, frame, l_color, &ui.theme);
+        let form_chain = Some(frame_form).into_iter()
+            .chain(pad_forms)
+            .chain(lock_forms.into_iter())
             .map(|form| form.shift(xy[0].round(), xy[1].round()));
 
-        // Turn the form into a renderable Element.
-        collage(dim[0] as i32, dim[1] as i32, form_chain.collect())
+        // Turn the form for drawing.
+        form_chain.collect()
+    }
+
+    /// Describe the XYPad for assistive technologies.
+    fn accessibility(state: &State<X, Y>) -> Accessibility {
+        let maybe_spans_text = state.maybe_label_spans.as_ref().map(|spans| spans.text());
+        let maybe_label = maybe_spans_text.as_ref().or(state.maybe_label.as_ref());
+        Accessibility::new(Role::XYPad)
+            .label(maybe_label.map(|l| &l[..]))
+            .value(format!("{}, {}", state.x.to_string(), state.y.to_string()))
     }
 
 }
@@ -310,20 +499,30 @@ impl Style {
     pub fn new() -> Style {
         Style {
             maybe_color: None,
+            maybe_color_role: None,
             maybe_frame: None,
             maybe_frame_color: None,
+            maybe_frame_sides: None,
+            maybe_frame_widths: None,
+            maybe_frame_style: None,
             maybe_label_color: None,
             maybe_label_font_size: None,
             maybe_value_font_size: None,
             maybe_line_width: None,
+            maybe_grid_lines: None,
+            maybe_width: None,
+            maybe_height: None,
+            maybe_font: None,
         }
     }
 
     /// Get the Color for an Element.
     pub fn color(&self, theme: &Theme) -> Color {
-        self.maybe_color.or(theme.maybe_xy_pad.as_ref().map(|style| {
-            style.maybe_color.unwrap_or(theme.shape_color)
-        })).unwrap_or(theme.shape_color)
+        self.maybe_color.or(self.maybe_color_role.map(|role| theme.palette[role]))
+            .or(theme.maybe_xy_pad.as_ref().map(|style| {
+                style.maybe_color.or(style.maybe_color_role.map(|role| theme.palette[role]))
+                    .unwrap_or(theme.shape_color)
+            })).unwrap_or(theme.shape_color)
     }
 
     /// Get the frame for an Element.
@@ -340,6 +539,27 @@ impl Style {
         })).unwrap_or(theme.frame_color)
     }
 
+    /// Get the sides upon which the frame is drawn.
+    pub fn frame_sides(&self, theme: &Theme) -> FrameSides {
+        self.maybe_frame_sides.or(theme.maybe_xy_pad.as_ref().map(|style| {
+            style.maybe_frame_sides.unwrap_or(FRAME_ALL)
+        })).unwrap_or(FRAME_ALL)
+    }
+
+    /// Get the width of the frame upon each side.
+    pub fn frame_widths(&self, theme: &Theme) -> FrameWidths {
+        self.maybe_frame_widths
+            .or(theme.maybe_xy_pad.as_ref().and_then(|style| style.maybe_frame_widths))
+            .unwrap_or_else(|| FrameWidths::sides(self.frame(theme), self.frame_sides(theme)))
+    }
+
+    /// Get the style with which the frame is shaded.
+    pub fn frame_style(&self, theme: &Theme) -> FrameStyle {
+        self.maybe_frame_style.or(theme.maybe_xy_pad.as_ref().map(|style| {
+            style.maybe_frame_style.unwrap_or(theme.frame_style)
+        })).unwrap_or(theme.frame_style)
+    }
+
     /// Get the label Color for an Element.
     pub fn label_color(&self, theme: &Theme) -> Color {
         self.maybe_label_color.or(theme.maybe_xy_pad.as_ref().map(|style| {
@@ -370,6 +590,47 @@ impl Style {
         })).unwrap_or(DEFAULT_LINE_WIDTH)
     }
 
+    /// Get whether or not grid lines should be drawn for an Element.
+    pub fn grid_lines(&self, theme: &Theme) -> bool {
+        const DEFAULT_GRID_LINES: bool = false;
+        self.maybe_grid_lines.or(theme.maybe_xy_pad.as_ref().map(|style| {
+            style.maybe_grid_lines.unwrap_or(DEFAULT_GRID_LINES)
+        })).unwrap_or(DEFAULT_GRID_LINES)
+    }
+
+    /// Get the Dimensions for an Element.
+    pub fn dim(&self, theme: &Theme) -> Dimensions {
+        const DEFAULT_DIM: Dimensions = [128.0, 128.0];
+        let width = self.maybe_width.or(theme.maybe_xy_pad.as_ref().map(|style| {
+            style.maybe_width.unwrap_or(DEFAULT_DIM[0])
+        })).unwrap_or(DEFAULT_DIM[0]);
+        let height = self.maybe_height.or(theme.maybe_xy_pad.as_ref().map(|style| {
+            style.maybe_height.unwrap_or(DEFAULT_DIM[1])
+        })).unwrap_or(DEFAULT_DIM[1]);
+        [width, height]
+    }
+
+    /// Get the font for an Element.
+    pub fn font(&self, theme: &Theme) -> FontId {
+        self.maybe_font.or(theme.maybe_xy_pad.as_ref().map(|style| {
+            style.maybe_font.unwrap_or(theme.font)
+        })).unwrap_or(theme.font)
+    }
+
+}
+
+impl<'a, X, Y, F> Enableable for XYPad<'a, X, Y, F> {
+    fn enabled(mut self, flag: bool) -> Self {
+        self.enabled = flag;
+        self
+    }
+}
+
+impl<'a, X, Y, F> ReadOnly for XYPad<'a, X, Y, F> {
+    fn read_only(mut self, flag: bool) -> Self {
+        self.read_only = flag;
+        self
+    }
 }
 
 impl<'a, X, Y, F> Colorable for XYPad<'a, X, Y, F> {
@@ -379,6 +640,20 @@ impl<'a, X, Y, F> Colorable for XYPad<'a, X, Y, F> {
     }
 }
 
+impl<'a, X, Y, F> Paletted for XYPad<'a, X, Y, F> {
+    fn color_role(mut self, role: ColorRole) -> Self {
+        self.style.maybe_color_role = Some(role);
+        self
+    }
+}
+
+impl<'a, X, Y, F> Fontable for XYPad<'a, X, Y, F> {
+    fn font(mut self, font: FontId) -> Self {
+        self.style.maybe_font = Some(font);
+        self
+    }
+}
+
 impl<'a, X, Y, F> Frameable for XYPad<'a, X, Y, F> {
     fn frame(mut self, width: f64) -> Self {
         self.style.maybe_frame = Some(width);
@@ -388,6 +663,18 @@ impl<'a, X, Y, F> Frameable for XYPad<'a, X, Y, F> {
         self.style.maybe_frame_color = Some(color);
         self
     }
+    fn frame_sides(mut self, sides: FrameSides) -> Self {
+        self.style.maybe_frame_sides = Some(sides);
+        self
+    }
+    fn frame_widths(mut self, widths: FrameWidths) -> Self {
+        self.style.maybe_frame_widths = Some(widths);
+        self
+    }
+    fn frame_style(mut self, style: FrameStyle) -> Self {
+        self.style.maybe_frame_style = Some(style);
+        self
+    }
 }
 
 impl<'a, X, Y, F> Labelable<'a> for XYPad<'a, X, Y, F>
@@ -397,6 +684,11 @@ impl<'a, X, Y, F> Labelable<'a> for XYPad<'a, X, Y, F>
         self
     }
 
+    fn label_spans(mut self, spans: &[Span]) -> Self {
+        self.maybe_label_spans = Some(SpannedLabel::new(spans));
+        self
+    }
+
     fn label_color(mut self, color: Color) -> Self {
         self.style.maybe_label_color = Some(color);
         self
@@ -421,18 +713,58 @@ impl<'a, X, Y, F> position::Positionable for XYPad<'a, X, Y, F> {
     fn vertical_align(self, v_align: VerticalAlign) -> Self {
         XYPad { maybe_v_align: Some(v_align), ..self }
     }
+    #[inline]
+    fn margin<M: Into<Margin>>(mut self, margin: M) -> Self {
+        self.margin = margin.into();
+        self
+    }
 }
 
 impl<'a, X, Y, F> position::Sizeable for XYPad<'a, X, Y, F> {
     #[inline]
-    fn width(self, w: f64) -> Self {
-        let h = self.dim[1];
-        XYPad { dim: [w, h], ..self }
+    fn width(mut self, w: f64) -> Self {
+        self.style.maybe_width = Some(w);
+        self
     }
     #[inline]
-    fn height(self, h: f64) -> Self {
-        let w = self.dim[0];
-        XYPad { dim: [w, h], ..self }
+    fn height(mut self, h: f64) -> Self {
+        self.style.maybe_height = Some(h);
+        self
+    }
+    #[inline]
+    fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::{grid_line_offsets, snap};
+
+    #[test]
+    fn values_snap_to_the_nearest_step_within_the_range() {
+        assert_eq!(snap(0.3, 0.0, 1.0, 0.25), 0.25);
+        assert_eq!(snap(0.9, 0.0, 1.0, 0.25), 1.0);
+        assert_eq!(snap(1.2, 0.0, 1.0, 0.25), 1.0);
+        // Steps are counted from the minimum, even if it is the greater end of the range.
+        assert_eq!(snap(0.6, 1.0, 0.0, 0.25), 0.5);
+        // A step of zero disables snapping.
+        assert_eq!(snap(0.3, 0.0, 1.0, 0.0), 0.3);
+    }
+
+    #[test]
+    fn grid_lines_are_spread_evenly_across_the_pad() {
+        assert_eq!(grid_line_offsets(0.0, 1.0, 0.25, 100.0), vec![-50.0, -25.0, 0.0, 25.0, 50.0]);
+        assert_eq!(grid_line_offsets(1.0, 0.0, 0.5, 100.0), vec![-50.0, 0.0, 50.0]);
+    }
+
+    #[test]
+    fn grid_lines_that_are_too_dense_are_skipped() {
+        assert!(grid_line_offsets(0.0, 1.0, 0.01, 100.0).is_empty());
+        assert!(grid_line_offsets(0.0, 1.0, 0.0, 100.0).is_empty());
+        assert!(grid_line_offsets(0.0, 0.0, 0.25, 100.0).is_empty());
+    }
+
+}
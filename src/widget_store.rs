@@ -0,0 +1,277 @@
+//!
+//! The dense store of every widget's cached state, indexed directly by `UiId`.
+//!
+//! Each position within the store holds the widget's `Cached` state along with the rest of the
+//! data the `Ui` keeps for it (its animations, rate limiter, layer and removal hook), so that
+//! setting a widget never needs to hash its `UiId`. The `Cached` states are kept contiguously so
+//! that the display list and hit-testing can walk them as a slice.
+//!
+//! As `UiId`s are chosen by the user and so are reused once a widget is removed, each position
+//! also carries a generation that is incremented every time the widget there is removed. A
+//! `WidgetKey` pairs a `UiId` with its generation, allowing anything that refers to a widget
+//! across frames to tell whether that widget still exists or has since been replaced.
+//!
+
+use animation::Animations;
+use position::Layer;
+use rate_limit::Limiter;
+use std::ops::{Index, IndexMut};
+use ui::UiId;
+use widget;
+
+
+/// Refers to the widget at a `UiId` during a single generation of that position, i.e. from when
+/// the widget is first set until it is removed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WidgetKey {
+    /// The position of the widget within the store.
+    pub ui_id: UiId,
+    /// The number of widgets removed from the position before this one was set there.
+    pub generation: u32,
+}
+
+/// The data kept for a widget alongside its `Cached` state.
+struct Slot {
+    generation: u32,
+    /// The number of consecutive draws for which the widget has gone unset.
+    unset_draws: u32,
+    animations: Animations,
+    maybe_limiter: Option<Limiter>,
+    maybe_layer: Option<Layer>,
+    maybe_removal_hook: Option<Box<FnMut(UiId)>>,
+}
+
+/// The data left behind by a widget removed from the store.
+pub struct Removed {
+    /// The function to be called now that the widget has been removed, if it was given one.
+    pub maybe_removal_hook: Option<Box<FnMut(UiId)>>,
+}
+
+/// Every widget's cached state along with its animations, rate limiter, layer and removal hook.
+pub struct WidgetStore {
+    widgets: Vec<widget::Cached>,
+    slots: Vec<Slot>,
+}
+
+
+impl Slot {
+    fn new(generation: u32) -> Slot {
+        Slot {
+            generation: generation,
+            unset_draws: 0,
+            animations: Animations::new(),
+            maybe_limiter: None,
+            maybe_layer: None,
+            maybe_removal_hook: None,
+        }
+    }
+}
+
+
+impl WidgetStore {
+
+    /// Construct a store with vacant positions for the first `reservation` UiIds.
+    pub fn new(reservation: usize) -> WidgetStore {
+        let mut store = WidgetStore { widgets: Vec::new(), slots: Vec::new() };
+        store.reserve(reservation);
+        store
+    }
+
+    /// Extend the store with vacant positions so that it holds at least `len` UiIds.
+    pub fn reserve(&mut self, len: usize) {
+        if self.widgets.len() < len {
+            let num_to_extend = len - self.widgets.len();
+            self.widgets.extend((0..num_to_extend).map(|_| widget::Cached::empty()));
+            self.slots.extend((0..num_to_extend).map(|_| Slot::new(0)));
+        }
+    }
+
+    /// The cached state of every position within the store, indexed by UiId.
+    #[inline]
+    pub fn widgets(&self) -> &[widget::Cached] {
+        &self.widgets
+    }
+
+    /// The cached state of every position within the store, indexed by UiId.
+    #[inline]
+    pub fn widgets_mut(&mut self) -> &mut [widget::Cached] {
+        &mut self.widgets
+    }
+
+    /// The cached state of the widget at the given UiId, if the store reaches that far.
+    #[inline]
+    pub fn get(&self, ui_id: UiId) -> Option<&widget::Cached> {
+        self.widgets.get(ui_id)
+    }
+
+    /// The key of the widget currently at the given UiId.
+    pub fn key(&self, ui_id: UiId) -> WidgetKey {
+        let generation = self.slots.get(ui_id).map_or(0, |slot| slot.generation);
+        WidgetKey { ui_id: ui_id, generation: generation }
+    }
+
+    /// Whether or not the widget to which the given key refers is still within the store.
+    pub fn contains_key(&self, key: WidgetKey) -> bool {
+        self.key(key.ui_id) == key && self.get(key.ui_id).map_or(false, |w| !w.is_empty())
+    }
+
+    /// The animated properties of the widget at the given UiId.
+    pub fn animations_mut(&mut self, ui_id: UiId) -> &mut Animations {
+        &mut self.slot_mut(ui_id).animations
+    }
+
+    /// Whether or not any of the properties of the widget at the given UiId are still being
+    /// animated at `now_ns`.
+    pub fn is_animating(&self, ui_id: UiId, now_ns: u64) -> bool {
+        self.slots.get(ui_id).map_or(false, |slot| slot.animations.is_animating(now_ns))
+    }
+
+    /// The rate limiter of the widget at the given UiId, created upon its first use.
+    pub fn limiter_mut(&mut self, ui_id: UiId) -> &mut Limiter {
+        let slot = self.slot_mut(ui_id);
+        if slot.maybe_limiter.is_none() {
+            slot.maybe_limiter = Some(Limiter::new());
+        }
+        slot.maybe_limiter.as_mut().unwrap()
+    }
+
+    /// Whether or not the widget at the given UiId has a rate limited value that is yet to be
+    /// delivered.
+    pub fn is_rate_limit_pending(&self, ui_id: UiId) -> bool {
+        self.slots.get(ui_id)
+            .and_then(|slot| slot.maybe_limiter.as_ref())
+            .map_or(false, |limiter| limiter.is_pending())
+    }
+
+    /// The layer in which the widget at the given UiId has been placed via `Ui::set_layer`, if
+    /// any.
+    pub fn layer_override(&self, ui_id: UiId) -> Option<Layer> {
+        self.slots.get(ui_id).and_then(|slot| slot.maybe_layer)
+    }
+
+    /// Place the widget at the given UiId in the given layer, or return it to the layer of its
+    /// scope if `None`.
+    pub fn set_layer_override(&mut self, ui_id: UiId, maybe_layer: Option<Layer>) {
+        self.slot_mut(ui_id).maybe_layer = maybe_layer;
+    }
+
+    /// Set the function to be called once the widget at the given UiId is removed.
+    pub fn set_removal_hook(&mut self, ui_id: UiId, hook: Box<FnMut(UiId)>) {
+        self.slot_mut(ui_id).maybe_removal_hook = Some(hook);
+    }
+
+    /// Count another draw for every widget that has not been set since the previous one, returning
+    /// the UiIds of the widgets that have now gone unset for more than `grace` consecutive draws.
+    pub fn unset_for_more_than(&mut self, grace: u32) -> Vec<UiId> {
+        let mut unset = Vec::new();
+        for (ui_id, (widget, slot)) in self.widgets.iter().zip(self.slots.iter_mut()).enumerate() {
+            if widget.is_empty() {
+                continue;
+            }
+            if widget.has_updated {
+                slot.unset_draws = 0;
+            } else {
+                slot.unset_draws += 1;
+                if slot.unset_draws > grace {
+                    unset.push(ui_id);
+                }
+            }
+        }
+        unset
+    }
+
+    /// Vacate the position at the given UiId, forgetting all of the data kept for its widget and
+    /// beginning the position's next generation.
+    pub fn remove(&mut self, ui_id: UiId) -> Removed {
+        self.reserve(ui_id + 1);
+        self.widgets[ui_id] = widget::Cached::empty();
+        let generation = self.slots[ui_id].generation.wrapping_add(1);
+        let slot = ::std::mem::replace(&mut self.slots[ui_id], Slot::new(generation));
+        Removed { maybe_removal_hook: slot.maybe_removal_hook }
+    }
+
+    /// The slot at the given UiId, extending the store if necessary.
+    fn slot_mut(&mut self, ui_id: UiId) -> &mut Slot {
+        self.reserve(ui_id + 1);
+        &mut self.slots[ui_id]
+    }
+
+}
+
+
+impl Index<UiId> for WidgetStore {
+    type Output = widget::Cached;
+    #[inline]
+    fn index(&self, ui_id: UiId) -> &widget::Cached {
+        &self.widgets[ui_id]
+    }
+}
+
+impl IndexMut<UiId> for WidgetStore {
+    #[inline]
+    fn index_mut(&mut self, ui_id: UiId) -> &mut widget::Cached {
+        &mut self.widgets[ui_id]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use animation::Ease;
+    use rate_limit::RateLimit;
+    use std::any::TypeId;
+    use super::WidgetStore;
+
+    #[test]
+    fn positions_are_reserved_on_first_use() {
+        let mut store = WidgetStore::new(2);
+        assert_eq!(store.widgets().len(), 2);
+        store.limiter_mut(9);
+        assert_eq!(store.widgets().len(), 10);
+        assert!(store[9].is_empty());
+    }
+
+    #[test]
+    fn removal_forgets_the_widget_and_advances_its_generation() {
+        let mut store = WidgetStore::new(4);
+        let key = store.key(1);
+        store.animations_mut(1).animate("x", 0.0, 1.0, Ease::Linear, 0);
+        store.animations_mut(1).animate("x", 1.0, 1.0, Ease::Linear, 0);
+        store.limiter_mut(1).limit(RateLimit::Debounce(1.0), Some(5), 0);
+        assert!(store.is_animating(1, 0));
+        assert!(store.is_rate_limit_pending(1));
+
+        store.remove(1);
+        assert!(!store.is_animating(1, 0));
+        assert!(!store.is_rate_limit_pending(1));
+        assert!(!store.contains_key(key));
+        assert_eq!(store.key(1).generation, key.generation + 1);
+        // Neighbouring widgets are untouched.
+        assert_eq!(store.key(0).generation, 0);
+    }
+
+    #[test]
+    fn widgets_are_unset_once_their_grace_has_passed() {
+        let mut store = WidgetStore::new(2);
+        store[0].maybe_type_id = Some(TypeId::of::<()>());
+        assert!(store.unset_for_more_than(1).is_empty());
+        // Being set resets the count.
+        store[0].has_updated = true;
+        assert!(store.unset_for_more_than(1).is_empty());
+        store[0].has_updated = false;
+        assert!(store.unset_for_more_than(1).is_empty());
+        assert_eq!(store.unset_for_more_than(1), vec![0]);
+    }
+
+    #[test]
+    fn widgets_are_rate_limited_independently() {
+        let mut store = WidgetStore::new(2);
+        let limit = RateLimit::Throttle(1.0);
+        assert_eq!(store.limiter_mut(0).limit(limit, Some(1), 0), Some(1));
+        assert_eq!(store.limiter_mut(1).limit(limit, Some(2), 0), Some(2));
+        assert_eq!(store.limiter_mut(0).limit(limit, Some(3), 0), None);
+        assert!(store.is_rate_limit_pending(0));
+        assert!(!store.is_rate_limit_pending(1));
+    }
+
+}